@@ -0,0 +1,284 @@
+//! Compatibility with event shapes written by earlier releases of this crate
+//!
+//! Version 0 events were serialized in snake_case with RFC 3339 timestamps,
+//! and several payloads predate the AG-UI spec (`tool_name`, `step_id`,
+//! `event_type` plus flattened custom data, a nested `tool_result`, ...).
+//! [`upgrade_event`] rewrites such events into the current layout so that
+//! streams from older Rust servers can still be read. Events are always
+//! written in the current layout.
+
+use chrono::DateTime;
+use serde_json::{Map, Value};
+use crate::events::BaseEvent;
+use crate::types::{LegacyMessage, Message};
+use crate::error::{AgUiError, Result};
+
+/// Schema version of a serialized event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SchemaVersion {
+    /// Layout written by ag-ui-wasm 0.1
+    V0,
+    /// The `@ag-ui/core` event schemas
+    V1,
+}
+
+impl SchemaVersion {
+    /// The version written by this crate
+    pub const CURRENT: SchemaVersion = SchemaVersion::V1;
+
+    /// Detect the schema version of a JSON event
+    ///
+    /// Anything that is not recognizably version 0 is reported as current,
+    /// leaving malformed input for the strict parser to reject.
+    pub fn detect(event: &Value) -> Self {
+        let Some(object) = event.as_object() else {
+            return Self::CURRENT;
+        };
+
+        let legacy_layout = object.keys().any(|key| key.contains('_'))
+            || object.get("timestamp").is_some_and(Value::is_string);
+
+        let legacy_payload = match object.get("type").and_then(Value::as_str) {
+            Some("TOOL_CALL_START") => renamed(object, "toolName", "toolCallName"),
+            Some("RUN_ERROR") => renamed(object, "error", "message"),
+            Some("STEP_STARTED" | "STEP_FINISHED") => renamed(object, "stepId", "stepName"),
+            Some("STATE_SNAPSHOT") => renamed(object, "state", "snapshot"),
+            Some("CUSTOM") => renamed(object, "eventType", "name"),
+            Some("TOOL_CALL_RESULT") => object.contains_key("toolResult"),
+            Some("MESSAGES_SNAPSHOT") => object
+                .get("messages")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_object)
+                .any(|message| message.keys().any(|key| key.contains('_'))),
+            _ => false,
+        };
+
+        if legacy_layout || legacy_payload {
+            SchemaVersion::V0
+        } else {
+            SchemaVersion::V1
+        }
+    }
+}
+
+fn renamed(object: &Map<String, Value>, old: &str, new: &str) -> bool {
+    object.contains_key(old) && !object.contains_key(new)
+}
+
+/// Fields that were dropped from each event type by the spec layout
+fn obsolete_fields(event_type: &str) -> &'static [&'static str] {
+    match event_type {
+        "RUN_ERROR" => &["threadId", "runId"],
+        "STEP_STARTED" => &["threadId", "runId", "stepType"],
+        "STEP_FINISHED" | "THINKING_START" | "THINKING_END" => &["threadId", "runId"],
+        "THINKING_TEXT_MESSAGE_START" => &["messageId", "role"],
+        "THINKING_TEXT_MESSAGE_CONTENT" | "THINKING_TEXT_MESSAGE_END" => &["messageId"],
+        "TOOL_CALL_END" => &["toolCall"],
+        _ => &[],
+    }
+}
+
+/// Rewrite a version 0 event into the current layout
+///
+/// Current events are returned unchanged.
+pub fn upgrade_event(event: Value) -> Value {
+    if SchemaVersion::detect(&event) == SchemaVersion::CURRENT {
+        return event;
+    }
+    let Value::Object(object) = event else {
+        return event;
+    };
+
+    let event_type = object
+        .get("type")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    let mut object = if event_type == "CUSTOM" {
+        upgrade_custom(object)
+    } else {
+        camel_case_keys(object)
+    };
+
+    if let Some(Value::String(timestamp)) = object.get("timestamp") {
+        if let Ok(parsed) = DateTime::parse_from_rfc3339(timestamp) {
+            object.insert("timestamp".to_string(), parsed.timestamp_millis().into());
+        }
+    }
+
+    for field in obsolete_fields(&event_type) {
+        object.remove(*field);
+    }
+
+    match event_type.as_str() {
+        "TOOL_CALL_START" => rename(&mut object, "toolName", "toolCallName"),
+        "RUN_ERROR" => rename(&mut object, "error", "message"),
+        "STEP_STARTED" | "STEP_FINISHED" => rename(&mut object, "stepId", "stepName"),
+        "STATE_SNAPSHOT" => rename(&mut object, "state", "snapshot"),
+        "TOOL_CALL_RESULT" => upgrade_tool_result(&mut object),
+        "MESSAGES_SNAPSHOT" => {
+            if let Some(Value::Array(messages)) = object.get_mut("messages") {
+                for message in messages {
+                    upgrade_message(message);
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Value::Object(object)
+}
+
+/// Parse a JSON event written in any supported schema version
+pub fn parse_event(json: &str) -> Result<BaseEvent> {
+    parse_event_value(serde_json::from_str(json)?)
+}
+
+/// Convert a JSON value in any supported schema version into an event
+pub fn parse_event_value(event: Value) -> Result<BaseEvent> {
+    serde_json::from_value(upgrade_event(event)).map_err(AgUiError::JsonError)
+}
+
+fn rename(object: &mut Map<String, Value>, old: &str, new: &str) {
+    if let Some(value) = object.remove(old) {
+        object.entry(new).or_insert(value);
+    }
+}
+
+/// `{event_type, ...data}` becomes `{name, value: {...data}}`
+fn upgrade_custom(object: Map<String, Value>) -> Map<String, Value> {
+    let mut upgraded = Map::new();
+    let mut value = Map::new();
+
+    for (key, field) in object {
+        match key.as_str() {
+            "type" | "timestamp" => {
+                upgraded.insert(key, field);
+            }
+            "raw_event" | "rawEvent" => {
+                upgraded.insert("rawEvent".to_string(), field);
+            }
+            "event_type" | "eventType" | "name" => {
+                upgraded.insert("name".to_string(), field);
+            }
+            "value" => {
+                upgraded.insert(key, field);
+            }
+            _ => {
+                value.insert(key, field);
+            }
+        }
+    }
+
+    if !value.is_empty() {
+        upgraded.entry("value").or_insert(Value::Object(value));
+    }
+    upgraded
+}
+
+/// A nested `toolResult` becomes a tool message with string content
+///
+/// Version 0 results had no message id, so one is derived from the tool call id.
+fn upgrade_tool_result(object: &mut Map<String, Value>) {
+    let Some(Value::Object(result)) = object.remove("toolResult") else {
+        return;
+    };
+    let result = camel_case_keys(result);
+
+    let tool_call_id = result
+        .get("toolCallId")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+
+    let content = match (result.get("result"), result.get("error")) {
+        (Some(Value::String(content)), _) => content.clone(),
+        (None | Some(Value::Null), Some(Value::String(error))) => error.clone(),
+        (Some(value), _) => value.to_string(),
+        (None, _) => String::new(),
+    };
+
+    object
+        .entry("messageId")
+        .or_insert_with(|| format!("{}-result", tool_call_id).into());
+    object.insert("toolCallId".to_string(), tool_call_id.into());
+    object.insert("content".to_string(), content.into());
+    object.entry("role").or_insert_with(|| "tool".into());
+}
+
+/// Flat version 0 messages are converted to the role-discriminated layout
+///
+/// Messages that cannot be converted are only renamed, leaving the strict
+/// parser to report them.
+fn upgrade_message(message: &mut Value) {
+    let Value::Object(object) = message else {
+        return;
+    };
+    let mut upgraded = camel_case_keys(std::mem::take(object));
+
+    if let Some(Value::Array(tool_calls)) = upgraded.get_mut("toolCalls") {
+        for tool_call in tool_calls {
+            if let Value::Object(fields) = tool_call {
+                *fields = upgrade_tool_call(camel_case_keys(std::mem::take(fields)));
+            }
+        }
+    }
+    if let Some(Value::Object(function_call)) = upgraded.get_mut("functionCall") {
+        *function_call = camel_case_keys(std::mem::take(function_call));
+    }
+
+    let converted = serde_json::from_value::<LegacyMessage>(Value::Object(upgraded.clone()))
+        .ok()
+        .and_then(|legacy| Message::try_from(legacy).ok())
+        .and_then(|message| serde_json::to_value(message).ok());
+
+    *message = converted.unwrap_or(Value::Object(upgraded));
+}
+
+/// A flat `{ id, name, arguments }` tool call is nested under `function`
+///
+/// Arguments that were stored as JSON are written back to text.
+fn upgrade_tool_call(mut fields: Map<String, Value>) -> Map<String, Value> {
+    if fields.contains_key("function") {
+        return fields;
+    }
+    let Some(name) = fields.remove("name") else {
+        return fields;
+    };
+    let arguments = match fields.remove("arguments") {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(arguments)) => arguments,
+        Some(arguments) => arguments.to_string(),
+    };
+
+    let mut function = Map::new();
+    function.insert("name".to_string(), name);
+    function.insert("arguments".to_string(), arguments.into());
+    fields.entry("type").or_insert_with(|| "function".into());
+    fields.insert("function".to_string(), Value::Object(function));
+    fields
+}
+
+/// Rename the keys of one object level; values are left untouched
+fn camel_case_keys(object: Map<String, Value>) -> Map<String, Value> {
+    object
+        .into_iter()
+        .map(|(key, value)| (camel_case(&key), value))
+        .collect()
+}
+
+fn camel_case(key: &str) -> String {
+    let mut parts = key.split('_');
+    let mut result = parts.next().unwrap_or_default().to_string();
+    for part in parts {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            result.extend(first.to_uppercase());
+            result.push_str(chars.as_str());
+        }
+    }
+    result
+}
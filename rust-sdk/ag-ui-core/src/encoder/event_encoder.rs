@@ -0,0 +1,120 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+#[cfg(feature = "wasm")]
+use js_sys::Uint8Array;
+use crate::events::BaseEvent;
+use crate::encoder::media_type::preferred_media_types;
+use crate::encoder::SseEncoder;
+use crate::error::Result;
+use crate::proto::{self, AGUI_MEDIA_TYPE};
+
+/// Content type of an SSE event stream
+pub const SSE_MEDIA_TYPE: &str = "text/event-stream";
+
+/// Encodes events in the format a client asked for, as `EventEncoder` in `@ag-ui/encoder`
+///
+/// Built from the request's `Accept` header: protobuf when the header
+/// prefers `AGUI_MEDIA_TYPE` to SSE, SSE otherwise (including when there is
+/// no header, or only wildcards). Respond with `content_type()` and a body
+/// of `encode_event` frames.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EventEncoder {
+    accepts_protobuf: bool,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl EventEncoder {
+    /// Create an encoder for a request's `Accept` header
+    #[wasm_bindgen(constructor)]
+    pub fn new_js(accept: Option<String>) -> EventEncoder {
+        Self::new(accept)
+    }
+
+    /// Whether events are encoded as protobuf
+    #[wasm_bindgen(getter, js_name = "acceptsProtobuf")]
+    pub fn accepts_protobuf_js(&self) -> bool {
+        self.accepts_protobuf
+    }
+
+    /// The `Content-Type` of the response body
+    #[wasm_bindgen(js_name = "getContentType")]
+    pub fn content_type_js(&self) -> String {
+        self.content_type().to_string()
+    }
+
+    /// Encode an event from JavaScript in the negotiated format
+    #[wasm_bindgen(js_name = "encodeBinary")]
+    pub fn encode_binary_js(&self, event_js: JsValue) -> std::result::Result<Uint8Array, JsValue> {
+        let event: BaseEvent = serde_wasm_bindgen::from_value(event_js)?;
+        Ok(Uint8Array::from(self.encode_event(&event)?.as_slice()))
+    }
+
+    /// Encode an event from JavaScript as an SSE message
+    #[wasm_bindgen(js_name = "encodeSSE")]
+    pub fn encode_sse_js(&self, event_js: JsValue) -> std::result::Result<String, JsValue> {
+        let event: BaseEvent = serde_wasm_bindgen::from_value(event_js)?;
+        Ok(self.encode_sse(&event)?)
+    }
+
+    /// Encode an event from JavaScript as a length-prefixed protobuf frame
+    #[wasm_bindgen(js_name = "encodeProtobuf")]
+    pub fn encode_protobuf_js(&self, event_js: JsValue) -> std::result::Result<Uint8Array, JsValue> {
+        let event: BaseEvent = serde_wasm_bindgen::from_value(event_js)?;
+        Ok(Uint8Array::from(self.encode_protobuf(&event)?.as_slice()))
+    }
+}
+
+impl EventEncoder {
+    /// Create an encoder for a request's `Accept` header, if it has one
+    pub fn new(accept: Option<String>) -> EventEncoder {
+        match accept {
+            Some(accept) => Self::from_accept(&accept),
+            None => Self::default(),
+        }
+    }
+
+    /// Negotiate the format from an `Accept` header value
+    ///
+    /// Picks whichever of SSE and protobuf `preferred_media_types` ranks
+    /// first, by the RFC 7231 quality values and then the header's order. SSE
+    /// wins what is left of a tie, so `*/*` keeps a client on SSE, as does
+    /// `application/vnd.ag-ui.event+proto;q=0.5, text/event-stream`.
+    pub fn from_accept(accept: &str) -> Self {
+        let preferred = preferred_media_types(Some(accept), &[SSE_MEDIA_TYPE, AGUI_MEDIA_TYPE]);
+        Self { accepts_protobuf: preferred.first() == Some(&AGUI_MEDIA_TYPE) }
+    }
+
+    /// Whether events are encoded as protobuf
+    pub fn accepts_protobuf(&self) -> bool {
+        self.accepts_protobuf
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        if self.accepts_protobuf {
+            AGUI_MEDIA_TYPE
+        } else {
+            SSE_MEDIA_TYPE
+        }
+    }
+
+    /// Encode an event in the negotiated format
+    pub fn encode_event(&self, event: &BaseEvent) -> Result<Vec<u8>> {
+        if self.accepts_protobuf {
+            self.encode_protobuf(event)
+        } else {
+            Ok(self.encode_sse(event)?.into_bytes())
+        }
+    }
+
+    /// Encode an event as an SSE message
+    pub fn encode_sse(&self, event: &BaseEvent) -> Result<String> {
+        SseEncoder::encode_event_string(event)
+    }
+
+    /// Encode an event as a length-prefixed protobuf frame
+    pub fn encode_protobuf(&self, event: &BaseEvent) -> Result<Vec<u8>> {
+        proto::encode_frame(event)
+    }
+}
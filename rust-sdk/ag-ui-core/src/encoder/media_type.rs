@@ -0,0 +1,183 @@
+//! `Accept` header negotiation (RFC 7231 section 5.3.2)
+//!
+//! A port of the `preferredMediaTypes` helper in `@ag-ui/encoder`, itself
+//! taken from the `negotiator` package, so both SDKs pick the same type for
+//! the same header.
+
+use std::cmp::Ordering;
+
+/// A media range from an `Accept` header
+#[derive(Debug, Clone)]
+struct MediaRange {
+    type_: String,
+    subtype: String,
+    params: Vec<(String, String)>,
+    q: f64,
+    index: usize,
+}
+
+/// How well an accepted range matches a provided type
+#[derive(Debug, Clone, Copy)]
+struct Priority {
+    /// Position of the matching range in the header
+    order: isize,
+    q: f64,
+    /// Specificity: 4 for the type, 2 for the subtype, 1 for the parameters
+    specificity: u8,
+    /// Position of the provided type
+    index: usize,
+}
+
+/// The `provided` media types the `accept` header allows, most preferred first
+///
+/// Types are ordered by quality, then by how specific the matching range
+/// is, then by the range's position in the header, then by their position
+/// in `provided`. A missing header accepts everything, as RFC 7231 requires;
+/// an empty one accepts nothing.
+pub fn preferred_media_types<'a>(accept: Option<&str>, provided: &[&'a str]) -> Vec<&'a str> {
+    let accepted = parse_accept(accept.unwrap_or("*/*"));
+
+    let mut priorities: Vec<(Priority, &str)> = provided
+        .iter()
+        .enumerate()
+        .map(|(index, media_type)| (media_type_priority(media_type, &accepted, index), *media_type))
+        .filter(|(priority, _)| priority.q > 0.0)
+        .collect();
+
+    priorities.sort_by(|(a, _), (b, _)| {
+        compare_q(b.q, a.q)
+            .then(b.specificity.cmp(&a.specificity))
+            .then(a.order.cmp(&b.order))
+            .then(a.index.cmp(&b.index))
+    });
+    priorities.into_iter().map(|(_, media_type)| media_type).collect()
+}
+
+fn parse_accept(accept: &str) -> Vec<MediaRange> {
+    split_quoted(accept, ',')
+        .iter()
+        .enumerate()
+        .filter_map(|(index, range)| parse_media_range(range.trim(), index))
+        .collect()
+}
+
+fn parse_media_range(range: &str, index: usize) -> Option<MediaRange> {
+    let (essence, parameters) = match range.split_once(';') {
+        Some((essence, parameters)) => (essence, Some(parameters)),
+        None => (range, None),
+    };
+    let (type_, subtype) = essence.trim().split_once('/')?;
+    let invalid = |part: &str| part.is_empty() || part.contains(char::is_whitespace);
+    if invalid(type_) || invalid(subtype) {
+        return None;
+    }
+
+    let mut media_range = MediaRange {
+        type_: type_.to_string(),
+        subtype: subtype.to_string(),
+        params: Vec::new(),
+        q: 1.0,
+        index,
+    };
+
+    for parameter in parameters.map(|parameters| split_quoted(parameters, ';')).unwrap_or_default() {
+        let parameter = parameter.trim();
+        let (key, value) = parameter.split_once('=').unwrap_or((parameter, ""));
+        let key = key.to_lowercase();
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .unwrap_or(value);
+
+        // Parameters after `q` are accept-extensions, not part of the range
+        if key == "q" {
+            media_range.q = parse_float(value);
+            break;
+        }
+        media_range.params.push((key, value.to_string()));
+    }
+
+    Some(media_range)
+}
+
+/// `parseFloat` semantics: the longest numeric prefix, or NaN (which never accepts)
+fn parse_float(value: &str) -> f64 {
+    let value = value.trim_start();
+    let numeric = value.find(|c: char| !matches!(c, '0'..='9' | '.' | '+' | '-' | 'e' | 'E')).unwrap_or(value.len());
+    (1..=numeric).rev().find_map(|end| value[..end].parse::<f64>().ok()).unwrap_or(f64::NAN)
+}
+
+/// Compare as JavaScript's `a - b || ...` chains do, where NaN counts as equal
+fn compare_q(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+fn media_type_priority(media_type: &str, accepted: &[MediaRange], index: usize) -> Priority {
+    let mut priority = Priority { order: -1, q: 0.0, specificity: 0, index };
+
+    for range in accepted {
+        let Some(spec) = specify(media_type, range, index) else {
+            continue;
+        };
+        let better = priority
+            .specificity
+            .cmp(&spec.specificity)
+            .then(compare_q(priority.q, spec.q))
+            .then(priority.order.cmp(&spec.order))
+            .is_lt();
+        if better {
+            priority = spec;
+        }
+    }
+
+    priority
+}
+
+fn specify(media_type: &str, range: &MediaRange, index: usize) -> Option<Priority> {
+    let provided = parse_media_range(media_type, 0)?;
+    let mut specificity = 0;
+
+    if range.type_.eq_ignore_ascii_case(&provided.type_) {
+        specificity |= 4;
+    } else if range.type_ != "*" {
+        return None;
+    }
+
+    if range.subtype.eq_ignore_ascii_case(&provided.subtype) {
+        specificity |= 2;
+    } else if range.subtype != "*" {
+        return None;
+    }
+
+    if !range.params.is_empty() {
+        let matches = range.params.iter().all(|(key, value)| {
+            let provided_value = provided.params.iter().find(|(k, _)| k == key).map_or("", |(_, v)| v.as_str());
+            value == "*" || value.eq_ignore_ascii_case(provided_value)
+        });
+        if !matches {
+            return None;
+        }
+        specificity |= 1;
+    }
+
+    Some(Priority { order: range.index as isize, q: range.q, specificity, index })
+}
+
+/// Split on `separator` outside double quotes
+fn split_quoted(value: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut quoted = false;
+    for (position, character) in value.char_indices() {
+        match character {
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                parts.push(&value[start..position]);
+                start = position + character.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&value[start..]);
+    parts
+}
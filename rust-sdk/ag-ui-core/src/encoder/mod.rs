@@ -0,0 +1,8 @@
+mod event_encoder;
+pub mod media_type;
+mod proto_encoder;
+mod sse_encoder;
+
+pub use event_encoder::{EventEncoder, SSE_MEDIA_TYPE};
+pub use proto_encoder::ProtoEncoder;
+pub use sse_encoder::{SseEncoder, SseEncoderOptions};
@@ -0,0 +1,67 @@
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+#[cfg(feature = "wasm")]
+use js_sys::Uint8Array;
+use crate::events::BaseEvent;
+use crate::error::Result;
+#[cfg(feature = "wasm")]
+use crate::error::AgUiError;
+use crate::proto;
+
+/// Length-prefixed protobuf encoder
+///
+/// The binary sibling of `SseEncoder`: each event becomes one frame of a
+/// response with the content type `AGUI_MEDIA_TYPE`.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Default)]
+pub struct ProtoEncoder;
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl ProtoEncoder {
+    /// Create a new protobuf encoder for JavaScript
+    #[wasm_bindgen(constructor)]
+    pub fn new_js() -> ProtoEncoder {
+        Self
+    }
+
+    /// The content type of the encoded stream
+    #[wasm_bindgen(js_name = "getContentType")]
+    pub fn content_type_js(&self) -> String {
+        self.content_type()
+    }
+
+    /// Encode an event from JavaScript as a length-prefixed frame
+    #[wasm_bindgen(js_name = "encodeEvent")]
+    pub fn encode_event_js_export(&self, event_js: JsValue) -> std::result::Result<Uint8Array, JsValue> {
+        let event: BaseEvent = serde_wasm_bindgen::from_value(event_js)
+            .map_err(AgUiError::from)?;
+
+        Ok(Uint8Array::from(self.encode_event(&event)?.as_slice()))
+    }
+}
+
+impl ProtoEncoder {
+    pub fn new() -> ProtoEncoder {
+        Self
+    }
+
+    /// The content type of the encoded stream
+    pub fn content_type(&self) -> String {
+        proto::AGUI_MEDIA_TYPE.to_string()
+    }
+
+    /// Encode an event as a length-prefixed frame
+    pub fn encode_event(&self, event: &BaseEvent) -> Result<Vec<u8>> {
+        proto::encode_frame(event)
+    }
+
+    /// Encode multiple events as consecutive frames
+    pub fn encode_events(&self, events: &[BaseEvent]) -> Result<Vec<u8>> {
+        let mut result = Vec::new();
+        for event in events {
+            result.extend(self.encode_event(event)?);
+        }
+        Ok(result)
+    }
+}
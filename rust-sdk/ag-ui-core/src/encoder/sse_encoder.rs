@@ -0,0 +1,243 @@
+use std::cell::Cell;
+use std::fmt::Write as _;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+#[cfg(feature = "wasm")]
+use js_sys::Uint8Array;
+use serde::Deserialize;
+use crate::events::BaseEvent;
+use crate::error::Result;
+#[cfg(feature = "wasm")]
+use crate::error::AgUiError;
+
+/// Which SSE fields an `SseEncoder` writes besides `data:`
+///
+/// From JavaScript pass a plain object to `SseEncoder.withOptions`, e.g.
+/// `{ eventNames: true, ids: true, lastEventId: 41, retry: 3000 }`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SseEncoderOptions {
+    /// Write `event: <TYPE>`, so `EventSource` listeners can subscribe by event type
+    pub event_names: bool,
+    /// Write `id:` fields numbered 1, 2, 3, … so a client can resume with `Last-Event-ID`
+    pub ids: bool,
+    /// The `Last-Event-ID` a client resumed with; numbering continues after it
+    pub last_event_id: Option<u64>,
+    /// Reconnection time in milliseconds, sent with the first event
+    pub retry: Option<u64>,
+}
+
+/// Server-Sent Events encoder
+///
+/// Encoding is plain Rust and produces UTF-8 bytes, so the encoder works in
+/// native servers and tests as well as in JS hosts; the JavaScript methods
+/// only copy those bytes into a `Uint8Array`.
+///
+/// By default each event is written as a single `data:` line, as
+/// `@ag-ui/encoder` does. `with_options` adds event names, IDs and a retry
+/// hint. Text containing line breaks is split over several `data:` (or
+/// comment) lines, which a client joins back with `\n`.
+///
+/// An encoder numbers the events it writes, so it is not `Clone`: a copy
+/// would hand out the same IDs again. Use one encoder per stream.
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug)]
+pub struct SseEncoder {
+    options: SseEncoderOptions,
+    next_id: Cell<u64>,
+    retry_sent: Cell<bool>,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl SseEncoder {
+    /// Create a new SSE encoder
+    #[wasm_bindgen(constructor)]
+    pub fn new_js() -> SseEncoder {
+        Self::new()
+    }
+
+    /// Create an encoder from an options object in JavaScript
+    #[wasm_bindgen(js_name = "withOptions")]
+    pub fn with_options_js(options: JsValue) -> std::result::Result<SseEncoder, JsValue> {
+        let options: SseEncoderOptions = serde_wasm_bindgen::from_value(options)?;
+        Ok(Self::with_options(options))
+    }
+
+    /// Encode an event from JavaScript
+    #[wasm_bindgen(js_name = "encodeEvent")]
+    pub fn encode_event_js_export(&self, event_js: JsValue) -> std::result::Result<Uint8Array, JsValue> {
+        let event: BaseEvent = serde_wasm_bindgen::from_value(event_js)
+            .map_err(AgUiError::from)?;
+
+        Ok(Uint8Array::from(self.encode_event(&event)?.as_slice()))
+    }
+
+    /// Encode an array of events from JavaScript into a single chunk
+    #[wasm_bindgen(js_name = "encodeEvents")]
+    pub fn encode_events_js(&self, events_js: JsValue) -> std::result::Result<Uint8Array, JsValue> {
+        let events: Vec<BaseEvent> = serde_wasm_bindgen::from_value(events_js)
+            .map_err(AgUiError::from)?;
+
+        Ok(Uint8Array::from(self.encode_events(&events)?.as_slice()))
+    }
+
+    /// Encode a message from JavaScript
+    #[wasm_bindgen(js_name = "encodeMessage")]
+    pub fn encode_message_js(&self, message: &str) -> Uint8Array {
+        Uint8Array::from(format_message(message).as_bytes())
+    }
+
+    /// Encode a comment from JavaScript
+    #[wasm_bindgen(js_name = "encodeComment")]
+    pub fn encode_comment_js(&self, comment: &str) -> Uint8Array {
+        Uint8Array::from(format_comment(comment).as_bytes())
+    }
+
+    /// Encode a ping from JavaScript
+    #[wasm_bindgen(js_name = "encodePing")]
+    pub fn encode_ping_js(&self) -> Uint8Array {
+        Uint8Array::from(PING.as_bytes())
+    }
+
+    /// Encode a reconnection time in milliseconds from JavaScript
+    #[wasm_bindgen(js_name = "encodeRetry")]
+    pub fn encode_retry_js(&self, retry: u32) -> Uint8Array {
+        Uint8Array::from(format_retry(retry.into()).as_bytes())
+    }
+}
+
+impl SseEncoder {
+    /// Create a new SSE encoder
+    pub fn new() -> SseEncoder {
+        Self::with_options(SseEncoderOptions::default())
+    }
+
+    /// Create an encoder that writes the fields `options` asks for
+    pub fn with_options(options: SseEncoderOptions) -> Self {
+        let next_id = options.last_event_id.map_or(1, |id| id.saturating_add(1));
+        Self { options, next_id: Cell::new(next_id), retry_sent: Cell::new(false) }
+    }
+
+    pub fn options(&self) -> &SseEncoderOptions {
+        &self.options
+    }
+
+    /// Encode an event as SSE bytes
+    pub fn encode_event(&self, event: &BaseEvent) -> Result<Vec<u8>> {
+        let mut encoded = String::new();
+        self.write_event(&mut encoded, event)?;
+        Ok(encoded.into_bytes())
+    }
+
+    /// Encode several events into one buffer, to send them in a single write
+    pub fn encode_events(&self, events: &[BaseEvent]) -> Result<Vec<u8>> {
+        let mut encoded = String::new();
+        for event in events {
+            self.write_event(&mut encoded, event)?;
+        }
+        Ok(encoded.into_bytes())
+    }
+
+    /// Encode a message as SSE bytes, one `data:` line per line of `message`
+    pub fn encode_message(&self, message: &str) -> Result<Vec<u8>> {
+        Ok(format_message(message).into_bytes())
+    }
+
+    /// Encode a comment, which clients ignore
+    pub fn encode_comment(&self, comment: &str) -> Result<Vec<u8>> {
+        Ok(format_comment(comment).into_bytes())
+    }
+
+    /// Encode a keep-alive ping
+    pub fn encode_ping(&self) -> Result<Vec<u8>> {
+        Ok(PING.as_bytes().to_vec())
+    }
+
+    /// Encode a reconnection time in milliseconds
+    pub fn encode_retry(&self, retry: u64) -> Result<Vec<u8>> {
+        Ok(format_retry(retry).into_bytes())
+    }
+
+    /// Encode an event as SSE string format
+    ///
+    /// Always a single `data:` line: serialized JSON has no line breaks.
+    pub fn encode_event_string(event: &BaseEvent) -> Result<String> {
+        let json = serde_json::to_string(event)?;
+        Ok(format_message(&json))
+    }
+
+    /// Encode multiple events as SSE string format
+    pub fn encode_events_string(events: &[BaseEvent]) -> Result<String> {
+        let mut result = String::new();
+        for event in events {
+            result.push_str(&Self::encode_event_string(event)?);
+        }
+        Ok(result)
+    }
+
+    fn write_event(&self, out: &mut String, event: &BaseEvent) -> Result<()> {
+        let json = serde_json::to_string(event)?;
+
+        if self.options.event_names {
+            let _ = writeln!(out, "event: {}", event.event_type());
+        }
+        if self.options.ids {
+            let id = self.next_id.get();
+            self.next_id.set(id.saturating_add(1));
+            let _ = writeln!(out, "id: {}", id);
+        }
+        if let Some(retry) = self.options.retry.filter(|_| !self.retry_sent.replace(true)) {
+            let _ = writeln!(out, "retry: {}", retry);
+        }
+        write_lines(out, "data: ", &json);
+        out.push('\n');
+        Ok(())
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl SseEncoder {
+    /// Convert SSE string to Uint8Array for JavaScript
+    pub fn to_uint8_array(sse_data: &str) -> std::result::Result<Uint8Array, JsValue> {
+        Ok(Uint8Array::from(sse_data.as_bytes()))
+    }
+}
+
+impl Default for SseEncoder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+const PING: &str = ": ping\n\n";
+
+fn format_message(message: &str) -> String {
+    let mut out = String::new();
+    write_lines(&mut out, "data: ", message);
+    out.push('\n');
+    out
+}
+
+fn format_comment(comment: &str) -> String {
+    let mut out = String::new();
+    write_lines(&mut out, ": ", comment);
+    out.push('\n');
+    out
+}
+
+fn format_retry(retry: u64) -> String {
+    format!("retry: {}\n\n", retry)
+}
+
+/// Write `text` as `prefix`ed lines, splitting at CRLF, LF and CR
+///
+/// A line break in a field value would otherwise end the field early, or
+/// the whole event at a blank line.
+fn write_lines(out: &mut String, prefix: &str, text: &str) {
+    for line in text.split("\r\n").flat_map(|line| line.split(['\r', '\n'])) {
+        out.push_str(prefix);
+        out.push_str(line);
+        out.push('\n');
+    }
+}
@@ -0,0 +1,447 @@
+use thiserror::Error;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::JsCast;
+#[cfg(feature = "wasm")]
+use serde::Serialize;
+use crate::events::{BaseEvent, EventData, EventType};
+
+/// Everything that can go wrong in the SDK
+///
+/// Each variant has a stable `code`, which is also the `code` of the JS
+/// `Error` an error becomes in JavaScript and of the `RUN_ERROR` event that
+/// reports it to a client.
+#[derive(Error, Debug)]
+pub enum AgUiError {
+    #[error("Connection error: {0}")]
+    ConnectionError(String),
+    
+    #[error("HTTP {status} {status_text}{}", if body.is_empty() { String::new() } else { format!(": {}", body) })]
+    HttpError { status: u16, status_text: String, body: String },
+    
+    #[error("Stream error: {0}")]
+    StreamError(String),
+    
+    #[error("Timed out: {0}")]
+    Timeout(String),
+    
+    #[error("Run was aborted")]
+    Aborted,
+    
+    #[error("Cannot resume the run: {0}")]
+    ResumeFailed(String),
+    
+    #[error("Run failed: {message}")]
+    RunError { message: String, code: Option<String> },
+    
+    #[error("JSON error: {0}")]
+    JsonError(#[from] serde_json::Error),
+    
+    #[error("Agent error: {0}")]
+    AgentError(String),
+    
+    #[error("IO error: {0}")]
+    IoError(String),
+    
+    #[error("Encoding error: {0}")]
+    EncodingError(String),
+    
+    #[error("Decoding error{}: {message}", offset.map(|offset| format!(" at byte {}", offset)).unwrap_or_default())]
+    DecodingError { message: String, offset: Option<u64> },
+    
+    #[error("Invalid message: {0}")]
+    InvalidMessage(String),
+    
+    #[error("JSON Patch error: {0}")]
+    PatchError(String),
+    
+    #[error("Cannot apply '{event_type}' event: chunk events must be expanded before they are applied")]
+    ChunkNotExpanded { event_type: EventType },
+    
+    #[error("First {event_type} must have a {field}")]
+    ChunkMissingField { event_type: EventType, field: &'static str },
+    
+    #[error("{violation}")]
+    ProtocolViolation { violation: ProtocolViolation, event: Box<BaseEvent> },
+    
+    #[error("{name}: {message}")]
+    JsError { name: String, message: String, thrown: ThrownValue },
+    
+    #[error("WASM bindgen error: {0}")]
+    WasmBindgenError(String),
+}
+
+/// A rule of the AG-UI protocol an event breaks, as found by `verify::EventVerifier`
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ProtocolViolation {
+    #[error("First event must be 'RUN_STARTED', got '{event_type}'")]
+    RunNotStarted { event_type: EventType },
+    
+    #[error("Cannot send 'RUN_STARTED' while a run is still active. The previous run must be finished with 'RUN_FINISHED' before starting a new run")]
+    RunAlreadyStarted,
+    
+    #[error("Cannot send event type '{event_type}': The run has already finished. Start a new run with 'RUN_STARTED'")]
+    RunAlreadyFinished { event_type: EventType },
+    
+    #[error("Cannot send event type '{event_type}': The run has already errored with 'RUN_ERROR'. No further events can be sent")]
+    RunAlreadyErrored { event_type: EventType },
+    
+    #[error("Cannot send 'RUN_FINISHED' while steps are still active: {}", .step_names.join(", "))]
+    UnfinishedSteps { step_names: Vec<String> },
+    
+    #[error("Cannot send 'RUN_FINISHED' while text messages are still active: {}", .message_ids.join(", "))]
+    UnfinishedTextMessages { message_ids: Vec<String> },
+    
+    #[error("Cannot send 'RUN_FINISHED' while tool calls are still active: {}", .tool_call_ids.join(", "))]
+    UnfinishedToolCalls { tool_call_ids: Vec<String> },
+    
+    #[error("Step \"{step_name}\" is already active for 'STEP_STARTED'")]
+    StepAlreadyActive { step_name: String },
+    
+    #[error("Cannot send 'STEP_FINISHED' for step \"{step_name}\" that was not started")]
+    StepNotActive { step_name: String },
+    
+    #[error("Cannot send 'TEXT_MESSAGE_START' event: A text message with ID '{message_id}' is already in progress. Complete it with 'TEXT_MESSAGE_END' first")]
+    TextMessageAlreadyActive { message_id: String },
+    
+    #[error("Cannot send '{event_type}' event: No active text message found with ID '{message_id}'. Start a text message with 'TEXT_MESSAGE_START' first")]
+    TextMessageNotActive { event_type: EventType, message_id: String },
+    
+    #[error("Cannot send 'TOOL_CALL_START' event: A tool call with ID '{tool_call_id}' is already in progress. Complete it with 'TOOL_CALL_END' first")]
+    ToolCallAlreadyActive { tool_call_id: String },
+    
+    #[error("Cannot send '{event_type}' event: No active tool call found with ID '{tool_call_id}'. Start a tool call with 'TOOL_CALL_START' first")]
+    ToolCallNotActive { event_type: EventType, tool_call_id: String },
+    
+    #[error("Cannot send 'THINKING_START' event: A thinking step is already in progress. End it with 'THINKING_END' first")]
+    ThinkingAlreadyActive,
+    
+    #[error("Cannot send '{event_type}' event: No active thinking step found. A 'THINKING_START' event must be sent first")]
+    ThinkingNotActive { event_type: EventType },
+    
+    #[error("Cannot send 'THINKING_TEXT_MESSAGE_START' event: A thinking message is already in progress. Complete it with 'THINKING_TEXT_MESSAGE_END' first")]
+    ThinkingMessageAlreadyActive,
+    
+    #[error("Cannot send '{event_type}' event: No active thinking message found. A 'THINKING_TEXT_MESSAGE_START' event must be sent first")]
+    ThinkingMessageNotActive { event_type: EventType },
+
+    /// A violation the other side reported in a `RUN_ERROR`, which only carries its message
+    #[error("{message}")]
+    Reported { message: String },
+}
+
+impl AgUiError {
+    /// The stable code of the error, such as `"HTTP_ERROR"` or `"PROTOCOL_VIOLATION"`
+    ///
+    /// Messages may change between releases; codes do not.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AgUiError::ConnectionError(_) => "CONNECTION_ERROR",
+            AgUiError::HttpError { .. } => "HTTP_ERROR",
+            AgUiError::StreamError(_) => "STREAM_ERROR",
+            AgUiError::Timeout(_) => "TIMEOUT",
+            AgUiError::Aborted => "ABORTED",
+            AgUiError::ResumeFailed(_) => "RESUME_FAILED",
+            AgUiError::RunError { .. } => "RUN_ERROR",
+            AgUiError::JsonError(_) => "JSON_ERROR",
+            AgUiError::AgentError(_) => "AGENT_ERROR",
+            AgUiError::IoError(_) => "IO_ERROR",
+            AgUiError::EncodingError(_) => "ENCODING_ERROR",
+            AgUiError::DecodingError { .. } => "DECODING_ERROR",
+            AgUiError::InvalidMessage(_) => "INVALID_MESSAGE",
+            AgUiError::PatchError(_) => "PATCH_ERROR",
+            AgUiError::ChunkNotExpanded { .. } => "CHUNK_NOT_EXPANDED",
+            AgUiError::ChunkMissingField { .. } | AgUiError::ProtocolViolation { .. } => "PROTOCOL_VIOLATION",
+            AgUiError::JsError { .. } | AgUiError::WasmBindgenError(_) => "JS_ERROR",
+        }
+    }
+
+    /// `violation`, committed by `event`
+    pub fn protocol(violation: ProtocolViolation, event: &BaseEvent) -> Self {
+        AgUiError::ProtocolViolation { violation, event: Box::new(event.clone()) }
+    }
+
+    /// The protocol rule the error reports a break of, if it does
+    pub fn violation(&self) -> Option<&ProtocolViolation> {
+        match self {
+            AgUiError::ProtocolViolation { violation, .. } => Some(violation),
+            _ => None,
+        }
+    }
+
+    /// A `JsError` that was not thrown from JavaScript, such as one a `RUN_ERROR` reports
+    pub fn js_error(name: impl Into<String>, message: impl Into<String>) -> Self {
+        AgUiError::JsError { name: name.into(), message: message.into(), thrown: ThrownValue::default() }
+    }
+
+    /// A `DecodingError` whose position is not known
+    pub fn decoding(message: impl Into<String>) -> Self {
+        AgUiError::DecodingError { message: message.into(), offset: None }
+    }
+
+    /// Place a decoding error at byte `offset` of the stream it was read from
+    ///
+    /// A `JsonError` becomes a `DecodingError`, since the offset is where
+    /// the stream stopped being valid. Other errors are returned unchanged.
+    pub fn at_offset(self, offset: u64) -> Self {
+        match self {
+            AgUiError::DecodingError { message, .. } => AgUiError::DecodingError { message, offset: Some(offset) },
+            AgUiError::JsonError(error) => {
+                AgUiError::DecodingError { message: error.to_string(), offset: Some(offset) }
+            }
+            error => error,
+        }
+    }
+
+    /// The error a `RUN_ERROR` event with `message` and `code` reports
+    ///
+    /// Every code `code()` returns maps back onto its variant, the inverse of
+    /// `to_run_error`. What a `RUN_ERROR` cannot carry is lost: a decoding
+    /// error's offset, and the rule and event of a protocol violation, which
+    /// arrives as `ProtocolViolation::Reported` with the `RUN_ERROR` as its
+    /// event. Any other code, or an HTTP or chunk error whose message is not
+    /// one this SDK writes, keeps the agent's message and code in a `RunError`.
+    pub fn from_run_error(message: impl Into<String>, code: Option<&str>) -> Self {
+        let message = message.into();
+        let run_error = |message| AgUiError::RunError { message, code: code.map(str::to_string) };
+        match code {
+            Some("CONNECTION_ERROR") => AgUiError::ConnectionError(message),
+            Some("HTTP_ERROR") => parse_http_error(&message).unwrap_or_else(|| run_error(message)),
+            Some("STREAM_ERROR") => AgUiError::StreamError(message),
+            Some("TIMEOUT") => AgUiError::Timeout(message),
+            Some("ABORTED") => AgUiError::Aborted,
+            Some("RESUME_FAILED") => AgUiError::ResumeFailed(message),
+            Some("JSON_ERROR") => AgUiError::JsonError(serde::de::Error::custom(message)),
+            Some("AGENT_ERROR") => AgUiError::AgentError(message),
+            Some("IO_ERROR") => AgUiError::IoError(message),
+            Some("ENCODING_ERROR") => AgUiError::EncodingError(message),
+            Some("DECODING_ERROR") => AgUiError::DecodingError { message, offset: None },
+            Some("INVALID_MESSAGE") => AgUiError::InvalidMessage(message),
+            Some("PATCH_ERROR") => AgUiError::PatchError(message),
+            Some("CHUNK_NOT_EXPANDED") => match parse_quoted_event_type(&message) {
+                Some(event_type) => AgUiError::ChunkNotExpanded { event_type },
+                None => run_error(message),
+            },
+            Some("PROTOCOL_VIOLATION") => {
+                let event = BaseEvent::run_error(message.clone(), code.map(str::to_string));
+                AgUiError::protocol(ProtocolViolation::Reported { message }, &event)
+            }
+            Some("JS_ERROR") => match message.split_once(": ") {
+                Some((name, message)) => AgUiError::js_error(name, message),
+                None => AgUiError::WasmBindgenError(message),
+            },
+            _ => run_error(message),
+        }
+    }
+
+    /// The error a `RUN_ERROR` event reports; `None` for any other event
+    pub fn from_event(event: &BaseEvent) -> Option<Self> {
+        match &event.data {
+            EventData::RunError(data) => Some(AgUiError::from_run_error(data.message.clone(), data.code.as_deref())),
+            _ => None,
+        }
+    }
+
+    /// The `RUN_ERROR` event that reports the error to a client
+    ///
+    /// The inverse of `from_run_error`: a `RunError` is sent as it arrived,
+    /// any other error with its `code` and its message without the prefix its
+    /// variant adds, so the client does not repeat it.
+    pub fn to_run_error(&self) -> BaseEvent {
+        match self {
+            AgUiError::RunError { message, code } => BaseEvent::run_error(message.clone(), code.clone()),
+            error => BaseEvent::run_error(error.bare_message(), Some(error.code().to_string())),
+        }
+    }
+
+    /// The error's message without the prefix its variant adds
+    ///
+    /// Errors whose message is all detail, such as HTTP errors and protocol
+    /// violations, keep all of it.
+    fn bare_message(&self) -> String {
+        match self {
+            AgUiError::ConnectionError(message)
+            | AgUiError::StreamError(message)
+            | AgUiError::Timeout(message)
+            | AgUiError::ResumeFailed(message)
+            | AgUiError::AgentError(message)
+            | AgUiError::IoError(message)
+            | AgUiError::EncodingError(message)
+            | AgUiError::InvalidMessage(message)
+            | AgUiError::PatchError(message)
+            | AgUiError::WasmBindgenError(message)
+            | AgUiError::RunError { message, .. }
+            | AgUiError::DecodingError { message, .. } => message.clone(),
+            AgUiError::JsonError(error) => error.to_string(),
+            error => error.to_string(),
+        }
+    }
+}
+
+/// The JS value a `JsError` was thrown as, which becomes the `cause` of the
+/// error again when it goes back to JavaScript
+///
+/// Only kept on single-threaded wasm32, where nothing can move it to another
+/// thread; elsewhere it is empty and the `cause` is rebuilt from the name and
+/// message.
+#[derive(Clone, Default)]
+pub struct ThrownValue {
+    #[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_feature = "atomics")))]
+    value: Option<JsValue>,
+}
+
+// SAFETY: without the atomics feature a wasm32 module runs on one thread
+#[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_feature = "atomics")))]
+unsafe impl Send for ThrownValue {}
+#[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_feature = "atomics")))]
+unsafe impl Sync for ThrownValue {}
+
+#[cfg(all(feature = "wasm", target_arch = "wasm32", not(target_feature = "atomics")))]
+impl ThrownValue {
+    fn new(value: &JsValue) -> Self {
+        Self { value: Some(value.clone()) }
+    }
+
+    /// The thrown value, when it was kept
+    pub fn value(&self) -> Option<&JsValue> {
+        self.value.as_ref()
+    }
+}
+
+#[cfg(all(feature = "wasm", not(all(target_arch = "wasm32", not(target_feature = "atomics")))))]
+impl ThrownValue {
+    fn new(_value: &JsValue) -> Self {
+        Self::default()
+    }
+
+    /// The thrown value, when it was kept
+    pub fn value(&self) -> Option<&JsValue> {
+        None
+    }
+}
+
+impl std::fmt::Debug for ThrownValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ThrownValue(..)")
+    }
+}
+
+/// The `HttpError` whose message is `message`, e.g. `HTTP 503 Service Unavailable: try later`
+fn parse_http_error(message: &str) -> Option<AgUiError> {
+    let (status, rest) = message.strip_prefix("HTTP ")?.split_once(' ')?;
+    let (status_text, body) = rest.split_once(": ").unwrap_or((rest, ""));
+    Some(AgUiError::HttpError {
+        status: status.parse().ok()?,
+        status_text: status_text.to_string(),
+        body: body.to_string(),
+    })
+}
+
+/// The event type named in the first quotes of `message`, as in a `ChunkNotExpanded` message
+fn parse_quoted_event_type(message: &str) -> Option<EventType> {
+    let (_, rest) = message.split_once('\'')?;
+    let (name, _) = rest.split_once('\'')?;
+    serde_json::from_value(serde_json::Value::String(name.to_string())).ok()
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen(inline_js = "
+export class AgUiError extends Error {
+    constructor(message, code, cause) {
+        super(message, cause === undefined ? undefined : { cause });
+        this.name = 'AgUiError';
+        this.code = code;
+    }
+}
+")]
+extern "C" {
+    /// The `Error` subclass SDK errors become in JavaScript
+    #[wasm_bindgen(extends = js_sys::Error, js_name = AgUiError)]
+    type JsAgUiError;
+
+    #[wasm_bindgen(constructor, js_class = AgUiError)]
+    fn new(message: &str, code: &str, cause: JsValue) -> JsAgUiError;
+}
+
+#[cfg(feature = "wasm")]
+impl From<JsValue> for AgUiError {
+    /// A thrown JS value: an `AbortError` is `Aborted`, a `TimeoutError` is
+    /// `Timeout`, and any other `Error` a `JsError` with its name and message
+    /// that keeps the value itself; other values are kept as text
+    fn from(value: JsValue) -> Self {
+        if let Some(s) = value.as_string() {
+            return AgUiError::WasmBindgenError(s);
+        }
+        if !value.is_object() {
+            return AgUiError::WasmBindgenError(format!("{:?}", value));
+        }
+        // DOMExceptions are not `Error`s on every host, so read the properties
+        let property = |name: &str| {
+            js_sys::Reflect::get(&value, &JsValue::from_str(name)).ok().and_then(|v| v.as_string())
+        };
+        match (property("name"), property("message")) {
+            (Some(name), _) if name == "AbortError" => AgUiError::Aborted,
+            (Some(name), Some(message)) if name == "TimeoutError" => AgUiError::Timeout(message),
+            (Some(name), Some(message)) => AgUiError::JsError { name, message, thrown: ThrownValue::new(&value) },
+            _ => AgUiError::WasmBindgenError(format!("{:?}", value)),
+        }
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<AgUiError> for JsValue {
+    /// An `AgUiError` JS `Error` with the error's `message`, `code` and, for
+    /// errors caused by another one, `cause`, which is the very value thrown
+    /// when the error came from JavaScript
+    ///
+    /// HTTP errors also carry `status`, `statusText` and `body`, decoding
+    /// errors `offset`, protocol violations the offending `event`, and run
+    /// errors the agent's own code as `runErrorCode`.
+    fn from(error: AgUiError) -> Self {
+        let cause = match &error {
+            AgUiError::JsError { name, message, thrown } => match thrown.value() {
+                Some(value) => value.clone(),
+                None => {
+                    let cause = js_sys::Error::new(message);
+                    cause.set_name(name);
+                    cause.into()
+                }
+            },
+            AgUiError::JsonError(error) => js_sys::Error::new(&error.to_string()).into(),
+            AgUiError::WasmBindgenError(message) => JsValue::from_str(message),
+            _ => JsValue::UNDEFINED,
+        };
+        let js_error = JsAgUiError::new(&error.to_string(), error.code(), cause);
+
+        let set = |key: &str, value: JsValue| {
+            let _ = js_sys::Reflect::set(&js_error, &JsValue::from_str(key), &value);
+        };
+        match &error {
+            AgUiError::HttpError { status, status_text, body } => {
+                set("status", JsValue::from(*status));
+                set("statusText", JsValue::from_str(status_text));
+                set("body", JsValue::from_str(body));
+            }
+            AgUiError::DecodingError { offset: Some(offset), .. } => set("offset", JsValue::from(*offset as f64)),
+            AgUiError::ProtocolViolation { event, .. } => {
+                let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+                if let Ok(event) = event.serialize(&serializer) {
+                    set("event", event);
+                }
+            }
+            AgUiError::RunError { code: Some(code), .. } => set("runErrorCode", JsValue::from_str(code)),
+            _ => {}
+        }
+        js_error.unchecked_into()
+    }
+}
+
+#[cfg(feature = "wasm")]
+impl From<serde_wasm_bindgen::Error> for AgUiError {
+    fn from(error: serde_wasm_bindgen::Error) -> Self {
+        AgUiError::WasmBindgenError(error.to_string())
+    }
+}
+
+pub type Result<T> = std::result::Result<T, AgUiError>; 
\ No newline at end of file
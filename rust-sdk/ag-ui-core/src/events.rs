@@ -0,0 +1,506 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::patch::{self, PatchOperation};
+use crate::types::{Message, Role, RunAgentInput, State};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+/// Event types in the AG-UI protocol
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EventType {
+    // Lifecycle events
+    RunStarted,
+    RunFinished,
+    RunAborted,
+    RunError,
+    StepStarted,
+    StepFinished,
+    
+    // Message events
+    TextMessageStart,
+    TextMessageContent,
+    TextMessageChunk,
+    TextMessageEnd,
+    MessagesSnapshot,
+    
+    // Thinking events
+    ThinkingTextMessageStart,
+    ThinkingTextMessageContent,
+    ThinkingTextMessageEnd,
+    ThinkingStart,
+    ThinkingEnd,
+    
+    // Tool events
+    ToolCallStart,
+    ToolCallArgs,
+    ToolCallChunk,
+    ToolCallEnd,
+    ToolCallResult,
+    
+    // State events
+    StateSnapshot,
+    StateDelta,
+    
+    // Error events
+    Error,
+    
+    // Other events
+    Raw,
+    Custom,
+}
+
+impl EventType {
+    /// The wire name of this event type, e.g. `TEXT_MESSAGE_START`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventType::RunStarted => "RUN_STARTED",
+            EventType::RunFinished => "RUN_FINISHED",
+            EventType::RunAborted => "RUN_ABORTED",
+            EventType::RunError => "RUN_ERROR",
+            EventType::StepStarted => "STEP_STARTED",
+            EventType::StepFinished => "STEP_FINISHED",
+            EventType::TextMessageStart => "TEXT_MESSAGE_START",
+            EventType::TextMessageContent => "TEXT_MESSAGE_CONTENT",
+            EventType::TextMessageChunk => "TEXT_MESSAGE_CHUNK",
+            EventType::TextMessageEnd => "TEXT_MESSAGE_END",
+            EventType::MessagesSnapshot => "MESSAGES_SNAPSHOT",
+            EventType::ThinkingTextMessageStart => "THINKING_TEXT_MESSAGE_START",
+            EventType::ThinkingTextMessageContent => "THINKING_TEXT_MESSAGE_CONTENT",
+            EventType::ThinkingTextMessageEnd => "THINKING_TEXT_MESSAGE_END",
+            EventType::ThinkingStart => "THINKING_START",
+            EventType::ThinkingEnd => "THINKING_END",
+            EventType::ToolCallStart => "TOOL_CALL_START",
+            EventType::ToolCallArgs => "TOOL_CALL_ARGS",
+            EventType::ToolCallChunk => "TOOL_CALL_CHUNK",
+            EventType::ToolCallEnd => "TOOL_CALL_END",
+            EventType::ToolCallResult => "TOOL_CALL_RESULT",
+            EventType::StateSnapshot => "STATE_SNAPSHOT",
+            EventType::StateDelta => "STATE_DELTA",
+            EventType::Error => "ERROR",
+            EventType::Raw => "RAW",
+            EventType::Custom => "CUSTOM",
+        }
+    }
+}
+
+impl std::fmt::Display for EventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Base event structure
+///
+/// The event type is not stored separately: it is derived from `data`, so an
+/// event can never carry a payload that contradicts its `type`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BaseEvent {
+    /// Serialized as milliseconds since the Unix epoch, as in `@ag-ui/core`
+    #[serde(default, with = "chrono::serde::ts_milliseconds_option")]
+    pub timestamp: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub raw_event: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub data: EventData,
+}
+
+/// Wire representation of `BaseEvent`, keeping the `@ag-ui/core` field order:
+/// `type`, `timestamp`, `rawEvent`, then the payload fields.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BaseEventRepr<'a> {
+    #[serde(rename = "type")]
+    event_type: EventType,
+    #[serde(
+        with = "chrono::serde::ts_milliseconds_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    timestamp: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw_event: Option<&'a serde_json::Value>,
+    #[serde(flatten)]
+    data: EventPayload<'a>,
+}
+
+impl Serialize for BaseEvent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        BaseEventRepr {
+            event_type: self.event_type(),
+            timestamp: self.timestamp,
+            raw_event: self.raw_event.as_ref(),
+            data: EventPayload::from(&self.data),
+        }
+        .serialize(serializer)
+    }
+}
+
+macro_rules! event_data {
+    ($($variant:ident($payload:ident),)*) => {
+        /// Event data variants, discriminated by the `type` field
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+        pub enum EventData {
+            $($variant($payload),)*
+        }
+
+        impl EventData {
+            /// The protocol event type of this payload
+            pub fn event_type(&self) -> EventType {
+                match self {
+                    $(EventData::$variant(_) => EventType::$variant,)*
+                }
+            }
+        }
+
+        $(
+            impl From<$payload> for EventData {
+                fn from(data: $payload) -> Self {
+                    EventData::$variant(data)
+                }
+            }
+        )*
+
+        /// Borrowed payload without the `type` tag, flattened into `BaseEventRepr`
+        #[derive(Serialize)]
+        #[serde(untagged)]
+        enum EventPayload<'a> {
+            $($variant(&'a $payload),)*
+        }
+
+        impl<'a> From<&'a EventData> for EventPayload<'a> {
+            fn from(data: &'a EventData) -> Self {
+                match data {
+                    $(EventData::$variant(payload) => EventPayload::$variant(payload),)*
+                }
+            }
+        }
+    };
+}
+
+event_data! {
+    RunStarted(RunStartedEvent),
+    RunFinished(RunFinishedEvent),
+    RunAborted(RunAbortedEvent),
+    RunError(RunErrorEvent),
+    StepStarted(StepStartedEvent),
+    StepFinished(StepFinishedEvent),
+    TextMessageStart(TextMessageStartEvent),
+    TextMessageContent(TextMessageContentEvent),
+    TextMessageChunk(TextMessageChunkEvent),
+    TextMessageEnd(TextMessageEndEvent),
+    MessagesSnapshot(MessagesSnapshotEvent),
+    ThinkingTextMessageStart(ThinkingTextMessageStartEvent),
+    ThinkingTextMessageContent(ThinkingTextMessageContentEvent),
+    ThinkingTextMessageEnd(ThinkingTextMessageEndEvent),
+    ThinkingStart(ThinkingStartEvent),
+    ThinkingEnd(ThinkingEndEvent),
+    ToolCallStart(ToolCallStartEvent),
+    ToolCallArgs(ToolCallArgsEvent),
+    ToolCallChunk(ToolCallChunkEvent),
+    ToolCallEnd(ToolCallEndEvent),
+    ToolCallResult(ToolCallResultEvent),
+    StateSnapshot(StateSnapshotEvent),
+    StateDelta(StateDeltaEvent),
+    Error(ErrorEvent),
+    Raw(RawEvent),
+    Custom(CustomEvent),
+}
+
+// Event structures
+//
+// Field sets follow the `@ag-ui/core` event schemas. `RunAborted` and `Error`
+// are extensions of this crate and have no counterpart in the spec.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunStartedEvent {
+    pub thread_id: String,
+    pub run_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_run_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<RunAgentInput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunFinishedEvent {
+    pub thread_id: String,
+    pub run_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunAbortedEvent {
+    pub thread_id: String,
+    pub run_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunErrorEvent {
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepStartedEvent {
+    pub step_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StepFinishedEvent {
+    pub step_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextMessageStartEvent {
+    pub message_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextMessageContentEvent {
+    pub message_id: String,
+    pub delta: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextMessageEndEvent {
+    pub message_id: String,
+}
+
+/// Compact form of a text message; every field may be omitted after the first chunk
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TextMessageChunkEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessagesSnapshotEvent {
+    pub messages: Vec<Message>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThinkingTextMessageStartEvent {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThinkingTextMessageContentEvent {
+    pub delta: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThinkingTextMessageEndEvent {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThinkingStartEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThinkingEndEvent {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCallStartEvent {
+    pub tool_call_id: String,
+    pub tool_call_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_message_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCallArgsEvent {
+    pub tool_call_id: String,
+    pub delta: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCallEndEvent {
+    pub tool_call_id: String,
+}
+
+/// Compact form of a tool call; every field may be omitted after the first chunk
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCallChunkEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_message_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCallResultEvent {
+    pub message_id: String,
+    pub tool_call_id: String,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<Role>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateSnapshotEvent {
+    pub snapshot: State,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StateDeltaEvent {
+    pub delta: serde_json::Value,
+}
+
+impl StateDeltaEvent {
+    /// A delta carrying `patch`
+    pub fn new(patch: &[PatchOperation]) -> Self {
+        Self { delta: serde_json::to_value(patch).expect("patch operations serialize to JSON") }
+    }
+
+    /// The delta that turns `from` into `to`
+    pub fn between(from: &State, to: &State) -> Self {
+        Self::new(&patch::diff_states(from, to))
+    }
+
+    /// Apply this delta to `state`, leaving it untouched if any operation fails
+    pub fn apply_to(&self, state: &mut State) -> crate::error::Result<()> {
+        patch::apply_state_patch(state, &patch::parse_patch(&self.delta)?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorEvent {
+    pub error: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawEvent {
+    pub event: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomEvent {
+    pub name: String,
+    #[serde(default)]
+    pub value: serde_json::Value,
+}
+
+// Helper implementations
+impl BaseEvent {
+    /// Create an event from any payload, stamped with the current time
+    pub fn new(data: impl Into<EventData>) -> Self {
+        Self {
+            timestamp: Some(Utc::now()),
+            raw_event: None,
+            data: data.into(),
+        }
+    }
+
+    /// The protocol event type, derived from the payload
+    pub fn event_type(&self) -> EventType {
+        self.data.event_type()
+    }
+
+    pub fn run_started(thread_id: String, run_id: String) -> Self {
+        Self {
+            timestamp: Some(Utc::now()),
+            raw_event: None,
+            data: EventData::RunStarted(RunStartedEvent {
+                thread_id,
+                run_id,
+                parent_run_id: None,
+                input: None,
+            }),
+        }
+    }
+    
+    pub fn run_finished(thread_id: String, run_id: String) -> Self {
+        Self {
+            timestamp: Some(Utc::now()),
+            raw_event: None,
+            data: EventData::RunFinished(RunFinishedEvent { thread_id, run_id, result: None }),
+        }
+    }
+    
+    pub fn run_error(message: String, code: Option<String>) -> Self {
+        Self {
+            timestamp: Some(Utc::now()),
+            raw_event: None,
+            data: EventData::RunError(RunErrorEvent { message, code }),
+        }
+    }
+    
+    pub fn text_message_content(message_id: String, delta: String) -> Self {
+        Self {
+            timestamp: Some(Utc::now()),
+            raw_event: None,
+            data: EventData::TextMessageContent(TextMessageContentEvent { message_id, delta }),
+        }
+    }
+    
+    pub fn text_message_start(message_id: String, role: Option<Role>) -> Self {
+        Self {
+            timestamp: Some(Utc::now()),
+            raw_event: None,
+            data: EventData::TextMessageStart(TextMessageStartEvent { message_id, role }),
+        }
+    }
+    
+    pub fn text_message_end(message_id: String) -> Self {
+        Self {
+            timestamp: Some(Utc::now()),
+            raw_event: None,
+            data: EventData::TextMessageEnd(TextMessageEndEvent { message_id }),
+        }
+    }
+    
+    pub fn error(error: String, code: Option<String>) -> Self {
+        Self {
+            timestamp: Some(Utc::now()),
+            raw_event: None,
+            data: EventData::Error(ErrorEvent { error, code, details: None }),
+        }
+    }
+} 
\ No newline at end of file
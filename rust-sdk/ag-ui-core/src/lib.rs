@@ -0,0 +1,26 @@
+//! Platform-neutral core of the AG-UI SDK
+//!
+//! Protocol types, errors, and the SSE and protobuf codecs, shared by
+//! `ag-ui-wasm` for V8 isolates and `ag-ui-rust` for native targets. Nothing
+//! here depends on a JS host or an async runtime; the `wasm` feature adds the
+//! JS bindings `ag-ui-wasm` needs.
+
+pub mod compat;
+pub mod encoder;
+pub mod error;
+pub mod events;
+pub mod patch;
+pub mod proto;
+pub mod proto_parser;
+pub mod retry;
+pub mod sse_parser;
+pub mod types;
+
+pub use encoder::{EventEncoder, ProtoEncoder, SseEncoder, SseEncoderOptions, SSE_MEDIA_TYPE};
+pub use error::{AgUiError, ProtocolViolation, Result};
+pub use events::{BaseEvent, EventData, EventType};
+pub use proto::AGUI_MEDIA_TYPE;
+pub use proto_parser::ProtoParser;
+pub use retry::{RetryPolicy, Resumption};
+pub use sse_parser::{SseEvent, SseParser};
+pub use types::{Message, Role, RunAgentInput, State};
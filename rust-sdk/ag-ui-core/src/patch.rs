@@ -0,0 +1,302 @@
+//! JSON Patch (RFC 6902) support for `STATE_DELTA` events
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::types::State;
+use crate::error::{AgUiError, Result};
+
+/// A single JSON Patch operation
+///
+/// Members other than `op`, `path`, `from` and `value` are ignored, as the
+/// RFC requires.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+    Move { from: String, path: String },
+    Copy { from: String, path: String },
+    Test { path: String, value: Value },
+}
+
+impl PatchOperation {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PatchOperation::Add { .. } => "add",
+            PatchOperation::Remove { .. } => "remove",
+            PatchOperation::Replace { .. } => "replace",
+            PatchOperation::Move { .. } => "move",
+            PatchOperation::Copy { .. } => "copy",
+            PatchOperation::Test { .. } => "test",
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOperation::Add { path, .. }
+            | PatchOperation::Remove { path }
+            | PatchOperation::Replace { path, .. }
+            | PatchOperation::Move { path, .. }
+            | PatchOperation::Copy { path, .. }
+            | PatchOperation::Test { path, .. } => path,
+        }
+    }
+}
+
+/// Read a patch document such as `StateDeltaEvent::delta`
+pub fn parse_patch(delta: &Value) -> Result<Vec<PatchOperation>> {
+    let operations = delta
+        .as_array()
+        .ok_or_else(|| AgUiError::PatchError("patch must be an array of operations".to_string()))?;
+
+    operations
+        .iter()
+        .enumerate()
+        .map(|(index, operation)| {
+            PatchOperation::deserialize(operation)
+                .map_err(|error| AgUiError::PatchError(format!("operation {} is invalid: {}", index, error)))
+        })
+        .collect()
+}
+
+/// Apply `patch` to `target`
+///
+/// The patch is applied to a copy first, so `target` is left untouched when
+/// any operation fails.
+pub fn apply_patch(target: &mut Value, patch: &[PatchOperation]) -> Result<()> {
+    let mut patched = target.clone();
+    for (index, operation) in patch.iter().enumerate() {
+        apply_operation(&mut patched, operation).map_err(|message| {
+            AgUiError::PatchError(format!("operation {} ({}) failed: {}", index, operation.name(), message))
+        })?;
+    }
+    *target = patched;
+    Ok(())
+}
+
+/// Apply `patch` to an agent state, which must remain a JSON object
+///
+/// Like `apply_patch`, a failing patch leaves `state` as it was.
+pub fn apply_state_patch(state: &mut State, patch: &[PatchOperation]) -> Result<()> {
+    let mut document = Value::Object(state.clone().into_iter().collect());
+    apply_patch(&mut document, patch)?;
+    match document {
+        Value::Object(map) => {
+            *state = map.into_iter().collect();
+            Ok(())
+        }
+        _ => Err(AgUiError::PatchError("state must remain a JSON object".to_string())),
+    }
+}
+
+fn apply_operation(document: &mut Value, operation: &PatchOperation) -> std::result::Result<(), String> {
+    match operation {
+        PatchOperation::Add { path, value } => add(document, path, value.clone()),
+        PatchOperation::Remove { path } => remove(document, path).map(drop),
+        PatchOperation::Replace { path, value } => {
+            *pointer_mut(document, path)? = value.clone();
+            Ok(())
+        }
+        PatchOperation::Move { from, path } => {
+            if path.len() > from.len() && path.starts_with(from.as_str()) && path[from.len()..].starts_with('/') {
+                return Err(format!("cannot move '{}' into its own child '{}'", from, path));
+            }
+            let value = remove(document, from)?;
+            add(document, path, value)
+        }
+        PatchOperation::Copy { from, path } => {
+            let value = pointer_mut(document, from)?.clone();
+            add(document, path, value)
+        }
+        PatchOperation::Test { path, value } => {
+            if !json_equal(pointer_mut(document, path)?, value) {
+                return Err(format!("value at '{}' does not match", path));
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Split a JSON Pointer into its parent pointer and unescaped last token
+fn split_pointer(path: &str) -> std::result::Result<(&str, String), String> {
+    if !path.starts_with('/') {
+        return Err(format!("invalid JSON pointer '{}'", path));
+    }
+    let index = path.rfind('/').unwrap_or(0);
+    Ok((&path[..index], path[index + 1..].replace("~1", "/").replace("~0", "~")))
+}
+
+fn pointer_mut<'a>(document: &'a mut Value, path: &str) -> std::result::Result<&'a mut Value, String> {
+    if !path.is_empty() && !path.starts_with('/') {
+        return Err(format!("invalid JSON pointer '{}'", path));
+    }
+    document.pointer_mut(path).ok_or_else(|| format!("path '{}' does not exist", path))
+}
+
+fn add(document: &mut Value, path: &str, value: Value) -> std::result::Result<(), String> {
+    if path.is_empty() {
+        *document = value;
+        return Ok(());
+    }
+
+    let (parent, token) = split_pointer(path)?;
+    match pointer_mut(document, parent)? {
+        Value::Object(map) => {
+            map.insert(token, value);
+            Ok(())
+        }
+        Value::Array(items) => {
+            let index = if token == "-" { items.len() } else { array_index(&token, items.len() + 1, path)? };
+            items.insert(index, value);
+            Ok(())
+        }
+        _ => Err(format!("cannot add '{}' to a scalar value", path)),
+    }
+}
+
+fn remove(document: &mut Value, path: &str) -> std::result::Result<Value, String> {
+    if path.is_empty() {
+        return Err("cannot remove the document root".to_string());
+    }
+
+    let (parent, token) = split_pointer(path)?;
+    match pointer_mut(document, parent)? {
+        Value::Object(map) => map.remove(&token).ok_or_else(|| format!("path '{}' does not exist", path)),
+        Value::Array(items) => {
+            let index = array_index(&token, items.len(), path)?;
+            Ok(items.remove(index))
+        }
+        _ => Err(format!("path '{}' does not exist", path)),
+    }
+}
+
+/// Parse an array index token, which must be below `bound`
+fn array_index(token: &str, bound: usize, path: &str) -> std::result::Result<usize, String> {
+    let valid = !token.is_empty()
+        && token.bytes().all(|byte| byte.is_ascii_digit())
+        && (token == "0" || !token.starts_with('0'));
+    match token.parse::<usize>() {
+        Ok(index) if valid && index < bound => Ok(index),
+        _ => Err(format!("invalid array index in '{}'", path)),
+    }
+}
+
+/// JSON equality as `test` defines it: numbers compare by value
+fn json_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(left), Value::Number(right)) => {
+            left == right || left.as_f64() == right.as_f64()
+        }
+        (Value::Array(left), Value::Array(right)) => {
+            left.len() == right.len() && left.iter().zip(right).all(|(left, right)| json_equal(left, right))
+        }
+        (Value::Object(left), Value::Object(right)) => {
+            left.len() == right.len()
+                && left.iter().all(|(key, value)| right.get(key).is_some_and(|other| json_equal(value, other)))
+        }
+        _ => left == right,
+    }
+}
+
+/// Compute a patch that turns `from` into `to`
+///
+/// Objects are compared member by member and arrays element by element
+/// around their longest common subsequence, so unchanged values never
+/// appear in the patch. Values of different types are replaced whole.
+pub fn diff(from: &Value, to: &Value) -> Vec<PatchOperation> {
+    let mut patch = Vec::new();
+    diff_into(&mut patch, String::new(), from, to);
+    patch
+}
+
+/// Compute the patch between two agent states, for a `STATE_DELTA` event
+pub fn diff_states(from: &State, to: &State) -> Vec<PatchOperation> {
+    let from: Map<String, Value> = from.clone().into_iter().collect();
+    let to: Map<String, Value> = to.clone().into_iter().collect();
+    let mut patch = Vec::new();
+    diff_objects(&mut patch, "", &from, &to);
+    patch
+}
+
+fn diff_into(patch: &mut Vec<PatchOperation>, path: String, from: &Value, to: &Value) {
+    match (from, to) {
+        _ if from == to => {}
+        (Value::Object(from), Value::Object(to)) => diff_objects(patch, &path, from, to),
+        (Value::Array(from), Value::Array(to)) => diff_arrays(patch, &path, from, to),
+        _ => patch.push(PatchOperation::Replace { path, value: to.clone() }),
+    }
+}
+
+fn diff_objects(patch: &mut Vec<PatchOperation>, path: &str, from: &Map<String, Value>, to: &Map<String, Value>) {
+    for (key, value) in from {
+        let member = format!("{}/{}", path, escape(key));
+        match to.get(key) {
+            Some(other) => diff_into(patch, member, value, other),
+            None => patch.push(PatchOperation::Remove { path: member }),
+        }
+    }
+    for (key, value) in to {
+        if !from.contains_key(key) {
+            patch.push(PatchOperation::Add { path: format!("{}/{}", path, escape(key)), value: value.clone() });
+        }
+    }
+}
+
+fn diff_arrays(patch: &mut Vec<PatchOperation>, path: &str, from: &[Value], to: &[Value]) {
+    // common[i][j] is the length of the longest common subsequence of from[i..] and to[j..]
+    let mut common = vec![vec![0usize; to.len() + 1]; from.len() + 1];
+    for i in (0..from.len()).rev() {
+        for j in (0..to.len()).rev() {
+            common[i][j] = if from[i] == to[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    // Walk both arrays, turning each run of removed and inserted elements
+    // between kept ones into replacements first and removes or adds after
+    let (mut i, mut j, mut index) = (0, 0, 0);
+    while i < from.len() || j < to.len() {
+        if i < from.len() && j < to.len() && from[i] == to[j] {
+            i += 1;
+            j += 1;
+            index += 1;
+            continue;
+        }
+
+        let (removed_start, inserted_start) = (i, j);
+        while i < from.len() || j < to.len() {
+            if i < from.len() && j < to.len() && from[i] == to[j] {
+                break;
+            }
+            if j == to.len() || (i < from.len() && common[i + 1][j] >= common[i][j + 1]) {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        let removed = &from[removed_start..i];
+        let inserted = &to[inserted_start..j];
+        let replaced = removed.len().min(inserted.len());
+        for (old, new) in removed.iter().zip(inserted) {
+            diff_into(patch, format!("{}/{}", path, index), old, new);
+            index += 1;
+        }
+        for _ in replaced..removed.len() {
+            patch.push(PatchOperation::Remove { path: format!("{}/{}", path, index) });
+        }
+        for value in &inserted[replaced..] {
+            patch.push(PatchOperation::Add { path: format!("{}/{}", path, index), value: value.clone() });
+            index += 1;
+        }
+    }
+}
+
+fn escape(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
@@ -0,0 +1,333 @@
+//! Protobuf messages of the `ag_ui` package
+//!
+//! Written out by hand with `prost` derives so the crate needs no `protoc`
+//! at build time. Field numbers and types follow `events.proto`,
+//! `types.proto` and `patch.proto` in `typescript-sdk/packages/proto`.
+
+use prost_types::Value;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum EventType {
+    TextMessageStart = 0,
+    TextMessageContent = 1,
+    TextMessageEnd = 2,
+    ToolCallStart = 3,
+    ToolCallArgs = 4,
+    ToolCallEnd = 5,
+    StateSnapshot = 6,
+    StateDelta = 7,
+    MessagesSnapshot = 8,
+    Raw = 9,
+    Custom = 10,
+    RunStarted = 11,
+    RunFinished = 12,
+    RunError = 13,
+    StepStarted = 14,
+    StepFinished = 15,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct BaseEvent {
+    #[prost(enumeration = "EventType", tag = "1")]
+    pub r#type: i32,
+    #[prost(int64, optional, tag = "2")]
+    pub timestamp: Option<i64>,
+    #[prost(message, optional, tag = "3")]
+    pub raw_event: Option<Value>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TextMessageStartEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub message_id: String,
+    #[prost(string, optional, tag = "3")]
+    pub role: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TextMessageContentEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub message_id: String,
+    #[prost(string, tag = "3")]
+    pub delta: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TextMessageEndEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub message_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ToolCallStartEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub tool_call_id: String,
+    #[prost(string, tag = "3")]
+    pub tool_call_name: String,
+    #[prost(string, optional, tag = "4")]
+    pub parent_message_id: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ToolCallArgsEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub tool_call_id: String,
+    #[prost(string, tag = "3")]
+    pub delta: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ToolCallEndEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub tool_call_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StateSnapshotEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(message, optional, tag = "2")]
+    pub snapshot: Option<Value>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StateDeltaEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(message, repeated, tag = "2")]
+    pub delta: Vec<JsonPatchOperation>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct MessagesSnapshotEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(message, repeated, tag = "2")]
+    pub messages: Vec<Message>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RawEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(message, optional, tag = "2")]
+    pub event: Option<Value>,
+    #[prost(string, optional, tag = "3")]
+    pub source: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct CustomEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub name: String,
+    #[prost(message, optional, tag = "3")]
+    pub value: Option<Value>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RunStartedEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub thread_id: String,
+    #[prost(string, tag = "3")]
+    pub run_id: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RunFinishedEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub thread_id: String,
+    #[prost(string, tag = "3")]
+    pub run_id: String,
+    #[prost(message, optional, tag = "4")]
+    pub result: Option<Value>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct RunErrorEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, optional, tag = "2")]
+    pub code: Option<String>,
+    #[prost(string, tag = "3")]
+    pub message: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StepStartedEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub step_name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct StepFinishedEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, tag = "2")]
+    pub step_name: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct TextMessageChunkEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, optional, tag = "2")]
+    pub message_id: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub role: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub delta: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ToolCallChunkEvent {
+    #[prost(message, optional, tag = "1")]
+    pub base_event: Option<BaseEvent>,
+    #[prost(string, optional, tag = "2")]
+    pub tool_call_id: Option<String>,
+    #[prost(string, optional, tag = "3")]
+    pub tool_call_name: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub parent_message_id: Option<String>,
+    #[prost(string, optional, tag = "5")]
+    pub delta: Option<String>,
+}
+
+/// The envelope every event is sent in
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Event {
+    #[prost(
+        oneof = "event::Event",
+        tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18"
+    )]
+    pub event: Option<event::Event>,
+}
+
+pub mod event {
+    use super::*;
+
+    #[derive(Clone, PartialEq, prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag = "1")]
+        TextMessageStart(TextMessageStartEvent),
+        #[prost(message, tag = "2")]
+        TextMessageContent(TextMessageContentEvent),
+        #[prost(message, tag = "3")]
+        TextMessageEnd(TextMessageEndEvent),
+        #[prost(message, tag = "4")]
+        ToolCallStart(ToolCallStartEvent),
+        #[prost(message, tag = "5")]
+        ToolCallArgs(ToolCallArgsEvent),
+        #[prost(message, tag = "6")]
+        ToolCallEnd(ToolCallEndEvent),
+        #[prost(message, tag = "7")]
+        StateSnapshot(StateSnapshotEvent),
+        #[prost(message, tag = "8")]
+        StateDelta(StateDeltaEvent),
+        #[prost(message, tag = "9")]
+        MessagesSnapshot(MessagesSnapshotEvent),
+        #[prost(message, tag = "10")]
+        Raw(RawEvent),
+        #[prost(message, tag = "11")]
+        Custom(CustomEvent),
+        #[prost(message, tag = "12")]
+        RunStarted(RunStartedEvent),
+        #[prost(message, tag = "13")]
+        RunFinished(RunFinishedEvent),
+        #[prost(message, tag = "14")]
+        RunError(RunErrorEvent),
+        #[prost(message, tag = "15")]
+        StepStarted(StepStartedEvent),
+        #[prost(message, tag = "16")]
+        StepFinished(StepFinishedEvent),
+        #[prost(message, tag = "17")]
+        TextMessageChunk(TextMessageChunkEvent),
+        #[prost(message, tag = "18")]
+        ToolCallChunk(ToolCallChunkEvent),
+    }
+}
+
+// types.proto
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ToolCall {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub r#type: String,
+    #[prost(message, optional, tag = "3")]
+    pub function: Option<tool_call::Function>,
+}
+
+pub mod tool_call {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Function {
+        #[prost(string, tag = "1")]
+        pub name: String,
+        #[prost(string, tag = "2")]
+        pub arguments: String,
+    }
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Message {
+    #[prost(string, tag = "1")]
+    pub id: String,
+    #[prost(string, tag = "2")]
+    pub role: String,
+    #[prost(string, optional, tag = "3")]
+    pub content: Option<String>,
+    #[prost(string, optional, tag = "4")]
+    pub name: Option<String>,
+    #[prost(message, repeated, tag = "5")]
+    pub tool_calls: Vec<ToolCall>,
+    #[prost(string, optional, tag = "6")]
+    pub tool_call_id: Option<String>,
+    #[prost(string, optional, tag = "7")]
+    pub error: Option<String>,
+}
+
+// patch.proto
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, prost::Enumeration)]
+#[repr(i32)]
+pub enum JsonPatchOperationType {
+    Add = 0,
+    Remove = 1,
+    Replace = 2,
+    Move = 3,
+    Copy = 4,
+    Test = 5,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct JsonPatchOperation {
+    #[prost(enumeration = "JsonPatchOperationType", tag = "1")]
+    pub op: i32,
+    #[prost(string, tag = "2")]
+    pub path: String,
+    #[prost(string, optional, tag = "3")]
+    pub from: Option<String>,
+    #[prost(message, optional, tag = "4")]
+    pub value: Option<Value>,
+}
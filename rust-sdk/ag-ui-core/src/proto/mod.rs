@@ -0,0 +1,451 @@
+//! Protocol Buffers encoding of events, as in `@ag-ui/proto`
+//!
+//! An event is sent as an `ag_ui.Event` message whose `oneof` holds the
+//! event payload. On the wire each message is preceded by its length as a
+//! 4-byte big-endian integer; responses using this framing have the content
+//! type `AGUI_MEDIA_TYPE`.
+//!
+//! The proto schema predates some of the JSON events: `RUN_ABORTED`, `ERROR`,
+//! `TOOL_CALL_RESULT` and the thinking events cannot be encoded, and
+//! `RUN_STARTED` loses its `parentRunId` and `input`.
+
+pub mod messages;
+
+use prost::Message as _;
+use prost_types::value::Kind;
+use prost_types::{ListValue, Struct};
+use serde_json::{Map, Number, Value};
+
+use crate::events::{
+    BaseEvent, CustomEvent, EventData, EventType, MessagesSnapshotEvent, RawEvent, RunErrorEvent, RunFinishedEvent,
+    RunStartedEvent, StateDeltaEvent, StateSnapshotEvent, StepFinishedEvent, StepStartedEvent, TextMessageChunkEvent,
+    TextMessageContentEvent, TextMessageEndEvent, TextMessageStartEvent, ToolCallArgsEvent, ToolCallChunkEvent,
+    ToolCallEndEvent, ToolCallStartEvent,
+};
+use crate::patch::{self, PatchOperation};
+use crate::types::{Message, Role, ToolCall};
+use crate::error::{AgUiError, Result};
+use messages::event::Event as ProtoEvent;
+
+/// Content type of a length-prefixed protobuf event stream
+pub const AGUI_MEDIA_TYPE: &str = "application/vnd.ag-ui.event+proto";
+
+/// Size of the length prefix in front of every message
+pub const FRAME_HEADER_LENGTH: usize = 4;
+
+/// Encode an event as an `ag_ui.Event` message, without framing
+pub fn encode(event: &BaseEvent) -> Result<Vec<u8>> {
+    Ok(to_proto(event)?.encode_to_vec())
+}
+
+/// Encode an event as a length-prefixed frame
+pub fn encode_frame(event: &BaseEvent) -> Result<Vec<u8>> {
+    let message = to_proto(event)?;
+    let length = u32::try_from(message.encoded_len())
+        .map_err(|_| AgUiError::EncodingError("Event is too large for a protobuf frame".to_string()))?;
+
+    let mut frame = Vec::with_capacity(FRAME_HEADER_LENGTH + length as usize);
+    frame.extend_from_slice(&length.to_be_bytes());
+    message.encode(&mut frame).map_err(|e| AgUiError::EncodingError(e.to_string()))?;
+    Ok(frame)
+}
+
+/// Decode an `ag_ui.Event` message, without framing
+///
+/// The event type is taken from the `oneof` field that is set.
+pub fn decode(bytes: &[u8]) -> Result<BaseEvent> {
+    let message = messages::Event::decode(bytes).map_err(|e| AgUiError::decoding(e.to_string()))?;
+    from_proto(message.event.ok_or_else(|| AgUiError::decoding("Invalid event: no event is set".to_string()))?)
+}
+
+fn to_proto(event: &BaseEvent) -> Result<messages::Event> {
+    let base_event = Some(messages::BaseEvent {
+        r#type: proto_event_type(event.event_type()).map_or(0, |event_type| event_type as i32),
+        timestamp: event.timestamp.map(|timestamp| timestamp.timestamp_millis()),
+        raw_event: event.raw_event.as_ref().map(to_proto_value),
+    });
+
+    let payload = match &event.data {
+        EventData::TextMessageStart(data) => ProtoEvent::TextMessageStart(messages::TextMessageStartEvent {
+            base_event,
+            message_id: data.message_id.clone(),
+            role: data.role.map(role_name),
+        }),
+        EventData::TextMessageContent(data) => ProtoEvent::TextMessageContent(messages::TextMessageContentEvent {
+            base_event,
+            message_id: data.message_id.clone(),
+            delta: data.delta.clone(),
+        }),
+        EventData::TextMessageEnd(data) => ProtoEvent::TextMessageEnd(messages::TextMessageEndEvent {
+            base_event,
+            message_id: data.message_id.clone(),
+        }),
+        EventData::ToolCallStart(data) => ProtoEvent::ToolCallStart(messages::ToolCallStartEvent {
+            base_event,
+            tool_call_id: data.tool_call_id.clone(),
+            tool_call_name: data.tool_call_name.clone(),
+            parent_message_id: data.parent_message_id.clone(),
+        }),
+        EventData::ToolCallArgs(data) => ProtoEvent::ToolCallArgs(messages::ToolCallArgsEvent {
+            base_event,
+            tool_call_id: data.tool_call_id.clone(),
+            delta: data.delta.clone(),
+        }),
+        EventData::ToolCallEnd(data) => ProtoEvent::ToolCallEnd(messages::ToolCallEndEvent {
+            base_event,
+            tool_call_id: data.tool_call_id.clone(),
+        }),
+        EventData::StateSnapshot(data) => ProtoEvent::StateSnapshot(messages::StateSnapshotEvent {
+            base_event,
+            snapshot: Some(prost_types::Value { kind: Some(Kind::StructValue(to_proto_struct(data.snapshot.iter()))) }),
+        }),
+        EventData::StateDelta(data) => ProtoEvent::StateDelta(messages::StateDeltaEvent {
+            base_event,
+            delta: patch::parse_patch(&data.delta)?.iter().map(to_proto_operation).collect(),
+        }),
+        EventData::MessagesSnapshot(data) => ProtoEvent::MessagesSnapshot(messages::MessagesSnapshotEvent {
+            base_event,
+            messages: data.messages.iter().map(to_proto_message).collect(),
+        }),
+        EventData::Raw(data) => ProtoEvent::Raw(messages::RawEvent {
+            base_event,
+            event: Some(to_proto_value(&data.event)),
+            source: data.source.clone(),
+        }),
+        EventData::Custom(data) => ProtoEvent::Custom(messages::CustomEvent {
+            base_event,
+            name: data.name.clone(),
+            value: Some(to_proto_value(&data.value)),
+        }),
+        EventData::RunStarted(data) => ProtoEvent::RunStarted(messages::RunStartedEvent {
+            base_event,
+            thread_id: data.thread_id.clone(),
+            run_id: data.run_id.clone(),
+        }),
+        EventData::RunFinished(data) => ProtoEvent::RunFinished(messages::RunFinishedEvent {
+            base_event,
+            thread_id: data.thread_id.clone(),
+            run_id: data.run_id.clone(),
+            result: data.result.as_ref().map(to_proto_value),
+        }),
+        EventData::RunError(data) => ProtoEvent::RunError(messages::RunErrorEvent {
+            base_event,
+            code: data.code.clone(),
+            message: data.message.clone(),
+        }),
+        EventData::StepStarted(data) => ProtoEvent::StepStarted(messages::StepStartedEvent {
+            base_event,
+            step_name: data.step_name.clone(),
+        }),
+        EventData::StepFinished(data) => ProtoEvent::StepFinished(messages::StepFinishedEvent {
+            base_event,
+            step_name: data.step_name.clone(),
+        }),
+        EventData::TextMessageChunk(data) => ProtoEvent::TextMessageChunk(messages::TextMessageChunkEvent {
+            base_event,
+            message_id: data.message_id.clone(),
+            role: data.role.map(role_name),
+            delta: data.delta.clone(),
+        }),
+        EventData::ToolCallChunk(data) => ProtoEvent::ToolCallChunk(messages::ToolCallChunkEvent {
+            base_event,
+            tool_call_id: data.tool_call_id.clone(),
+            tool_call_name: data.tool_call_name.clone(),
+            parent_message_id: data.parent_message_id.clone(),
+            delta: data.delta.clone(),
+        }),
+        EventData::RunAborted(_)
+        | EventData::Error(_)
+        | EventData::ToolCallResult(_)
+        | EventData::ThinkingStart(_)
+        | EventData::ThinkingEnd(_)
+        | EventData::ThinkingTextMessageStart(_)
+        | EventData::ThinkingTextMessageContent(_)
+        | EventData::ThinkingTextMessageEnd(_) => {
+            return Err(AgUiError::EncodingError(format!(
+                "'{}' events have no protobuf encoding",
+                event.event_type()
+            )));
+        }
+    };
+
+    Ok(messages::Event { event: Some(payload) })
+}
+
+fn from_proto(event: ProtoEvent) -> Result<BaseEvent> {
+    let (base_event, data): (_, EventData) = match event {
+        ProtoEvent::TextMessageStart(event) => (
+            event.base_event,
+            TextMessageStartEvent { message_id: event.message_id, role: event.role.map(parse_role).transpose()? }
+                .into(),
+        ),
+        ProtoEvent::TextMessageContent(event) => (
+            event.base_event,
+            TextMessageContentEvent { message_id: event.message_id, delta: event.delta }.into(),
+        ),
+        ProtoEvent::TextMessageEnd(event) => {
+            (event.base_event, TextMessageEndEvent { message_id: event.message_id }.into())
+        }
+        ProtoEvent::ToolCallStart(event) => (
+            event.base_event,
+            ToolCallStartEvent {
+                tool_call_id: event.tool_call_id,
+                tool_call_name: event.tool_call_name,
+                parent_message_id: event.parent_message_id,
+            }
+            .into(),
+        ),
+        ProtoEvent::ToolCallArgs(event) => (
+            event.base_event,
+            ToolCallArgsEvent { tool_call_id: event.tool_call_id, delta: event.delta }.into(),
+        ),
+        ProtoEvent::ToolCallEnd(event) => {
+            (event.base_event, ToolCallEndEvent { tool_call_id: event.tool_call_id }.into())
+        }
+        ProtoEvent::StateSnapshot(event) => {
+            let snapshot = match event.snapshot.map(from_proto_value) {
+                Some(Value::Object(snapshot)) => snapshot.into_iter().collect(),
+                None => Default::default(),
+                Some(_) => return Err(AgUiError::decoding("State snapshot must be an object".to_string())),
+            };
+            (event.base_event, StateSnapshotEvent { snapshot }.into())
+        }
+        ProtoEvent::StateDelta(event) => {
+            let operations = event.delta.into_iter().map(from_proto_operation).collect::<Result<Vec<_>>>()?;
+            (event.base_event, StateDeltaEvent::new(&operations).into())
+        }
+        ProtoEvent::MessagesSnapshot(event) => {
+            let messages = event.messages.into_iter().map(from_proto_message).collect::<Result<Vec<_>>>()?;
+            (event.base_event, MessagesSnapshotEvent { messages }.into())
+        }
+        ProtoEvent::Raw(event) => (
+            event.base_event,
+            RawEvent { event: event.event.map_or(Value::Null, from_proto_value), source: event.source }.into(),
+        ),
+        ProtoEvent::Custom(event) => (
+            event.base_event,
+            CustomEvent { name: event.name, value: event.value.map_or(Value::Null, from_proto_value) }.into(),
+        ),
+        ProtoEvent::RunStarted(event) => (
+            event.base_event,
+            RunStartedEvent { thread_id: event.thread_id, run_id: event.run_id, parent_run_id: None, input: None }
+                .into(),
+        ),
+        ProtoEvent::RunFinished(event) => (
+            event.base_event,
+            RunFinishedEvent {
+                thread_id: event.thread_id,
+                run_id: event.run_id,
+                result: event.result.map(from_proto_value),
+            }
+            .into(),
+        ),
+        ProtoEvent::RunError(event) => {
+            (event.base_event, RunErrorEvent { message: event.message, code: event.code }.into())
+        }
+        ProtoEvent::StepStarted(event) => {
+            (event.base_event, StepStartedEvent { step_name: event.step_name }.into())
+        }
+        ProtoEvent::StepFinished(event) => {
+            (event.base_event, StepFinishedEvent { step_name: event.step_name }.into())
+        }
+        ProtoEvent::TextMessageChunk(event) => (
+            event.base_event,
+            TextMessageChunkEvent {
+                message_id: event.message_id,
+                role: event.role.map(parse_role).transpose()?,
+                delta: event.delta,
+            }
+            .into(),
+        ),
+        ProtoEvent::ToolCallChunk(event) => (
+            event.base_event,
+            ToolCallChunkEvent {
+                tool_call_id: event.tool_call_id,
+                tool_call_name: event.tool_call_name,
+                parent_message_id: event.parent_message_id,
+                delta: event.delta,
+            }
+            .into(),
+        ),
+    };
+
+    let base_event = base_event.unwrap_or_default();
+    let timestamp = match base_event.timestamp {
+        Some(millis) => Some(
+            chrono::DateTime::from_timestamp_millis(millis)
+                .ok_or_else(|| AgUiError::decoding(format!("Timestamp {} is out of range", millis)))?,
+        ),
+        None => None,
+    };
+
+    Ok(BaseEvent { timestamp, raw_event: base_event.raw_event.map(from_proto_value), data })
+}
+
+/// The proto enum value of an event type; the chunk events have none
+fn proto_event_type(event_type: EventType) -> Option<messages::EventType> {
+    use messages::EventType as Proto;
+
+    Some(match event_type {
+        EventType::TextMessageStart => Proto::TextMessageStart,
+        EventType::TextMessageContent => Proto::TextMessageContent,
+        EventType::TextMessageEnd => Proto::TextMessageEnd,
+        EventType::ToolCallStart => Proto::ToolCallStart,
+        EventType::ToolCallArgs => Proto::ToolCallArgs,
+        EventType::ToolCallEnd => Proto::ToolCallEnd,
+        EventType::StateSnapshot => Proto::StateSnapshot,
+        EventType::StateDelta => Proto::StateDelta,
+        EventType::MessagesSnapshot => Proto::MessagesSnapshot,
+        EventType::Raw => Proto::Raw,
+        EventType::Custom => Proto::Custom,
+        EventType::RunStarted => Proto::RunStarted,
+        EventType::RunFinished => Proto::RunFinished,
+        EventType::RunError => Proto::RunError,
+        EventType::StepStarted => Proto::StepStarted,
+        EventType::StepFinished => Proto::StepFinished,
+        _ => return None,
+    })
+}
+
+fn role_name(role: Role) -> String {
+    match serde_json::to_value(role) {
+        Ok(Value::String(name)) => name,
+        _ => unreachable!("roles serialize as strings"),
+    }
+}
+
+fn parse_role(name: String) -> Result<Role> {
+    serde_json::from_value(Value::String(name)).map_err(|e| AgUiError::decoding(e.to_string()))
+}
+
+fn to_proto_message(message: &Message) -> messages::Message {
+    messages::Message {
+        id: message.id().to_string(),
+        role: role_name(message.role()),
+        content: message.content().map(str::to_string),
+        name: message.name().map(str::to_string),
+        tool_calls: message.tool_calls().unwrap_or_default().iter().map(to_proto_tool_call).collect(),
+        tool_call_id: message.tool_call_id().map(str::to_string),
+        error: message.error().map(str::to_string),
+    }
+}
+
+fn from_proto_message(message: messages::Message) -> Result<Message> {
+    let mut fields = Map::new();
+    fields.insert("id".to_string(), Value::String(message.id));
+    fields.insert("role".to_string(), Value::String(message.role));
+    if let Some(content) = message.content {
+        fields.insert("content".to_string(), Value::String(content));
+    }
+    if let Some(name) = message.name {
+        fields.insert("name".to_string(), Value::String(name));
+    }
+    // Repeated fields cannot be absent, so no tool calls means none were sent
+    if !message.tool_calls.is_empty() {
+        let tool_calls: Vec<ToolCall> = message.tool_calls.into_iter().map(from_proto_tool_call).collect();
+        fields.insert("toolCalls".to_string(), serde_json::to_value(tool_calls)?);
+    }
+    if let Some(tool_call_id) = message.tool_call_id {
+        fields.insert("toolCallId".to_string(), Value::String(tool_call_id));
+    }
+    if let Some(error) = message.error {
+        fields.insert("error".to_string(), Value::String(error));
+    }
+    serde_json::from_value(Value::Object(fields)).map_err(|e| AgUiError::decoding(format!("Invalid message: {}", e)))
+}
+
+fn to_proto_tool_call(tool_call: &ToolCall) -> messages::ToolCall {
+    messages::ToolCall {
+        id: tool_call.id.clone(),
+        r#type: "function".to_string(),
+        function: Some(messages::tool_call::Function {
+            name: tool_call.function.name.clone(),
+            arguments: tool_call.function.arguments.clone(),
+        }),
+    }
+}
+
+fn from_proto_tool_call(tool_call: messages::ToolCall) -> ToolCall {
+    let function = tool_call.function.unwrap_or_default();
+    ToolCall::new(tool_call.id, function.name, function.arguments)
+}
+
+fn to_proto_operation(operation: &PatchOperation) -> messages::JsonPatchOperation {
+    use messages::JsonPatchOperationType as Op;
+
+    let (op, from, value) = match operation {
+        PatchOperation::Add { value, .. } => (Op::Add, None, Some(value)),
+        PatchOperation::Remove { .. } => (Op::Remove, None, None),
+        PatchOperation::Replace { value, .. } => (Op::Replace, None, Some(value)),
+        PatchOperation::Move { from, .. } => (Op::Move, Some(from.clone()), None),
+        PatchOperation::Copy { from, .. } => (Op::Copy, Some(from.clone()), None),
+        PatchOperation::Test { value, .. } => (Op::Test, None, Some(value)),
+    };
+    messages::JsonPatchOperation {
+        op: op as i32,
+        path: operation.path().to_string(),
+        from,
+        value: value.map(to_proto_value),
+    }
+}
+
+fn from_proto_operation(operation: messages::JsonPatchOperation) -> Result<PatchOperation> {
+    use messages::JsonPatchOperationType as Op;
+
+    let op = Op::try_from(operation.op)
+        .map_err(|_| AgUiError::decoding(format!("Unknown JSON Patch operation {}", operation.op)))?;
+    let path = operation.path;
+    let missing = |field: &str| {
+        AgUiError::decoding(format!("JSON Patch operation at '{}' has no {}", path, field))
+    };
+    let value = || operation.value.clone().map(from_proto_value).ok_or_else(|| missing("value"));
+    let from = || operation.from.clone().ok_or_else(|| missing("from"));
+
+    Ok(match op {
+        Op::Add => PatchOperation::Add { value: value()?, path },
+        Op::Remove => PatchOperation::Remove { path },
+        Op::Replace => PatchOperation::Replace { value: value()?, path },
+        Op::Move => PatchOperation::Move { from: from()?, path },
+        Op::Copy => PatchOperation::Copy { from: from()?, path },
+        Op::Test => PatchOperation::Test { value: value()?, path },
+    })
+}
+
+fn to_proto_value(value: &Value) -> prost_types::Value {
+    let kind = match value {
+        Value::Null => Kind::NullValue(prost_types::NullValue::NullValue as i32),
+        Value::Bool(value) => Kind::BoolValue(*value),
+        Value::Number(number) => Kind::NumberValue(number.as_f64().unwrap_or(f64::NAN)),
+        Value::String(value) => Kind::StringValue(value.clone()),
+        Value::Array(values) => Kind::ListValue(ListValue { values: values.iter().map(to_proto_value).collect() }),
+        Value::Object(fields) => Kind::StructValue(to_proto_struct(fields.iter())),
+    };
+    prost_types::Value { kind: Some(kind) }
+}
+
+fn to_proto_struct<'a>(fields: impl Iterator<Item = (&'a String, &'a Value)>) -> Struct {
+    Struct { fields: fields.map(|(key, value)| (key.clone(), to_proto_value(value))).collect() }
+}
+
+fn from_proto_value(value: prost_types::Value) -> Value {
+    match value.kind {
+        None | Some(Kind::NullValue(_)) => Value::Null,
+        Some(Kind::BoolValue(value)) => Value::Bool(value),
+        Some(Kind::NumberValue(number)) => from_proto_number(number),
+        Some(Kind::StringValue(value)) => Value::String(value),
+        Some(Kind::ListValue(list)) => Value::Array(list.values.into_iter().map(from_proto_value).collect()),
+        Some(Kind::StructValue(fields)) => {
+            Value::Object(fields.fields.into_iter().map(|(key, value)| (key, from_proto_value(value))).collect())
+        }
+    }
+}
+
+/// JSON numbers travel as doubles; whole numbers come back as integers
+fn from_proto_number(number: f64) -> Value {
+    const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+    if number.fract() == 0.0 && number.abs() <= MAX_SAFE_INTEGER {
+        Value::Number(Number::from(number as i64))
+    } else {
+        Number::from_f64(number).map_or(Value::Null, Value::Number)
+    }
+}
@@ -0,0 +1,129 @@
+//! Incremental parser for length-prefixed protobuf event streams
+//!
+//! Splits a response body with the content type `AGUI_MEDIA_TYPE` into its
+//! messages, each preceded by its length as a 4-byte big-endian integer.
+//! Like `SseParser` it depends on no JS API.
+
+use crate::events::BaseEvent;
+use crate::error::{AgUiError, Result};
+use crate::proto::{self, FRAME_HEADER_LENGTH};
+
+/// Turns a stream of byte chunks into encoded `ag_ui.Event` messages
+///
+/// Chunks may split the length prefix and the message anywhere. A frame
+/// longer than `max_frame_len` fails as soon as its length prefix is read,
+/// so a corrupt or hostile prefix cannot make the parser buffer up to 4 GiB.
+/// The messages before it are still returned, and the stream cannot be
+/// parsed past it: every later call fails with the same error.
+#[derive(Debug, Clone)]
+pub struct ProtoParser {
+    buffer: Vec<u8>,
+    /// Offset in the stream of `buffer[0]`
+    position: u64,
+    max_frame_len: usize,
+    /// Offset and length of a frame over `max_frame_len`, once one was read
+    oversized: Option<(u64, usize)>,
+}
+
+impl ProtoParser {
+    /// The largest message accepted by default, 16 MiB
+    pub const DEFAULT_MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+    pub fn new() -> Self {
+        Self { buffer: Vec::new(), position: 0, max_frame_len: Self::DEFAULT_MAX_FRAME_LEN, oversized: None }
+    }
+
+    /// Accept messages of up to `max_frame_len` bytes, not counting the length prefix
+    pub fn with_max_frame_len(mut self, max_frame_len: usize) -> Self {
+        self.max_frame_len = max_frame_len;
+        self
+    }
+
+    pub fn max_frame_len(&self) -> usize {
+        self.max_frame_len
+    }
+
+    /// Parse the next chunk and return the messages it completes
+    ///
+    /// Fails with the offset of a frame longer than `max_frame_len`. When
+    /// the chunk completes messages before that frame, those are returned
+    /// and the next call fails instead.
+    pub fn feed(&mut self, chunk: &[u8]) -> Result<Vec<Vec<u8>>> {
+        Ok(self.feed_with_offsets(chunk)?.into_iter().map(|(_, message)| message).collect())
+    }
+
+    /// Like `feed`, with the byte offset in the stream where each message's frame starts
+    pub fn feed_with_offsets(&mut self, chunk: &[u8]) -> Result<Vec<(u64, Vec<u8>)>> {
+        self.check_oversized()?;
+        self.buffer.extend_from_slice(chunk);
+
+        let mut messages = Vec::new();
+        let mut offset = 0;
+        while let Some(header) = self.buffer.get(offset..offset + FRAME_HEADER_LENGTH) {
+            let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+            if length > self.max_frame_len {
+                // Nothing after the frame can be parsed, so none of it is kept
+                self.oversized = Some((self.position + offset as u64, length));
+                self.buffer.clear();
+                if messages.is_empty() {
+                    self.check_oversized()?;
+                }
+                return Ok(messages);
+            }
+            let start = offset + FRAME_HEADER_LENGTH;
+            let Some(message) = self.buffer.get(start..start + length) else {
+                break;
+            };
+            messages.push((self.position + offset as u64, message.to_vec()));
+            offset = start + length;
+        }
+        self.buffer.drain(..offset);
+        self.position += offset as u64;
+
+        Ok(messages)
+    }
+
+    /// Parse the next chunk and decode the events it completes
+    ///
+    /// Fails if any of them is too long or does not decode, with the offset
+    /// of its frame.
+    pub fn feed_events(&mut self, chunk: &[u8]) -> Result<Vec<BaseEvent>> {
+        self.feed_with_offsets(chunk)?
+            .iter()
+            .map(|(offset, message)| proto::decode(message).map_err(|e| e.at_offset(*offset)))
+            .collect()
+    }
+
+    /// Signal the end of the stream
+    ///
+    /// Fails if the stream stopped in the middle of a message, with the offset
+    /// where that message starts. `@ag-ui/client` only logs a warning in that
+    /// case; here the truncated body is reported so the caller does not
+    /// mistake it for a complete run.
+    pub fn finish(&mut self) -> Result<()> {
+        self.check_oversized()?;
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let leftover = std::mem::take(&mut self.buffer).len();
+        Err(AgUiError::decoding(format!("Protobuf stream ended with {} bytes of an incomplete message", leftover))
+            .at_offset(self.position))
+    }
+
+    fn check_oversized(&self) -> Result<()> {
+        match self.oversized {
+            Some((offset, length)) => Err(AgUiError::decoding(format!(
+                "Protobuf message of {} bytes exceeds the limit of {} bytes",
+                length, self.max_frame_len
+            ))
+            .at_offset(offset)),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for ProtoParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
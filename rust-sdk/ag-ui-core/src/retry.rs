@@ -0,0 +1,249 @@
+//! Reconnecting to an interrupted event stream
+//!
+//! `RetryPolicy` says how often and how soon a client reconnects; `Resumption`
+//! follows one run across its connections. Neither sleeps or sends requests,
+//! so `ag-ui-wasm` and `ag-ui-rust` drive them with their own timers and HTTP
+//! clients.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+use serde::Deserialize;
+use crate::error::{AgUiError, Result};
+use crate::events::{BaseEvent, EventType};
+
+/// How a client reconnects when a run's stream is interrupted
+///
+/// The first reconnection waits `initial_delay_ms`, or the `retry:` time the
+/// server sent, and every further one `multiplier` times longer, up to
+/// `max_delay_ms`. The count of retries starts over whenever an event
+/// arrives, so `max_retries` limits consecutive failed attempts.
+///
+/// From JavaScript pass a plain object as the `retry` of a `WebAgent` config,
+/// e.g. `{ maxRetries: 5, initialDelayMs: 250 }`; missing fields keep their
+/// defaults.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetryPolicy {
+    /// Reconnections tried in a row before the run fails
+    pub max_retries: u32,
+    /// Delay before the first reconnection, in milliseconds
+    pub initial_delay_ms: u64,
+    /// Longest delay between reconnections, in milliseconds
+    pub max_delay_ms: u64,
+    /// Factor the delay grows by with each retry
+    pub multiplier: f64,
+}
+
+impl Default for RetryPolicy {
+    /// Three retries after 0.5, 1 and 2 seconds
+    fn default() -> Self {
+        Self { max_retries: 3, initial_delay_ms: 500, max_delay_ms: 10_000, multiplier: 2.0 }
+    }
+}
+
+impl RetryPolicy {
+    /// The delay before retry number `attempt`, counting from 0
+    ///
+    /// `server_retry` is the reconnection time the server asked for, which
+    /// replaces `initial_delay_ms`.
+    pub fn delay(&self, attempt: u32, server_retry: Option<u64>) -> Duration {
+        let initial = server_retry.unwrap_or(self.initial_delay_ms) as f64;
+        let delay = initial * self.multiplier.max(1.0).powi(attempt.min(i32::MAX as u32) as i32);
+        Duration::from_millis(delay.min(self.max_delay_ms as f64) as u64)
+    }
+}
+
+/// Whether a request that failed with `status` may succeed when sent again
+///
+/// Timeouts, rate limits and server errors are; other client errors are not.
+pub fn is_retryable_status(status: u16) -> bool {
+    matches!(status, 408 | 429) || (500..600).contains(&status)
+}
+
+/// Follows one run across the connections of its stream
+///
+/// The client passes every event it reads to `accept`, which drops events a
+/// resumed stream sends again, and asks `retry_after` or `end` what to do when
+/// a connection fails or ends. A reconnection sends `last_event_id` as the
+/// `Last-Event-ID` header, so the server continues after the last event the
+/// client received.
+///
+/// Events are told apart by their SSE `id`. Numeric IDs, which `AgentRoute`
+/// and `SseEncoder` write, only grow, so an event is a replay when its ID is
+/// not above the last numeric ID accepted. Other IDs are compared with the
+/// last `RECENT_ID_WINDOW` received, so a long run costs no more memory than
+/// a short one. A run whose events carry no IDs can only be retried until its
+/// first event arrives; after that, a failure ends it with
+/// `AgUiError::ResumeFailed`.
+#[derive(Debug, Clone)]
+pub struct Resumption {
+    policy: RetryPolicy,
+    retries: u32,
+    server_retry: Option<u64>,
+    last_event_id: Option<String>,
+    last_numeric_id: Option<u64>,
+    recent_ids: VecDeque<String>,
+    received: bool,
+    reconnected: bool,
+    finished: bool,
+}
+
+impl Resumption {
+    /// How many non-numeric IDs are remembered to recognize replayed events
+    pub const RECENT_ID_WINDOW: usize = 64;
+
+    pub fn new(policy: RetryPolicy) -> Self {
+        Self {
+            policy,
+            retries: 0,
+            server_retry: None,
+            last_event_id: None,
+            last_numeric_id: None,
+            recent_ids: VecDeque::new(),
+            received: false,
+            reconnected: false,
+            finished: false,
+        }
+    }
+
+    /// The ID of the last event received, to send as `Last-Event-ID`
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.last_event_id.as_deref()
+    }
+
+    /// Whether the run's `RUN_FINISHED`, `RUN_ERROR` or `RUN_ABORTED` has arrived
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Use the reconnection time the server sent in a `retry:` field
+    pub fn set_server_retry(&mut self, retry: Option<u64>) {
+        if retry.is_some() {
+            self.server_retry = retry;
+        }
+    }
+
+    /// Whether to pass on `event`, which arrived with the SSE ID `id`
+    ///
+    /// `id` must come from the event's own `id:` field (`SseEvent::own_id`):
+    /// an event that only inherits an earlier event's ID would look like a
+    /// replay of it.
+    ///
+    /// An event whose ID was already received is a replay and is dropped. The
+    /// first event after a reconnection fails the run when its numeric ID
+    /// shows that events in between were lost.
+    pub fn accept(&mut self, event: &BaseEvent, id: Option<&str>) -> Result<bool> {
+        if let Some(id) = id {
+            if self.is_replay(id) {
+                return Ok(false);
+            }
+            if std::mem::take(&mut self.reconnected) {
+                self.check_contiguous(id)?;
+            }
+            self.remember(id);
+        }
+
+        self.received = true;
+        self.retries = 0;
+        if matches!(event.event_type(), EventType::RunFinished | EventType::RunError | EventType::RunAborted) {
+            self.finished = true;
+        }
+        Ok(true)
+    }
+
+    /// How long to wait before reconnecting after a connection failed with `error`
+    ///
+    /// Returns the error that ends the run instead when `error` is not a
+    /// network failure, when the retries are used up, or when the run cannot
+    /// be resumed.
+    pub fn retry_after(&mut self, error: AgUiError) -> Result<Duration> {
+        if self.finished || !is_transient(&error) {
+            return Err(error);
+        }
+        if self.received && self.last_event_id.is_none() {
+            return Err(AgUiError::ResumeFailed(format!("the server sent no event IDs to resume from ({})", error)));
+        }
+        if self.retries >= self.policy.max_retries {
+            if !self.received {
+                return Err(error);
+            }
+            return Err(AgUiError::ResumeFailed(format!("gave up after {} retries ({})", self.retries, error)));
+        }
+
+        let delay = self.policy.delay(self.retries, self.server_retry);
+        self.retries += 1;
+        self.reconnected = true;
+        Ok(delay)
+    }
+
+    /// What to do when a connection's stream ends
+    ///
+    /// `None` once the run has finished; otherwise the stream ended early and
+    /// this is `retry_after` for that.
+    pub fn end(&mut self) -> Result<Option<Duration>> {
+        if self.finished {
+            return Ok(None);
+        }
+        let error = AgUiError::StreamError("The stream ended before the run finished".to_string());
+        self.retry_after(error).map(Some)
+    }
+
+    /// The error for a reconnection the server answered with a client error
+    ///
+    /// Once events were received the server's refusal means the run cannot be
+    /// resumed, e.g. because its replay buffer no longer has the events after
+    /// `last_event_id`.
+    pub fn refused(&self, error: AgUiError) -> AgUiError {
+        if self.received {
+            AgUiError::ResumeFailed(format!("the server refused to resume ({})", error))
+        } else {
+            error
+        }
+    }
+
+    /// Whether the event with the ID `id` was already received
+    fn is_replay(&self, id: &str) -> bool {
+        match id.parse::<u64>() {
+            Ok(id) => self.last_numeric_id.is_some_and(|last| id <= last),
+            Err(_) => self.recent_ids.iter().any(|recent| recent == id),
+        }
+    }
+
+    fn remember(&mut self, id: &str) {
+        match id.parse::<u64>() {
+            Ok(numeric) => self.last_numeric_id = Some(numeric),
+            Err(_) => {
+                if self.recent_ids.len() == Self::RECENT_ID_WINDOW {
+                    self.recent_ids.pop_front();
+                }
+                self.recent_ids.push_back(id.to_string());
+            }
+        }
+        self.last_event_id = Some(id.to_string());
+    }
+
+    /// Fail when the numeric ID `id` does not follow the last numeric ID received
+    fn check_contiguous(&self, id: &str) -> Result<()> {
+        let last = self.last_event_id.as_deref().and_then(|last| last.parse::<u64>().ok());
+        if let (Some(last), Ok(id)) = (last, id.parse::<u64>()) {
+            if id > last.saturating_add(1) {
+                return Err(AgUiError::ResumeFailed(format!("the events after {} are no longer available", last)));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Whether `error` is a network failure that reconnecting may get past
+fn is_transient(error: &AgUiError) -> bool {
+    match error {
+        AgUiError::HttpError { status, .. } => is_retryable_status(*status),
+        AgUiError::ConnectionError(_)
+        | AgUiError::StreamError(_)
+        | AgUiError::Timeout(_)
+        | AgUiError::IoError(_)
+        | AgUiError::JsError { .. }
+        | AgUiError::WasmBindgenError(_) => true,
+        _ => false,
+    }
+}
@@ -0,0 +1,213 @@
+//! Incremental Server-Sent Events parser
+//!
+//! Implements the event stream interpretation rules of the WHATWG HTML
+//! specification (section 9.2.6) without depending on any JS API, so the same
+//! code parses `fetch` bodies in `ag-ui-wasm`, response bodies in the native
+//! `ag-ui-rust` client, and can be tested and fuzzed natively.
+
+/// An event dispatched by the parser
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The `event:` field, `"message"` when the event has none
+    pub event: String,
+    /// The `data:` lines of the event joined with `\n`
+    pub data: String,
+    /// The last event ID seen on the stream, if it is not empty
+    ///
+    /// As with `lastEventId` in the browser, an event without an `id:` field
+    /// of its own carries the ID of an earlier one; `own_id` tells them apart.
+    pub id: Option<String>,
+    /// Whether the event had an `id:` field of its own
+    pub has_own_id: bool,
+    /// Where the event's first field starts, in bytes of the stream's UTF-8 text
+    pub offset: u64,
+}
+
+impl SseEvent {
+    /// The ID set by the event's own `id:` field, if it had one and it is not empty
+    pub fn own_id(&self) -> Option<&str> {
+        self.id.as_deref().filter(|_| self.has_own_id)
+    }
+}
+
+/// Turns a stream of text or byte chunks into `SseEvent`s
+///
+/// Chunks may split lines, fields and UTF-8 sequences anywhere. Lines may end
+/// with CRLF, LF or CR. An event is dispatched by the blank line that ends
+/// it; an unterminated event at the end of the stream is discarded, as the
+/// specification requires.
+#[derive(Debug, Clone, Default)]
+pub struct SseParser {
+    line: String,
+    data: String,
+    event_type: String,
+    last_event_id: String,
+    /// Whether the event being parsed has an `id:` field
+    event_has_id: bool,
+    retry: Option<u64>,
+    undecoded: Vec<u8>,
+    after_cr: bool,
+    started: bool,
+    /// Bytes of text parsed so far
+    position: u64,
+    line_start: u64,
+    event_start: Option<u64>,
+}
+
+impl SseParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the next chunk of text
+    pub fn feed(&mut self, chunk: &str) -> Vec<SseEvent> {
+        let mut events = Vec::new();
+        let mut chunk = chunk;
+
+        if !self.started && !chunk.is_empty() {
+            self.started = true;
+            if let Some(rest) = chunk.strip_prefix('\u{feff}') {
+                chunk = rest;
+                self.position = '\u{feff}'.len_utf8() as u64;
+                self.line_start = self.position;
+            }
+        }
+
+        for character in chunk.chars() {
+            self.position += character.len_utf8() as u64;
+            match character {
+                '\n' if self.after_cr => {
+                    self.after_cr = false;
+                    self.line_start = self.position;
+                }
+                '\r' | '\n' => {
+                    self.after_cr = character == '\r';
+                    let line = std::mem::take(&mut self.line);
+                    self.process_line(&line, &mut events);
+                    self.line_start = self.position;
+                }
+                _ => {
+                    self.after_cr = false;
+                    self.line.push(character);
+                }
+            }
+        }
+
+        events
+    }
+
+    /// Parse the next chunk of UTF-8 bytes
+    ///
+    /// A sequence split between chunks is decoded once it is complete;
+    /// invalid sequences become U+FFFD, as `TextDecoder` does.
+    pub fn feed_bytes(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.undecoded.extend_from_slice(chunk);
+        let mut text = String::new();
+        let mut rest = self.undecoded.as_slice();
+
+        loop {
+            match std::str::from_utf8(rest) {
+                Ok(valid) => {
+                    text.push_str(valid);
+                    rest = &[];
+                    break;
+                }
+                Err(error) => {
+                    let (valid, after) = rest.split_at(error.valid_up_to());
+                    text.push_str(std::str::from_utf8(valid).unwrap_or_default());
+                    match error.error_len() {
+                        Some(length) => {
+                            text.push(char::REPLACEMENT_CHARACTER);
+                            rest = &after[length..];
+                        }
+                        // An incomplete sequence at the end waits for the next chunk
+                        None => {
+                            rest = after;
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        self.undecoded = rest.to_vec();
+        self.feed(&text)
+    }
+
+    /// End the stream, discarding any unterminated event
+    pub fn finish(&mut self) {
+        let last_event_id = std::mem::take(&mut self.last_event_id);
+        let retry = self.retry;
+        *self = Self { last_event_id, retry, ..Self::default() };
+    }
+
+    /// Where the unterminated event or line the parser holds starts, if any
+    ///
+    /// `finish` discards it as the specification requires; a caller that
+    /// treats a truncated stream as an error can check this first.
+    pub fn unterminated_offset(&self) -> Option<u64> {
+        self.event_start
+            .or_else(|| (!self.line.is_empty() || !self.undecoded.is_empty()).then_some(self.line_start))
+    }
+
+    /// The ID of the last event seen, to send back as `Last-Event-ID`
+    pub fn last_event_id(&self) -> &str {
+        &self.last_event_id
+    }
+
+    /// The reconnection time in milliseconds requested by the server
+    pub fn retry(&self) -> Option<u64> {
+        self.retry
+    }
+
+    fn process_line(&mut self, line: &str, events: &mut Vec<SseEvent>) {
+        if line.is_empty() {
+            self.dispatch(events);
+            return;
+        }
+        if line.starts_with(':') {
+            return;
+        }
+        self.event_start.get_or_insert(self.line_start);
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        match field {
+            "event" => self.event_type = value.to_string(),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => {
+                self.last_event_id = value.to_string();
+                self.event_has_id = true;
+            }
+            "retry" if !value.is_empty() && value.bytes().all(|byte| byte.is_ascii_digit()) => {
+                self.retry = value.parse().ok().or(Some(u64::MAX));
+            }
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self, events: &mut Vec<SseEvent>) {
+        let event_type = std::mem::take(&mut self.event_type);
+        let has_own_id = std::mem::take(&mut self.event_has_id);
+        let offset = self.event_start.take().unwrap_or(self.line_start);
+        if self.data.is_empty() {
+            return;
+        }
+
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+        events.push(SseEvent {
+            event: if event_type.is_empty() { "message".to_string() } else { event_type },
+            data,
+            id: (!self.last_event_id.is_empty()).then(|| self.last_event_id.clone()),
+            has_own_id,
+            offset,
+        });
+    }
+}
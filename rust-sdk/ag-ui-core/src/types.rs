@@ -0,0 +1,560 @@
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+use crate::error::{AgUiError, Result};
+
+/// Message role in a conversation
+#[cfg_attr(feature = "wasm", wasm_bindgen)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Role {
+    Developer,
+    User,
+    Assistant,
+    System,
+    Tool,
+}
+
+/// The function a tool call invokes, with its arguments as JSON text
+///
+/// Also the legacy `function_call` of an assistant message.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+/// A message in the conversation
+///
+/// Messages are discriminated by `role`, as in the TypeScript `MessageSchema`
+/// union; each variant only carries the fields that are valid for its role.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "role", rename_all = "lowercase")]
+pub enum Message {
+    Developer(DeveloperMessage),
+    System(SystemMessage),
+    Assistant(AssistantMessage),
+    User(UserMessage),
+    Tool(ToolMessage),
+}
+
+/// Instructions from the application developer
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeveloperMessage {
+    pub id: String,
+    pub content: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// System prompt
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemMessage {
+    pub id: String,
+    pub content: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Reply from the assistant, optionally requesting tool calls
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssistantMessage {
+    pub id: String,
+    #[serde(default)]
+    pub content: Option<String>,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub tool_calls: Option<Vec<ToolCall>>,
+}
+
+/// Input from the user
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserMessage {
+    pub id: String,
+    pub content: String,
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+/// Result of a tool call
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolMessage {
+    pub id: String,
+    pub content: String,
+    pub tool_call_id: String,
+    #[serde(default)]
+    pub error: Option<String>,
+}
+
+fn new_message_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+impl DeveloperMessage {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self { id: new_message_id(), content: content.into(), name: None }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+impl SystemMessage {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self { id: new_message_id(), content: content.into(), name: None }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+impl AssistantMessage {
+    /// An assistant message without content or tool calls
+    pub fn new() -> Self {
+        Self { id: new_message_id(), content: None, name: None, tool_calls: None }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn with_content(mut self, content: impl Into<String>) -> Self {
+        self.content = Some(content.into());
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    pub fn with_tool_calls(mut self, tool_calls: Vec<ToolCall>) -> Self {
+        self.tool_calls = Some(tool_calls);
+        self
+    }
+}
+
+impl Default for AssistantMessage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UserMessage {
+    pub fn new(content: impl Into<String>) -> Self {
+        Self { id: new_message_id(), content: content.into(), name: None }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+}
+
+impl ToolMessage {
+    pub fn new(content: impl Into<String>, tool_call_id: impl Into<String>) -> Self {
+        Self { id: new_message_id(), content: content.into(), tool_call_id: tool_call_id.into(), error: None }
+    }
+
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Mark the tool call as failed with `error`
+    pub fn with_error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+}
+
+impl Message {
+    pub fn developer(content: impl Into<String>) -> Self {
+        DeveloperMessage::new(content).into()
+    }
+
+    pub fn system(content: impl Into<String>) -> Self {
+        SystemMessage::new(content).into()
+    }
+
+    pub fn assistant(content: impl Into<String>) -> Self {
+        AssistantMessage::new().with_content(content).into()
+    }
+
+    pub fn user(content: impl Into<String>) -> Self {
+        UserMessage::new(content).into()
+    }
+
+    pub fn tool(content: impl Into<String>, tool_call_id: impl Into<String>) -> Self {
+        ToolMessage::new(content, tool_call_id).into()
+    }
+
+    pub fn id(&self) -> &str {
+        match self {
+            Message::Developer(message) => &message.id,
+            Message::System(message) => &message.id,
+            Message::Assistant(message) => &message.id,
+            Message::User(message) => &message.id,
+            Message::Tool(message) => &message.id,
+        }
+    }
+
+    pub fn role(&self) -> Role {
+        match self {
+            Message::Developer(_) => Role::Developer,
+            Message::System(_) => Role::System,
+            Message::Assistant(_) => Role::Assistant,
+            Message::User(_) => Role::User,
+            Message::Tool(_) => Role::Tool,
+        }
+    }
+
+    /// Text content; only assistant messages may have none
+    pub fn content(&self) -> Option<&str> {
+        match self {
+            Message::Developer(message) => Some(&message.content),
+            Message::System(message) => Some(&message.content),
+            Message::Assistant(message) => message.content.as_deref(),
+            Message::User(message) => Some(&message.content),
+            Message::Tool(message) => Some(&message.content),
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Message::Developer(message) => message.name.as_deref(),
+            Message::System(message) => message.name.as_deref(),
+            Message::Assistant(message) => message.name.as_deref(),
+            Message::User(message) => message.name.as_deref(),
+            Message::Tool(_) => None,
+        }
+    }
+
+    pub fn tool_calls(&self) -> Option<&[ToolCall]> {
+        match self {
+            Message::Assistant(message) => message.tool_calls.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn tool_call_id(&self) -> Option<&str> {
+        match self {
+            Message::Tool(message) => Some(&message.tool_call_id),
+            _ => None,
+        }
+    }
+
+    /// Why a tool call failed; only tool messages have one
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            Message::Tool(message) => message.error.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! message_variants {
+    ($($variant:ident($message:ident)),* $(,)?) => {
+        $(
+            impl From<$message> for Message {
+                fn from(message: $message) -> Self {
+                    Message::$variant(message)
+                }
+            }
+        )*
+    };
+}
+
+message_variants! {
+    Developer(DeveloperMessage),
+    System(SystemMessage),
+    Assistant(AssistantMessage),
+    User(UserMessage),
+    Tool(ToolMessage),
+}
+
+/// Fields are written in the order of the TypeScript schemas, which place
+/// `role` after `content` for tool messages only.
+impl Serialize for Message {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("id", self.id())?;
+        if let Message::Tool(message) = self {
+            map.serialize_entry("content", &message.content)?;
+            map.serialize_entry("role", &Role::Tool)?;
+            map.serialize_entry("toolCallId", &message.tool_call_id)?;
+            if let Some(error) = &message.error {
+                map.serialize_entry("error", error)?;
+            }
+            return map.end();
+        }
+
+        map.serialize_entry("role", &self.role())?;
+        if let Some(content) = self.content() {
+            map.serialize_entry("content", content)?;
+        }
+        if let Some(name) = self.name() {
+            map.serialize_entry("name", name)?;
+        }
+        if let Some(tool_calls) = self.tool_calls() {
+            map.serialize_entry("toolCalls", tool_calls)?;
+        }
+        map.end()
+    }
+}
+
+/// The flat message struct used before messages were discriminated by role
+///
+/// Kept for reading stored conversations; convert with `Message::try_from`.
+/// `metadata` and `created_at` have no counterpart in the spec and are
+/// dropped by the conversion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LegacyMessage {
+    pub id: String,
+    pub role: Role,
+    pub content: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_call_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_calls: Option<Vec<ToolCall>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function_call: Option<FunctionCall>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<LegacyMessage> for Message {
+    type Error = AgUiError;
+
+    /// Fails for field combinations the spec does not allow: a tool message
+    /// without `tool_call_id`, or tool call fields on any other role.
+    /// An assistant's `function_call` becomes a tool call with the message id.
+    fn try_from(legacy: LegacyMessage) -> Result<Self> {
+        let LegacyMessage { id, role, content, name, tool_call_id, tool_calls, function_call, .. } = legacy;
+
+        if role != Role::Tool && tool_call_id.is_some() {
+            return Err(AgUiError::InvalidMessage(format!("{:?} message {} has a tool_call_id", role, id)));
+        }
+        if role != Role::Assistant && (tool_calls.is_some() || function_call.is_some()) {
+            return Err(AgUiError::InvalidMessage(format!("{:?} message {} has tool calls", role, id)));
+        }
+
+        Ok(match role {
+            Role::Developer => Message::Developer(DeveloperMessage { id, content, name }),
+            Role::System => Message::System(SystemMessage { id, content, name }),
+            Role::User => Message::User(UserMessage { id, content, name }),
+            Role::Assistant => {
+                let mut tool_calls = tool_calls;
+                if let Some(function_call) = function_call {
+                    tool_calls.get_or_insert_with(Vec::new).push(ToolCall {
+                        id: id.clone(),
+                        call_type: ToolCallType::Function,
+                        function: function_call,
+                    });
+                }
+                Message::Assistant(AssistantMessage { id, content: Some(content), name, tool_calls })
+            }
+            Role::Tool => {
+                let tool_call_id = tool_call_id.ok_or_else(|| {
+                    AgUiError::InvalidMessage(format!("Tool message {} has no tool_call_id", id))
+                })?;
+                Message::Tool(ToolMessage { id, content, tool_call_id, error: None })
+            }
+        })
+    }
+}
+
+/// A tool message's `error` has no field in the flat struct and is dropped.
+impl From<Message> for LegacyMessage {
+    fn from(message: Message) -> Self {
+        let role = message.role();
+        let (id, content, name, tool_call_id, tool_calls) = match message {
+            Message::Developer(DeveloperMessage { id, content, name })
+            | Message::System(SystemMessage { id, content, name })
+            | Message::User(UserMessage { id, content, name }) => (id, content, name, None, None),
+            Message::Assistant(AssistantMessage { id, content, name, tool_calls }) => {
+                (id, content.unwrap_or_default(), name, None, tool_calls)
+            }
+            Message::Tool(ToolMessage { id, content, tool_call_id, .. }) => (id, content, None, Some(tool_call_id), None),
+        };
+
+        LegacyMessage {
+            id,
+            role,
+            content,
+            name,
+            tool_call_id,
+            tool_calls,
+            function_call: None,
+            metadata: None,
+            created_at: None,
+        }
+    }
+}
+
+/// Tool definition
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tool {
+    pub name: String,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<serde_json::Value>,
+}
+
+/// A piece of context the agent can draw on, such as a document or a preference
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Context {
+    pub description: String,
+    pub value: String,
+}
+
+impl Context {
+    pub fn new(description: impl Into<String>, value: impl Into<String>) -> Self {
+        Self { description: description.into(), value: value.into() }
+    }
+}
+
+/// State object for maintaining conversation state
+pub type State = HashMap<String, serde_json::Value>;
+
+/// Input for running an agent
+///
+/// Every member is always serialized, in the order of `RunAgentInputSchema`;
+/// missing collections are read as empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunAgentInput {
+    pub thread_id: String,
+    pub run_id: String,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub state: State,
+    #[serde(default)]
+    pub messages: Vec<Message>,
+    #[serde(default)]
+    pub tools: Vec<Tool>,
+    #[serde(default)]
+    pub context: Vec<Context>,
+    #[serde(default, deserialize_with = "null_as_default")]
+    pub forwarded_props: HashMap<String, serde_json::Value>,
+}
+
+impl RunAgentInput {
+    pub fn new(thread_id: String, run_id: String) -> Self {
+        Self {
+            thread_id,
+            run_id,
+            state: State::new(),
+            messages: Vec::new(),
+            tools: Vec::new(),
+            context: Vec::new(),
+            forwarded_props: HashMap::new(),
+        }
+    }
+
+    /// Complete the input with an agent's thread, messages and state
+    ///
+    /// An empty thread ID becomes `thread_id`, or a new one, and an empty run
+    /// ID a new one. `messages` and `state` are filled in when the input has
+    /// none. `ag-ui-wasm` and `ag-ui-rust` both send inputs prepared this way.
+    pub fn prepare(mut self, thread_id: Option<&str>, messages: &[Message], state: &State) -> Self {
+        if self.thread_id.is_empty() {
+            self.thread_id = thread_id.map_or_else(|| Uuid::new_v4().to_string(), str::to_string);
+        }
+        if self.run_id.is_empty() {
+            self.run_id = Uuid::new_v4().to_string();
+        }
+        if self.messages.is_empty() {
+            self.messages = messages.to_vec();
+        }
+        if self.state.is_empty() {
+            self.state = state.clone();
+        }
+        self
+    }
+}
+
+/// Read `null` as the type's default, for members TypeScript types as `any`
+fn null_as_default<'de, D, T>(deserializer: D) -> std::result::Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
+/// The kind of a tool call; the spec only defines function calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolCallType {
+    #[default]
+    Function,
+}
+
+/// A tool call requested by an assistant message
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolCall {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub call_type: ToolCallType,
+    pub function: FunctionCall,
+}
+
+impl ToolCall {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, arguments: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            call_type: ToolCallType::Function,
+            function: FunctionCall { name: name.into(), arguments: arguments.into() },
+        }
+    }
+}
+
+/// Tool result
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolResult {
+    pub tool_call_id: String,
+    pub result: serde_json::Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+} 
\ No newline at end of file
@@ -0,0 +1,235 @@
+use futures::stream::{BoxStream, Stream, StreamExt};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT, CONTENT_TYPE};
+use ag_ui_core::error::{AgUiError, Result};
+use ag_ui_core::events::BaseEvent;
+use ag_ui_core::retry::{is_retryable_status, Resumption, RetryPolicy};
+use ag_ui_core::types::{Message, RunAgentInput, State};
+use crate::stream::event_stream::{sse_received_stream, ReceivedEvent};
+use crate::stream::EventStream;
+
+/// AG-UI agent client over HTTP, as `HttpAgent` in `@ag-ui/client`
+///
+/// POSTs a `RunAgentInput` as JSON and reads the Server-Sent Events
+/// response as an `EventStream`.
+#[derive(Debug, Clone)]
+pub struct HttpAgent {
+    endpoint: Endpoint,
+    retry: Option<RetryPolicy>,
+    agent_id: Option<String>,
+    thread_id: Option<String>,
+    messages: Vec<Message>,
+    state: State,
+}
+
+impl HttpAgent {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            endpoint: Endpoint { client: reqwest::Client::new(), url: url.into(), headers: HeaderMap::new() },
+            retry: None,
+            agent_id: None,
+            thread_id: None,
+            messages: Vec::new(),
+            state: State::new(),
+        }
+    }
+
+    /// Send requests with `client`, e.g. to share its connection pool or set timeouts
+    pub fn with_client(mut self, client: reqwest::Client) -> Self {
+        self.endpoint.client = client;
+        self
+    }
+
+    /// Send `name: value` with every request, e.g. an `Authorization` header
+    pub fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.endpoint.headers.insert(name, value);
+        self
+    }
+
+    /// Reconnect as `policy` says when a run's stream is interrupted
+    ///
+    /// A reconnection sends the same input with the `Last-Event-ID` of the
+    /// last event received, and events the server sends again are dropped.
+    /// Only a server that sends SSE IDs, like `AgentRoute::with_replay`, can
+    /// resume a run after its first event; otherwise the run fails with
+    /// `AgUiError::ResumeFailed`.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.endpoint.url
+    }
+
+    pub fn agent_id(&self) -> Option<&str> {
+        self.agent_id.as_deref()
+    }
+
+    pub fn set_agent_id(&mut self, agent_id: String) {
+        self.agent_id = Some(agent_id);
+    }
+
+    pub fn thread_id(&self) -> Option<&str> {
+        self.thread_id.as_deref()
+    }
+
+    pub fn set_thread_id(&mut self, thread_id: String) {
+        self.thread_id = Some(thread_id);
+    }
+
+    pub fn messages(&self) -> &[Message] {
+        &self.messages
+    }
+
+    pub fn set_messages(&mut self, messages: Vec<Message>) {
+        self.messages = messages;
+    }
+
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    pub fn set_state(&mut self, state: State) {
+        self.state = state;
+    }
+
+    /// Run the agent, returning its events as the server sends them
+    ///
+    /// `input` is completed with the agent's thread, messages and state as
+    /// `RunAgentInput::prepare` does, so an empty thread or run ID gets one. An
+    /// unsuccessful HTTP status is an `HttpError` with the response body, and
+    /// a request or response body the client's timeout cuts short a
+    /// `Timeout`. With a retry
+    /// policy, failed requests and interrupted streams are retried before the
+    /// error is returned.
+    pub async fn run_agent(&self, input: RunAgentInput) -> Result<EventStream> {
+        let input = input.prepare(self.thread_id.as_deref(), &self.messages, &self.state);
+
+        let Some(policy) = self.retry.clone() else {
+            let response = self.endpoint.send(&input, None).await?;
+            if !response.status().is_success() {
+                return Err(http_error(response).await);
+            }
+            let events = sse_received_stream(body(response));
+            return Ok(events.map(|received| received.map(|received| received.event)).boxed());
+        };
+
+        let mut run = ResumingRun {
+            endpoint: self.endpoint.clone(),
+            input,
+            resumption: Resumption::new(policy),
+            body: futures::stream::empty().boxed(),
+        };
+        run.connect().await?;
+        Ok(futures::stream::unfold(Some(run), |run| async move {
+            let mut run = run?;
+            match run.next_event().await {
+                Ok(Some(event)) => Some((Ok(event), Some(run))),
+                Ok(None) => None,
+                Err(error) => Some((Err(error), None)),
+            }
+        })
+        .boxed())
+    }
+}
+
+/// Where and how the agent's requests are sent
+#[derive(Debug, Clone)]
+struct Endpoint {
+    client: reqwest::Client,
+    url: String,
+    headers: HeaderMap,
+}
+
+impl Endpoint {
+    /// POST `input`, resuming after `last_event_id` if there is one
+    async fn send(&self, input: &RunAgentInput, last_event_id: Option<&str>) -> Result<reqwest::Response> {
+        let mut request = self.client
+            .post(&self.url)
+            .headers(self.headers.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .header(ACCEPT, "text/event-stream");
+        if let Some(id) = last_event_id {
+            request = request.header("Last-Event-ID", id);
+        }
+
+        request
+            .body(serde_json::to_vec(input)?)
+            .send()
+            .await
+            .map_err(|e| match e.is_timeout() {
+                true => AgUiError::Timeout(e.to_string()),
+                false => AgUiError::ConnectionError(e.to_string()),
+            })
+    }
+}
+
+/// The chunks of `response`'s body, failing with a `Timeout` when the client's timeout cuts it short
+fn body(response: reqwest::Response) -> impl Stream<Item = Result<impl AsRef<[u8]>>> {
+    response.bytes_stream().map(|chunk| {
+        chunk.map_err(|e| match e.is_timeout() {
+            true => AgUiError::Timeout(e.to_string()),
+            false => AgUiError::StreamError(format!("Failed to read response body: {}", e)),
+        })
+    })
+}
+
+/// A run that reconnects when its stream is interrupted
+struct ResumingRun {
+    endpoint: Endpoint,
+    input: RunAgentInput,
+    resumption: Resumption,
+    body: BoxStream<'static, Result<ReceivedEvent>>,
+}
+
+impl ResumingRun {
+    /// Send the run's request until a response streams its events
+    async fn connect(&mut self) -> Result<()> {
+        loop {
+            let error = match self.endpoint.send(&self.input, self.resumption.last_event_id()).await {
+                Ok(response) if response.status().is_success() => {
+                    self.body = sse_received_stream(body(response));
+                    return Ok(());
+                }
+                Ok(response) if !is_retryable_status(response.status().as_u16()) => {
+                    return Err(self.resumption.refused(http_error(response).await));
+                }
+                Ok(response) => http_error(response).await,
+                Err(error) => error,
+            };
+            tokio::time::sleep(self.resumption.retry_after(error)?).await;
+        }
+    }
+
+    /// The next event not received before, reconnecting as often as the policy allows
+    async fn next_event(&mut self) -> Result<Option<BaseEvent>> {
+        loop {
+            let delay = match self.body.next().await {
+                Some(Ok(received)) => {
+                    self.resumption.set_server_retry(received.retry);
+                    if self.resumption.accept(&received.event, received.id.as_deref())? {
+                        return Ok(Some(received.event));
+                    }
+                    continue;
+                }
+                Some(Err(error)) => self.resumption.retry_after(error)?,
+                None => match self.resumption.end()? {
+                    Some(delay) => delay,
+                    None => return Ok(None),
+                },
+            };
+            tokio::time::sleep(delay).await;
+            self.connect().await?;
+        }
+    }
+}
+
+/// The error for an unsuccessful `response`, with its body
+async fn http_error(response: reqwest::Response) -> AgUiError {
+    let status = response.status();
+    AgUiError::HttpError {
+        status: status.as_u16(),
+        status_text: status.canonical_reason().unwrap_or_default().to_string(),
+        body: response.text().await.unwrap_or_default(),
+    }
+}
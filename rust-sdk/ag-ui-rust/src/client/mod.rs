@@ -0,0 +1,3 @@
+pub mod http_agent;
+
+pub use http_agent::HttpAgent;
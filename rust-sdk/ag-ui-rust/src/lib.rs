@@ -0,0 +1,26 @@
+//! AG-UI SDK for native Rust
+//!
+//! A tokio/reqwest client for the Agent-User Interaction Protocol, for
+//! backend services and CLI tools. It shares its protocol types with
+//! `ag-ui-wasm` through `ag-ui-core`. With the `axum` feature, `server`
+//! serves agents written in Rust as AG-UI endpoints.
+
+pub mod client;
+#[cfg(feature = "axum")]
+pub mod server;
+pub mod stream;
+
+pub use ag_ui_core::{compat, encoder, error, events, patch, proto, proto_parser, retry, sse_parser, types};
+
+// Re-export commonly used types
+pub use ag_ui_core::{
+    AgUiError, BaseEvent, EventData, EventType, Message, Result, RetryPolicy, Role, RunAgentInput,
+    SseEvent, SseParser, State,
+};
+pub use client::HttpAgent;
+#[cfg(feature = "axum")]
+pub use server::{agent_route, AgentRoute, ReplayBuffer};
+pub use stream::{sse_event_stream, EventStream};
+
+// Version information
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
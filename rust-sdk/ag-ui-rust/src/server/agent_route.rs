@@ -0,0 +1,236 @@
+use std::sync::Arc;
+use std::time::Duration;
+use axum::body::Body;
+use axum::extract::rejection::JsonRejection;
+use axum::http::header::{
+    ACCEPT, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN,
+    ACCESS_CONTROL_MAX_AGE, CACHE_CONTROL, CONTENT_TYPE,
+};
+use axum::http::{HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{post, MethodRouter};
+use axum::Json;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use ag_ui_core::encoder::{EventEncoder, SseEncoder, SseEncoderOptions};
+use ag_ui_core::error::Result;
+use ag_ui_core::events::BaseEvent;
+use ag_ui_core::proto;
+use ag_ui_core::types::RunAgentInput;
+use crate::server::keep_alive::KeepAlive;
+use crate::server::replay::ReplayBuffer;
+
+/// How long an SSE response may stay silent before a ping is sent
+const DEFAULT_KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// The header a reconnecting client names the last event it received in
+const LAST_EVENT_ID: &str = "last-event-id";
+
+/// The status and body text a request is refused with
+type Refusal = (StatusCode, &'static str);
+
+/// Serves an agent as an AG-UI endpoint on an axum router
+///
+/// The route answers `POST` with a `RunAgentInput` JSON body by calling the
+/// handler and streaming the events it yields, as SSE or, when the `Accept`
+/// header asks for `AGUI_MEDIA_TYPE`, as length-prefixed protobuf. It also
+/// answers CORS preflight requests.
+///
+/// When the client disconnects, the response body is dropped and with it the
+/// handler's stream, so the agent stops at its next `.await`. With
+/// `with_replay` the run continues instead, so the client can resume it.
+///
+/// ```
+/// use ag_ui_rust::{AgentRoute, BaseEvent, RunAgentInput};
+/// use axum::Router;
+/// use futures::stream::{self, Stream};
+///
+/// fn my_agent(input: RunAgentInput) -> impl Stream<Item = BaseEvent> {
+///     stream::iter([
+///         BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone()),
+///         BaseEvent::run_finished(input.thread_id, input.run_id),
+///     ])
+/// }
+///
+/// let app: Router = Router::new().route("/agent", AgentRoute::new(my_agent).into_method_router());
+/// ```
+#[derive(Debug, Clone)]
+pub struct AgentRoute<H> {
+    handler: H,
+    keep_alive: Option<Duration>,
+    allow_origin: HeaderValue,
+    replay: Option<Arc<ReplayBuffer>>,
+}
+
+impl<H, S> AgentRoute<H>
+where
+    H: Fn(RunAgentInput) -> S + Clone + Send + Sync + 'static,
+    S: Stream<Item = BaseEvent> + Send + 'static,
+{
+    /// Serve `handler`, pinging idle SSE streams every 15 seconds and allowing any origin
+    pub fn new(handler: H) -> Self {
+        Self {
+            handler,
+            keep_alive: Some(DEFAULT_KEEP_ALIVE),
+            allow_origin: HeaderValue::from_static("*"),
+            replay: None,
+        }
+    }
+
+    /// Ping an SSE stream that has sent nothing for `interval`
+    pub fn with_keep_alive(mut self, interval: Duration) -> Self {
+        self.keep_alive = Some(interval);
+        self
+    }
+
+    /// Never send keep-alive pings
+    pub fn without_keep_alive(mut self) -> Self {
+        self.keep_alive = None;
+        self
+    }
+
+    /// Set the `Access-Control-Allow-Origin` of every response
+    pub fn with_allow_origin(mut self, origin: HeaderValue) -> Self {
+        self.allow_origin = origin;
+        self
+    }
+
+    /// Keep recent runs in `buffer`, so clients can resume interrupted streams
+    ///
+    /// SSE events are sent with `id:` fields. A `POST` for a run the buffer
+    /// has, such as a client reconnecting with `Last-Event-ID`, streams the
+    /// run's events after that ID instead of calling the handler again. When
+    /// the buffer no longer has them, the route answers `410 Gone`, and when
+    /// `Last-Event-ID` is not an event number, `400 Bad Request`.
+    pub fn with_replay(mut self, buffer: ReplayBuffer) -> Self {
+        self.replay = Some(Arc::new(buffer));
+        self
+    }
+
+    /// The `POST` and `OPTIONS` handlers, to mount with `Router::route`
+    pub fn into_method_router<T>(self) -> MethodRouter<T>
+    where
+        T: Clone + Send + Sync + 'static,
+    {
+        let allow_origin = self.allow_origin.clone();
+        post(move |headers: HeaderMap, input: std::result::Result<Json<RunAgentInput>, JsonRejection>| {
+            let route = self.clone();
+            async move { route.run(&headers, input) }
+        })
+        .options(move || async move { preflight(allow_origin) })
+    }
+
+    fn run(&self, headers: &HeaderMap, input: std::result::Result<Json<RunAgentInput>, JsonRejection>) -> Response {
+        let mut response = match input {
+            Ok(Json(input)) => self.respond(headers, input),
+            Err(rejection) => rejection.into_response(),
+        };
+        response.headers_mut().insert(ACCESS_CONTROL_ALLOW_ORIGIN, self.allow_origin.clone());
+        response
+    }
+
+    /// The streaming response to a valid request for `input`
+    fn respond(&self, headers: &HeaderMap, input: RunAgentInput) -> Response {
+        let (events, resumed_after) = match self.events(headers, input) {
+            Ok(events) => events,
+            Err(refusal) => return refusal.into_response(),
+        };
+        let encoder = EventEncoder::new(accept(headers));
+        let body = self.encode(events, encoder, resumed_after);
+        (
+            [
+                (CONTENT_TYPE, HeaderValue::from_static(encoder.content_type())),
+                (CACHE_CONTROL, HeaderValue::from_static("no-cache")),
+            ],
+            Body::from_stream(body),
+        )
+            .into_response()
+    }
+
+    /// The events to send for `input`, and with replay the ID they follow
+    ///
+    /// With replay, a `Last-Event-ID` that is not an event number is refused
+    /// with `400 Bad Request`, and a run the buffer cannot resume with
+    /// `410 Gone`.
+    fn events(
+        &self,
+        headers: &HeaderMap,
+        input: RunAgentInput,
+    ) -> std::result::Result<(BoxStream<'static, BaseEvent>, Option<u64>), Refusal> {
+        let Some(buffer) = &self.replay else {
+            return Ok(((self.handler)(input).boxed(), None));
+        };
+
+        let last_event_id = match headers.get(LAST_EVENT_ID) {
+            Some(id) => match id.to_str().ok().and_then(|id| id.trim().parse().ok()) {
+                Some(id) => Some(id),
+                None => return Err((StatusCode::BAD_REQUEST, "Last-Event-ID must be an event number")),
+            },
+            None => None,
+        };
+        let run_id = input.run_id.clone();
+        let events = buffer
+            .open(&run_id, last_event_id, || (self.handler)(input))
+            .ok_or((StatusCode::GONE, "The run cannot be resumed"))?;
+        Ok((events, Some(last_event_id.unwrap_or(0))))
+    }
+
+    /// The response body: one chunk per event, and pings on an idle SSE stream
+    ///
+    /// SSE events are numbered after `resumed_after` when it is set. An event
+    /// with no protobuf encoding ends a protobuf body with an error, which
+    /// aborts the response.
+    fn encode(
+        &self,
+        events: BoxStream<'static, BaseEvent>,
+        encoder: EventEncoder,
+        resumed_after: Option<u64>,
+    ) -> BoxStream<'static, Result<Vec<u8>>> {
+        if encoder.accepts_protobuf() {
+            return events.map(|event| proto::encode_frame(&event)).boxed();
+        }
+
+        let sse = match resumed_after {
+            Some(last_event_id) => SseEncoder::with_options(SseEncoderOptions {
+                ids: true,
+                last_event_id: Some(last_event_id),
+                ..SseEncoderOptions::default()
+            }),
+            None => SseEncoder::new(),
+        };
+        let body = events.map(move |event| sse.encode_event(&event)).boxed();
+        match self.keep_alive {
+            Some(interval) => KeepAlive::new(body, interval).boxed(),
+            None => body,
+        }
+    }
+}
+
+/// Serve `handler` as an AG-UI endpoint with the default settings of `AgentRoute`
+pub fn agent_route<H, S, T>(handler: H) -> MethodRouter<T>
+where
+    H: Fn(RunAgentInput) -> S + Clone + Send + Sync + 'static,
+    S: Stream<Item = BaseEvent> + Send + 'static,
+    T: Clone + Send + Sync + 'static,
+{
+    AgentRoute::new(handler).into_method_router()
+}
+
+/// The `Accept` header, with repeated headers combined into one list
+fn accept(headers: &HeaderMap) -> Option<String> {
+    let values: Vec<_> = headers.get_all(ACCEPT).iter().filter_map(|value| value.to_str().ok()).collect();
+    Some(values.join(", ")).filter(|accept| !accept.is_empty())
+}
+
+/// Answer a CORS preflight for the `POST` an AG-UI client sends
+fn preflight(allow_origin: HeaderValue) -> Response {
+    (
+        StatusCode::NO_CONTENT,
+        [
+            (ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin),
+            (ACCESS_CONTROL_ALLOW_METHODS, HeaderValue::from_static("POST, OPTIONS")),
+            (ACCESS_CONTROL_ALLOW_HEADERS, HeaderValue::from_static("Content-Type, Accept, Authorization, Last-Event-ID")),
+            (ACCESS_CONTROL_MAX_AGE, HeaderValue::from_static("86400")),
+        ],
+    )
+        .into_response()
+}
@@ -0,0 +1,53 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use futures::stream::{Stream, StreamExt};
+use tokio::time::{Instant, Sleep};
+use ag_ui_core::encoder::SseEncoder;
+use ag_ui_core::error::Result;
+
+/// Sends an SSE ping whenever the wrapped body has been idle for `interval`
+///
+/// Proxies and load balancers close connections that carry nothing for a
+/// while, which an agent waiting on a slow model can easily trigger. Clients
+/// ignore the ping, which is an SSE comment.
+pub(crate) struct KeepAlive<S> {
+    body: S,
+    encoder: SseEncoder,
+    interval: Duration,
+    idle: Pin<Box<Sleep>>,
+}
+
+impl<S> KeepAlive<S> {
+    pub(crate) fn new(body: S, interval: Duration) -> Self {
+        Self {
+            body,
+            encoder: SseEncoder::new(),
+            interval,
+            idle: Box::pin(tokio::time::sleep(interval)),
+        }
+    }
+}
+
+impl<S> Stream for KeepAlive<S>
+where
+    S: Stream<Item = Result<Vec<u8>>> + Unpin,
+{
+    type Item = Result<Vec<u8>>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let deadline = Instant::now() + self.interval;
+        if let Poll::Ready(chunk) = self.body.poll_next_unpin(cx) {
+            self.idle.as_mut().reset(deadline);
+            return Poll::Ready(chunk);
+        }
+        match self.idle.as_mut().poll(cx) {
+            Poll::Ready(()) => {
+                self.idle.as_mut().reset(deadline);
+                Poll::Ready(Some(self.encoder.encode_ping()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
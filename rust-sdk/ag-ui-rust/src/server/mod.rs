@@ -0,0 +1,6 @@
+pub mod agent_route;
+mod keep_alive;
+pub mod replay;
+
+pub use agent_route::{agent_route, AgentRoute};
+pub use replay::ReplayBuffer;
@@ -0,0 +1,176 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use futures::stream::{BoxStream, Stream, StreamExt};
+use tokio::sync::watch;
+use ag_ui_core::events::BaseEvent;
+
+/// Keeps the latest events of recent runs, so clients can resume their streams
+///
+/// A run's events are numbered from 1, which an `AgentRoute` using the buffer
+/// sends as their SSE IDs. The buffer keeps the last `max_events` events of
+/// each of the last `max_runs` runs, looked up by `run_id`.
+///
+/// Each run's stream is read on its own tokio task, so the run goes on while
+/// its client reconnects. It stops once the run has left the buffer and no
+/// response is reading it.
+#[derive(Debug)]
+pub struct ReplayBuffer {
+    max_runs: usize,
+    max_events: usize,
+    runs: Mutex<RecentRuns>,
+}
+
+impl ReplayBuffer {
+    /// Keep up to `max_events` events of each of the last `max_runs` runs
+    pub fn new(max_runs: usize, max_events: usize) -> Self {
+        Self {
+            max_runs: max_runs.max(1),
+            max_events: max_events.max(1),
+            runs: Mutex::default(),
+        }
+    }
+
+    /// The events of run `run_id` after the event `last_event_id`
+    ///
+    /// A run the buffer does not have yet is started with `start` when there
+    /// is no `last_event_id`. `None` when the buffer cannot continue the run
+    /// after `last_event_id`: the run or the events after it are gone.
+    ///
+    /// `start` is called without holding the buffer's lock, so a slow handler
+    /// does not hold up other requests. When two requests start the same run
+    /// at once, the first to insert it wins and the other's stream is dropped
+    /// unread.
+    pub(crate) fn open<S>(
+        &self,
+        run_id: &str,
+        last_event_id: Option<u64>,
+        start: impl FnOnce() -> S,
+    ) -> Option<BoxStream<'static, BaseEvent>>
+    where
+        S: Stream<Item = BaseEvent> + Send + 'static,
+    {
+        let log = match self.log(run_id) {
+            Some(log) => log,
+            None if last_event_id.is_some() => return None,
+            None => {
+                let events = start();
+                let mut runs = self.lock();
+                match runs.logs.get(run_id) {
+                    Some(log) => log.clone(),
+                    None => {
+                        let log = self.start(events);
+                        runs.insert(run_id.to_string(), log.clone(), self.max_runs);
+                        log
+                    }
+                }
+            }
+        };
+
+        let after = last_event_id.unwrap_or(0);
+        {
+            let run = log.borrow();
+            if after + 1 < run.first_id || after > run.last_id() {
+                return None;
+            }
+        }
+        Some(replay(log, after))
+    }
+
+    fn log(&self, run_id: &str) -> Option<watch::Receiver<RunLog>> {
+        self.lock().logs.get(run_id).cloned()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, RecentRuns> {
+        self.runs.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Read `events` into a new run log on its own task
+    fn start<S>(&self, events: S) -> watch::Receiver<RunLog>
+    where
+        S: Stream<Item = BaseEvent> + Send + 'static,
+    {
+        let (sender, receiver) = watch::channel(RunLog { first_id: 1, events: VecDeque::new() });
+        let max_events = self.max_events;
+        tokio::spawn(async move {
+            let mut events = Box::pin(events);
+            while let Some(event) = events.next().await {
+                sender.send_modify(|run| run.push(event, max_events));
+                if sender.is_closed() {
+                    break;
+                }
+            }
+        });
+        receiver
+    }
+}
+
+/// The runs in a `ReplayBuffer`, oldest first
+#[derive(Debug, Default)]
+struct RecentRuns {
+    order: VecDeque<String>,
+    logs: HashMap<String, watch::Receiver<RunLog>>,
+}
+
+impl RecentRuns {
+    fn insert(&mut self, run_id: String, log: watch::Receiver<RunLog>, max_runs: usize) {
+        while self.order.len() >= max_runs {
+            if let Some(oldest) = self.order.pop_front() {
+                self.logs.remove(&oldest);
+            }
+        }
+        self.order.push_back(run_id.clone());
+        self.logs.insert(run_id, log);
+    }
+}
+
+/// The latest events of one run
+#[derive(Debug)]
+struct RunLog {
+    /// The ID of `events[0]`
+    first_id: u64,
+    events: VecDeque<BaseEvent>,
+}
+
+impl RunLog {
+    fn push(&mut self, event: BaseEvent, max_events: usize) {
+        if self.events.len() == max_events {
+            self.events.pop_front();
+            self.first_id += 1;
+        }
+        self.events.push_back(event);
+    }
+
+    /// The ID of the latest event, 0 before the first
+    fn last_id(&self) -> u64 {
+        self.first_id + self.events.len() as u64 - 1
+    }
+
+    fn event(&self, id: u64) -> Option<&BaseEvent> {
+        let index = id.checked_sub(self.first_id)?;
+        self.events.get(usize::try_from(index).ok()?)
+    }
+}
+
+/// The events of `log` after `after`, as the run produces them
+///
+/// Ends when the run does, or early when a slow reader falls so far behind
+/// that its next event has left the buffer; the client then fails to resume.
+fn replay(log: watch::Receiver<RunLog>, after: u64) -> BoxStream<'static, BaseEvent> {
+    futures::stream::unfold((log, after), |(mut log, last)| async move {
+        loop {
+            let (next, first_id) = {
+                let run = log.borrow_and_update();
+                (run.event(last + 1).cloned(), run.first_id)
+            };
+            if let Some(event) = next {
+                return Some((event, (log, last + 1)));
+            }
+            if last + 1 < first_id {
+                return None;
+            }
+            // Fails once the run has ended and every event was seen
+            log.changed().await.ok()?;
+        }
+    })
+    .boxed()
+}
@@ -0,0 +1,92 @@
+use std::collections::VecDeque;
+use std::fmt::Display;
+use futures::stream::{BoxStream, Stream, StreamExt};
+use ag_ui_core::compat;
+use ag_ui_core::error::{AgUiError, Result};
+use ag_ui_core::events::BaseEvent;
+use ag_ui_core::sse_parser::{SseEvent, SseParser};
+
+/// A stream of AG-UI events
+///
+/// The stream ends after the first error.
+pub type EventStream = BoxStream<'static, Result<BaseEvent>>;
+
+/// Decode a stream of SSE body chunks into events
+///
+/// Chunks may split events, lines and UTF-8 sequences anywhere, as network
+/// reads do. Works with `reqwest::Response::bytes_stream()` and any other
+/// stream of byte chunks. An event that does not decode ends the stream
+/// with a `DecodingError` at the event's byte offset in the body.
+pub fn sse_event_stream<S, B, E>(body: S) -> EventStream
+where
+    S: Stream<Item = std::result::Result<B, E>> + Send + 'static,
+    B: AsRef<[u8]>,
+    E: Display,
+{
+    let body = body.map(|chunk| {
+        chunk.map_err(|error| AgUiError::StreamError(format!("Failed to read response body: {}", error)))
+    });
+    sse_received_stream(body).map(|received| received.map(|received| received.event)).boxed()
+}
+
+/// An event decoded from an SSE body, with the fields a reconnection needs
+#[derive(Debug, Clone)]
+pub(crate) struct ReceivedEvent {
+    pub event: BaseEvent,
+    /// The ID from the event's own `id:` field, if it had one
+    pub id: Option<String>,
+    /// The reconnection time the server sent so far, in milliseconds
+    pub retry: Option<u64>,
+}
+
+/// Like `sse_event_stream`, keeping each event's SSE ID
+///
+/// A read error ends the stream as it is, so the caller decides what it is.
+pub(crate) fn sse_received_stream<S, B>(body: S) -> BoxStream<'static, Result<ReceivedEvent>>
+where
+    S: Stream<Item = Result<B>> + Send + 'static,
+    B: AsRef<[u8]>,
+{
+    let source = SseBody { body: Box::pin(body), parser: SseParser::new(), pending: VecDeque::new() };
+    futures::stream::unfold(Some(source), |source| async move {
+        let mut source = source?;
+        match source.next_event().await {
+            Ok(Some(event)) => Some((Ok(event), Some(source))),
+            Ok(None) => None,
+            Err(error) => Some((Err(error), None)),
+        }
+    })
+    .boxed()
+}
+
+/// Reads an SSE body and decodes it into events
+struct SseBody<S> {
+    body: S,
+    parser: SseParser,
+    pending: VecDeque<SseEvent>,
+}
+
+impl<S, B> SseBody<S>
+where
+    S: Stream<Item = Result<B>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    async fn next_event(&mut self) -> Result<Option<ReceivedEvent>> {
+        loop {
+            if let Some(sse_event) = self.pending.pop_front() {
+                let event = compat::parse_event(&sse_event.data).map_err(|e| e.at_offset(sse_event.offset))?;
+                let id = sse_event.own_id().map(str::to_string);
+                return Ok(Some(ReceivedEvent { event, id, retry: self.parser.retry() }));
+            }
+            
+            match self.body.next().await {
+                Some(Ok(chunk)) => self.pending.extend(self.parser.feed_bytes(chunk.as_ref())),
+                Some(Err(error)) => return Err(error),
+                None => {
+                    self.parser.finish();
+                    return Ok(None);
+                }
+            }
+        }
+    }
+}
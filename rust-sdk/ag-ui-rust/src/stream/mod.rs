@@ -0,0 +1,3 @@
+pub mod event_stream;
+
+pub use event_stream::{sse_event_stream, EventStream};
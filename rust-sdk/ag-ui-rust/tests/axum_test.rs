@@ -0,0 +1,303 @@
+//! Tests for serving agents with `AgentRoute`
+//!
+//! Each test runs the route on an in-process axum server on a local port.
+//! Needs the `axum` feature: `cargo test -p ag-ui-rust --features axum`.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use ag_ui_rust::{AgentRoute, BaseEvent, EventType, HttpAgent, ReplayBuffer, RetryPolicy, RunAgentInput};
+use ag_ui_rust::proto::AGUI_MEDIA_TYPE;
+use ag_ui_rust::proto_parser::ProtoParser;
+use ag_ui_rust::sse_parser::SseParser;
+use axum::routing::MethodRouter;
+use axum::Router;
+use futures::stream::{self, Stream, StreamExt};
+use tokio::net::TcpListener;
+
+/// Serve `route` at `/agent`, returning its URL
+async fn serve(route: MethodRouter) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/agent", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, Router::new().route("/agent", route)).await.unwrap();
+    });
+    url
+}
+
+fn echo_run(input: RunAgentInput) -> impl Stream<Item = BaseEvent> {
+    stream::iter(vec![
+        BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone()),
+        BaseEvent::text_message_start("msg1".to_string(), None),
+        BaseEvent::text_message_content("msg1".to_string(), "Hello".to_string()),
+        BaseEvent::text_message_end("msg1".to_string()),
+        BaseEvent::run_finished(input.thread_id, input.run_id),
+    ])
+}
+
+fn input() -> RunAgentInput {
+    RunAgentInput::new("thread1".to_string(), "run1".to_string())
+}
+
+/// A `POST` of `input()` as JSON, asking for SSE
+fn post(client: &reqwest::Client, url: &str) -> reqwest::RequestBuilder {
+    client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "text/event-stream")
+        .body(serde_json::to_vec(&input()).unwrap())
+}
+
+#[tokio::test]
+async fn test_http_agent_runs_a_served_agent() {
+    let url = serve(AgentRoute::new(echo_run).into_method_router()).await;
+
+    let events: Vec<_> = HttpAgent::new(url).run_agent(input()).await.unwrap().collect().await;
+    let events: Vec<_> = events.into_iter().map(Result::unwrap).collect();
+    let types: Vec<_> = events.iter().map(BaseEvent::event_type).collect();
+    assert_eq!(
+        types,
+        [
+            EventType::RunStarted,
+            EventType::TextMessageStart,
+            EventType::TextMessageContent,
+            EventType::TextMessageEnd,
+            EventType::RunFinished,
+        ]
+    );
+    assert_eq!(serde_json::to_value(&events[0]).unwrap()["threadId"], "thread1");
+}
+
+#[tokio::test]
+async fn test_protobuf_when_accepted() {
+    let url = serve(AgentRoute::new(echo_run).into_method_router()).await;
+
+    // A second `Accept` header adds to the first
+    let response = reqwest::Client::new()
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "text/event-stream;q=0.5")
+        .header("Accept", AGUI_MEDIA_TYPE)
+        .body(serde_json::to_vec(&input()).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["content-type"], AGUI_MEDIA_TYPE);
+
+    let mut parser = ProtoParser::new();
+    let events = parser.feed_events(&response.bytes().await.unwrap()).unwrap();
+    parser.finish().unwrap();
+    assert_eq!(events.len(), 5);
+    assert_eq!(events[4].event_type(), EventType::RunFinished);
+}
+
+#[tokio::test]
+async fn test_idle_sse_stream_is_pinged() {
+    let slow_run = |input: RunAgentInput| {
+        let started = BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone());
+        let finished = BaseEvent::run_finished(input.thread_id, input.run_id);
+        stream::iter([started]).chain(stream::once(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            finished
+        }))
+    };
+    let route = AgentRoute::new(slow_run).with_keep_alive(Duration::from_millis(40));
+    let url = serve(route.into_method_router()).await;
+
+    let response = post(&reqwest::Client::new(), &url).send().await.unwrap();
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let body = response.text().await.unwrap();
+    assert!(body.contains(": ping\n\n"));
+
+    // Pings are comments, so the client sees only the two events
+    let events = SseParser::new().feed(&body);
+    assert_eq!(events.len(), 2);
+}
+
+#[tokio::test]
+async fn test_cors_headers() {
+    let route = AgentRoute::new(echo_run).with_allow_origin("https://app.example.com".parse().unwrap());
+    let url = serve(route.into_method_router()).await;
+    let client = reqwest::Client::new();
+
+    let preflight = client.request(reqwest::Method::OPTIONS, &url).send().await.unwrap();
+    assert_eq!(preflight.status(), 204);
+    assert_eq!(preflight.headers()["access-control-allow-origin"], "https://app.example.com");
+    assert_eq!(preflight.headers()["access-control-allow-methods"], "POST, OPTIONS");
+    assert!(preflight.headers()["access-control-allow-headers"].to_str().unwrap().contains("Content-Type"));
+
+    let response = post(&client, &url).send().await.unwrap();
+    assert_eq!(response.headers()["access-control-allow-origin"], "https://app.example.com");
+
+    // A body that is not a `RunAgentInput` is rejected, still with CORS headers
+    let rejected = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .body("{\"threadId\": 1}")
+        .send()
+        .await
+        .unwrap();
+    assert!(rejected.status().is_client_error());
+    assert_eq!(rejected.headers()["access-control-allow-origin"], "https://app.example.com");
+}
+
+/// Sets its flag when dropped
+struct DropFlag(Arc<AtomicBool>);
+
+impl Drop for DropFlag {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn test_client_disconnect_drops_the_agent_stream() {
+    let dropped = Arc::new(AtomicBool::new(false));
+    let flag = dropped.clone();
+    let endless_run = move |input: RunAgentInput| {
+        let guard = DropFlag(flag.clone());
+        stream::iter([BaseEvent::run_started(input.thread_id, input.run_id)]).chain(stream::once(async move {
+            let _guard = guard;
+            futures::future::pending::<BaseEvent>().await
+        }))
+    };
+    let route = AgentRoute::new(endless_run).with_keep_alive(Duration::from_millis(20));
+    let url = serve(route.into_method_router()).await;
+
+    let mut body = post(&reqwest::Client::new(), &url).send().await.unwrap().bytes_stream();
+    let first = body.next().await.unwrap().unwrap();
+    assert!(String::from_utf8_lossy(&first).contains("RUN_STARTED"));
+    drop(body);
+
+    for _ in 0..100 {
+        if dropped.load(Ordering::SeqCst) {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("the agent's stream was not dropped after the client disconnected");
+}
+
+/// The IDs and types of the SSE events in `body`
+fn ids_and_types(body: &str) -> Vec<(String, EventType)> {
+    SseParser::new()
+        .feed(body)
+        .into_iter()
+        .map(|event| {
+            let parsed: BaseEvent = serde_json::from_str(&event.data).unwrap();
+            (event.id.unwrap(), parsed.event_type())
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_replay_resumes_after_the_last_event_id() {
+    let runs = Arc::new(AtomicUsize::new(0));
+    let counter = runs.clone();
+    let counted_run = move |input: RunAgentInput| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        echo_run(input)
+    };
+    let route = AgentRoute::new(counted_run).with_replay(ReplayBuffer::new(8, 64));
+    let url = serve(route.into_method_router()).await;
+    let client = reqwest::Client::new();
+
+    let body = post(&client, &url).send().await.unwrap().text().await.unwrap();
+    let ids: Vec<_> = ids_and_types(&body).into_iter().map(|(id, _)| id).collect();
+    assert_eq!(ids, ["1", "2", "3", "4", "5"]);
+
+    let resumed = post(&client, &url).header("Last-Event-ID", "3").send().await.unwrap().text().await.unwrap();
+    assert_eq!(
+        ids_and_types(&resumed),
+        [("4".to_string(), EventType::TextMessageEnd), ("5".to_string(), EventType::RunFinished)]
+    );
+
+    // Asking for the same run again replays it instead of running the agent twice
+    let agent = HttpAgent::new(url).with_retry(RetryPolicy::default());
+    let replayed: Vec<_> = agent.run_agent(input()).await.unwrap().collect().await;
+    assert_eq!(replayed.len(), 5);
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_replay_starts_runs_without_holding_up_other_requests() {
+    // The first run only starts once the second one has
+    let (started, wait_for_start) = std::sync::mpsc::channel::<()>();
+    let wait_for_start = Arc::new(std::sync::Mutex::new(wait_for_start));
+    let waited = Arc::new(AtomicBool::new(false));
+    let first_waited = waited.clone();
+    let run = move |input: RunAgentInput| {
+        if input.run_id == "run1" {
+            // Hand this worker's other tasks off while it waits
+            let second_started = tokio::task::block_in_place(|| {
+                wait_for_start.lock().unwrap().recv_timeout(Duration::from_secs(5)).is_ok()
+            });
+            first_waited.store(second_started, Ordering::SeqCst);
+        } else {
+            started.send(()).unwrap();
+        }
+        echo_run(input)
+    };
+    let route = AgentRoute::new(run).with_replay(ReplayBuffer::new(8, 64));
+    let url = serve(route.into_method_router()).await;
+    let client = reqwest::Client::new();
+
+    let first = tokio::spawn(post(&client, &url).send());
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let mut second = input();
+    second.run_id = "run2".to_string();
+    let second = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Accept", "text/event-stream")
+        .body(serde_json::to_vec(&second).unwrap())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(second.status(), 200);
+    assert_eq!(first.await.unwrap().unwrap().status(), 200);
+    assert!(waited.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn test_replay_refuses_runs_it_cannot_resume() {
+    // Keeps only the last two events of each run
+    let route = AgentRoute::new(echo_run).with_replay(ReplayBuffer::new(8, 2));
+    let url = serve(route.into_method_router()).await;
+    let client = reqwest::Client::new();
+    post(&client, &url).send().await.unwrap().text().await.unwrap();
+
+    let evicted = post(&client, &url).header("Last-Event-ID", "1").send().await.unwrap();
+    assert_eq!(evicted.status(), 410);
+    assert_eq!(evicted.headers()["access-control-allow-origin"], "*");
+
+    let mut unknown = input();
+    unknown.run_id = "run2".to_string();
+    let unknown = client
+        .post(&url)
+        .header("Content-Type", "application/json")
+        .header("Last-Event-ID", "1")
+        .body(serde_json::to_vec(&unknown).unwrap())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(unknown.status(), 410);
+
+    let kept = post(&client, &url).header("Last-Event-ID", "3").send().await.unwrap().text().await.unwrap();
+    assert_eq!(ids_and_types(&kept).len(), 2);
+}
+
+#[tokio::test]
+async fn test_replay_rejects_a_last_event_id_that_is_not_a_number() {
+    let route = AgentRoute::new(echo_run).with_replay(ReplayBuffer::new(8, 64));
+    let url = serve(route.into_method_router()).await;
+    let client = reqwest::Client::new();
+    post(&client, &url).send().await.unwrap().text().await.unwrap();
+
+    for id in ["evt-3", "-1", "99999999999999999999"] {
+        let response = post(&client, &url).header("Last-Event-ID", id).send().await.unwrap();
+        assert_eq!(response.status(), 400, "Last-Event-ID: {}", id);
+        assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    }
+}
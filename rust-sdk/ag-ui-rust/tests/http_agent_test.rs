@@ -0,0 +1,356 @@
+//! Tests for the native `HttpAgent` and SSE body decoding
+//!
+//! The agent talks to a one-shot HTTP server on a local port, so these run
+//! without network access.
+
+use ag_ui_rust::{sse_event_stream, AgUiError, BaseEvent, EventType, HttpAgent, Message, RetryPolicy, RunAgentInput};
+use futures::StreamExt;
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Serve one request with `status` and `body`, resolving to the raw request
+async fn serve_once(status: &'static str, body: String) -> (String, tokio::task::JoinHandle<String>) {
+    let (url, server) = serve_responses(vec![response(status, &body, body.len())]).await;
+    (url, tokio::spawn(async move { server.await.unwrap().remove(0) }))
+}
+
+/// Serve one request per raw response in turn, resolving to the raw requests
+async fn serve_responses(responses: Vec<String>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/agent", listener.local_addr().unwrap());
+
+    let server = tokio::spawn(async move {
+        let mut requests = Vec::new();
+        for response in responses {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            requests.push(read_request(&mut socket).await);
+            socket.write_all(response.as_bytes()).await.unwrap();
+            socket.shutdown().await.unwrap();
+        }
+        requests
+    });
+
+    (url, server)
+}
+
+async fn read_request(socket: &mut TcpStream) -> String {
+    let mut request = Vec::new();
+    let mut buffer = [0u8; 4096];
+    loop {
+        let read = socket.read(&mut buffer).await.unwrap();
+        request.extend_from_slice(&buffer[..read]);
+        let text = String::from_utf8_lossy(&request);
+        if let Some(end) = text.find("\r\n\r\n") {
+            let length = text[..end]
+                .lines()
+                .find_map(|line| line.to_ascii_lowercase().strip_prefix("content-length:").map(|v| v.trim().parse::<usize>().unwrap()))
+                .unwrap_or(0);
+            if request.len() >= end + 4 + length {
+                break;
+            }
+        }
+    }
+    String::from_utf8(request).unwrap()
+}
+
+/// A raw response whose `Content-Length` is `length`; a longer length than
+/// the body's cuts the response off, as a dropped connection does
+fn response(status: &str, body: &str, length: usize) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/event-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status, length, body
+    )
+}
+
+fn sse(events: &[BaseEvent]) -> String {
+    events.iter().map(|event| format!("data: {}\n\n", serde_json::to_string(event).unwrap())).collect()
+}
+
+/// `events` as SSE with the IDs `first_id`, `first_id + 1`, …
+fn sse_with_ids(first_id: u64, events: &[BaseEvent]) -> String {
+    events
+        .iter()
+        .zip(first_id..)
+        .map(|(event, id)| format!("id: {}\ndata: {}\n\n", id, serde_json::to_string(event).unwrap()))
+        .collect()
+}
+
+/// The five events of a run with one text message
+fn run_events() -> Vec<BaseEvent> {
+    vec![
+        BaseEvent::run_started("thread1".to_string(), "run1".to_string()),
+        BaseEvent::text_message_start("msg1".to_string(), None),
+        BaseEvent::text_message_content("msg1".to_string(), "Hello".to_string()),
+        BaseEvent::text_message_end("msg1".to_string()),
+        BaseEvent::run_finished("thread1".to_string(), "run1".to_string()),
+    ]
+}
+
+fn retrying_agent(url: String) -> HttpAgent {
+    HttpAgent::new(url).with_retry(RetryPolicy { initial_delay_ms: 1, ..RetryPolicy::default() })
+}
+
+async fn run(agent: &HttpAgent) -> Vec<ag_ui_rust::Result<BaseEvent>> {
+    run_with(agent, RunAgentInput::new("thread1".to_string(), "run1".to_string())).await
+}
+
+async fn run_with(agent: &HttpAgent, input: RunAgentInput) -> Vec<ag_ui_rust::Result<BaseEvent>> {
+    agent.run_agent(input).await.unwrap().collect().await
+}
+
+#[tokio::test]
+async fn test_run_agent_streams_events() {
+    let body = sse(&[
+        BaseEvent::run_started("thread1".to_string(), "run1".to_string()),
+        BaseEvent::text_message_content("msg1".to_string(), "Hello".to_string()),
+        BaseEvent::run_finished("thread1".to_string(), "run1".to_string()),
+    ]);
+    let (url, server) = serve_once("200 OK", body).await;
+
+    let mut agent = HttpAgent::new(url).with_header(AUTHORIZATION, HeaderValue::from_static("Bearer token"));
+    agent.set_messages(vec![Message::user("Hi")]);
+
+    let events = agent.run_agent(RunAgentInput::new("thread1".to_string(), "run1".to_string())).await.unwrap();
+    let types: Vec<_> = events.map(|event| event.unwrap().event_type()).collect().await;
+    assert_eq!(types, [EventType::RunStarted, EventType::TextMessageContent, EventType::RunFinished]);
+
+    let request = server.await.unwrap().to_ascii_lowercase();
+    assert!(request.starts_with("post /agent http/1.1"));
+    assert!(request.contains("accept: text/event-stream"));
+    assert!(request.contains("content-type: application/json"));
+    assert!(request.contains("authorization: bearer token"));
+    // The agent's messages are sent when the input has none
+    assert!(request.contains("\"threadid\":\"thread1\""));
+    assert!(request.contains("\"content\":\"hi\""));
+}
+
+#[tokio::test]
+async fn test_run_agent_fills_in_the_thread_and_run() {
+    let (url, server) = serve_once("200 OK", sse(&run_events())).await;
+    let mut agent = HttpAgent::new(url);
+    agent.set_thread_id("thread-7".to_string());
+
+    run_with(&agent, RunAgentInput::new(String::new(), String::new())).await;
+
+    let request = server.await.unwrap();
+    let body: serde_json::Value = serde_json::from_str(&request[request.find("\r\n\r\n").unwrap() + 4..]).unwrap();
+    assert_eq!(body["threadId"], "thread-7");
+    assert!(!body["runId"].as_str().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_run_agent_rejects_error_status() {
+    let (url, server) = serve_once("500 Internal Server Error", "model unavailable".to_string()).await;
+
+    let result = HttpAgent::new(url).run_agent(RunAgentInput::new("t".to_string(), "r".to_string())).await;
+    let error = match result {
+        Err(error @ AgUiError::HttpError { .. }) => error,
+        other => panic!("expected an HTTP error, got {:?}", other.map(|_| ())),
+    };
+    assert!(matches!(&error, AgUiError::HttpError { status: 500, status_text, body }
+        if status_text == "Internal Server Error" && body == "model unavailable"));
+    assert_eq!(error.code(), "HTTP_ERROR");
+    assert_eq!(error.to_string(), "HTTP 500 Internal Server Error: model unavailable");
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_timeout_while_streaming_is_a_timeout() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/agent", listener.local_addr().unwrap());
+    let server = tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        read_request(&mut socket).await;
+        // One event, then nothing until the client gives up
+        let first = sse(&run_events()[..1]);
+        socket.write_all(response("200 OK", &first, first.len() + 100).as_bytes()).await.unwrap();
+        tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+    });
+    let client = reqwest::Client::builder().timeout(std::time::Duration::from_millis(300)).build().unwrap();
+
+    let received = run(&HttpAgent::new(url).with_client(client)).await;
+
+    assert_eq!(received.len(), 2);
+    assert!(received[0].is_ok());
+    match &received[1] {
+        Err(error @ AgUiError::Timeout(_)) => assert_eq!(error.code(), "TIMEOUT"),
+        other => panic!("expected a timeout, got {:?}", other),
+    }
+    server.abort();
+}
+
+#[tokio::test]
+async fn test_sse_event_stream_reassembles_split_chunks() {
+    let body = sse(&[
+        BaseEvent::text_message_content("msg1".to_string(), "héllo 👋".to_string()),
+        BaseEvent::run_finished("t".to_string(), "r".to_string()),
+    ]);
+    // Split every few bytes, including inside multi-byte characters
+    let chunks: Vec<Result<Vec<u8>, String>> = body.as_bytes().chunks(3).map(|chunk| Ok(chunk.to_vec())).collect();
+
+    let events: Vec<_> = sse_event_stream(futures::stream::iter(chunks)).collect().await;
+    assert_eq!(events.len(), 2);
+    let first = events[0].as_ref().unwrap();
+    assert_eq!(serde_json::to_value(first).unwrap()["delta"], "héllo 👋");
+    assert_eq!(events[1].as_ref().unwrap().event_type(), EventType::RunFinished);
+}
+
+#[tokio::test]
+async fn test_sse_event_stream_ends_after_an_error() {
+    let run_finished = sse(&[BaseEvent::run_finished("t".to_string(), "r".to_string())]);
+    let chunks: Vec<Result<String, String>> =
+        vec![Ok(run_finished.clone()), Ok("data: {not json}\n\n".to_string()), Ok(run_finished.clone())];
+
+    let mut events = sse_event_stream(futures::stream::iter(chunks));
+    assert!(matches!(events.next().await, Some(Ok(_))));
+    // The error names where the event starts in the body
+    let offset = run_finished.len() as u64;
+    let error = events.next().await.unwrap().unwrap_err();
+    assert!(matches!(error, AgUiError::DecodingError { offset: Some(at), .. } if at == offset));
+    assert!(events.next().await.is_none());
+
+    let failing: Vec<Result<String, String>> = vec![Err("connection reset".to_string())];
+    let mut events = sse_event_stream(futures::stream::iter(failing));
+    match events.next().await {
+        Some(Err(AgUiError::StreamError(message))) => assert!(message.contains("connection reset")),
+        other => panic!("expected a stream error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_retry_resumes_after_the_last_event_received() {
+    let events = run_events();
+    let cut_off = sse_with_ids(1, &events[..3]);
+    // The resumed stream starts with the last event received, which is dropped
+    let resumed = sse_with_ids(3, &events[2..]);
+    let (url, server) = serve_responses(vec![
+        response("200 OK", &cut_off, cut_off.len() + 100),
+        response("200 OK", &resumed, resumed.len()),
+    ])
+    .await;
+
+    let received = run(&retrying_agent(url)).await;
+
+    let types: Vec<_> = received.into_iter().map(|event| event.unwrap().event_type()).collect();
+    assert_eq!(types, events.iter().map(BaseEvent::event_type).collect::<Vec<_>>());
+    let requests = server.await.unwrap();
+    assert!(!requests[0].to_ascii_lowercase().contains("last-event-id"));
+    assert!(requests[1].to_ascii_lowercase().contains("last-event-id: 3"));
+    // The same run is requested again
+    assert!(requests[1].contains("\"runId\":\"run1\""));
+}
+
+#[tokio::test]
+async fn test_events_without_their_own_id_are_not_replays() {
+    let events = run_events();
+    // Only some events carry an `id:`; the others inherit the last one sent
+    let body: String = events
+        .iter()
+        .enumerate()
+        .map(|(index, event)| {
+            let id = if index % 2 == 0 { format!("id: {}\n", index + 1) } else { String::new() };
+            format!("{}data: {}\n\n", id, serde_json::to_string(event).unwrap())
+        })
+        .collect();
+    let (url, server) = serve_once("200 OK", body).await;
+
+    let received = run(&retrying_agent(url)).await;
+
+    let types: Vec<_> = received.into_iter().map(|event| event.unwrap().event_type()).collect();
+    assert_eq!(types, events.iter().map(BaseEvent::event_type).collect::<Vec<_>>());
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_retry_after_a_server_error() {
+    let body = sse(&run_events());
+    let (url, server) = serve_responses(vec![
+        response("503 Service Unavailable", "", 0),
+        response("200 OK", &body, body.len()),
+    ])
+    .await;
+
+    let received = run(&retrying_agent(url)).await;
+
+    assert_eq!(received.len(), 5);
+    assert!(received.iter().all(Result::is_ok));
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_retry_gives_up_after_max_retries() {
+    let (url, server) = serve_responses(vec![
+        response("503 Service Unavailable", "", 0),
+        response("503 Service Unavailable", "", 0),
+    ])
+    .await;
+    let agent = HttpAgent::new(url).with_retry(RetryPolicy { max_retries: 1, initial_delay_ms: 1, ..RetryPolicy::default() });
+
+    let result = agent.run_agent(RunAgentInput::new("thread1".to_string(), "run1".to_string())).await;
+
+    assert!(matches!(result, Err(AgUiError::HttpError { status: 503, .. })));
+    assert_eq!(server.await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_client_errors_are_not_retried() {
+    let (url, server) = serve_once("404 Not Found", String::new()).await;
+
+    let result = retrying_agent(url).run_agent(RunAgentInput::new("thread1".to_string(), "run1".to_string())).await;
+
+    assert!(matches!(result, Err(AgUiError::HttpError { status: 404, .. })));
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_resume_fails_without_event_ids() {
+    let cut_off = sse(&run_events()[..2]);
+    let (url, server) = serve_responses(vec![response("200 OK", &cut_off, cut_off.len() + 100)]).await;
+
+    let received = run(&retrying_agent(url)).await;
+
+    assert_eq!(received.len(), 3);
+    assert!(matches!(&received[2], Err(AgUiError::ResumeFailed(_))));
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_resume_fails_when_events_were_lost() {
+    let events = run_events();
+    let cut_off = sse_with_ids(1, &events[..2]);
+    let skipping = sse_with_ids(4, &events[3..]);
+    let (url, server) = serve_responses(vec![
+        response("200 OK", &cut_off, cut_off.len() + 100),
+        response("200 OK", &skipping, skipping.len()),
+    ])
+    .await;
+
+    let received = run(&retrying_agent(url)).await;
+
+    assert_eq!(received.len(), 3);
+    match &received[2] {
+        Err(AgUiError::ResumeFailed(message)) => assert!(message.contains("events after 2")),
+        other => panic!("expected a resume failure, got {:?}", other),
+    }
+    server.await.unwrap();
+}
+
+#[tokio::test]
+async fn test_resume_fails_when_the_server_refuses() {
+    let cut_off = sse_with_ids(1, &run_events()[..2]);
+    let (url, server) = serve_responses(vec![
+        response("200 OK", &cut_off, cut_off.len() + 100),
+        response("410 Gone", "", 0),
+    ])
+    .await;
+
+    let received = run(&retrying_agent(url)).await;
+
+    assert_eq!(received.len(), 3);
+    match &received[2] {
+        Err(AgUiError::ResumeFailed(message)) => assert!(message.contains("HTTP 410")),
+        other => panic!("expected a resume failure, got {:?}", other),
+    }
+    server.await.unwrap();
+}
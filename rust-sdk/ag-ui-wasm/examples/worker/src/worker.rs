@@ -1,15 +1,7 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::future_to_promise;
 use web_sys::{Request, Response, ResponseInit, Headers, Url};
-use ag_ui_wasm::{
-    BaseEvent, EventType, EventData, RunAgentInput, 
-    SSEEncoder, Role,
-    core::events::{
-        TextMessageStartEvent, TextMessageContentEvent, 
-        TextMessageEndEvent, RunStartedEvent, RunFinishedEvent
-    }
-};
-use uuid::Uuid;
+use ag_ui_wasm::{EventEncoder, RunAgentInput, RunEmitter};
 
 const TEST_HTML: &str = r#"<!DOCTYPE html>
 <html lang="en">
@@ -203,7 +195,7 @@ const TEST_HTML: &str = r#"<!DOCTYPE html>
                 <li><strong>RUN_FINISHED:</strong> Workflow complete - Stream closes and resources cleaned up automatically</li>
             </ol>
             <p style="margin: 10px 0 5px 0; font-size: 0.85em; font-style: italic;">
-                💡 All events happen synchronously in this demo, but in production they could be spaced out as real AI processing occurs!
+                💡 Events come from an async Rust function that the response body pulls from, so in production it can await real AI processing between them!
             </p>
         </div>
         
@@ -231,7 +223,7 @@ const TEST_HTML: &str = r#"<!DOCTYPE html>
                 <li><strong>WASM Compilation:</strong> Rust source compiled to WebAssembly binary via wasm-bindgen, running in V8's secure isolate sandbox</li>
                 <li><strong>JavaScript Shim:</strong> Minimal 26-line loader that initializes WASM module and forwards requests to Rust handler</li>
                 <li><strong>V8 Isolate:</strong> Each request runs in its own isolated V8 context with deterministic execution and memory limits</li>
-                <li><strong>Stream Flow:</strong> Rust creates ReadableStream → SSEEncoder formats events → Server-Sent Events → Browser EventSource API</li>
+                <li><strong>Stream Flow:</strong> Async Rust producer → EventStream pulled by a ReadableStream → SSE-encoded body → Browser EventSource API</li>
                 <li><strong>Type Safety:</strong> Every event uses strongly-typed Rust structs from ag-ui-wasm crate, compiled to WASM with zero runtime overhead</li>
                 <li><strong>Protocol:</strong> Full <a href="https://github.com/attackordie/ag-ui" target="_blank" style="color: #007cba;">AG-UI</a> compliance with proper event lifecycle management (CopilotKit's protocol)</li>
                 <li><strong>Performance:</strong> Zero-copy WASM integration, automatic memory management, and V8 JIT optimization</li>
@@ -300,8 +292,8 @@ const TEST_HTML: &str = r#"<!DOCTYPE html>
                         'Content-Type': 'application/json',
                     },
                     body: JSON.stringify({
-                        thread_id: threadId,
-                        run_id: runId
+                        threadId: threadId,
+                        runId: runId
                     })
                 });
                 
@@ -430,16 +422,19 @@ fn serve_test_page() -> Result<Response, JsValue> {
 }
 
 async fn handle_agent_request(request: Request) -> Result<Response, JsValue> {
+    // Answer in the format the client accepts: protobuf or SSE
+    let encoder = EventEncoder::new(request.headers().get("Accept")?);
+    
     // Parse request body
     let body = wasm_bindgen_futures::JsFuture::from(request.json()?).await?;
     let input: RunAgentInput = serde_wasm_bindgen::from_value(body)?;
     
-    // Create SSE stream
-    let stream = create_agent_stream(input)?;
+    // Create event stream
+    let stream = create_agent_stream(input, encoder)?;
     
     // Create response headers
     let headers = Headers::new()?;
-    headers.set("Content-Type", "text/event-stream")?;
+    headers.set("Content-Type", encoder.content_type())?;
     headers.set("Cache-Control", "no-cache")?;
     headers.set("Transfer-Encoding", "chunked")?;
     headers.set("Access-Control-Allow-Origin", "*")?;
@@ -468,92 +463,22 @@ fn handle_cors_preflight() -> Result<Response, JsValue> {
     Response::new_with_opt_str_and_init(None, &init)
 }
 
-fn create_agent_stream(input: RunAgentInput) -> Result<web_sys::ReadableStream, JsValue> {
-    let encoder = SSEEncoder::new();
-    let thread_id = input.thread_id;
-    let run_id = input.run_id;
-    
-    let source = js_sys::Object::new();
-    
-    // Start function
-    let start = Closure::wrap(Box::new(move |controller: web_sys::ReadableStreamDefaultController| -> Result<(), JsValue> {
-        // Send RUN_STARTED
-        let event = BaseEvent {
-            event_type: EventType::RunStarted,
-            timestamp: None,
-            raw_event: None,
-            data: EventData::RunStarted(RunStartedEvent {
-                thread_id: thread_id.clone(),
-                run_id: run_id.clone(),
-            }),
-        };
-        let encoded = encoder.encode_event(&event).map_err(|e| JsValue::from_str(&e.to_string()))?;
-        controller.enqueue_with_chunk(&encoded.into())?;
-        
-        // Simulate message generation
-        let message_id = Uuid::new_v4().to_string();
+fn create_agent_stream(input: RunAgentInput, encoder: EventEncoder) -> Result<web_sys::ReadableStream, JsValue> {
+    // RUN_STARTED is sent before the closure runs, and RUN_FINISHED (or
+    // RUN_ERROR if it fails) once it returns
+    let stream = RunEmitter::stream(input.thread_id, input.run_id, |mut run| async move {
+        // Message IDs are generated UUIDs that link the message events together
+        let message_id = run.start_message().await?;
         
-        // TEXT_MESSAGE_START
-        let event = BaseEvent {
-            event_type: EventType::TextMessageStart,
-            timestamp: None,
-            raw_event: None,
-            data: EventData::TextMessageStart(TextMessageStartEvent {
-                message_id: message_id.clone(),
-                role: Some(Role::Assistant),
-            }),
-        };
-        let encoded = encoder.encode_event(&event).map_err(|e| JsValue::from_str(&e.to_string()))?;
-        controller.enqueue_with_chunk(&encoded.into())?;
-        
-        // Send message content
+        // A real agent would await its model here and push each delta as it
+        // arrives; each push waits until the client has read the previous event
         let content = "Hello! I'm an AG-UI agent running in a Cloudflare Worker (Pure Rust implementation). AG-UI is CopilotKit's Agent User Interaction Protocol.";
-        let event = BaseEvent {
-            event_type: EventType::TextMessageContent,
-            timestamp: None,
-            raw_event: None,
-            data: EventData::TextMessageContent(TextMessageContentEvent {
-                message_id: message_id.clone(),
-                delta: content.to_string(),
-            }),
-        };
-        let encoded = encoder.encode_event(&event).map_err(|e| JsValue::from_str(&e.to_string()))?;
-        controller.enqueue_with_chunk(&encoded.into())?;
-        
-        // TEXT_MESSAGE_END
-        let event = BaseEvent {
-            event_type: EventType::TextMessageEnd,
-            timestamp: None,
-            raw_event: None,
-            data: EventData::TextMessageEnd(TextMessageEndEvent {
-                message_id: message_id.clone(),
-            }),
-        };
-        let encoded = encoder.encode_event(&event).map_err(|e| JsValue::from_str(&e.to_string()))?;
-        controller.enqueue_with_chunk(&encoded.into())?;
+        run.push_text(&message_id, content).await?;
         
-        // RUN_FINISHED
-        let event = BaseEvent {
-            event_type: EventType::RunFinished,
-            timestamp: None,
-            raw_event: None,
-            data: EventData::RunFinished(RunFinishedEvent {
-                thread_id: thread_id.clone(),
-                run_id: run_id.clone(),
-            }),
-        };
-        let encoded = encoder.encode_event(&event).map_err(|e| JsValue::from_str(&e.to_string()))?;
-        controller.enqueue_with_chunk(&encoded.into())?;
-        
-        // Close stream
-        controller.close()?;
-        Ok(())
-    }) as Box<dyn FnMut(_) -> Result<(), JsValue>>);
-    
-    js_sys::Reflect::set(&source, &"start".into(), start.as_ref())?;
-    start.forget();
+        run.end_message(&message_id).await
+    });
     
-    web_sys::ReadableStream::new_with_underlying_source(&source)
+    Ok(stream.into_encoded_stream(encoder)?)
 }
 
 fn create_error_response(message: &str, status: u16) -> Result<Response, JsValue> {
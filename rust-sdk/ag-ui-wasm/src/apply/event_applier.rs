@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+use futures::{Stream, StreamExt};
+use crate::core::events::{BaseEvent, EventData, ToolCallStartEvent};
+use crate::core::types::{
+    AssistantMessage, DeveloperMessage, Message, Role, State, SystemMessage, ToolCall, ToolMessage,
+    UserMessage,
+};
+use crate::error::{AgUiError, Result};
+
+/// Messages and state an agent run builds up from its events
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentState {
+    pub messages: Vec<Message>,
+    pub state: State,
+    /// IDs of the tool calls that have started but not yet ended
+    pub pending_tool_calls: HashSet<String>,
+}
+
+/// Which parts of an `AgentState` an event changed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AgentStateMutation {
+    pub messages: bool,
+    pub state: bool,
+}
+
+impl AgentStateMutation {
+    /// True when the event left the agent state as it was
+    pub fn is_empty(&self) -> bool {
+        !self.messages && !self.state
+    }
+}
+
+impl AgentState {
+    pub fn new(messages: Vec<Message>, state: State) -> Self {
+        Self { messages, state, pending_tool_calls: HashSet::new() }
+    }
+
+    /// Fold a single event into the messages and state
+    ///
+    /// Mirrors `defaultApplyEvents` from the TypeScript client: events that
+    /// refer to a message or tool call this state does not know about are
+    /// ignored. A failing `STATE_DELTA` leaves the state unchanged.
+    pub fn apply(&mut self, event: &BaseEvent) -> Result<AgentStateMutation> {
+        let mut mutation = AgentStateMutation::default();
+
+        match &event.data {
+            EventData::TextMessageStart(data) => {
+                let message = empty_message(&data.message_id, data.role.unwrap_or(Role::Assistant))?;
+                self.messages.push(message);
+                mutation.messages = true;
+            }
+            EventData::TextMessageContent(data) => {
+                if let Some(message) = self.messages.iter_mut().find(|message| message.id() == data.message_id) {
+                    append_content(message, &data.delta);
+                    mutation.messages = true;
+                }
+            }
+            EventData::ToolCallStart(data) => {
+                self.start_tool_call(data);
+                mutation.messages = true;
+            }
+            EventData::ToolCallArgs(data) => {
+                if let Some(tool_call) = find_tool_call(&mut self.messages, &data.tool_call_id) {
+                    tool_call.function.arguments.push_str(&data.delta);
+                    mutation.messages = true;
+                }
+            }
+            EventData::ToolCallEnd(data) => {
+                self.pending_tool_calls.remove(&data.tool_call_id);
+            }
+            EventData::ToolCallResult(data) => {
+                let message = ToolMessage::new(data.content.clone(), data.tool_call_id.clone())
+                    .with_id(data.message_id.clone());
+                self.messages.push(message.into());
+                mutation.messages = true;
+            }
+            EventData::StateSnapshot(data) => {
+                self.state = data.snapshot.clone();
+                mutation.state = true;
+            }
+            EventData::StateDelta(data) => {
+                data.apply_to(&mut self.state)?;
+                mutation.state = true;
+            }
+            EventData::MessagesSnapshot(data) => {
+                self.messages = data.messages.clone();
+                let messages = &self.messages;
+                self.pending_tool_calls
+                    .retain(|tool_call_id| messages.iter().any(|message| has_tool_call(message, tool_call_id)));
+                mutation.messages = true;
+            }
+            EventData::TextMessageChunk(_) | EventData::ToolCallChunk(_) => {
+                return Err(AgUiError::ChunkNotExpanded { event_type: event.event_type() });
+            }
+            EventData::TextMessageEnd(_)
+            | EventData::ThinkingStart(_)
+            | EventData::ThinkingEnd(_)
+            | EventData::ThinkingTextMessageStart(_)
+            | EventData::ThinkingTextMessageContent(_)
+            | EventData::ThinkingTextMessageEnd(_)
+            | EventData::RunStarted(_)
+            | EventData::RunFinished(_)
+            | EventData::RunAborted(_)
+            | EventData::RunError(_)
+            | EventData::StepStarted(_)
+            | EventData::StepFinished(_)
+            | EventData::Error(_)
+            | EventData::Raw(_)
+            | EventData::Custom(_) => {}
+        }
+
+        Ok(mutation)
+    }
+
+    fn start_tool_call(&mut self, data: &ToolCallStartEvent) {
+        let tool_call = ToolCall::new(data.tool_call_id.clone(), data.tool_call_name.clone(), "");
+        self.pending_tool_calls.insert(data.tool_call_id.clone());
+
+        // Tool calls join the assistant message they belong to when it is the latest one
+        if let Some(Message::Assistant(parent)) = self.messages.last_mut() {
+            if data.parent_message_id.as_deref() == Some(parent.id.as_str()) {
+                parent.tool_calls.get_or_insert_with(Vec::new).push(tool_call);
+                return;
+            }
+        }
+
+        let id = data.parent_message_id.clone().unwrap_or_else(|| data.tool_call_id.clone());
+        self.messages.push(AssistantMessage::new().with_id(id).with_tool_calls(vec![tool_call]).into());
+    }
+}
+
+fn empty_message(id: &str, role: Role) -> Result<Message> {
+    let id = id.to_string();
+    Ok(match role {
+        Role::Developer => DeveloperMessage::new("").with_id(id).into(),
+        Role::System => SystemMessage::new("").with_id(id).into(),
+        Role::Assistant => AssistantMessage::new().with_id(id).with_content("").into(),
+        Role::User => UserMessage::new("").with_id(id).into(),
+        Role::Tool => {
+            return Err(AgUiError::InvalidMessage(format!(
+                "text message '{}' cannot have the tool role; tool results arrive as 'TOOL_CALL_RESULT'",
+                id
+            )))
+        }
+    })
+}
+
+fn append_content(message: &mut Message, delta: &str) {
+    match message {
+        Message::Developer(message) => message.content.push_str(delta),
+        Message::System(message) => message.content.push_str(delta),
+        Message::Assistant(message) => message.content.get_or_insert_with(String::new).push_str(delta),
+        Message::User(message) => message.content.push_str(delta),
+        Message::Tool(message) => message.content.push_str(delta),
+    }
+}
+
+fn find_tool_call<'a>(messages: &'a mut [Message], tool_call_id: &str) -> Option<&'a mut ToolCall> {
+    messages.iter_mut().rev().find_map(|message| match message {
+        Message::Assistant(message) => message
+            .tool_calls
+            .as_mut()?
+            .iter_mut()
+            .find(|tool_call| tool_call.id == tool_call_id),
+        _ => None,
+    })
+}
+
+fn has_tool_call(message: &Message, tool_call_id: &str) -> bool {
+    message
+        .tool_calls()
+        .is_some_and(|tool_calls| tool_calls.iter().any(|tool_call| tool_call.id == tool_call_id))
+}
+
+type MutationListener = Box<dyn FnMut(&AgentStateMutation, &AgentState)>;
+
+/// Folds events into an `AgentState` and notifies listeners after each one
+///
+/// Listeners are called after every applied event, including events that
+/// changed nothing; check `AgentStateMutation::is_empty` to skip those.
+#[derive(Default)]
+pub struct EventApplier {
+    state: AgentState,
+    listeners: Vec<MutationListener>,
+}
+
+impl EventApplier {
+    pub fn new(state: AgentState) -> Self {
+        Self { state, listeners: Vec::new() }
+    }
+
+    /// Register a listener called with the mutation and the updated state
+    pub fn on_mutation(&mut self, listener: impl FnMut(&AgentStateMutation, &AgentState) + 'static) {
+        self.listeners.push(Box::new(listener));
+    }
+
+    /// Apply one event and notify the listeners
+    ///
+    /// Listeners are not called for an event that fails to apply.
+    pub fn apply(&mut self, event: &BaseEvent) -> Result<AgentStateMutation> {
+        let mutation = self.state.apply(event)?;
+        for listener in &mut self.listeners {
+            listener(&mutation, &self.state);
+        }
+        Ok(mutation)
+    }
+
+    /// Apply a sequence of events, stopping at the first failure
+    pub fn apply_all<'a>(&mut self, events: impl IntoIterator<Item = &'a BaseEvent>) -> Result<()> {
+        events.into_iter().try_for_each(|event| self.apply(event).map(drop))
+    }
+
+    /// Apply every event of `events` until it ends or yields an error
+    pub async fn apply_stream<S>(&mut self, events: S) -> Result<()>
+    where
+        S: Stream<Item = Result<BaseEvent>>,
+    {
+        futures::pin_mut!(events);
+        while let Some(event) = events.next().await {
+            self.apply(&event?)?;
+        }
+        Ok(())
+    }
+
+    pub fn state(&self) -> &AgentState {
+        &self.state
+    }
+
+    pub fn into_state(self) -> AgentState {
+        self.state
+    }
+}
+
+impl std::fmt::Debug for EventApplier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventApplier")
+            .field("state", &self.state)
+            .field("listeners", &self.listeners.len())
+            .finish()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod event_applier;
+
+pub use event_applier::{AgentState, AgentStateMutation, EventApplier};
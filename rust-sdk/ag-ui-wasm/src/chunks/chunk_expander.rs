@@ -0,0 +1,189 @@
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Utc};
+
+use crate::core::events::{
+    BaseEvent, EventData, EventType, TextMessageChunkEvent, TextMessageContentEvent, TextMessageEndEvent,
+    TextMessageStartEvent, ToolCallArgsEvent, ToolCallChunkEvent, ToolCallEndEvent, ToolCallStartEvent,
+};
+use crate::core::types::Role;
+use crate::error::{AgUiError, Result};
+use crate::stream::EventStream;
+
+/// The message or tool call the expander is in the middle of
+#[derive(Debug, Clone, PartialEq)]
+enum Pending {
+    Text { message_id: String },
+    Tool { tool_call_id: String },
+}
+
+/// Expands chunk events into the canonical START/CONTENT/END lifecycle
+///
+/// Mirrors `transformChunks` from the TypeScript client. A
+/// `TEXT_MESSAGE_CHUNK` opens a text message unless one with the same ID is
+/// already open and becomes its `TEXT_MESSAGE_CONTENT`; a `TOOL_CALL_CHUNK`
+/// does the same with `TOOL_CALL_START`/`TOOL_CALL_ARGS`. Chunks without an
+/// ID continue the open message or tool call. The open message or tool call
+/// is ended by a chunk for a different ID, by any other event except `RAW`,
+/// and by `finish` at the end of the stream.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkExpander {
+    pending: Option<Pending>,
+}
+
+impl ChunkExpander {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Expand one event into the events that replace it
+    pub fn expand(&mut self, event: BaseEvent) -> Result<Vec<BaseEvent>> {
+        let mut expanded = Vec::new();
+
+        match &event.data {
+            EventData::TextMessageChunk(chunk) => self.expand_text_chunk(chunk, &event, &mut expanded)?,
+            EventData::ToolCallChunk(chunk) => self.expand_tool_call_chunk(chunk, &event, &mut expanded)?,
+            EventData::Raw(_) => expanded.push(event),
+            _ => {
+                expanded.extend(self.close_pending(event.timestamp));
+                expanded.push(event);
+            }
+        }
+
+        Ok(expanded)
+    }
+
+    /// End the message or tool call still open when the stream ends
+    pub fn finish(&mut self) -> Vec<BaseEvent> {
+        self.close_pending(Some(Utc::now())).into_iter().collect()
+    }
+
+    /// Expand every event read from `stream`
+    pub fn wrap(self, stream: EventStream) -> ExpandedEventStream {
+        ExpandedEventStream { stream, expander: self, buffered: VecDeque::new() }
+    }
+
+    fn expand_text_chunk(
+        &mut self,
+        chunk: &TextMessageChunkEvent,
+        source: &BaseEvent,
+        expanded: &mut Vec<BaseEvent>,
+    ) -> Result<()> {
+        let continues = match (&self.pending, &chunk.message_id) {
+            (Some(Pending::Text { message_id }), Some(id)) => message_id == id,
+            (Some(Pending::Text { .. }), None) => true,
+            _ => false,
+        };
+
+        if !continues {
+            let message_id = chunk.message_id.clone().ok_or(AgUiError::ChunkMissingField {
+                event_type: EventType::TextMessageChunk,
+                field: "messageId",
+            })?;
+            expanded.extend(self.close_pending(source.timestamp));
+            expanded.push(derived(
+                source,
+                TextMessageStartEvent { message_id: message_id.clone(), role: Some(chunk.role.unwrap_or(Role::Assistant)) },
+            ));
+            self.pending = Some(Pending::Text { message_id });
+        }
+
+        if let (Some(delta), Some(Pending::Text { message_id })) = (&chunk.delta, &self.pending) {
+            expanded.push(derived(
+                source,
+                TextMessageContentEvent { message_id: message_id.clone(), delta: delta.clone() },
+            ));
+        }
+        Ok(())
+    }
+
+    fn expand_tool_call_chunk(
+        &mut self,
+        chunk: &ToolCallChunkEvent,
+        source: &BaseEvent,
+        expanded: &mut Vec<BaseEvent>,
+    ) -> Result<()> {
+        let continues = match (&self.pending, &chunk.tool_call_id) {
+            (Some(Pending::Tool { tool_call_id }), Some(id)) => tool_call_id == id,
+            (Some(Pending::Tool { .. }), None) => true,
+            _ => false,
+        };
+
+        if !continues {
+            let missing = |field| AgUiError::ChunkMissingField { event_type: EventType::ToolCallChunk, field };
+            let tool_call_id = chunk.tool_call_id.clone().ok_or_else(|| missing("toolCallId"))?;
+            let tool_call_name = chunk.tool_call_name.clone().ok_or_else(|| missing("toolCallName"))?;
+            expanded.extend(self.close_pending(source.timestamp));
+            expanded.push(derived(
+                source,
+                ToolCallStartEvent {
+                    tool_call_id: tool_call_id.clone(),
+                    tool_call_name,
+                    parent_message_id: chunk.parent_message_id.clone(),
+                },
+            ));
+            self.pending = Some(Pending::Tool { tool_call_id });
+        }
+
+        if let (Some(delta), Some(Pending::Tool { tool_call_id })) = (&chunk.delta, &self.pending) {
+            expanded.push(derived(
+                source,
+                ToolCallArgsEvent { tool_call_id: tool_call_id.clone(), delta: delta.clone() },
+            ));
+        }
+        Ok(())
+    }
+
+    fn close_pending(&mut self, timestamp: Option<DateTime<Utc>>) -> Option<BaseEvent> {
+        let data: EventData = match self.pending.take()? {
+            Pending::Text { message_id } => TextMessageEndEvent { message_id }.into(),
+            Pending::Tool { tool_call_id } => ToolCallEndEvent { tool_call_id }.into(),
+        };
+        Some(BaseEvent { timestamp, raw_event: None, data })
+    }
+}
+
+/// An event generated for `source`, sharing its timestamp
+fn derived(source: &BaseEvent, data: impl Into<EventData>) -> BaseEvent {
+    BaseEvent { timestamp: source.timestamp, raw_event: None, data: data.into() }
+}
+
+/// Expand the chunk events of an in-memory sequence
+pub fn expand_chunks(events: impl IntoIterator<Item = BaseEvent>) -> Result<Vec<BaseEvent>> {
+    let mut expander = ChunkExpander::new();
+    let mut expanded = Vec::new();
+    for event in events {
+        expanded.extend(expander.expand(event)?);
+    }
+    expanded.extend(expander.finish());
+    Ok(expanded)
+}
+
+/// An `EventStream` whose chunk events are expanded by a `ChunkExpander`
+pub struct ExpandedEventStream {
+    stream: EventStream,
+    expander: ChunkExpander,
+    buffered: VecDeque<BaseEvent>,
+}
+
+impl ExpandedEventStream {
+    /// Read the next expanded event
+    ///
+    /// When the underlying stream ends, the message or tool call still open
+    /// is ended before `None` is returned.
+    pub async fn next_event(&mut self) -> Result<Option<BaseEvent>> {
+        loop {
+            if let Some(event) = self.buffered.pop_front() {
+                return Ok(Some(event));
+            }
+            match self.stream.next_event().await? {
+                Some(event) => self.buffered.extend(self.expander.expand(event)?),
+                None => return Ok(self.expander.finish().pop()),
+            }
+        }
+    }
+
+    pub fn into_inner(self) -> EventStream {
+        self.stream
+    }
+}
@@ -0,0 +1,3 @@
+pub mod chunk_expander;
+
+pub use chunk_expander::{expand_chunks, ChunkExpander, ExpandedEventStream};
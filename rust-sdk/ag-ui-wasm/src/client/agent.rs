@@ -1,27 +1,222 @@
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::apply::AgentState;
+use crate::chunks::ChunkExpander;
+use crate::core::events::{BaseEvent, EventData};
 use crate::core::types::{Message, RunAgentInput, State};
 use crate::stream::EventStream;
-use crate::error::Result;
+use crate::verify::EventVerifier;
+use crate::error::{AgUiError, Result};
+
+/// What an agent remembers between runs
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AgentData {
+    pub agent_id: Option<String>,
+    pub thread_id: Option<String>,
+    pub messages: Vec<Message>,
+    pub state: State,
+}
 
-/// Trait for AG-UI agents in WASM environments
+/// What a run produced, like `RunAgentResult` in TypeScript
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunAgentResult {
+    /// The `result` of the run's `RUN_FINISHED` event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// The messages the run added, in order
+    pub new_messages: Vec<Message>,
+}
+
+/// Trait for AG-UI agents, the Rust counterpart of `AbstractAgent`
+///
+/// An implementation keeps an `AgentData` and provides `run`, which sends a
+/// complete `RunAgentInput` over its transport, or produces the events itself
+/// for an in-process agent. The provided methods keep the messages and state,
+/// and `run_agent` fills them into the input, reads the run's events and
+/// applies them back.
+///
+/// The data sits in a `RefCell` so a run can update it when it ends while the
+/// agent is shared, as a JavaScript caller shares a `WebAgent`. It is never
+/// borrowed across an `.await`. The futures are not `Send`: agents run on the
+/// single thread of a V8 isolate and their streams hold JavaScript objects.
+#[allow(async_fn_in_trait)]
 pub trait Agent {
-    /// Run the agent with the given input
-    fn run_agent(&self, input: RunAgentInput) -> Result<EventStream>;
-    
+    /// The agent's messages, state and IDs
+    fn data(&self) -> &RefCell<AgentData>;
+
+    /// Start a run with an input that already carries the messages and state
+    async fn run(&self, input: RunAgentInput) -> Result<EventStream>;
+
+    /// Run the agent and apply its events to the messages and state
+    ///
+    /// See `run_agent_with`.
+    async fn run_agent(&self, input: RunAgentInput) -> Result<RunAgentResult> {
+        self.run_agent_with(input, |_| {}).await
+    }
+
+    /// Run the agent, calling `on_event` with every event before it is applied
+    ///
+    /// As in `AbstractAgent.runAgent`, chunk events are expanded and every
+    /// event is verified before it is applied. When the run ends the agent
+    /// takes on the new messages and state and the input's thread ID, so the
+    /// next run continues the thread. A run that fails part way keeps what it
+    /// applied before the error.
+    ///
+    /// A run that ends with a `RUN_ERROR` event fails with the error the
+    /// event reports, see `AgUiError::from_run_error`, after its messages
+    /// and state are kept. A run that `run` or its stream ends with
+    /// `AgUiError::Aborted` gets a local `RUN_ERROR` with the code `ABORTED`,
+    /// which is passed to `on_event`, and fails with `Aborted`.
+    async fn run_agent_with(&self, input: RunAgentInput, mut on_event: impl FnMut(&BaseEvent)) -> Result<RunAgentResult> {
+        let input = self.prepare_run_input(input);
+        let thread_id = input.thread_id.clone();
+
+        let mut run = AppliedRun::new(&self.data().borrow());
+        let outcome = match self.run(input).await {
+            Ok(events) => run.consume(events, &mut on_event).await,
+            Err(error) => Err(error),
+        };
+        if let Err(AgUiError::Aborted) = outcome {
+            run.abort(&mut on_event);
+        }
+        let run_error = run.run_error.take();
+        let result = run.commit(&mut self.data().borrow_mut(), thread_id);
+        outcome.and_then(|()| run_error.map_or(Ok(result), Err))
+    }
+
+    /// Stop the run in progress
+    ///
+    /// Does nothing unless the agent's transport can be cancelled. `WebAgent`
+    /// aborts its fetch, and the run fails with `AgUiError::Aborted`.
+    fn abort_run(&self) {}
+
+    /// Complete an input with the agent's thread, messages and state
+    ///
+    /// An empty thread ID becomes the agent's thread ID, or a new one, and an
+    /// empty run ID a new one. Messages and state are filled in when the
+    /// input has none.
+    fn prepare_run_input(&self, input: RunAgentInput) -> RunAgentInput {
+        let data = self.data().borrow();
+        input.prepare(data.thread_id.as_deref(), &data.messages, &data.state)
+    }
+
     /// Get the current messages
-    fn messages(&self) -> Vec<Message>;
-    
+    fn messages(&self) -> Vec<Message> {
+        self.data().borrow().messages.clone()
+    }
+
     /// Set messages
-    fn set_messages(&mut self, messages: Vec<Message>);
-    
+    fn set_messages(&mut self, messages: Vec<Message>) {
+        self.data().borrow_mut().messages = messages;
+    }
+
+    /// Append a message
+    fn add_message(&mut self, message: Message) {
+        self.data().borrow_mut().messages.push(message);
+    }
+
     /// Get the current state
-    fn state(&self) -> State;
-    
+    fn state(&self) -> State {
+        self.data().borrow().state.clone()
+    }
+
     /// Set state
-    fn set_state(&mut self, state: State);
-    
+    fn set_state(&mut self, state: State) {
+        self.data().borrow_mut().state = state;
+    }
+
     /// Get the agent ID
-    fn agent_id(&self) -> Option<String>;
-    
+    fn agent_id(&self) -> Option<String> {
+        self.data().borrow().agent_id.clone()
+    }
+
     /// Get the thread ID
-    fn thread_id(&self) -> Option<String>;
-} 
\ No newline at end of file
+    fn thread_id(&self) -> Option<String> {
+        self.data().borrow().thread_id.clone()
+    }
+}
+
+/// One run's events folded into a copy of the agent's messages and state
+struct AppliedRun {
+    state: AgentState,
+    known_messages: HashSet<String>,
+    expander: ChunkExpander,
+    verifier: EventVerifier,
+    result: Option<Value>,
+    /// The error the run's `RUN_ERROR` reported
+    run_error: Option<AgUiError>,
+}
+
+impl AppliedRun {
+    fn new(data: &AgentData) -> Self {
+        Self {
+            state: AgentState::new(data.messages.clone(), data.state.clone()),
+            known_messages: data.messages.iter().map(|message| message.id().to_string()).collect(),
+            expander: ChunkExpander::new(),
+            verifier: EventVerifier::new(),
+            result: None,
+            run_error: None,
+        }
+    }
+
+    /// Apply every event of `events`, stopping at the first error
+    ///
+    /// On an error the rest of the stream is cancelled.
+    async fn consume(&mut self, mut events: EventStream, on_event: &mut impl FnMut(&BaseEvent)) -> Result<()> {
+        let outcome = self.consume_events(&mut events, on_event).await;
+        if outcome.is_err() {
+            events.cancel();
+        }
+        outcome
+    }
+
+    async fn consume_events(&mut self, events: &mut EventStream, on_event: &mut impl FnMut(&BaseEvent)) -> Result<()> {
+        while let Some(event) = events.next_event().await? {
+            for event in self.expander.expand(event)? {
+                self.apply(&event, on_event)?;
+            }
+        }
+        for event in self.expander.finish() {
+            self.apply(&event, on_event)?;
+        }
+        Ok(())
+    }
+
+    /// End an aborted run with a local `RUN_ERROR`, as `RunEmitter` does
+    ///
+    /// The event is not verified: it must be delivered whatever the run sent.
+    fn abort(&mut self, on_event: &mut impl FnMut(&BaseEvent)) {
+        on_event(&AgUiError::Aborted.to_run_error());
+    }
+
+    fn apply(&mut self, event: &BaseEvent, on_event: &mut impl FnMut(&BaseEvent)) -> Result<()> {
+        self.verifier.verify(event)?;
+        on_event(event);
+        match &event.data {
+            EventData::RunFinished(data) => self.result = data.result.clone(),
+            EventData::RunError(_) => self.run_error = AgUiError::from_event(event),
+            _ => {}
+        }
+        self.state.apply(event).map(drop)
+    }
+
+    /// Store the messages, state and thread in `data`
+    fn commit(self, data: &mut AgentData, thread_id: String) -> RunAgentResult {
+        let new_messages = self
+            .state
+            .messages
+            .iter()
+            .filter(|message| !self.known_messages.contains(message.id()))
+            .cloned()
+            .collect();
+        data.thread_id = Some(thread_id);
+        data.messages = self.state.messages;
+        data.state = self.state.state;
+        RunAgentResult { result: self.result, new_messages }
+    }
+}
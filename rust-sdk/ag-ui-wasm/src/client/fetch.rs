@@ -0,0 +1,32 @@
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Request, Response};
+use crate::error::{AgUiError, Result};
+
+/// The host's `fetch`, read from `globalThis`
+///
+/// `globalThis` is the `Window` in browsers, the `WorkerGlobalScope` in Web
+/// Workers and Cloudflare Workers, and the global object in Node 18+ and Deno,
+/// so this works wherever `fetch` exists. `web_sys::window()` only works in
+/// the first.
+pub fn global_fetch() -> Result<Function> {
+    Reflect::get(&js_sys::global(), &JsValue::from_str("fetch"))?
+        .dyn_into()
+        .map_err(|_| AgUiError::ConnectionError(
+            "No global fetch function; pass one in the WebAgent config".to_string()
+        ))
+}
+
+/// Send `request` with `fetch`, or with the host's `fetch` when it is `None`
+///
+/// The host's `fetch` is called on the global object, as some hosts require.
+/// An injected function is called without a receiver.
+pub async fn fetch(fetch: Option<&Function>, request: &Request) -> Result<Response> {
+    let response = match fetch {
+        Some(fetch) => fetch.call1(&JsValue::UNDEFINED, request)?,
+        None => global_fetch()?.call1(&js_sys::global(), request)?,
+    };
+    let response = JsFuture::from(Promise::resolve(&response)).await?;
+    response.dyn_into().map_err(|_| AgUiError::ConnectionError("fetch did not resolve to a Response".to_string()))
+}
@@ -1,5 +1,8 @@
 pub mod agent;
+pub mod fetch;
 pub mod web_agent;
+pub mod web_agent_config;
 
-pub use agent::Agent;
-pub use web_agent::WebAgent; 
\ No newline at end of file
+pub use agent::{Agent, AgentData, RunAgentResult};
+pub use web_agent::WebAgent;
+pub use web_agent_config::{HeaderProvider, RequestMutator, WebAgentConfig}; 
\ No newline at end of file
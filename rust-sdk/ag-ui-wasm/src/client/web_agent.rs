@@ -1,150 +1,446 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+use futures::channel::oneshot;
+use serde::Serialize;
+use serde_json::Value;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Request, RequestInit, RequestMode, Response, Headers};
-use crate::client::agent::Agent;
+use web_sys::{AbortController, AbortSignal, Request, RequestInit, Headers, Response};
+use crate::client::agent::{Agent, AgentData};
+use crate::client::fetch;
+use crate::client::web_agent_config::WebAgentConfig;
+use crate::core::events::BaseEvent;
+use crate::core::retry::{is_retryable_status, Resumption, RetryPolicy};
 use crate::core::types::{Message, RunAgentInput, State};
+use crate::proto::AGUI_MEDIA_TYPE;
 use crate::stream::EventStream;
 use crate::error::{AgUiError, Result};
-use js_sys::Promise;
+use js_sys::{Function, Promise, Reflect};
 
 /// Web-based AG-UI agent client using Fetch API
+///
+/// Clones share the agent's messages, state, IDs and current run, so a run
+/// started from JavaScript updates, and is aborted by, the agent it was
+/// started on.
 #[wasm_bindgen]
+#[derive(Clone)]
 pub struct WebAgent {
-    url: String,
-    agent_id: Option<String>,
-    thread_id: Option<String>,
-    messages: Vec<Message>,
-    state: State,
+    config: Rc<WebAgentConfig>,
+    data: Rc<RefCell<AgentData>>,
+    /// Aborts the fetch of the latest run
+    abort_controller: Rc<RefCell<Option<AbortController>>>,
 }
 
 #[wasm_bindgen]
 impl WebAgent {
     #[wasm_bindgen(constructor)]
     pub fn new(url: String) -> Self {
-        Self {
-            url,
-            agent_id: None,
-            thread_id: None,
-            messages: Vec::new(),
-            state: State::new(),
-        }
+        Self::with_config(WebAgentConfig::new(url))
+    }
+    
+    /// Create an agent from a `WebAgentConfig` object in JavaScript
+    #[wasm_bindgen(js_name = "withConfig")]
+    pub fn with_config_js(config: JsValue) -> std::result::Result<WebAgent, JsValue> {
+        Ok(Self::with_config(WebAgentConfig::from_js(&config)?))
     }
     
     #[wasm_bindgen(js_name = "setAgentId")]
     pub fn set_agent_id(&mut self, agent_id: String) {
-        self.agent_id = Some(agent_id);
+        self.data.borrow_mut().agent_id = Some(agent_id);
     }
     
     #[wasm_bindgen(js_name = "setThreadId")]
     pub fn set_thread_id(&mut self, thread_id: String) {
-        self.thread_id = Some(thread_id);
+        self.data.borrow_mut().thread_id = Some(thread_id);
+    }
+    
+    #[wasm_bindgen(getter, js_name = "agentId")]
+    pub fn agent_id_js(&self) -> Option<String> {
+        self.agent_id()
+    }
+    
+    #[wasm_bindgen(getter, js_name = "threadId")]
+    pub fn thread_id_js(&self) -> Option<String> {
+        self.thread_id()
+    }
+    
+    /// The agent's messages, including those added by finished runs
+    #[wasm_bindgen(getter, js_name = "messages")]
+    pub fn messages_js(&self) -> std::result::Result<JsValue, JsValue> {
+        to_js(&self.data.borrow().messages)
+    }
+    
+    #[wasm_bindgen(js_name = "setMessages")]
+    pub fn set_messages_js(&mut self, messages: JsValue) -> std::result::Result<(), JsValue> {
+        let messages: Vec<Message> = serde_wasm_bindgen::from_value(messages)?;
+        self.set_messages(messages);
+        Ok(())
     }
     
+    /// The agent's state, as updated by finished runs
+    #[wasm_bindgen(getter, js_name = "state")]
+    pub fn state_js(&self) -> std::result::Result<JsValue, JsValue> {
+        to_js(&self.data.borrow().state)
+    }
+    
+    #[wasm_bindgen(js_name = "setState")]
+    pub fn set_state_js(&mut self, state: JsValue) -> std::result::Result<(), JsValue> {
+        let state: State = serde_wasm_bindgen::from_value(state)?;
+        self.set_state(state);
+        Ok(())
+    }
+    
+    /// Run the agent and apply its events to the messages and state
+    ///
+    /// Resolves to `{ newMessages, result }` when the run ends. `onEvent` is
+    /// called with every event before it is applied; exceptions it throws
+    /// are ignored. `threadId` and `runId` may be left out: the thread
+    /// defaults to the agent's and the run ID to a new one.
     #[wasm_bindgen(js_name = "runAgent")]
-    pub fn run_agent_js(&self, input_js: JsValue) -> Promise {
-        let input: RunAgentInput = match serde_wasm_bindgen::from_value(input_js) {
+    pub fn run_agent_js(&self, input_js: JsValue, on_event: Option<Function>) -> Promise {
+        let input = match input_from_js(input_js) {
+            Ok(input) => input,
+            Err(e) => return Promise::reject(&e),
+        };
+        
+        let agent = self.clone();
+        wasm_bindgen_futures::future_to_promise(async move {
+            let result = agent.run_agent_with(input, |event| {
+                if let (Some(on_event), Ok(event)) = (&on_event, to_js(event)) {
+                    let _ = on_event.call1(&JsValue::NULL, &event);
+                }
+            }).await?;
+            to_js(&result)
+        })
+    }
+    
+    /// Start a run without applying its events, resolving to a
+    /// `ReadableStream` of events that can be iterated with `for await`
+    #[wasm_bindgen(js_name = "run")]
+    pub fn run_js(&self, input_js: JsValue) -> Promise {
+        let input = match input_from_js(input_js) {
             Ok(input) => input,
-            Err(e) => return Promise::reject(&JsValue::from_str(&format!("Invalid RunAgentInput: {}", e))),
+            Err(e) => return Promise::reject(&e),
         };
         
-        let url = self.url.clone();
-        let messages = self.messages.clone();
-        let state = self.state.clone();
+        let agent = self.clone();
+        let input = self.prepare_run_input(input);
         
         wasm_bindgen_futures::future_to_promise(async move {
-            let stream = run_agent_internal(url, input, messages, state).await
-                .map_err(|e| JsValue::from(e))?;
-            Ok(stream.into())
+            let stream = agent.run(input).await?;
+            Ok(stream.into_readable_stream()?.into())
         })
     }
+    
+    /// Abort the latest run
+    ///
+    /// Cancels its fetch and response body. `runAgent` then calls `onEvent`
+    /// with a `RUN_ERROR` with the code `ABORTED` and rejects, and the agent
+    /// keeps the messages and state of the events read before the abort.
+    #[wasm_bindgen(js_name = "abortRun")]
+    pub fn abort_run_js(&self) {
+        self.abort_run();
+    }
+    
+    /// Give the next run its own `AbortController`, returning its signal
+    fn start_run(&self) -> Result<AbortSignal> {
+        let controller = AbortController::new()?;
+        let signal = controller.signal();
+        *self.abort_controller.borrow_mut() = Some(controller);
+        Ok(signal)
+    }
 }
 
-// Internal implementation
-async fn run_agent_internal(
-    url: String,
-    mut input: RunAgentInput,
-    messages: Vec<Message>,
-    state: State,
-) -> Result<EventStream> {
-    // Add current messages if not provided
-    if input.messages.is_none() && !messages.is_empty() {
-        input.messages = Some(messages);
+impl WebAgent {
+    /// Create an agent that builds its requests as `config` says
+    pub fn with_config(config: WebAgentConfig) -> Self {
+        Self {
+            config: Rc::new(config),
+            data: Rc::default(),
+            abort_controller: Rc::default(),
+        }
     }
     
-    // Add current state if not provided
-    if input.state.is_none() && !state.is_empty() {
-        input.state = Some(state);
+    pub fn config(&self) -> &WebAgentConfig {
+        &self.config
     }
-    
+}
+
+/// Read a `RunAgentInput` from JavaScript, where `threadId` and `runId` are optional
+fn input_from_js(input_js: JsValue) -> std::result::Result<RunAgentInput, JsValue> {
+    let invalid = |e: &dyn std::fmt::Display| {
+        JsValue::from(AgUiError::decoding(format!("Invalid RunAgentInput: {}", e)))
+    };
+    let mut input: Value = serde_wasm_bindgen::from_value(input_js).map_err(|e| invalid(&e))?;
+    if let Some(fields) = input.as_object_mut() {
+        for field in ["threadId", "runId"] {
+            fields.entry(field).or_insert_with(|| Value::String(String::new()));
+        }
+    }
+    serde_json::from_value(input).map_err(|e| invalid(&e))
+}
+
+fn to_js<T: Serialize + ?Sized>(value: &T) -> std::result::Result<JsValue, JsValue> {
+    Ok(value.serialize(&serde_wasm_bindgen::Serializer::json_compatible()).map_err(AgUiError::from)?)
+}
+
+// Internal implementation
+async fn run_agent_internal(config: &WebAgentConfig, input: RunAgentInput, signal: AbortSignal) -> Result<EventStream> {
+    let response = send_request(config, &input, &signal, None).await?;
+    if !response.ok() {
+        return Err(http_error(&response).await);
+    }
+    response_events(&response, signal)
+}
+
+/// POST `input`, resuming after `last_event_id` if there is one
+async fn send_request(
+    config: &WebAgentConfig,
+    input: &RunAgentInput,
+    signal: &AbortSignal,
+    last_event_id: Option<&str>,
+) -> Result<Response> {
     // Create request options
     let opts = RequestInit::new();
+    // `mode` keeps the default, `cors`: Cloudflare Workers reject the field
     opts.set_method("POST");
-    opts.set_mode(RequestMode::Cors);
+    opts.set_signal(Some(signal));
+    if let Some(credentials) = config.credentials() {
+        opts.set_credentials(credentials);
+    }
     
     // Set headers
     let headers = Headers::new()?;
-    headers.set("Content-Type", "application/json")?;
-    headers.set("Accept", "text/event-stream")?;
+    for (name, value) in config.request_headers().await? {
+        headers.set(&name, &value)?;
+    }
+    if let Some(id) = last_event_id {
+        headers.set("Last-Event-ID", id)?;
+    }
     opts.set_headers(&headers);
     
     // Set body
-    let body = serde_json::to_string(&input)?;
+    let body = serde_json::to_string(input)?;
     let body_js = JsValue::from_str(&body);
     opts.set_body(&body_js);
     
     // Create and send request
-    let request = Request::new_with_str_and_init(&url, &opts)?;
-    let window = web_sys::window().unwrap();
-    let response_promise = window.fetch_with_request(&request);
-    let response = JsFuture::from(response_promise).await?;
-    let response: Response = response.dyn_into()?;
-    
-    if !response.ok() {
-        return Err(AgUiError::ConnectionError(format!(
-            "HTTP {}: {}",
-            response.status(),
-            response.status_text()
-        )));
+    let request = Request::new_with_str_and_init(config.url(), &opts)?;
+    let request = config.mutate_request(request).await?;
+    fetch::fetch(config.fetch(), &request).await
+        .map_err(|e| if signal.aborted() { AgUiError::Aborted } else { e })
+}
+
+/// The error for an unsuccessful `response`, with its body
+async fn http_error(response: &Response) -> AgUiError {
+    let body = match response.text() {
+        Ok(text) => JsFuture::from(text).await.ok().and_then(|text| text.as_string()),
+        Err(_) => None,
+    };
+    AgUiError::HttpError {
+        status: response.status(),
+        status_text: response.status_text(),
+        body: body.unwrap_or_default(),
     }
-    
+}
+
+/// The events of a successful response
+fn response_events(response: &Response, signal: AbortSignal) -> Result<EventStream> {
     // Get the response body as a ReadableStream
     let body = response.body()
         .ok_or_else(|| AgUiError::StreamError("No response body".to_string()))?;
     
-    // Create EventStream from ReadableStream
-    EventStream::from_readable_stream(body)
+    // Decode the body as the server encoded it, as `transformHttpEventStream` does
+    let content_type = response.headers().get("Content-Type")?.unwrap_or_default();
+    let stream = if is_proto_content_type(&content_type) {
+        EventStream::from_proto_readable_stream(body)?
+    } else {
+        EventStream::from_readable_stream(body)?
+    };
+    Ok(stream.with_abort_signal(signal))
 }
 
-impl Agent for WebAgent {
-    fn run_agent(&self, _input: RunAgentInput) -> Result<EventStream> {
-        // For the sync trait, we need to create a stream that can be populated later
-        // This is a limitation of the current design - in practice you'd use the async JS method
-        Err(AgUiError::AgentError(
-            "Use run_agent_js() for async operation in WASM environment".to_string()
-        ))
+/// Start a run that reconnects as `policy` says when its stream is interrupted
+async fn run_with_retry(
+    config: Rc<WebAgentConfig>,
+    input: RunAgentInput,
+    signal: AbortSignal,
+    policy: RetryPolicy,
+) -> Result<EventStream> {
+    let mut run = ResumingRun {
+        config,
+        input,
+        signal,
+        resumption: Resumption::new(policy),
+        events: EventStream::from_stream(futures::stream::empty()),
+    };
+    run.connect().await?;
+    Ok(EventStream::from_stream(futures::stream::unfold(Some(run), |run| async move {
+        let mut run = run?;
+        match run.next_event().await {
+            Ok(Some(event)) => Some((Ok(event), Some(run))),
+            Ok(None) => None,
+            Err(error) => Some((Err(error), None)),
+        }
+    })))
+}
+
+/// A run that reconnects when its stream is interrupted
+struct ResumingRun {
+    config: Rc<WebAgentConfig>,
+    input: RunAgentInput,
+    signal: AbortSignal,
+    resumption: Resumption,
+    events: EventStream,
+}
+
+impl ResumingRun {
+    /// Send the run's request until a response streams its events
+    async fn connect(&mut self) -> Result<()> {
+        loop {
+            let last_event_id = self.resumption.last_event_id();
+            let error = match send_request(&self.config, &self.input, &self.signal, last_event_id).await {
+                Ok(response) if response.ok() => {
+                    self.events = response_events(&response, self.signal.clone())?;
+                    return Ok(());
+                }
+                Ok(response) if !is_retryable_status(response.status()) => {
+                    return Err(self.resumption.refused(http_error(&response).await));
+                }
+                Ok(response) => http_error(&response).await,
+                // A fetch rejected by the abort is not a network failure to retry
+                Err(_) if self.signal.aborted() => return Err(AgUiError::Aborted),
+                Err(error) => error,
+            };
+            let delay = self.resumption.retry_after(error)?;
+            self.wait(delay).await?;
+        }
     }
     
-    fn messages(&self) -> Vec<Message> {
-        self.messages.clone()
+    /// The next event not received before, reconnecting as often as the policy allows
+    async fn next_event(&mut self) -> Result<Option<BaseEvent>> {
+        loop {
+            let delay = match self.events.next_event().await {
+                Ok(Some(event)) => {
+                    self.resumption.set_server_retry(self.events.retry());
+                    if self.resumption.accept(&event, self.events.event_id().as_deref())? {
+                        return Ok(Some(event));
+                    }
+                    continue;
+                }
+                Err(error) => self.resumption.retry_after(error)?,
+                Ok(None) => match self.resumption.end()? {
+                    Some(delay) => delay,
+                    None => return Ok(None),
+                },
+            };
+            self.wait(delay).await?;
+            self.connect().await?;
+        }
     }
     
-    fn set_messages(&mut self, messages: Vec<Message>) {
-        self.messages = messages;
+    /// Wait `delay` with the host's `setTimeout`, failing as soon as the run is aborted
+    async fn wait(&self, delay: Duration) -> Result<()> {
+        AbortableDelay::start(delay, &self.signal)?.finished().await
     }
-    
-    fn state(&self) -> State {
-        self.state.clone()
+}
+
+/// A `setTimeout` raced against the `abort` event of a run's signal
+///
+/// Dropping it clears the timer and removes the abort listener, so neither
+/// calls a closure that no longer exists when a run's stream is dropped while
+/// it waits.
+struct AbortableDelay {
+    signal: AbortSignal,
+    clear_timeout: Function,
+    timer: JsValue,
+    on_abort: Closure<dyn FnMut()>,
+    on_timeout: Closure<dyn FnMut()>,
+    /// Whether the timer fired, `false` when the run was aborted first
+    fired: oneshot::Receiver<bool>,
+}
+
+impl AbortableDelay {
+    fn start(delay: Duration, signal: &AbortSignal) -> Result<Self> {
+        if signal.aborted() {
+            return Err(AgUiError::Aborted);
+        }
+        let set_timeout = global_function("setTimeout")?;
+        let clear_timeout = global_function("clearTimeout")?;
+        
+        let (sender, fired) = oneshot::channel();
+        let sender = Rc::new(RefCell::new(Some(sender)));
+        let settle = move |sender: &RefCell<Option<oneshot::Sender<bool>>>, timed_out: bool| {
+            if let Some(sender) = sender.borrow_mut().take() {
+                let _ = sender.send(timed_out);
+            }
+        };
+        let on_timeout = Closure::<dyn FnMut()>::new({
+            let sender = sender.clone();
+            move || settle(&sender, true)
+        });
+        let on_abort = Closure::<dyn FnMut()>::new(move || settle(&sender, false));
+        
+        signal.add_event_listener_with_callback("abort", on_abort.as_ref().unchecked_ref())?;
+        let millis = JsValue::from_f64(delay.as_millis() as f64);
+        let mut delay = Self {
+            signal: signal.clone(),
+            clear_timeout,
+            timer: JsValue::UNDEFINED,
+            on_abort,
+            on_timeout,
+            fired,
+        };
+        // Dropped on failure, which removes the listener again
+        delay.timer = set_timeout.call2(&js_sys::global(), delay.on_timeout.as_ref(), &millis)?;
+        Ok(delay)
     }
     
-    fn set_state(&mut self, state: State) {
-        self.state = state;
+    async fn finished(mut self) -> Result<()> {
+        match (&mut self.fired).await {
+            Ok(true) => Ok(()),
+            _ => Err(AgUiError::Aborted),
+        }
+    }
+}
+
+impl Drop for AbortableDelay {
+    fn drop(&mut self) {
+        let _ = self.clear_timeout.call1(&js_sys::global(), &self.timer);
+        let _ = self.signal.remove_event_listener_with_callback("abort", self.on_abort.as_ref().unchecked_ref());
+    }
+}
+
+/// A function of the host's global object, such as `setTimeout`
+fn global_function(name: &str) -> Result<Function> {
+    Reflect::get(&js_sys::global(), &JsValue::from_str(name))?
+        .dyn_into()
+        .map_err(|_| AgUiError::ConnectionError(format!("No global {} function", name)))
+}
+
+/// Whether a `Content-Type` header names the protobuf event stream, ignoring parameters
+fn is_proto_content_type(content_type: &str) -> bool {
+    let media_type = content_type.split(';').next().unwrap_or_default().trim();
+    media_type.eq_ignore_ascii_case(AGUI_MEDIA_TYPE)
+}
+
+impl Agent for WebAgent {
+    fn data(&self) -> &RefCell<AgentData> {
+        &self.data
     }
     
-    fn agent_id(&self) -> Option<String> {
-        self.agent_id.clone()
+    async fn run(&self, input: RunAgentInput) -> Result<EventStream> {
+        let signal = self.start_run()?;
+        match self.config.retry() {
+            Some(policy) => run_with_retry(self.config.clone(), input, signal, policy.clone()).await,
+            None => run_agent_internal(&self.config, input, signal).await,
+        }
     }
     
-    fn thread_id(&self) -> Option<String> {
-        self.thread_id.clone()
+    fn abort_run(&self) {
+        if let Some(controller) = self.abort_controller.borrow_mut().take() {
+            controller.abort();
+        }
     }
-} 
\ No newline at end of file
+}
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::rc::Rc;
+use futures::future::{FutureExt, LocalBoxFuture};
+use js_sys::{Function, Promise, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Request, RequestCredentials};
+use crate::core::retry::RetryPolicy;
+use crate::error::{AgUiError, Result};
+
+/// Produces headers for each request, e.g. a freshly refreshed token
+pub type HeaderProvider = Rc<dyn Fn() -> LocalBoxFuture<'static, Result<Vec<(String, String)>>>>;
+
+/// Changes or replaces the request just before it is fetched
+pub type RequestMutator = Rc<dyn Fn(Request) -> LocalBoxFuture<'static, Result<Request>>>;
+
+/// How a `WebAgent` builds its requests
+///
+/// Every request is a `POST` of the JSON `RunAgentInput` with `Content-Type`
+/// and `Accept` set. The static headers are added next, then the headers of
+/// the header provider, each replacing a header of the same name. The request
+/// mutator sees the finished `Request`, so it can add query parameters with
+/// `new Request(url, request)` or anything else `fetch` accepts.
+///
+/// From JavaScript pass a plain object to `WebAgent.withConfig`, e.g.
+/// `{ url, headers: { 'X-Team': 'a' }, credentials: 'include',
+/// headerProvider: async () => ({ Authorization: `Bearer ${await token()}` }),
+/// requestMutator: (request) => request, fetch: (request) => env.AGENT.fetch(request),
+/// retry: { maxRetries: 5 } }`. `retry: true` uses the default `RetryPolicy`.
+#[derive(Clone)]
+pub struct WebAgentConfig {
+    url: String,
+    headers: Vec<(String, String)>,
+    header_provider: Option<HeaderProvider>,
+    credentials: Option<RequestCredentials>,
+    request_mutator: Option<RequestMutator>,
+    fetch: Option<Function>,
+    retry: Option<RetryPolicy>,
+}
+
+impl WebAgentConfig {
+    /// Send requests to `url` with the default headers and the browser's default credentials mode
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            headers: Vec::new(),
+            header_provider: None,
+            credentials: None,
+            request_mutator: None,
+            fetch: None,
+            retry: None,
+        }
+    }
+
+    /// Send `name: value` with every request
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Ask `provider` for more headers before every request
+    pub fn with_header_provider<F, Fut>(mut self, provider: F) -> Self
+    where
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<Vec<(String, String)>>> + 'static,
+    {
+        self.header_provider = Some(Rc::new(move || provider().boxed_local()));
+        self
+    }
+
+    /// Set the request's credentials mode, e.g. `Include` to send cookies cross-origin
+    ///
+    /// Cloudflare Workers reject requests that set it.
+    pub fn with_credentials(mut self, credentials: RequestCredentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Pass every request through `mutator` before it is fetched
+    pub fn with_request_mutator<F, Fut>(mut self, mutator: F) -> Self
+    where
+        F: Fn(Request) -> Fut + 'static,
+        Fut: Future<Output = Result<Request>> + 'static,
+    {
+        self.request_mutator = Some(Rc::new(move |request| mutator(request).boxed_local()));
+        self
+    }
+
+    /// Send requests with `fetch` instead of the host's global `fetch`
+    ///
+    /// The function is called with the `Request` and may return a `Response`
+    /// or a promise of one, e.g. a mock in tests or a service binding's
+    /// `fetch` in Cloudflare Workers.
+    pub fn with_fetch(mut self, fetch: Function) -> Self {
+        self.fetch = Some(fetch);
+        self
+    }
+
+    /// Reconnect as `policy` says when a run's stream is interrupted
+    ///
+    /// A reconnection sends the same input with the `Last-Event-ID` of the
+    /// last event received, and events the server sends again are dropped.
+    /// Only a server that sends SSE IDs can resume a run after its first
+    /// event; otherwise the run fails with `AgUiError::ResumeFailed`.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn credentials(&self) -> Option<RequestCredentials> {
+        self.credentials
+    }
+
+    /// The injected `fetch`, if there is one
+    pub fn fetch(&self) -> Option<&Function> {
+        self.fetch.as_ref()
+    }
+
+    pub fn retry(&self) -> Option<&RetryPolicy> {
+        self.retry.as_ref()
+    }
+
+    /// The headers of the next request, in the order they are set
+    ///
+    /// A later header replaces an earlier one of the same name.
+    pub async fn request_headers(&self) -> Result<Vec<(String, String)>> {
+        let mut headers = vec![
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("Accept".to_string(), "text/event-stream".to_string()),
+        ];
+        headers.extend(self.headers.iter().cloned());
+        if let Some(provider) = &self.header_provider {
+            headers.extend(provider().await?);
+        }
+        Ok(headers)
+    }
+
+    /// Apply the request mutator, if there is one
+    pub async fn mutate_request(&self, request: Request) -> Result<Request> {
+        match &self.request_mutator {
+            Some(mutator) => mutator(request).await,
+            None => Ok(request),
+        }
+    }
+
+    /// Read a configuration object passed from JavaScript
+    pub fn from_js(config: &JsValue) -> Result<Self> {
+        let url = field(config, "url")?
+            .as_string()
+            .ok_or_else(|| invalid("`url` must be a string"))?;
+        let mut agent_config = Self::new(url);
+
+        let headers = field(config, "headers")?;
+        if !headers.is_undefined() {
+            let headers: HashMap<String, String> = serde_wasm_bindgen::from_value(headers)
+                .map_err(|e| invalid(&format!("`headers` must map names to strings: {}", e)))?;
+            agent_config.headers.extend(headers);
+        }
+
+        let credentials = field(config, "credentials")?;
+        if !credentials.is_undefined() {
+            let credentials = RequestCredentials::from_js_value(&credentials)
+                .ok_or_else(|| invalid("`credentials` must be \"omit\", \"same-origin\" or \"include\""))?;
+            agent_config.credentials = Some(credentials);
+        }
+
+        if let Some(provider) = function(config, "headerProvider")? {
+            agent_config = agent_config.with_header_provider(move || {
+                let headers = provider.call0(&JsValue::NULL);
+                async move {
+                    let headers = resolve(headers?).await?;
+                    let headers: HashMap<String, String> = serde_wasm_bindgen::from_value(headers)
+                        .map_err(|e| invalid(&format!("`headerProvider` must resolve to a map of strings: {}", e)))?;
+                    Ok(headers.into_iter().collect())
+                }
+            });
+        }
+
+        if let Some(mutator) = function(config, "requestMutator")? {
+            agent_config = agent_config.with_request_mutator(move |request| {
+                let mutated = mutator.call1(&JsValue::NULL, &request);
+                async move {
+                    let mutated = resolve(mutated?).await?;
+                    // Returning nothing keeps the request, changed in place
+                    if mutated.is_undefined() {
+                        return Ok(request);
+                    }
+                    mutated.dyn_into().map_err(|_| invalid("`requestMutator` must return a Request"))
+                }
+            });
+        }
+
+        agent_config.fetch = function(config, "fetch")?;
+
+        let retry = field(config, "retry")?;
+        agent_config.retry = match retry.as_bool() {
+            Some(true) => Some(RetryPolicy::default()),
+            Some(false) => None,
+            None if retry.is_undefined() => None,
+            None => Some(serde_wasm_bindgen::from_value(retry)
+                .map_err(|e| invalid(&format!("`retry` must be a boolean or a retry policy: {}", e)))?),
+        };
+
+        Ok(agent_config)
+    }
+}
+
+impl fmt::Debug for WebAgentConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebAgentConfig")
+            .field("url", &self.url)
+            .field("headers", &self.headers)
+            .field("header_provider", &self.header_provider.is_some())
+            .field("credentials", &self.credentials)
+            .field("request_mutator", &self.request_mutator.is_some())
+            .field("fetch", &self.fetch.is_some())
+            .field("retry", &self.retry)
+            .finish()
+    }
+}
+
+fn invalid(message: &str) -> AgUiError {
+    AgUiError::AgentError(format!("Invalid WebAgent config: {}", message))
+}
+
+fn field(config: &JsValue, name: &str) -> Result<JsValue> {
+    Ok(Reflect::get(config, &JsValue::from_str(name))?)
+}
+
+fn function(config: &JsValue, name: &str) -> Result<Option<Function>> {
+    let value = field(config, name)?;
+    if value.is_undefined() {
+        return Ok(None);
+    }
+    value.dyn_into().map(Some).map_err(|_| invalid(&format!("`{}` must be a function", name)))
+}
+
+/// Wait for `value` if it is a promise
+async fn resolve(value: JsValue) -> Result<JsValue> {
+    Ok(JsFuture::from(Promise::resolve(&value)).await?)
+}
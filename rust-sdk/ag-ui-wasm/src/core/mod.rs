@@ -1,2 +1,3 @@
-pub mod events;
-pub mod types; 
\ No newline at end of file
+//! Protocol types, re-exported from `ag-ui-core`
+
+pub use ag_ui_core::{compat, events, patch, retry, types};
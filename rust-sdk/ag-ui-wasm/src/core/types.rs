@@ -1,133 +0,0 @@
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use uuid::Uuid;
-use chrono::{DateTime, Utc};
-use wasm_bindgen::prelude::*;
-
-/// Message role in a conversation
-#[wasm_bindgen]
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-#[serde(rename_all = "lowercase")]
-pub enum Role {
-    Developer,
-    User,
-    Assistant,
-    System,
-    Tool,
-}
-
-/// Function call (legacy support)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct FunctionCall {
-    pub name: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub arguments: Option<String>,
-}
-
-/// A message in the conversation
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Message {
-    pub id: String,
-    pub role: Role,
-    pub content: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub name: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_call_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tool_calls: Option<Vec<ToolCall>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub function_call: Option<FunctionCall>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub created_at: Option<DateTime<Utc>>,
-}
-
-impl Message {
-    pub fn new(role: Role, content: String) -> Self {
-        Self {
-            id: Uuid::new_v4().to_string(),
-            role,
-            content,
-            name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        }
-    }
-}
-
-/// Tool definition
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Tool {
-    pub name: String,
-    pub description: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub parameters: Option<serde_json::Value>,
-}
-
-/// Context for the agent run
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Context {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub user_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub session_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
-}
-
-/// State object for maintaining conversation state
-pub type State = HashMap<String, serde_json::Value>;
-
-/// Input for running an agent
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct RunAgentInput {
-    pub thread_id: String,
-    pub run_id: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub messages: Option<Vec<Message>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub tools: Option<Vec<Tool>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub context: Option<Vec<Context>>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub state: Option<State>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub forwarded_props: Option<HashMap<String, serde_json::Value>>,
-}
-
-impl RunAgentInput {
-    pub fn new(thread_id: String, run_id: String) -> Self {
-        Self {
-            thread_id,
-            run_id,
-            messages: None,
-            tools: None,
-            context: None,
-            state: None,
-            forwarded_props: None,
-        }
-    }
-}
-
-/// Tool call information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ToolCall {
-    pub id: String,
-    pub name: String,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub arguments: Option<serde_json::Value>,
-}
-
-/// Tool result
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct ToolResult {
-    pub tool_call_id: String,
-    pub result: serde_json::Value,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub error: Option<String>,
-} 
\ No newline at end of file
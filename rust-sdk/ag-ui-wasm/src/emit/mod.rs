@@ -0,0 +1,3 @@
+pub mod run_emitter;
+
+pub use run_emitter::RunEmitter;
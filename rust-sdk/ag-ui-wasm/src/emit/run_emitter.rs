@@ -0,0 +1,294 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::future::Future;
+#[cfg(not(target_arch = "wasm32"))]
+use std::panic::AssertUnwindSafe;
+use std::rc::Rc;
+
+use futures::future::{AbortHandle, AbortRegistration, Abortable};
+use futures::FutureExt;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::core::events::{
+    BaseEvent, EventType, RunFinishedEvent, StateDeltaEvent, StateSnapshotEvent, StepFinishedEvent, StepStartedEvent,
+    ToolCallArgsEvent, ToolCallEndEvent, ToolCallResultEvent, ToolCallStartEvent,
+};
+use crate::core::patch::PatchOperation;
+use crate::core::types::{Role, State};
+use crate::error::{AgUiError, ProtocolViolation, Result};
+use crate::stream::{EventSink, EventStream};
+use crate::verify::EventVerifier;
+
+/// Verification state shared by a run's emitter and the task that ends the run
+#[derive(Debug, Default)]
+struct RunState {
+    verifier: EventVerifier,
+    ended: bool,
+    /// The sink, handed back by an emitter dropped before the run ended
+    sink: Option<EventSink>,
+}
+
+/// Emits the events of one agent run
+///
+/// `RunEmitter::stream` sends `RUN_STARTED`, hands the emitter to the
+/// producer and makes sure the run ends with exactly one `RUN_FINISHED` or
+/// `RUN_ERROR`, however the producer stops:
+///
+/// - returning `Ok` finishes the run, ending whatever is still open first;
+/// - returning `Err` sends `RUN_ERROR` with the error's message and code;
+/// - panicking sends `RUN_ERROR` with the code `AGENT_ERROR` on native
+///   targets. On wasm32 a panic aborts: the module traps and no event is sent;
+/// - aborting through the handle from `abortable_stream` drops the producer
+///   and sends `RUN_ERROR` with the code `ABORTED`.
+///
+/// The producer can also end the run itself with `finish`, `fail` or
+/// `fail_with`. Every event is checked by an `EventVerifier` before it is
+/// sent, so an event the protocol does not allow fails to emit instead of
+/// reaching the client. Message and tool call IDs are generated UUIDs.
+#[derive(Debug)]
+pub struct RunEmitter {
+    thread_id: String,
+    run_id: String,
+    /// Only `None` while the emitter is being dropped
+    sink: Option<EventSink>,
+    state: Rc<RefCell<RunState>>,
+}
+
+impl RunEmitter {
+    /// Stream the events `producer` emits for the run `run_id` of `thread_id`
+    pub fn stream<F, Fut>(thread_id: impl Into<String>, run_id: impl Into<String>, producer: F) -> EventStream
+    where
+        F: FnOnce(RunEmitter) -> Fut + 'static,
+        Fut: Future<Output = Result<()>> + 'static,
+    {
+        Self::run(thread_id.into(), run_id.into(), producer, None)
+    }
+
+    /// Like `stream`, with a handle that aborts the run
+    pub fn abortable_stream<F, Fut>(
+        thread_id: impl Into<String>,
+        run_id: impl Into<String>,
+        producer: F,
+    ) -> (EventStream, AbortHandle)
+    where
+        F: FnOnce(RunEmitter) -> Fut + 'static,
+        Fut: Future<Output = Result<()>> + 'static,
+    {
+        let (handle, registration) = AbortHandle::new_pair();
+        (Self::run(thread_id.into(), run_id.into(), producer, Some(registration)), handle)
+    }
+
+    fn run<F, Fut>(thread_id: String, run_id: String, producer: F, abort: Option<AbortRegistration>) -> EventStream
+    where
+        F: FnOnce(RunEmitter) -> Fut + 'static,
+        Fut: Future<Output = Result<()>> + 'static,
+    {
+        EventStream::from_async_fn(move |sink| async move {
+            let state = Rc::<RefCell<RunState>>::default();
+            let mut emitter =
+                RunEmitter { thread_id: thread_id.clone(), run_id: run_id.clone(), sink: Some(sink), state: state.clone() };
+            emitter.emit(BaseEvent::run_started(thread_id.clone(), run_id.clone())).await?;
+
+            let producer = catch_panic(producer(emitter));
+            let outcome = match abort {
+                Some(registration) => Abortable::new(producer, registration).await.ok(),
+                None => Some(producer.await),
+            };
+
+            // The producer is gone, and with it the emitter, which handed the sink back unless the run ended
+            let Some(sink) = state.borrow_mut().sink.take() else {
+                return Ok(());
+            };
+            let ending = RunEmitter { thread_id, run_id, sink: Some(sink), state };
+            // Once the client is gone there is nobody left to tell, so failing to send is not an error
+            let _ = match outcome {
+                Some(Ok(Ok(()))) => ending.finish(None).await,
+                Some(Ok(Err(error))) => ending.fail_with(&error).await,
+                Some(Err(panic)) => ending.fail_with(&AgUiError::AgentError(panic_message(panic))).await,
+                None => ending.fail_with(&AgUiError::Aborted).await,
+            };
+            Ok(())
+        })
+    }
+
+    pub fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Verify and send any event
+    ///
+    /// A rejected event is not sent and leaves the run as it was.
+    pub async fn emit(&mut self, event: BaseEvent) -> Result<()> {
+        {
+            let mut state = self.state.borrow_mut();
+            let event_type = event.event_type();
+            // The verifier allows a new run or a late RUN_ERROR after RUN_FINISHED; an emitter does not
+            if state.ended && matches!(event_type, EventType::RunStarted | EventType::RunError) {
+                return Err(AgUiError::protocol(ProtocolViolation::RunAlreadyFinished { event_type }, &event));
+            }
+            state.verifier.verify(&event)?;
+            state.ended = matches!(event_type, EventType::RunFinished | EventType::RunError | EventType::RunAborted);
+        }
+        self.sink.as_mut().expect("an emitter keeps its sink until it is dropped").send(event).await
+    }
+
+    /// Start an assistant message and return its ID
+    pub async fn start_message(&mut self) -> Result<String> {
+        let message_id = new_id();
+        self.emit(BaseEvent::text_message_start(message_id.clone(), Some(Role::Assistant))).await?;
+        Ok(message_id)
+    }
+
+    /// Append text to an open message; an empty delta sends nothing
+    pub async fn push_text(&mut self, message_id: &str, delta: impl Into<String>) -> Result<()> {
+        let delta = delta.into();
+        if delta.is_empty() {
+            return Ok(());
+        }
+        self.emit(BaseEvent::text_message_content(message_id.to_string(), delta)).await
+    }
+
+    pub async fn end_message(&mut self, message_id: &str) -> Result<()> {
+        self.emit(BaseEvent::text_message_end(message_id.to_string())).await
+    }
+
+    /// Send a whole assistant message and return its ID
+    pub async fn text_message(&mut self, content: impl Into<String>) -> Result<String> {
+        let message_id = self.start_message().await?;
+        self.push_text(&message_id, content).await?;
+        self.end_message(&message_id).await?;
+        Ok(message_id)
+    }
+
+    /// Start a tool call and return its ID
+    pub async fn start_tool_call(
+        &mut self,
+        tool_call_name: impl Into<String>,
+        parent_message_id: Option<&str>,
+    ) -> Result<String> {
+        let tool_call_id = new_id();
+        self.emit(BaseEvent::new(ToolCallStartEvent {
+            tool_call_id: tool_call_id.clone(),
+            tool_call_name: tool_call_name.into(),
+            parent_message_id: parent_message_id.map(str::to_string),
+        }))
+        .await?;
+        Ok(tool_call_id)
+    }
+
+    /// Append argument text to an open tool call; an empty delta sends nothing
+    pub async fn push_tool_call_args(&mut self, tool_call_id: &str, delta: impl Into<String>) -> Result<()> {
+        let delta = delta.into();
+        if delta.is_empty() {
+            return Ok(());
+        }
+        self.emit(BaseEvent::new(ToolCallArgsEvent { tool_call_id: tool_call_id.to_string(), delta })).await
+    }
+
+    pub async fn end_tool_call(&mut self, tool_call_id: &str) -> Result<()> {
+        self.emit(BaseEvent::new(ToolCallEndEvent { tool_call_id: tool_call_id.to_string() })).await
+    }
+
+    /// Send a whole tool call with JSON `arguments` and return its ID
+    pub async fn tool_call(
+        &mut self,
+        tool_call_name: impl Into<String>,
+        arguments: &Value,
+        parent_message_id: Option<&str>,
+    ) -> Result<String> {
+        let tool_call_id = self.start_tool_call(tool_call_name, parent_message_id).await?;
+        self.push_tool_call_args(&tool_call_id, arguments.to_string()).await?;
+        self.end_tool_call(&tool_call_id).await?;
+        Ok(tool_call_id)
+    }
+
+    /// Send the result of a tool call as a tool message and return the message's ID
+    pub async fn tool_call_result(&mut self, tool_call_id: &str, content: impl Into<String>) -> Result<String> {
+        let message_id = new_id();
+        self.emit(BaseEvent::new(ToolCallResultEvent {
+            message_id: message_id.clone(),
+            tool_call_id: tool_call_id.to_string(),
+            content: content.into(),
+            role: Some(Role::Tool),
+        }))
+        .await?;
+        Ok(message_id)
+    }
+
+    pub async fn start_step(&mut self, step_name: impl Into<String>) -> Result<()> {
+        self.emit(BaseEvent::new(StepStartedEvent { step_name: step_name.into() })).await
+    }
+
+    pub async fn finish_step(&mut self, step_name: impl Into<String>) -> Result<()> {
+        self.emit(BaseEvent::new(StepFinishedEvent { step_name: step_name.into() })).await
+    }
+
+    pub async fn state_snapshot(&mut self, snapshot: State) -> Result<()> {
+        self.emit(BaseEvent::new(StateSnapshotEvent { snapshot })).await
+    }
+
+    pub async fn state_delta(&mut self, patch: &[PatchOperation]) -> Result<()> {
+        self.emit(BaseEvent::new(StateDeltaEvent::new(patch))).await
+    }
+
+    /// End whatever is still open and finish the run with an optional result
+    pub async fn finish(mut self, result: Option<Value>) -> Result<()> {
+        let closing = self.state.borrow().verifier.closing_events();
+        for event in closing {
+            self.emit(event).await?;
+        }
+        let (thread_id, run_id) = (self.thread_id.clone(), self.run_id.clone());
+        self.emit(BaseEvent::new(RunFinishedEvent { thread_id, run_id, result })).await
+    }
+
+    /// End the run with `RUN_ERROR`
+    pub async fn fail(mut self, message: impl Into<String>, code: Option<String>) -> Result<()> {
+        self.emit(BaseEvent::run_error(message.into(), code)).await
+    }
+
+    /// End the run with the `RUN_ERROR` that reports `error`, coded as `AgUiError::code`
+    pub async fn fail_with(mut self, error: &AgUiError) -> Result<()> {
+        self.emit(error.to_run_error()).await
+    }
+}
+
+impl Drop for RunEmitter {
+    fn drop(&mut self) {
+        let mut state = self.state.borrow_mut();
+        if !state.ended {
+            state.sink = self.sink.take();
+        }
+    }
+}
+
+/// `future`, with a panic in it caught as an `Err`
+///
+/// Panics only unwind on native targets; on wasm32 they abort, so there is
+/// nothing to catch.
+#[cfg(not(target_arch = "wasm32"))]
+fn catch_panic<F: Future>(future: F) -> impl Future<Output = std::thread::Result<F::Output>> {
+    AssertUnwindSafe(future).catch_unwind()
+}
+
+#[cfg(target_arch = "wasm32")]
+fn catch_panic<F: Future>(future: F) -> impl Future<Output = std::thread::Result<F::Output>> {
+    future.map(Ok)
+}
+
+/// Describe a panic of the producer by its payload
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    let detail = match panic.downcast::<String>() {
+        Ok(message) => *message,
+        Err(panic) => panic.downcast_ref::<&str>().map_or("unknown cause", |message| message).to_string(),
+    };
+    format!("Run panicked: {}", detail)
+}
+
+fn new_id() -> String {
+    Uuid::new_v4().to_string()
+}
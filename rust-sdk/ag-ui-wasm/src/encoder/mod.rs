@@ -1,3 +1,3 @@
-mod sse_encoder;
+//! SSE and protobuf encoders, re-exported from `ag-ui-core`
 
-pub use sse_encoder::SseEncoder; 
\ No newline at end of file
+pub use ag_ui_core::encoder::{media_type, EventEncoder, ProtoEncoder, SseEncoder, SseEncoderOptions, SSE_MEDIA_TYPE};
@@ -5,14 +5,22 @@
 
 use wasm_bindgen::prelude::*;
 
+pub mod apply;
+pub mod chunks;
 pub mod client;
 pub mod core;
+pub mod emit;
 pub mod encoder;
+pub mod proto;
 pub mod stream;
+pub mod verify;
 pub mod error;
 
 // Re-export commonly used types
-pub use client::web_agent::WebAgent;
+pub use apply::{AgentState, AgentStateMutation, EventApplier};
+pub use chunks::{expand_chunks, ChunkExpander, ExpandedEventStream};
+pub use client::{Agent, AgentData, RunAgentResult, WebAgent, WebAgentConfig};
+pub use emit::RunEmitter;
 pub use core::{
     events::{
         BaseEvent, EventType, EventData,
@@ -25,11 +33,18 @@ pub use core::{
         ThinkingTextMessageStartEvent, ThinkingTextMessageContentEvent, ThinkingTextMessageEndEvent,
         ErrorEvent, RawEvent, CustomEvent
     },
-    types::{Message, RunAgentInput, State, Role, Tool, Context, ToolCall, ToolResult, FunctionCall},
+    patch::PatchOperation,
+    retry::RetryPolicy,
+    types::{
+        Message, DeveloperMessage, SystemMessage, AssistantMessage, UserMessage, ToolMessage, LegacyMessage,
+        RunAgentInput, State, Role, Tool, Context, ToolCall, ToolCallType, ToolResult, FunctionCall,
+    },
 };
-pub use encoder::SseEncoder as SSEEncoder;
-pub use error::{AgUiError, Result};
-pub use stream::EventStream;
+pub use encoder::{EventEncoder, ProtoEncoder, SseEncoder as SSEEncoder, SseEncoderOptions};
+pub use error::{AgUiError, ProtocolViolation, Result};
+pub use proto::AGUI_MEDIA_TYPE;
+pub use stream::{EventSink, EventStream, ProtoParser, SseEvent, SseParser};
+pub use verify::{EventVerifier, VerifiedEventStream};
 
 // Set panic hook for better error messages in browser
 #[cfg(feature = "console_error_panic_hook")]
@@ -0,0 +1,3 @@
+//! Protocol Buffers encoding of events, re-exported from `ag-ui-core`
+
+pub use ag_ui_core::proto::*;
@@ -0,0 +1,86 @@
+//! Producing an `EventStream` from Rust async code
+
+use futures::channel::mpsc;
+use futures::future::LocalBoxFuture;
+use futures::stream::Stream;
+use futures::{FutureExt, SinkExt, StreamExt};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use crate::core::events::BaseEvent;
+use crate::error::{AgUiError, Result};
+
+/// The sending half handed to an `EventStream::from_async_fn` producer
+///
+/// `send` waits until the consumer has room for the event, so a producer
+/// never runs more than one event ahead of whoever reads the stream. Each
+/// clone of the sink may hold one more event.
+#[derive(Debug, Clone)]
+pub struct EventSink {
+    sender: mpsc::Sender<BaseEvent>,
+}
+
+impl EventSink {
+    /// Emit an event, waiting for the consumer to pull
+    ///
+    /// Fails once the stream has been dropped or cancelled; a producer
+    /// should stop when that happens.
+    pub async fn send(&mut self, event: BaseEvent) -> Result<()> {
+        self.sender
+            .send(event)
+            .await
+            .map_err(|_| AgUiError::StreamError("Event stream was closed by its consumer".to_string()))
+    }
+
+    /// Whether the stream has been dropped or cancelled
+    pub fn is_closed(&self) -> bool {
+        self.sender.is_closed()
+    }
+}
+
+/// Drives a producer future and yields the events it sends
+///
+/// The producer only runs while the stream is polled. Its error, if any, is
+/// yielded after the events it sent before failing.
+pub(crate) struct ProducerStream {
+    producer: Option<LocalBoxFuture<'static, Result<()>>>,
+    receiver: mpsc::Receiver<BaseEvent>,
+    error: Option<AgUiError>,
+}
+
+impl ProducerStream {
+    pub(crate) fn new<F, Fut>(producer: F) -> Self
+    where
+        F: FnOnce(EventSink) -> Fut,
+        Fut: Future<Output = Result<()>> + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(0);
+        let producer = producer(EventSink { sender }).boxed_local();
+        Self { producer: Some(producer), receiver, error: None }
+    }
+}
+
+impl Stream for ProducerStream {
+    type Item = Result<BaseEvent>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(producer) = self.producer.as_mut() {
+            if let Poll::Ready(result) = producer.poll_unpin(cx) {
+                self.producer = None;
+                if let Err(error) = result {
+                    self.error = Some(error);
+                    // Events already sent are still delivered, nothing after them
+                    self.receiver.close();
+                }
+            }
+        }
+
+        match self.receiver.poll_next_unpin(cx) {
+            Poll::Ready(Some(event)) => Poll::Ready(Some(Ok(event))),
+            // The producer dropped its sink but has not returned yet
+            Poll::Ready(None) if self.producer.is_some() => Poll::Pending,
+            Poll::Ready(None) => Poll::Ready(self.error.take().map(Err)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
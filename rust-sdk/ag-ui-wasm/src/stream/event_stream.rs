@@ -1,112 +1,335 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{ReadableStream, ReadableStreamDefaultReader, TextDecoder, TransformStream, TransformStreamDefaultController};
-use js_sys::Uint8Array;
+use web_sys::{
+    AbortSignal, ReadableStream, ReadableStreamDefaultController, ReadableStreamDefaultReader, TransformStream,
+    TransformStreamDefaultController,
+};
+use js_sys::{Object, Promise, Reflect, Uint8Array};
+use futures::stream::{LocalBoxStream, Stream, StreamExt};
+use futures::FutureExt;
+use serde::Serialize;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::task::{Context, Poll};
+use crate::core::compat;
 use crate::core::events::BaseEvent;
+use crate::encoder::{EventEncoder, SseEncoder};
 use crate::error::{AgUiError, Result};
+use crate::proto;
+use crate::stream::event_sink::{EventSink, ProducerStream};
+use crate::stream::proto_parser::ProtoParser;
+use crate::stream::sse_parser::{SseEvent, SseParser};
 
-/// A stream of server-sent events
+/// Most events an SSE or protobuf body sends in one chunk
+const MAX_BATCH: usize = 64;
+
+/// A stream of AG-UI events
+///
+/// Rust code reads it with `next_event` or as a `futures::Stream`; JavaScript
+/// gets a `ReadableStream` of event objects from `toReadableStream`.
 #[wasm_bindgen]
 pub struct EventStream {
-    reader: ReadableStreamDefaultReader,
-    decoder: TextDecoder,
+    events: LocalBoxStream<'static, Result<BaseEvent>>,
+    parser: Option<Rc<RefCell<SseParser>>>,
+    /// The SSE IDs of the last event read, which the parser may be ahead of
+    read_ids: Rc<RefCell<ReadIds>>,
+    body: Option<ReadableStreamDefaultReader>,
 }
 
 #[wasm_bindgen]
 impl EventStream {
+    /// Convert into a `ReadableStream` of event objects
+    ///
+    /// The stream can be read with `getReader()`, piped, or iterated with
+    /// `for await`, which is polyfilled where `ReadableStream` is not yet
+    /// async iterable.
+    #[wasm_bindgen(js_name = "toReadableStream")]
+    pub fn to_readable_stream(self) -> std::result::Result<ReadableStream, JsValue> {
+        Ok(self.into_readable_stream()?)
+    }
+    
     /// Convert EventStream to JsValue for JavaScript interop
     #[wasm_bindgen(js_name = "toJsValue")]
-    pub fn to_js_value(&self) -> JsValue {
-        // Return a placeholder for now
-        JsValue::NULL
+    pub fn to_js_value(self) -> std::result::Result<JsValue, JsValue> {
+        Ok(self.into_readable_stream()?.into())
     }
 }
 
 impl EventStream {
-    /// Create a new EventStream from a ReadableStream
+    /// Create a new EventStream from a ReadableStream of SSE bytes
+    ///
+    /// The stream ends after the first error.
     pub fn from_readable_stream(stream: ReadableStream) -> Result<Self> {
+        let parser = Rc::new(RefCell::new(SseParser::new()));
+        let mut event_stream = Self::from_body(stream, BodyDecoder::Sse(parser.clone()))?;
+        event_stream.parser = Some(parser);
+        Ok(event_stream)
+    }
+    
+    /// Create a new EventStream from a ReadableStream of length-prefixed protobuf messages
+    ///
+    /// This is the body of a response with the content type
+    /// `AGUI_MEDIA_TYPE`. The stream ends after the first error, and a body
+    /// that stops in the middle of a message ends it with an error.
+    pub fn from_proto_readable_stream(stream: ReadableStream) -> Result<Self> {
+        Self::from_body(stream, BodyDecoder::Proto(ProtoParser::new()))
+    }
+    
+    fn from_body(stream: ReadableStream, decoder: BodyDecoder) -> Result<Self> {
         let reader = stream.get_reader()
             .dyn_into::<ReadableStreamDefaultReader>()
             .map_err(|e| AgUiError::StreamError(format!("Failed to get reader: {:?}", e)))?;
         
-        let decoder = TextDecoder::new()
-            .map_err(|e| AgUiError::StreamError(format!("Failed to create decoder: {:?}", e)))?;
+        let read_ids = Rc::new(RefCell::new(ReadIds::default()));
+        let source = BodySource {
+            reader: reader.clone(),
+            decoder,
+            pending: VecDeque::new(),
+            read_ids: read_ids.clone(),
+        };
+        let events = futures::stream::unfold(Some(source), |source| async move {
+            let mut source = source?;
+            match source.next_event().await {
+                Ok(Some(event)) => Some((Ok(event), Some(source))),
+                Ok(None) => None,
+                Err(error) => Some((Err(error), None)),
+            }
+        });
         
-        Ok(Self { reader, decoder })
+        Ok(Self { events: events.boxed_local(), parser: None, read_ids, body: Some(reader) })
+    }
+    
+    /// Create an EventStream over events produced in Rust
+    pub fn from_stream(events: impl Stream<Item = Result<BaseEvent>> + 'static) -> Self {
+        Self { events: events.boxed_local(), parser: None, read_ids: Rc::default(), body: None }
     }
     
     /// Read the next event from the stream
-    pub async fn next_event(&self) -> Result<Option<BaseEvent>> {
-        let result = JsFuture::from(self.reader.read()).await?;
-        let chunk = js_sys::Reflect::get(&result, &JsValue::from_str("value"))
-            .map_err(|e| AgUiError::StreamError(format!("Failed to get chunk value: {:?}", e)))?;
-        
-        let done = js_sys::Reflect::get(&result, &JsValue::from_str("done"))
-            .map_err(|e| AgUiError::StreamError(format!("Failed to get done flag: {:?}", e)))?
-            .as_bool()
-            .unwrap_or(false);
-        
-        if done {
-            return Ok(None);
+    ///
+    /// Reads as many network chunks as it takes to complete an event and
+    /// returns `Ok(None)` only once the stream has ended.
+    pub async fn next_event(&mut self) -> Result<Option<BaseEvent>> {
+        self.events.next().await.transpose()
+    }
+    
+    /// The ID of the last event read, for resuming the stream
+    ///
+    /// Only SSE streams carry event IDs.
+    pub fn last_event_id(&self) -> Option<String> {
+        self.read_ids.borrow().last.clone()
+    }
+
+    /// The ID the last event read set with its own `id:` field, if it had one
+    ///
+    /// Unlike `last_event_id`, this is `None` for an event that only carries
+    /// the ID of an earlier one, so it tells events apart.
+    pub fn event_id(&self) -> Option<String> {
+        self.read_ids.borrow().own.clone()
+    }
+    
+    /// The reconnection time in milliseconds requested by the server
+    pub fn retry(&self) -> Option<u64> {
+        self.parser.as_ref()?.borrow().retry()
+    }
+    
+    /// End with `AgUiError::Aborted` instead of the read error once `signal` is aborted
+    ///
+    /// Aborting a fetch makes reading its body fail with a DOM `AbortError`;
+    /// this turns that failure into an error callers can tell apart.
+    pub fn with_abort_signal(mut self, signal: AbortSignal) -> Self {
+        self.events = self
+            .events
+            .map(move |event| match event {
+                Err(_) if signal.aborted() => Err(AgUiError::Aborted),
+                event => event,
+            })
+            .boxed_local();
+        self
+    }
+    
+    /// Stop reading, cancelling the underlying response body if there is one
+    pub fn cancel(&mut self) {
+        self.events = futures::stream::empty().boxed_local();
+        if let Some(body) = self.body.take() {
+            // Cancelling only fails for a reader that was released, which this one never is
+            let _ = body.cancel();
         }
+    }
+    
+    /// Convert into a `ReadableStream` of event objects, pulled on demand
+    pub fn into_readable_stream(self) -> Result<ReadableStream> {
+        let stream = self.into_pulled_stream(1, |events| event_to_js(&events[0]))?;
+        install_async_iterator(&stream)?;
+        Ok(stream)
+    }
+    
+    /// Convert into a `ReadableStream` of SSE-encoded bytes, pulled on demand
+    ///
+    /// This is the body of a `text/event-stream` response. An error from the
+    /// event stream errors the body, which aborts the response.
+    pub fn into_sse_stream(self) -> Result<ReadableStream> {
+        self.into_sse_stream_with(SseEncoder::new())
+    }
+    
+    /// Like `into_sse_stream`, writing the SSE fields `encoder` is configured for
+    ///
+    /// Each pull sends the next event together with any that are already
+    /// waiting, in one chunk.
+    pub fn into_sse_stream_with(self, encoder: SseEncoder) -> Result<ReadableStream> {
+        self.into_pulled_stream(MAX_BATCH, move |events| Ok(Uint8Array::from(encoder.encode_events(events)?.as_slice()).into()))
+    }
+    
+    /// Convert into a `ReadableStream` of length-prefixed protobuf messages, pulled on demand
+    ///
+    /// This is the body of a response with the content type
+    /// `AGUI_MEDIA_TYPE`. An event that has no protobuf encoding errors the
+    /// body. Like the SSE body, each chunk carries every event that is ready.
+    pub fn into_proto_stream(self) -> Result<ReadableStream> {
+        self.into_pulled_stream(MAX_BATCH, |events| {
+            let mut frames = Vec::new();
+            for event in events {
+                frames.extend(proto::encode_frame(event)?);
+            }
+            Ok(Uint8Array::from(frames.as_slice()).into())
+        })
+    }
+    
+    /// Convert into a response body in the format `encoder` negotiated
+    ///
+    /// Send it with `encoder.content_type()` as the `Content-Type`.
+    pub fn into_encoded_stream(self, encoder: EventEncoder) -> Result<ReadableStream> {
+        if encoder.accepts_protobuf() {
+            self.into_proto_stream()
+        } else {
+            self.into_sse_stream()
+        }
+    }
+    
+    /// A `ReadableStream` that enqueues one chunk per pull, as `to_chunk` converts a batch of events
+    ///
+    /// A pull waits for the next event, then adds events that are already
+    /// available without waiting, up to `batch_limit`. An error or the end
+    /// of the stream found that way takes effect after the batch is enqueued.
+    fn into_pulled_stream(
+        self,
+        batch_limit: usize,
+        to_chunk: impl Fn(&[BaseEvent]) -> Result<JsValue> + 'static,
+    ) -> Result<ReadableStream> {
+        let events = Rc::new(RefCell::new(Some(self)));
+        let cancelled = Rc::new(Cell::new(false));
+        let to_chunk = Rc::new(to_chunk);
+        let source = Object::new();
         
-        let chunk = chunk.dyn_into::<Uint8Array>()
-            .map_err(|e| AgUiError::StreamError(format!("Failed to convert to Uint8Array: {:?}", e)))?;
+        let (pulled, pull_cancelled) = (events.clone(), cancelled.clone());
+        let pull = Closure::<dyn FnMut(ReadableStreamDefaultController) -> Promise>::new(move |controller: ReadableStreamDefaultController| {
+            let (events, cancelled, to_chunk) = (pulled.clone(), pull_cancelled.clone(), to_chunk.clone());
+            wasm_bindgen_futures::future_to_promise(async move {
+                // The ReadableStream does not pull again before this promise settles
+                let Some(mut stream) = events.borrow_mut().take() else {
+                    return Ok(JsValue::UNDEFINED);
+                };
+                let next = stream.next_event().await;
+                if cancelled.get() {
+                    stream.cancel();
+                    return Ok(JsValue::UNDEFINED);
+                }
+                let mut batch = match next {
+                    Ok(Some(event)) => vec![event],
+                    Ok(None) => {
+                        controller.close()?;
+                        return Ok(JsValue::UNDEFINED);
+                    }
+                    Err(error) => {
+                        controller.error_with_e(&error.into());
+                        return Ok(JsValue::UNDEFINED);
+                    }
+                };
+                
+                let mut end = None;
+                while batch.len() < batch_limit {
+                    match stream.events.next().now_or_never() {
+                        Some(Some(Ok(event))) => batch.push(event),
+                        Some(Some(Err(error))) => {
+                            end = Some(Err(error));
+                            break;
+                        }
+                        Some(None) => {
+                            end = Some(Ok(()));
+                            break;
+                        }
+                        None => break,
+                    }
+                }
+                
+                controller.enqueue_with_chunk(&to_chunk(&batch)?)?;
+                match end {
+                    None => *events.borrow_mut() = Some(stream),
+                    Some(Ok(())) => controller.close()?,
+                    Some(Err(error)) => controller.error_with_e(&error.into()),
+                }
+                Ok(JsValue::UNDEFINED)
+            })
+        });
         
-        // Convert Uint8Array to Vec<u8> and then back to slice for decoding
-        let chunk_vec = chunk.to_vec();
+        let cancel = Closure::<dyn FnMut(JsValue)>::new(move |_reason: JsValue| {
+            cancelled.set(true);
+            if let Some(mut stream) = events.borrow_mut().take() {
+                stream.cancel();
+            }
+        });
         
-        // Use the decode method with a slice
-        let text = self.decoder.decode_with_u8_array(&chunk_vec)
-            .map_err(|e| AgUiError::StreamError(format!("Failed to decode text: {:?}", e)))?;
+        Reflect::set(&source, &JsValue::from_str("pull"), &pull.into_js_value())?;
+        Reflect::set(&source, &JsValue::from_str("cancel"), &cancel.into_js_value())?;
         
-        // Parse the SSE data
-        if text.starts_with("data: ") {
-            let json_str = &text[6..]; // Remove "data: " prefix
-            let event: BaseEvent = serde_json::from_str(json_str)
-                .map_err(|e| AgUiError::JsonError(e))?;
-            Ok(Some(event))
-        } else {
-            // Skip non-data lines (comments, event types, etc.)
-            Ok(None)
-        }
+        ReadableStream::new_with_underlying_source(&source)
+            .map_err(|e| AgUiError::StreamError(format!("Failed to create readable stream: {:?}", e)))
     }
     
     /// Create a TransformStream for processing events
+    ///
+    /// The stream takes the bytes of an SSE response body and emits one
+    /// parsed event object per SSE event; an event that fails to parse
+    /// errors the stream, and so does a body that ends in the middle of an
+    /// event.
     pub fn create_transform_stream() -> Result<TransformStream> {
-        let decoder = TextDecoder::new()
-            .map_err(|e| AgUiError::StreamError(format!("Failed to create decoder: {:?}", e)))?;
+        let parser = Rc::new(RefCell::new(SseParser::new()));
+        let flush_parser = parser.clone();
         
         let transformer = js_sys::Object::new();
         
-        let transform_fn = Closure::wrap(Box::new(move |chunk: JsValue, controller: TransformStreamDefaultController| -> std::result::Result<(), JsValue> {
-            let uint8_array = chunk.dyn_into::<Uint8Array>()?;
-            let chunk_vec = uint8_array.to_vec();
-            
-            let text = decoder.decode_with_u8_array(&chunk_vec)?;
-            
-            // Parse SSE data and extract events
-            for line in text.lines() {
-                if line.starts_with("data: ") {
-                    let json_str = &line[6..];
-                    if let Ok(event) = serde_json::from_str::<BaseEvent>(json_str) {
-                        let js_event = serde_wasm_bindgen::to_value(&event)
-                            .map_err(|e| JsValue::from_str(&e.to_string()))?;
-                        controller.enqueue_with_chunk(&js_event)?;
-                    }
+        let transform = Closure::<dyn FnMut(JsValue, TransformStreamDefaultController) -> std::result::Result<(), JsValue>>::new(
+            move |chunk: JsValue, controller: TransformStreamDefaultController| {
+                let uint8_array = chunk.dyn_into::<Uint8Array>()?;
+                
+                for sse_event in parser.borrow_mut().feed_bytes(&uint8_array.to_vec()) {
+                    let event = compat::parse_event(&sse_event.data).map_err(|e| e.at_offset(sse_event.offset))?;
+                    controller.enqueue_with_chunk(&event_to_js(&event)?)?;
                 }
-            }
-            
-            Ok(())
-        }) as Box<dyn FnMut(JsValue, TransformStreamDefaultController) -> std::result::Result<(), JsValue>>);
+                
+                Ok(())
+            },
+        );
         
-        js_sys::Reflect::set(
-            &transformer,
-            &JsValue::from_str("transform"),
-            transform_fn.as_ref().unchecked_ref(),
-        )?;
+        let flush = Closure::<dyn FnMut(TransformStreamDefaultController) -> std::result::Result<(), JsValue>>::new(
+            move |_controller: TransformStreamDefaultController| {
+                let mut parser = flush_parser.borrow_mut();
+                let unterminated = parser.unterminated_offset();
+                parser.finish();
+                match unterminated {
+                    Some(offset) => {
+                        Err(AgUiError::decoding("SSE stream ended in the middle of an event").at_offset(offset).into())
+                    }
+                    None => Ok(()),
+                }
+            },
+        );
         
-        transform_fn.forget();
+        // The transformer owns the closures, so they live as long as the stream
+        js_sys::Reflect::set(&transformer, &JsValue::from_str("transform"), &transform.into_js_value())?;
+        js_sys::Reflect::set(&transformer, &JsValue::from_str("flush"), &flush.into_js_value())?;
         
         let transform_stream = TransformStream::new_with_transformer(&transformer)
             .map_err(|e| AgUiError::StreamError(format!("Failed to create transform stream: {:?}", e)))?;
@@ -114,14 +337,163 @@ impl EventStream {
         Ok(transform_stream)
     }
     
-    /// Create an EventStream from an async function
-    pub fn from_async_fn<F, Fut>(_f: F) -> Result<Self>
+    /// Create an EventStream whose events are produced by async Rust code
+    ///
+    /// `producer` receives an `EventSink` and sends events into it over
+    /// time, awaiting whatever it needs in between. It only runs while the
+    /// stream is read, and `EventSink::send` waits for the reader, so a slow
+    /// consumer slows the producer down. The stream ends when the producer
+    /// returns (and the sink, if it moved it elsewhere, is dropped); an `Err`
+    /// it returns is the stream's last item. Dropping or cancelling the
+    /// stream drops the producer.
+    pub fn from_async_fn<F, Fut>(producer: F) -> Self
     where
-        F: FnOnce() -> Fut,
-        Fut: std::future::Future<Output = Result<()>>,
+        F: FnOnce(EventSink) -> Fut,
+        Fut: Future<Output = Result<()>> + 'static,
     {
-        // This would need more complex implementation for creating streams from async functions
-        // For now, we'll just return an error
-        Err(AgUiError::StreamError("from_async_fn not implemented".to_string()))
+        Self::from_stream(ProducerStream::new(producer))
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<BaseEvent>;
+    
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events.poll_next_unpin(cx)
+    }
+}
+
+/// How a response body is split into events
+enum BodyDecoder {
+    Sse(Rc<RefCell<SseParser>>),
+    Proto(ProtoParser),
+}
+
+/// An event read from the body but not yet decoded
+enum PendingEvent {
+    Sse(SseEvent),
+    /// A protobuf message and the offset of its frame in the body
+    Proto(u64, Vec<u8>),
+}
+
+impl PendingEvent {
+    /// Decode the event; a failure is a `DecodingError` at the event's offset
+    fn decode(self) -> Result<BaseEvent> {
+        match self {
+            PendingEvent::Sse(sse_event) => {
+                compat::parse_event(&sse_event.data).map_err(|e| e.at_offset(sse_event.offset))
+            }
+            PendingEvent::Proto(offset, message) => proto::decode(&message).map_err(|e| e.at_offset(offset)),
+        }
     }
-} 
\ No newline at end of file
+}
+
+/// The SSE IDs of the last event read from a body
+#[derive(Default)]
+struct ReadIds {
+    last: Option<String>,
+    own: Option<String>,
+}
+
+/// Reads a response body and decodes it into events
+struct BodySource {
+    reader: ReadableStreamDefaultReader,
+    decoder: BodyDecoder,
+    pending: VecDeque<PendingEvent>,
+    read_ids: Rc<RefCell<ReadIds>>,
+}
+
+impl BodySource {
+    async fn next_event(&mut self) -> Result<Option<BaseEvent>> {
+        loop {
+            if let Some(pending) = self.pending.pop_front() {
+                if let PendingEvent::Sse(sse_event) = &pending {
+                    *self.read_ids.borrow_mut() = ReadIds {
+                        last: sse_event.id.clone(),
+                        own: sse_event.own_id().map(str::to_string),
+                    };
+                }
+                return pending.decode().map(Some);
+            }
+            
+            match (self.read_chunk().await?, &mut self.decoder) {
+                (Some(chunk), BodyDecoder::Sse(parser)) => {
+                    self.pending.extend(parser.borrow_mut().feed_bytes(&chunk).into_iter().map(PendingEvent::Sse));
+                }
+                (Some(chunk), BodyDecoder::Proto(parser)) => {
+                    let messages = parser.feed_with_offsets(&chunk)?.into_iter();
+                    self.pending.extend(messages.map(|(offset, message)| PendingEvent::Proto(offset, message)));
+                }
+                (None, BodyDecoder::Sse(parser)) => {
+                    parser.borrow_mut().finish();
+                    return Ok(None);
+                }
+                (None, BodyDecoder::Proto(parser)) => {
+                    parser.finish()?;
+                    return Ok(None);
+                }
+            }
+        }
+    }
+    
+    /// Read the next network chunk, or `None` once the stream is done
+    async fn read_chunk(&self) -> Result<Option<Vec<u8>>> {
+        let result = JsFuture::from(self.reader.read()).await?;
+        let chunk = Reflect::get(&result, &JsValue::from_str("value"))
+            .map_err(|e| AgUiError::StreamError(format!("Failed to get chunk value: {:?}", e)))?;
+        
+        let done = Reflect::get(&result, &JsValue::from_str("done"))
+            .map_err(|e| AgUiError::StreamError(format!("Failed to get done flag: {:?}", e)))?
+            .as_bool()
+            .unwrap_or(false);
+        
+        if done {
+            return Ok(None);
+        }
+        
+        let chunk = chunk.dyn_into::<Uint8Array>()
+            .map_err(|e| AgUiError::StreamError(format!("Failed to convert to Uint8Array: {:?}", e)))?;
+        
+        Ok(Some(chunk.to_vec()))
+    }
+}
+
+/// Convert an event to a plain JS object in its wire format
+fn event_to_js(event: &BaseEvent) -> Result<JsValue> {
+    Ok(event.serialize(&serde_wasm_bindgen::Serializer::json_compatible())?)
+}
+
+/// Make `for await` work on `stream` where `ReadableStream` is not async iterable
+fn install_async_iterator(stream: &ReadableStream) -> Result<()> {
+    let key = js_sys::Symbol::async_iterator();
+    if Reflect::has(stream, &key)? {
+        return Ok(());
+    }
+    
+    let target = stream.clone();
+    let iterate = Closure::<dyn FnMut() -> std::result::Result<JsValue, JsValue>>::new(move || {
+        let reader = target.get_reader().dyn_into::<ReadableStreamDefaultReader>()?;
+        let iterator = Object::new();
+        
+        let next_reader = reader.clone();
+        let next = Closure::<dyn FnMut() -> Promise>::new(move || next_reader.read());
+        // Leaving the loop early cancels the stream, as the native iterator does
+        let finish = Closure::<dyn FnMut(JsValue) -> Promise>::new(move |value: JsValue| {
+            let cancelled = JsFuture::from(reader.cancel_with_reason(&value));
+            wasm_bindgen_futures::future_to_promise(async move {
+                cancelled.await?;
+                let result = Object::new();
+                Reflect::set(&result, &JsValue::from_str("value"), &value)?;
+                Reflect::set(&result, &JsValue::from_str("done"), &JsValue::TRUE)?;
+                Ok(result.into())
+            })
+        });
+        
+        Reflect::set(&iterator, &JsValue::from_str("next"), &next.into_js_value())?;
+        Reflect::set(&iterator, &JsValue::from_str("return"), &finish.into_js_value())?;
+        Ok(iterator.into())
+    });
+    
+    Reflect::set(stream, &key, &iterate.into_js_value())?;
+    Ok(())
+}
@@ -1,3 +1,8 @@
+pub mod event_sink;
 pub mod event_stream;
+pub use ag_ui_core::{proto_parser, sse_parser};
 
-pub use event_stream::EventStream; 
\ No newline at end of file
+pub use event_sink::EventSink;
+pub use event_stream::EventStream;
+pub use proto_parser::ProtoParser;
+pub use sse_parser::{SseEvent, SseParser};
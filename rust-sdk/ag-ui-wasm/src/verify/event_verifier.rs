@@ -0,0 +1,242 @@
+use crate::core::events::{
+    BaseEvent, EventData, EventType, StepFinishedEvent, ThinkingEndEvent, ThinkingTextMessageEndEvent, ToolCallEndEvent,
+};
+use crate::error::{AgUiError, ProtocolViolation, Result};
+use crate::stream::EventStream;
+
+/// Checks that a sequence of events follows the AG-UI protocol
+///
+/// Mirrors `verifyEvents` from the TypeScript client: every stream starts
+/// with `RUN_STARTED`, nothing but `RUN_ERROR` or a new `RUN_STARTED` follows
+/// `RUN_FINISHED`, nothing follows `RUN_ERROR`, and messages, tool calls,
+/// steps and thinking blocks are opened before they are used and closed
+/// before the run finishes. Several messages and tool calls may be open at
+/// the same time as long as their IDs differ.
+///
+/// Chunk events are not checked; expand them into their START/CONTENT/END
+/// form with `ChunkExpander` first to have them verified.
+#[derive(Debug, Clone, Default)]
+pub struct EventVerifier {
+    first_event_received: bool,
+    run_started: bool,
+    run_finished: bool,
+    run_errored: bool,
+    active_messages: Vec<String>,
+    active_tool_calls: Vec<String>,
+    active_steps: Vec<String>,
+    thinking_active: bool,
+    thinking_message_active: bool,
+}
+
+impl EventVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check the next event of the sequence
+    ///
+    /// A rejected event does not change the verifier's state; the error
+    /// carries the event with the violation.
+    pub fn verify(&mut self, event: &BaseEvent) -> Result<()> {
+        self.check(event).map_err(|violation| AgUiError::protocol(violation, event))
+    }
+
+    fn check(&mut self, event: &BaseEvent) -> std::result::Result<(), ProtocolViolation> {
+        let event_type = event.event_type();
+
+        if self.run_errored {
+            return Err(ProtocolViolation::RunAlreadyErrored { event_type });
+        }
+        if self.run_finished && !matches!(event_type, EventType::RunStarted | EventType::RunError) {
+            return Err(ProtocolViolation::RunAlreadyFinished { event_type });
+        }
+        if !self.first_event_received {
+            if !matches!(event_type, EventType::RunStarted | EventType::RunError) {
+                return Err(ProtocolViolation::RunNotStarted { event_type });
+            }
+        } else if event_type == EventType::RunStarted {
+            if self.run_started && !self.run_finished {
+                return Err(ProtocolViolation::RunAlreadyStarted);
+            }
+            if self.run_finished {
+                // A new run on the same stream starts from a clean slate
+                *self = Self::default();
+            }
+        }
+
+        match &event.data {
+            EventData::RunStarted(_) => self.run_started = true,
+            EventData::RunFinished(_) => {
+                if !self.active_steps.is_empty() {
+                    return Err(ProtocolViolation::UnfinishedSteps { step_names: self.active_steps.clone() });
+                }
+                if !self.active_messages.is_empty() {
+                    return Err(ProtocolViolation::UnfinishedTextMessages { message_ids: self.active_messages.clone() });
+                }
+                if !self.active_tool_calls.is_empty() {
+                    return Err(ProtocolViolation::UnfinishedToolCalls { tool_call_ids: self.active_tool_calls.clone() });
+                }
+                self.run_finished = true;
+            }
+            // Aborting ends the run wherever it was
+            EventData::RunAborted(_) => self.run_finished = true,
+            EventData::RunError(_) => self.run_errored = true,
+
+            EventData::StepStarted(data) => {
+                if self.active_steps.contains(&data.step_name) {
+                    return Err(ProtocolViolation::StepAlreadyActive { step_name: data.step_name.clone() });
+                }
+                self.active_steps.push(data.step_name.clone());
+            }
+            EventData::StepFinished(data) => {
+                if !remove(&mut self.active_steps, &data.step_name) {
+                    return Err(ProtocolViolation::StepNotActive { step_name: data.step_name.clone() });
+                }
+            }
+
+            EventData::TextMessageStart(data) => {
+                if self.active_messages.contains(&data.message_id) {
+                    return Err(ProtocolViolation::TextMessageAlreadyActive { message_id: data.message_id.clone() });
+                }
+                self.active_messages.push(data.message_id.clone());
+            }
+            EventData::TextMessageContent(data) => {
+                if !self.active_messages.contains(&data.message_id) {
+                    return Err(ProtocolViolation::TextMessageNotActive { event_type, message_id: data.message_id.clone() });
+                }
+            }
+            EventData::TextMessageEnd(data) => {
+                if !remove(&mut self.active_messages, &data.message_id) {
+                    return Err(ProtocolViolation::TextMessageNotActive { event_type, message_id: data.message_id.clone() });
+                }
+            }
+
+            EventData::ToolCallStart(data) => {
+                if self.active_tool_calls.contains(&data.tool_call_id) {
+                    return Err(ProtocolViolation::ToolCallAlreadyActive { tool_call_id: data.tool_call_id.clone() });
+                }
+                self.active_tool_calls.push(data.tool_call_id.clone());
+            }
+            EventData::ToolCallArgs(data) => {
+                if !self.active_tool_calls.contains(&data.tool_call_id) {
+                    return Err(ProtocolViolation::ToolCallNotActive { event_type, tool_call_id: data.tool_call_id.clone() });
+                }
+            }
+            EventData::ToolCallEnd(data) => {
+                if !remove(&mut self.active_tool_calls, &data.tool_call_id) {
+                    return Err(ProtocolViolation::ToolCallNotActive { event_type, tool_call_id: data.tool_call_id.clone() });
+                }
+            }
+
+            EventData::ThinkingStart(_) => {
+                if self.thinking_active {
+                    return Err(ProtocolViolation::ThinkingAlreadyActive);
+                }
+                self.thinking_active = true;
+            }
+            EventData::ThinkingEnd(_) => {
+                if !self.thinking_active {
+                    return Err(ProtocolViolation::ThinkingNotActive { event_type });
+                }
+                self.thinking_active = false;
+            }
+            EventData::ThinkingTextMessageStart(_) => {
+                if !self.thinking_active {
+                    return Err(ProtocolViolation::ThinkingNotActive { event_type });
+                }
+                if self.thinking_message_active {
+                    return Err(ProtocolViolation::ThinkingMessageAlreadyActive);
+                }
+                self.thinking_message_active = true;
+            }
+            EventData::ThinkingTextMessageContent(_) => {
+                if !self.thinking_message_active {
+                    return Err(ProtocolViolation::ThinkingMessageNotActive { event_type });
+                }
+            }
+            EventData::ThinkingTextMessageEnd(_) => {
+                if !self.thinking_message_active {
+                    return Err(ProtocolViolation::ThinkingMessageNotActive { event_type });
+                }
+                self.thinking_message_active = false;
+            }
+
+            EventData::TextMessageChunk(_)
+            | EventData::ToolCallChunk(_)
+            | EventData::ToolCallResult(_)
+            | EventData::MessagesSnapshot(_)
+            | EventData::StateSnapshot(_)
+            | EventData::StateDelta(_)
+            | EventData::Error(_)
+            | EventData::Raw(_)
+            | EventData::Custom(_) => {}
+        }
+
+        self.first_event_received = true;
+        Ok(())
+    }
+
+    /// Check a complete sequence of events, stopping at the first violation
+    pub fn verify_all<'a>(&mut self, events: impl IntoIterator<Item = &'a BaseEvent>) -> Result<()> {
+        events.into_iter().try_for_each(|event| self.verify(event))
+    }
+
+    /// The events that end every step, message, tool call and thinking block still open
+    ///
+    /// Innermost first, so that a `RUN_FINISHED` after them is accepted.
+    pub(crate) fn closing_events(&self) -> Vec<BaseEvent> {
+        let mut events = Vec::new();
+        if self.thinking_message_active {
+            events.push(BaseEvent::new(ThinkingTextMessageEndEvent {}));
+        }
+        if self.thinking_active {
+            events.push(BaseEvent::new(ThinkingEndEvent {}));
+        }
+        for tool_call_id in self.active_tool_calls.iter().rev() {
+            events.push(BaseEvent::new(ToolCallEndEvent { tool_call_id: tool_call_id.clone() }));
+        }
+        for message_id in self.active_messages.iter().rev() {
+            events.push(BaseEvent::text_message_end(message_id.clone()));
+        }
+        for step_name in self.active_steps.iter().rev() {
+            events.push(BaseEvent::new(StepFinishedEvent { step_name: step_name.clone() }));
+        }
+        events
+    }
+
+    /// Verify every event read from `stream`
+    pub fn wrap(self, stream: EventStream) -> VerifiedEventStream {
+        VerifiedEventStream { stream, verifier: self }
+    }
+}
+
+fn remove(active: &mut Vec<String>, id: &str) -> bool {
+    let before = active.len();
+    active.retain(|active_id| active_id != id);
+    active.len() != before
+}
+
+/// An `EventStream` whose events are checked by an `EventVerifier`
+pub struct VerifiedEventStream {
+    stream: EventStream,
+    verifier: EventVerifier,
+}
+
+impl VerifiedEventStream {
+    /// Read the next event, failing on the first protocol violation
+    pub async fn next_event(&mut self) -> Result<Option<BaseEvent>> {
+        let event = self.stream.next_event().await?;
+        if let Some(event) = &event {
+            self.verifier.verify(event)?;
+        }
+        Ok(event)
+    }
+
+    pub fn verifier(&self) -> &EventVerifier {
+        &self.verifier
+    }
+
+    pub fn into_inner(self) -> EventStream {
+        self.stream
+    }
+}
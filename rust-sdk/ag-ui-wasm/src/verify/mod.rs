@@ -0,0 +1,3 @@
+pub mod event_verifier;
+
+pub use event_verifier::{EventVerifier, VerifiedEventStream};
@@ -0,0 +1,276 @@
+//! Tests for driving agents through the `Agent` trait
+//!
+//! An in-process agent implements only `run`; the trait keeps its messages
+//! and state, fills them into each run's input and applies the run's events
+//! back, as it does for `WebAgent`.
+
+use ag_ui_wasm::{
+    Agent, AgUiError, AgentData, BaseEvent, EventStream, EventType, Message, ProtocolViolation, RunAgentInput,
+    RunFinishedEvent, TextMessageChunkEvent, WebAgent,
+};
+use futures::executor::block_on;
+use serde_json::json;
+use std::cell::RefCell;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+type Script = fn(&RunAgentInput) -> Vec<ag_ui_wasm::Result<BaseEvent>>;
+
+/// Answers every run with the events of its script and remembers the inputs it saw
+struct ScriptedAgent {
+    data: RefCell<AgentData>,
+    inputs: RefCell<Vec<RunAgentInput>>,
+    script: Script,
+}
+
+impl ScriptedAgent {
+    fn new(script: Script) -> Self {
+        Self { data: RefCell::default(), inputs: RefCell::default(), script }
+    }
+}
+
+impl Agent for ScriptedAgent {
+    fn data(&self) -> &RefCell<AgentData> {
+        &self.data
+    }
+
+    async fn run(&self, input: RunAgentInput) -> ag_ui_wasm::Result<EventStream> {
+        self.inputs.borrow_mut().push(input.clone());
+        Ok(EventStream::from_stream(futures::stream::iter((self.script)(&input))))
+    }
+}
+
+/// One assistant message per run, with the run ID as the run's result
+fn echo(input: &RunAgentInput) -> Vec<ag_ui_wasm::Result<BaseEvent>> {
+    let message_id = format!("reply-{}", input.run_id);
+    vec![
+        Ok(BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone())),
+        Ok(BaseEvent::text_message_start(message_id.clone(), None)),
+        Ok(BaseEvent::text_message_content(message_id.clone(), "Hello".to_string())),
+        Ok(BaseEvent::text_message_end(message_id)),
+        Ok(BaseEvent::new(RunFinishedEvent {
+            thread_id: input.thread_id.clone(),
+            run_id: input.run_id.clone(),
+            result: Some(json!(input.run_id)),
+        })),
+    ]
+}
+
+fn input() -> RunAgentInput {
+    RunAgentInput::new("thread-1".to_string(), "run-1".to_string())
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_agent_applies_the_run() {
+    let mut agent = ScriptedAgent::new(echo);
+    agent.set_messages(vec![Message::user("Hi")]);
+
+    let result = block_on(agent.run_agent(input())).unwrap();
+
+    assert_eq!(result.result, Some(json!("run-1")));
+    assert_eq!(result.new_messages.len(), 1);
+    assert_eq!(result.new_messages[0].id(), "reply-run-1");
+    assert_eq!(result.new_messages[0].content(), Some("Hello"));
+
+    let messages = agent.messages();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[1], result.new_messages[0]);
+    assert_eq!(agent.thread_id().as_deref(), Some("thread-1"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_agent_sends_the_messages_and_state() {
+    let mut agent = ScriptedAgent::new(echo);
+    agent.set_messages(vec![Message::user("Hi")]);
+    agent.add_message(Message::user("Are you there?"));
+    agent.set_state([("count".to_string(), json!(1))].into_iter().collect());
+
+    block_on(agent.run_agent(input())).unwrap();
+
+    let sent = agent.inputs.borrow()[0].clone();
+    assert_eq!(sent.messages.len(), 2);
+    assert_eq!(sent.state["count"], json!(1));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_input_messages_and_state_take_precedence() {
+    let mut agent = ScriptedAgent::new(echo);
+    agent.set_messages(vec![Message::user("Hi")]);
+    agent.set_state([("count".to_string(), json!(1))].into_iter().collect());
+
+    let mut own = input();
+    own.messages = vec![Message::user("Hello")];
+    own.state = [("count".to_string(), json!(2))].into_iter().collect();
+    block_on(agent.run_agent(own.clone())).unwrap();
+
+    let sent = agent.inputs.borrow()[0].clone();
+    assert_eq!(sent.messages, own.messages);
+    assert_eq!(sent.state, own.state);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_empty_messages_and_state_are_sent_empty() {
+    let agent = ScriptedAgent::new(echo);
+
+    block_on(agent.run_agent(input())).unwrap();
+
+    let sent = agent.inputs.borrow()[0].clone();
+    assert!(sent.messages.is_empty());
+    assert!(sent.state.is_empty());
+
+    let json = serde_json::to_value(&sent).unwrap();
+    assert_eq!(json["messages"], json!([]));
+    assert_eq!(json["state"], json!({}));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_sequential_runs_continue_the_thread() {
+    let agent = ScriptedAgent::new(echo);
+
+    // No thread or run ID: the first run starts a thread, the second continues it
+    let blank = || RunAgentInput::new(String::new(), String::new());
+    let first = block_on(agent.run_agent(blank())).unwrap();
+    let second = block_on(agent.run_agent(blank())).unwrap();
+
+    let inputs = agent.inputs.borrow();
+    assert!(!inputs[0].thread_id.is_empty());
+    assert_eq!(inputs[1].thread_id, inputs[0].thread_id);
+    assert_ne!(inputs[1].run_id, inputs[0].run_id);
+    assert_eq!(&inputs[1].messages, &first.new_messages);
+
+    assert_eq!(second.new_messages.len(), 1);
+    assert_eq!(agent.messages().len(), 2);
+    assert_eq!(agent.thread_id().as_ref(), Some(&inputs[0].thread_id));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_chunks_are_expanded_before_they_reach_on_event() {
+    let chunked = |input: &RunAgentInput| {
+        vec![
+            Ok(BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone())),
+            Ok(BaseEvent::new(TextMessageChunkEvent {
+                message_id: Some("msg-1".to_string()),
+                role: None,
+                delta: Some("Hello".to_string()),
+            })),
+            Ok(BaseEvent::run_finished(input.thread_id.clone(), input.run_id.clone())),
+        ]
+    };
+    let agent = ScriptedAgent::new(chunked);
+
+    let mut seen = Vec::new();
+    let result = block_on(agent.run_agent_with(input(), |event| seen.push(event.event_type()))).unwrap();
+
+    assert_eq!(
+        seen,
+        [
+            EventType::RunStarted,
+            EventType::TextMessageStart,
+            EventType::TextMessageContent,
+            EventType::TextMessageEnd,
+            EventType::RunFinished,
+        ]
+    );
+    assert_eq!(result.new_messages[0].content(), Some("Hello"));
+    assert_eq!(result.result, None);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_failed_run_keeps_what_it_applied() {
+    let broken = |input: &RunAgentInput| {
+        vec![
+            Ok(BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone())),
+            Ok(BaseEvent::text_message_start("msg-1".to_string(), None)),
+            Ok(BaseEvent::text_message_content("msg-1".to_string(), "Hel".to_string())),
+            Err(AgUiError::StreamError("connection reset".to_string())),
+        ]
+    };
+    let agent = ScriptedAgent::new(broken);
+
+    let error = block_on(agent.run_agent(input())).unwrap_err();
+
+    assert!(matches!(error, AgUiError::StreamError(_)));
+    let messages = agent.messages();
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].content(), Some("Hel"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_aborted_run_ends_with_a_local_run_error() {
+    let aborted = |input: &RunAgentInput| {
+        vec![
+            Ok(BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone())),
+            Ok(BaseEvent::text_message_start("msg-1".to_string(), None)),
+            Ok(BaseEvent::text_message_content("msg-1".to_string(), "Hel".to_string())),
+            Err(AgUiError::Aborted),
+        ]
+    };
+    let agent = ScriptedAgent::new(aborted);
+
+    let mut seen = Vec::new();
+    let error = block_on(agent.run_agent_with(input(), |event| seen.push(event.clone()))).unwrap_err();
+
+    assert!(matches!(error, AgUiError::Aborted));
+    let last = seen.last().unwrap();
+    assert_eq!(last.event_type(), EventType::RunError);
+    assert_eq!(serde_json::to_value(last).unwrap()["code"], "ABORTED");
+    assert_eq!(agent.messages()[0].content(), Some("Hel"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_error_fails_the_run_with_its_error() {
+    let failing = |input: &RunAgentInput| {
+        vec![
+            Ok(BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone())),
+            Ok(BaseEvent::text_message_start("msg-1".to_string(), None)),
+            Ok(BaseEvent::text_message_content("msg-1".to_string(), "Hel".to_string())),
+            Ok(BaseEvent::run_error("Rate limit exceeded".to_string(), Some("rate_limit".to_string()))),
+        ]
+    };
+    let agent = ScriptedAgent::new(failing);
+
+    let error = block_on(agent.run_agent(input())).unwrap_err();
+
+    assert!(matches!(&error, AgUiError::RunError { message, code: Some(code) }
+        if message == "Rate limit exceeded" && code == "rate_limit"));
+    assert_eq!(error.code(), "RUN_ERROR");
+    // What the run produced before it failed is kept
+    assert_eq!(agent.messages()[0].content(), Some("Hel"));
+
+    let timed_out = |input: &RunAgentInput| {
+        vec![
+            Ok(BaseEvent::run_started(input.thread_id.clone(), input.run_id.clone())),
+            Ok(BaseEvent::run_error("Model call timed out".to_string(), Some("TIMEOUT".to_string()))),
+        ]
+    };
+    let error = block_on(ScriptedAgent::new(timed_out).run_agent(input())).unwrap_err();
+    assert!(matches!(error, AgUiError::Timeout(message) if message == "Model call timed out"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_protocol_violations_fail_the_run() {
+    let unstarted = |_: &RunAgentInput| vec![Ok(BaseEvent::text_message_start("msg-1".to_string(), None))];
+    let agent = ScriptedAgent::new(unstarted);
+
+    let error = block_on(agent.run_agent(input())).unwrap_err();
+
+    assert!(matches!(error.violation(), Some(ProtocolViolation::RunNotStarted { .. })));
+    assert!(agent.messages().is_empty());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_web_agent_clones_share_their_data() {
+    let mut agent = WebAgent::new("https://agent.example.com/run".to_string());
+    let shared = agent.clone();
+    agent.set_thread_id("thread-1".to_string());
+    agent.set_agent_id("agent-1".to_string());
+    agent.set_messages(vec![Message::user("Hi")]);
+
+    assert_eq!(shared.thread_id().as_deref(), Some("thread-1"));
+    assert_eq!(shared.agent_id().as_deref(), Some("agent-1"));
+
+    let prepared = shared.prepare_run_input(RunAgentInput::new(String::new(), "run-1".to_string()));
+    assert_eq!(prepared.thread_id, "thread-1");
+    assert_eq!(prepared.messages.len(), 1);
+}
@@ -0,0 +1,252 @@
+//! Event application tests matching the TypeScript client's apply tests
+//!
+//! `EventApplier` folds events into an `AgentState` and reports which parts
+//! of it every event changed.
+
+use ag_ui_wasm::{
+    AgUiError, AgentState, AgentStateMutation, AssistantMessage, BaseEvent, EventApplier, EventType,
+    Message, MessagesSnapshotEvent, Role, StateDeltaEvent, StateSnapshotEvent, TextMessageChunkEvent,
+    ToolCall, ToolCallArgsEvent, ToolCallEndEvent, ToolCallResultEvent, ToolCallStartEvent, ToolMessage,
+};
+use serde_json::json;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const MESSAGES: AgentStateMutation = AgentStateMutation { messages: true, state: false };
+const STATE: AgentStateMutation = AgentStateMutation { messages: false, state: true };
+const NOTHING: AgentStateMutation = AgentStateMutation { messages: false, state: false };
+
+fn tool_call_start(tool_call_id: &str, parent_message_id: Option<&str>) -> BaseEvent {
+    BaseEvent::new(ToolCallStartEvent {
+        tool_call_id: tool_call_id.to_string(),
+        tool_call_name: "search".to_string(),
+        parent_message_id: parent_message_id.map(str::to_string),
+    })
+}
+
+fn tool_call_args(tool_call_id: &str, delta: &str) -> BaseEvent {
+    BaseEvent::new(ToolCallArgsEvent { tool_call_id: tool_call_id.to_string(), delta: delta.to_string() })
+}
+
+fn tool_call_end(tool_call_id: &str) -> BaseEvent {
+    BaseEvent::new(ToolCallEndEvent { tool_call_id: tool_call_id.to_string() })
+}
+
+fn state_delta(delta: serde_json::Value) -> BaseEvent {
+    BaseEvent::new(StateDeltaEvent { delta })
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_text_message_deltas_are_appended() {
+    let mut state = AgentState::default();
+
+    assert_eq!(state.apply(&BaseEvent::text_message_start("msg-1".to_string(), None)).unwrap(), MESSAGES);
+    for delta in ["Hello", ", ", "world"] {
+        let event = BaseEvent::text_message_content("msg-1".to_string(), delta.to_string());
+        assert_eq!(state.apply(&event).unwrap(), MESSAGES);
+    }
+    assert_eq!(state.apply(&BaseEvent::text_message_end("msg-1".to_string())).unwrap(), NOTHING);
+
+    assert_eq!(state.messages, vec![Message::from(AssistantMessage::new().with_id("msg-1").with_content("Hello, world"))]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_text_message_start_uses_the_event_role() {
+    let mut state = AgentState::default();
+    state.apply(&BaseEvent::text_message_start("msg-1".to_string(), Some(Role::User))).unwrap();
+    state.apply(&BaseEvent::text_message_content("msg-1".to_string(), "Hi".to_string())).unwrap();
+    assert_eq!(state.messages[0].role(), Role::User);
+    assert_eq!(state.messages[0].content(), Some("Hi"));
+
+    let error = state.apply(&BaseEvent::text_message_start("msg-2".to_string(), Some(Role::Tool))).unwrap_err();
+    assert!(matches!(error, AgUiError::InvalidMessage(_)));
+    assert_eq!(state.messages.len(), 1);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_content_for_unknown_message_is_ignored() {
+    let mut state = AgentState::default();
+    let mutation = state.apply(&BaseEvent::text_message_content("missing".to_string(), "Hi".to_string())).unwrap();
+
+    assert!(mutation.is_empty());
+    assert!(state.messages.is_empty());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_tool_call_args_are_assembled() {
+    let mut state = AgentState::default();
+    state.apply(&tool_call_start("call-1", None)).unwrap();
+    assert!(state.pending_tool_calls.contains("call-1"));
+
+    state.apply(&tool_call_args("call-1", r#"{"query": "#)).unwrap();
+    assert_eq!(state.messages[0].tool_calls().unwrap()[0].function.arguments, r#"{"query": "#);
+
+    state.apply(&tool_call_args("call-1", r#""rust"}"#)).unwrap();
+    assert_eq!(state.messages[0].tool_calls().unwrap()[0].function.arguments, r#"{"query": "rust"}"#);
+
+    state.apply(&tool_call_end("call-1")).unwrap();
+    assert!(state.pending_tool_calls.is_empty());
+    assert_eq!(
+        state.messages,
+        vec![Message::from(AssistantMessage::new().with_id("call-1").with_tool_calls(vec![ToolCall::new("call-1", "search", r#"{"query": "rust"}"#)]))]
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_tool_calls_join_their_parent_message() {
+    let mut state = AgentState::default();
+    state.apply(&BaseEvent::text_message_start("msg-1".to_string(), None)).unwrap();
+    state.apply(&BaseEvent::text_message_content("msg-1".to_string(), "Searching".to_string())).unwrap();
+    state.apply(&tool_call_start("call-1", Some("msg-1"))).unwrap();
+    state.apply(&tool_call_start("call-2", Some("msg-1"))).unwrap();
+    state.apply(&tool_call_start("call-3", Some("msg-2"))).unwrap();
+
+    assert_eq!(state.messages.len(), 2);
+    let ids: Vec<&str> = state.messages[0].tool_calls().unwrap().iter().map(|call| call.id.as_str()).collect();
+    assert_eq!(ids, ["call-1", "call-2"]);
+    assert_eq!(state.messages[0].content(), Some("Searching"));
+    assert_eq!(state.messages[1].id(), "msg-2");
+    assert_eq!(state.messages[1].tool_calls().unwrap()[0].id, "call-3");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_invalid_tool_call_args_are_kept_as_text() {
+    let mut state = AgentState::default();
+    state.apply(&tool_call_start("call-1", None)).unwrap();
+    assert_eq!(state.apply(&tool_call_args("call-1", "not json")).unwrap(), MESSAGES);
+    assert!(state.apply(&tool_call_end("call-1")).unwrap().is_empty());
+
+    assert_eq!(state.messages[0].tool_calls().unwrap()[0].function.arguments, "not json");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_tool_call_result_adds_a_tool_message() {
+    let mut state = AgentState::default();
+    let event = BaseEvent::new(ToolCallResultEvent {
+        message_id: "result-1".to_string(),
+        tool_call_id: "call-1".to_string(),
+        content: "18°C".to_string(),
+        role: Some(Role::Tool),
+    });
+
+    assert_eq!(state.apply(&event).unwrap(), MESSAGES);
+    assert_eq!(state.messages, vec![Message::from(ToolMessage::new("18°C", "call-1").with_id("result-1"))]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_state_snapshot_and_delta() {
+    let mut state = AgentState::default();
+    let snapshot = HashMap::from([("count".to_string(), json!(1)), ("items".to_string(), json!(["a"]))]);
+
+    assert_eq!(state.apply(&BaseEvent::new(StateSnapshotEvent { snapshot })).unwrap(), STATE);
+
+    let delta = state_delta(json!([
+        {"op": "replace", "path": "/count", "value": 2},
+        {"op": "add", "path": "/items/-", "value": "b"},
+        {"op": "add", "path": "/status", "value": "running"}
+    ]));
+    assert_eq!(state.apply(&delta).unwrap(), STATE);
+
+    assert_eq!(
+        state.state,
+        HashMap::from([
+            ("count".to_string(), json!(2)),
+            ("items".to_string(), json!(["a", "b"])),
+            ("status".to_string(), json!("running")),
+        ])
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_failed_state_delta_leaves_state_unchanged() {
+    let mut state = AgentState::new(Vec::new(), HashMap::from([("count".to_string(), json!(1))]));
+    let delta = state_delta(json!([
+        {"op": "replace", "path": "/count", "value": 2},
+        {"op": "remove", "path": "/missing"}
+    ]));
+
+    assert!(matches!(state.apply(&delta), Err(AgUiError::PatchError(_))));
+    assert_eq!(state.state, HashMap::from([("count".to_string(), json!(1))]));
+
+    let replace_root = state_delta(json!([{"op": "replace", "path": "", "value": [1, 2]}]));
+    assert!(matches!(state.apply(&replace_root), Err(AgUiError::PatchError(_))));
+    assert_eq!(state.state, HashMap::from([("count".to_string(), json!(1))]));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_messages_snapshot_replaces_messages() {
+    let mut state = AgentState::new(vec![Message::user("Old")], HashMap::new());
+    state.apply(&tool_call_start("call-1", None)).unwrap();
+    state.apply(&tool_call_start("call-2", None)).unwrap();
+
+    let kept = state.messages[2].clone();
+    let snapshot = vec![Message::user("New"), kept.clone()];
+    let mutation = state.apply(&BaseEvent::new(MessagesSnapshotEvent { messages: snapshot.clone() })).unwrap();
+
+    assert_eq!(mutation, MESSAGES);
+    assert_eq!(state.messages, snapshot);
+    assert_eq!(state.pending_tool_calls.iter().collect::<Vec<_>>(), ["call-2"]);
+
+    // Arguments keep streaming into the tool call carried over by the snapshot
+    state.apply(&tool_call_args("call-2", "{}")).unwrap();
+    assert_eq!(state.messages[1].tool_calls().unwrap()[0].function.arguments, "{}");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_chunks_must_be_expanded_first() {
+    let mut state = AgentState::default();
+    let chunk = BaseEvent::new(TextMessageChunkEvent {
+        message_id: Some("msg-1".to_string()),
+        role: None,
+        delta: Some("Hi".to_string()),
+    });
+
+    let error = state.apply(&chunk).unwrap_err();
+    assert!(matches!(error, AgUiError::ChunkNotExpanded { event_type: EventType::TextMessageChunk }));
+    assert!(state.messages.is_empty());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_listeners_are_notified_after_every_event() {
+    let notifications = Rc::new(RefCell::new(Vec::new()));
+    let mut applier = EventApplier::default();
+    let recorded = notifications.clone();
+    applier.on_mutation(move |mutation, state| recorded.borrow_mut().push((*mutation, state.messages.len())));
+
+    applier
+        .apply_all(&[
+            BaseEvent::run_started("thread-1".to_string(), "run-1".to_string()),
+            BaseEvent::text_message_start("msg-1".to_string(), None),
+            BaseEvent::text_message_content("msg-1".to_string(), "Hi".to_string()),
+            BaseEvent::text_message_end("msg-1".to_string()),
+            state_delta(json!([{"op": "add", "path": "/done", "value": true}])),
+        ])
+        .unwrap();
+
+    assert_eq!(*notifications.borrow(), [(NOTHING, 0), (MESSAGES, 1), (MESSAGES, 1), (NOTHING, 1), (STATE, 1)]);
+
+    // A failing event is not reported
+    assert!(applier.apply(&state_delta(json!([{"op": "remove", "path": "/missing"}]))).is_err());
+    assert_eq!(notifications.borrow().len(), 5);
+    assert_eq!(applier.into_state().state.get("done"), Some(&json!(true)));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_apply_stream_folds_every_event() {
+    let events = vec![
+        Ok(BaseEvent::text_message_start("msg-1".to_string(), None)),
+        Ok(BaseEvent::text_message_content("msg-1".to_string(), "Hi".to_string())),
+        Err(AgUiError::StreamError("connection closed".to_string())),
+        Ok(BaseEvent::text_message_content("msg-1".to_string(), " there".to_string())),
+    ];
+
+    let mut applier = EventApplier::default();
+    let result = futures::executor::block_on(applier.apply_stream(futures::stream::iter(events)));
+
+    assert!(matches!(result, Err(AgUiError::StreamError(_))));
+    assert_eq!(applier.state().messages[0].content(), Some("Hi"));
+}
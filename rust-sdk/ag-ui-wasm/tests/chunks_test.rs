@@ -0,0 +1,199 @@
+//! Chunk expansion tests matching the TypeScript client's transform tests
+//!
+//! `ChunkExpander` turns `TEXT_MESSAGE_CHUNK` and `TOOL_CALL_CHUNK` events
+//! into the START/CONTENT/END and START/ARGS/END events UIs expect.
+
+use ag_ui_wasm::{
+    expand_chunks, AgUiError, AgentState, BaseEvent, ChunkExpander, EventData, EventType, EventVerifier,
+    RawEvent, Role, TextMessageChunkEvent, ToolCallChunkEvent,
+};
+use serde_json::{json, Value};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn text_chunk(message_id: Option<&str>, delta: Option<&str>) -> BaseEvent {
+    BaseEvent::new(TextMessageChunkEvent {
+        message_id: message_id.map(str::to_string),
+        role: None,
+        delta: delta.map(str::to_string),
+    })
+}
+
+fn tool_chunk(tool_call_id: Option<&str>, tool_call_name: Option<&str>, delta: Option<&str>) -> BaseEvent {
+    BaseEvent::new(ToolCallChunkEvent {
+        tool_call_id: tool_call_id.map(str::to_string),
+        tool_call_name: tool_call_name.map(str::to_string),
+        parent_message_id: None,
+        delta: delta.map(str::to_string),
+    })
+}
+
+/// Wire form of `events` without timestamps, for comparison
+fn wire(events: &[BaseEvent]) -> Vec<Value> {
+    events
+        .iter()
+        .map(|event| {
+            let mut value = serde_json::to_value(event).unwrap();
+            value.as_object_mut().unwrap().remove("timestamp");
+            value
+        })
+        .collect()
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_text_chunks_expand_to_a_message() {
+    let expanded = expand_chunks(vec![
+        text_chunk(Some("msg-1"), Some("Hello")),
+        text_chunk(Some("msg-1"), Some(" world")),
+        text_chunk(None, Some("!")),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        wire(&expanded),
+        [
+            json!({"type": "TEXT_MESSAGE_START", "messageId": "msg-1", "role": "assistant"}),
+            json!({"type": "TEXT_MESSAGE_CONTENT", "messageId": "msg-1", "delta": "Hello"}),
+            json!({"type": "TEXT_MESSAGE_CONTENT", "messageId": "msg-1", "delta": " world"}),
+            json!({"type": "TEXT_MESSAGE_CONTENT", "messageId": "msg-1", "delta": "!"}),
+            json!({"type": "TEXT_MESSAGE_END", "messageId": "msg-1"}),
+        ]
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_text_chunk_role_is_kept() {
+    let mut chunk = text_chunk(Some("msg-1"), None);
+    if let EventData::TextMessageChunk(data) = &mut chunk.data {
+        data.role = Some(Role::User);
+    }
+
+    let expanded = expand_chunks(vec![chunk]).unwrap();
+
+    assert_eq!(
+        wire(&expanded),
+        [
+            json!({"type": "TEXT_MESSAGE_START", "messageId": "msg-1", "role": "user"}),
+            json!({"type": "TEXT_MESSAGE_END", "messageId": "msg-1"}),
+        ]
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_tool_call_chunks_expand_to_a_tool_call() {
+    let mut first = tool_chunk(Some("call-1"), Some("search"), Some(r#"{"q":"#));
+    if let EventData::ToolCallChunk(data) = &mut first.data {
+        data.parent_message_id = Some("msg-1".to_string());
+    }
+
+    let expanded = expand_chunks(vec![first, tool_chunk(None, None, Some(r#""rust"}"#))]).unwrap();
+
+    assert_eq!(
+        wire(&expanded),
+        [
+            json!({"type": "TOOL_CALL_START", "toolCallId": "call-1", "toolCallName": "search", "parentMessageId": "msg-1"}),
+            json!({"type": "TOOL_CALL_ARGS", "toolCallId": "call-1", "delta": r#"{"q":"#}),
+            json!({"type": "TOOL_CALL_ARGS", "toolCallId": "call-1", "delta": r#""rust"}"#}),
+            json!({"type": "TOOL_CALL_END", "toolCallId": "call-1"}),
+        ]
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_changing_ids_closes_the_open_message() {
+    let expanded = expand_chunks(vec![
+        text_chunk(Some("msg-1"), Some("One")),
+        text_chunk(Some("msg-2"), Some("Two")),
+        tool_chunk(Some("call-1"), Some("search"), None),
+        text_chunk(Some("msg-3"), None),
+    ])
+    .unwrap();
+
+    let summary: Vec<(EventType, String)> = wire(&expanded)
+        .iter()
+        .zip(&expanded)
+        .map(|(value, event)| {
+            let id = value.get("messageId").or(value.get("toolCallId")).unwrap();
+            (event.event_type(), id.as_str().unwrap().to_string())
+        })
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            (EventType::TextMessageStart, "msg-1".to_string()),
+            (EventType::TextMessageContent, "msg-1".to_string()),
+            (EventType::TextMessageEnd, "msg-1".to_string()),
+            (EventType::TextMessageStart, "msg-2".to_string()),
+            (EventType::TextMessageContent, "msg-2".to_string()),
+            (EventType::TextMessageEnd, "msg-2".to_string()),
+            (EventType::ToolCallStart, "call-1".to_string()),
+            (EventType::ToolCallEnd, "call-1".to_string()),
+            (EventType::TextMessageStart, "msg-3".to_string()),
+            (EventType::TextMessageEnd, "msg-3".to_string()),
+        ]
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_other_events_close_the_open_message() {
+    let mut expander = ChunkExpander::new();
+    expander.expand(text_chunk(Some("msg-1"), Some("Hi"))).unwrap();
+
+    let finished = expander.expand(BaseEvent::run_finished("thread-1".to_string(), "run-1".to_string())).unwrap();
+    let types: Vec<EventType> = finished.iter().map(BaseEvent::event_type).collect();
+    assert_eq!(types, [EventType::TextMessageEnd, EventType::RunFinished]);
+
+    assert!(expander.finish().is_empty());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_raw_events_pass_through() {
+    let mut expander = ChunkExpander::new();
+    expander.expand(text_chunk(Some("msg-1"), Some("Hi"))).unwrap();
+
+    let raw = BaseEvent::new(RawEvent { event: json!({"vendor": "payload"}), source: None });
+    let passed = expander.expand(raw).unwrap();
+    assert_eq!(wire(&passed), [json!({"type": "RAW", "event": {"vendor": "payload"}})]);
+
+    let content = expander.expand(text_chunk(None, Some(" there"))).unwrap();
+    assert_eq!(wire(&content), [json!({"type": "TEXT_MESSAGE_CONTENT", "messageId": "msg-1", "delta": " there"})]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_first_chunk_must_identify_its_message() {
+    let error = expand_chunks(vec![text_chunk(None, Some("Hi"))]).unwrap_err();
+    assert!(matches!(error, AgUiError::ChunkMissingField { event_type: EventType::TextMessageChunk, field: "messageId" }));
+    assert_eq!(error.to_string(), "First TEXT_MESSAGE_CHUNK must have a messageId");
+
+    let error = expand_chunks(vec![tool_chunk(None, Some("search"), None)]).unwrap_err();
+    assert!(matches!(error, AgUiError::ChunkMissingField { field: "toolCallId", .. }));
+
+    let error = expand_chunks(vec![tool_chunk(Some("call-1"), None, None)]).unwrap_err();
+    assert_eq!(error.to_string(), "First TOOL_CALL_CHUNK must have a toolCallName");
+
+    // A tool call chunk without an ID does not continue a text message
+    let error = expand_chunks(vec![text_chunk(Some("msg-1"), None), tool_chunk(None, None, Some("{}"))]).unwrap_err();
+    assert!(matches!(error, AgUiError::ChunkMissingField { field: "toolCallId", .. }));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_expanded_events_verify_and_apply() {
+    let expanded = expand_chunks(vec![
+        BaseEvent::run_started("thread-1".to_string(), "run-1".to_string()),
+        text_chunk(Some("msg-1"), Some("Let me search")),
+        tool_chunk(Some("call-1"), Some("search"), Some(r#"{"q":"rust"}"#)),
+        BaseEvent::run_finished("thread-1".to_string(), "run-1".to_string()),
+    ])
+    .unwrap();
+
+    EventVerifier::new().verify_all(&expanded).unwrap();
+
+    let mut state = AgentState::default();
+    for event in &expanded {
+        state.apply(event).unwrap();
+    }
+    assert_eq!(state.messages.len(), 2);
+    assert_eq!(state.messages[0].content(), Some("Let me search"));
+    assert_eq!(state.messages[1].tool_calls().unwrap()[0].function.arguments, r#"{"q":"rust"}"#);
+}
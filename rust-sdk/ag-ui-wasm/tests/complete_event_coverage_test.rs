@@ -5,8 +5,8 @@ use ag_ui_wasm::{
     TextMessageStartEvent, TextMessageContentEvent, TextMessageEndEvent,
     ToolCallStartEvent, ToolCallChunkEvent, ToolCallEndEvent,
     StateSnapshotEvent, StateDeltaEvent, MessagesSnapshotEvent,
-    RunStartedEvent, RunFinishedEvent, ErrorEvent,
-    Message, ToolCall,
+    ErrorEvent,
+    Message, AssistantMessage, DeveloperMessage, SystemMessage, ToolMessage, UserMessage, ToolCall,
 };
 use wasm_bindgen_test::*;
 use serde_json::json;
@@ -17,11 +17,10 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== MESSAGE EVENTS COMPREHENSIVE TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_start_event_comprehensive() {
     // Test with all fields
     let event = BaseEvent {
-        event_type: EventType::TextMessageStart,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({"source": "test", "version": "1.0"})),
         data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -30,21 +29,21 @@ fn test_text_message_start_event_comprehensive() {
         }),
     };
 
-    assert_eq!(event.event_type, EventType::TextMessageStart);
+    assert_eq!(event.event_type(), EventType::TextMessageStart);
 
     // Test serialization
     let json_str = serde_json::to_string(&event).unwrap();
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "TEXT_MESSAGE_START");
-    assert_eq!(parsed["message_id"], "msg-comprehensive");
+    assert_eq!(parsed["messageId"], "msg-comprehensive");
     assert_eq!(parsed["role"], "assistant");
     assert!(parsed["timestamp"].is_number());
-    assert_eq!(parsed["raw_event"]["source"], "test");
+    assert_eq!(parsed["rawEvent"]["source"], "test");
 
     // Test deserialization
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-    assert_eq!(deserialized.event_type, EventType::TextMessageStart);
+    assert_eq!(deserialized.event_type(), EventType::TextMessageStart);
 
     if let EventData::TextMessageStart(data) = &deserialized.data {
         assert_eq!(data.message_id, "msg-comprehensive");
@@ -52,7 +51,7 @@ fn test_text_message_start_event_comprehensive() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_content_event_comprehensive() {
     // Test with special characters and different content types
     let test_cases = vec![
@@ -66,7 +65,6 @@ fn test_text_message_content_event_comprehensive() {
 
     for (test_name, content) in test_cases {
         let event = BaseEvent {
-            event_type: EventType::TextMessageContent,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageContent(TextMessageContentEvent {
@@ -86,10 +84,9 @@ fn test_text_message_content_event_comprehensive() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_end_event_comprehensive() {
     let event = BaseEvent {
-        event_type: EventType::TextMessageEnd,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::TextMessageEnd(TextMessageEndEvent {
@@ -101,15 +98,14 @@ fn test_text_message_end_event_comprehensive() {
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "TEXT_MESSAGE_END");
-    assert_eq!(parsed["message_id"], "msg-end-test");
+    assert_eq!(parsed["messageId"], "msg-end-test");
 }
 
 // ===== TOOL CALL EVENTS COMPREHENSIVE TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_start_event_comprehensive() {
     let event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({
             "original": "event data",
@@ -118,7 +114,7 @@ fn test_tool_call_start_event_comprehensive() {
         })),
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: "call-comprehensive-123".to_string(),
-            tool_name: "advanced_data_processor".to_string(),
+            tool_call_name: "advanced_data_processor".to_string(),
             parent_message_id: Some("parent-msg-456".to_string()),
         }),
     };
@@ -127,21 +123,21 @@ fn test_tool_call_start_event_comprehensive() {
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "TOOL_CALL_START");
-    assert_eq!(parsed["tool_call_id"], "call-comprehensive-123");
-    assert_eq!(parsed["tool_name"], "advanced_data_processor");
-    assert_eq!(parsed["parent_message_id"], "parent-msg-456");
-    assert_eq!(parsed["raw_event"]["trace_id"], "trace_123");
+    assert_eq!(parsed["toolCallId"], "call-comprehensive-123");
+    assert_eq!(parsed["toolCallName"], "advanced_data_processor");
+    assert_eq!(parsed["parentMessageId"], "parent-msg-456");
+    assert_eq!(parsed["rawEvent"]["trace_id"], "trace_123");
 
     // Test deserialization
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
     if let EventData::ToolCallStart(data) = &deserialized.data {
         assert_eq!(data.tool_call_id, "call-comprehensive-123");
-        assert_eq!(data.tool_name, "advanced_data_processor");
+        assert_eq!(data.tool_call_name, "advanced_data_processor");
         assert_eq!(data.parent_message_id, Some("parent-msg-456".to_string()));
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_chunk_event_comprehensive() {
     // Test different types of tool call argument chunks
     let test_cases = vec![
@@ -155,12 +151,12 @@ fn test_tool_call_chunk_event_comprehensive() {
 
     for (test_name, chunk_content) in test_cases {
         let event = BaseEvent {
-            event_type: EventType::ToolCallChunk,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallChunk(ToolCallChunkEvent {
-                tool_call_id: format!("call-{}", test_name),
-                delta: chunk_content.to_string(),
+                tool_call_id: Some(format!("call-{}", test_name)),
+                delta: Some(chunk_content.to_string()),
+                ..Default::default()
             }),
         };
 
@@ -168,35 +164,19 @@ fn test_tool_call_chunk_event_comprehensive() {
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
         if let EventData::ToolCallChunk(data) = &deserialized.data {
-            assert_eq!(data.delta, chunk_content);
-            assert_eq!(data.tool_call_id, format!("call-{}", test_name));
+            assert_eq!(data.delta.as_deref(), Some(chunk_content));
+            assert_eq!(data.tool_call_id, Some(format!("call-{}", test_name)));
         }
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_end_event_comprehensive() {
-    // Test with complete tool call
-    let tool_call = ToolCall {
-        id: "call-end-complete".to_string(),
-        name: "calculate_statistics".to_string(),
-        arguments: Some(json!({
-            "dataset": "sales_2023",
-            "metrics": ["mean", "median", "std_dev"],
-            "filters": {
-                "region": "North America",
-                "quarter": "Q4"
-            }
-        })),
-    };
-
     let event = BaseEvent {
-        event_type: EventType::ToolCallEnd,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallEnd(ToolCallEndEvent {
             tool_call_id: "call-end-complete".to_string(),
-            tool_call: Some(tool_call),
         }),
     };
 
@@ -204,79 +184,66 @@ fn test_tool_call_end_event_comprehensive() {
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "TOOL_CALL_END");
-    assert_eq!(parsed["tool_call_id"], "call-end-complete");
-    assert!(parsed["tool_call"].is_object());
-    assert_eq!(parsed["tool_call"]["id"], "call-end-complete");
-    assert_eq!(parsed["tool_call"]["name"], "calculate_statistics");
-    assert_eq!(parsed["tool_call"]["arguments"]["dataset"], "sales_2023");
+    assert_eq!(parsed["toolCallId"], "call-end-complete");
 
-    // Test without tool call (minimal)
+    // Test without timestamp (minimal)
     let minimal_event = BaseEvent {
-        event_type: EventType::ToolCallEnd,
         timestamp: None,
         raw_event: None,
         data: EventData::ToolCallEnd(ToolCallEndEvent {
             tool_call_id: "call-minimal".to_string(),
-            tool_call: None,
         }),
     };
 
     let minimal_json = serde_json::to_string(&minimal_event).unwrap();
     let minimal_parsed: serde_json::Value = serde_json::from_str(&minimal_json).unwrap();
 
-    assert_eq!(minimal_parsed["tool_call_id"], "call-minimal");
-    assert!(minimal_parsed["tool_call"].is_null() || !minimal_parsed.as_object().unwrap().contains_key("tool_call"));
+    assert_eq!(minimal_parsed["toolCallId"], "call-minimal");
+    assert!(minimal_parsed["toolCall"].is_null() || !minimal_parsed.as_object().unwrap().contains_key("toolCall"));
 }
 
 // ===== TOOL CALL EVENT SEQUENCE TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_complete_tool_call_sequence() {
     // Test a complete tool call sequence matching TypeScript patterns
     let tool_call_id = "sequence-test-123";
 
     let start_event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: tool_call_id.to_string(),
-            tool_name: "database_query".to_string(),
+            tool_call_name: "database_query".to_string(),
             parent_message_id: Some("msg-parent".to_string()),
         }),
     };
 
     let chunk1_event = BaseEvent {
-        event_type: EventType::ToolCallChunk,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallChunk(ToolCallChunkEvent {
-            tool_call_id: tool_call_id.to_string(),
-            delta: r#"{"query": "SELECT * FROM"#.to_string(),
+            tool_call_id: Some(tool_call_id.to_string()),
+            delta: Some(r#"{"query": "SELECT * FROM"#.to_string()),
+            ..Default::default()
         }),
     };
 
     let chunk2_event = BaseEvent {
-        event_type: EventType::ToolCallChunk,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallChunk(ToolCallChunkEvent {
-            tool_call_id: tool_call_id.to_string(),
-            delta: r#" users WHERE age > 18"}"#.to_string(),
+            tool_call_id: Some(tool_call_id.to_string()),
+            delta: Some(r#" users WHERE age > 18"}"#.to_string()),
+            ..Default::default()
         }),
     };
 
     let end_event = BaseEvent {
-        event_type: EventType::ToolCallEnd,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallEnd(ToolCallEndEvent {
             tool_call_id: tool_call_id.to_string(),
-            tool_call: Some(ToolCall {
-                id: tool_call_id.to_string(),
-                name: "database_query".to_string(),
-                arguments: Some(json!({"query": "SELECT * FROM users WHERE age > 18"})),
-            }),
         }),
     };
 
@@ -287,7 +254,7 @@ fn test_complete_tool_call_sequence() {
         let json_str = serde_json::to_string(event).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
-        assert_eq!(parsed["tool_call_id"], tool_call_id);
+        assert_eq!(parsed["toolCallId"], tool_call_id);
 
         // Test deserialization
         let _deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
@@ -297,17 +264,17 @@ fn test_complete_tool_call_sequence() {
     let start_json = serde_json::to_string(&events[0]).unwrap();
     let start_parsed: serde_json::Value = serde_json::from_str(&start_json).unwrap();
     assert_eq!(start_parsed["type"], "TOOL_CALL_START");
-    assert_eq!(start_parsed["tool_name"], "database_query");
+    assert_eq!(start_parsed["toolCallName"], "database_query");
 
     let end_json = serde_json::to_string(&events[3]).unwrap();
     let end_parsed: serde_json::Value = serde_json::from_str(&end_json).unwrap();
     assert_eq!(end_parsed["type"], "TOOL_CALL_END");
-    assert_eq!(end_parsed["tool_call"]["name"], "database_query");
+    assert!(end_parsed.get("toolCall").is_none());
 }
 
 // ===== STATE EVENTS COMPREHENSIVE TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_snapshot_event_comprehensive() {
     // Test with complex nested state (matching TypeScript patterns)
     let mut state = HashMap::new();
@@ -362,7 +329,7 @@ fn test_state_snapshot_event_comprehensive() {
         "empty_string": "",
         "zero": 0,
         "negative_number": -123,
-        "float_number": 3.14159,
+        "float_number": 2.5,
         "empty_array": [],
         "empty_object": {},
         "bool_values": {"true": true, "false": false},
@@ -371,10 +338,9 @@ fn test_state_snapshot_event_comprehensive() {
     }));
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot: state }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
@@ -382,28 +348,28 @@ fn test_state_snapshot_event_comprehensive() {
 
     // Verify structure preservation
     assert_eq!(parsed["type"], "STATE_SNAPSHOT");
-    assert_eq!(parsed["state"]["user_profile"]["name"], "John Doe");
-    assert_eq!(parsed["state"]["user_profile"]["contact"]["address"]["coordinates"]["lat"], 37.7749);
-    assert_eq!(parsed["state"]["service_config"]["endpoints"][0]["name"], "api1");
-    assert_eq!(parsed["state"]["service_config"]["retry_policy"]["timeouts"][2], 4000);
+    assert_eq!(parsed["snapshot"]["user_profile"]["name"], "John Doe");
+    assert_eq!(parsed["snapshot"]["user_profile"]["contact"]["address"]["coordinates"]["lat"], 37.7749);
+    assert_eq!(parsed["snapshot"]["service_config"]["endpoints"][0]["name"], "api1");
+    assert_eq!(parsed["snapshot"]["service_config"]["retry_policy"]["timeouts"][2], 4000);
 
     // Verify edge cases
-    assert!(parsed["state"]["edge_cases"]["null_value"].is_null());
-    assert_eq!(parsed["state"]["edge_cases"]["empty_string"], "");
-    assert_eq!(parsed["state"]["edge_cases"]["zero"], 0);
-    assert_eq!(parsed["state"]["edge_cases"]["negative_number"], -123);
-    assert_eq!(parsed["state"]["edge_cases"]["float_number"], 3.14159);
+    assert!(parsed["snapshot"]["edge_cases"]["null_value"].is_null());
+    assert_eq!(parsed["snapshot"]["edge_cases"]["empty_string"], "");
+    assert_eq!(parsed["snapshot"]["edge_cases"]["zero"], 0);
+    assert_eq!(parsed["snapshot"]["edge_cases"]["negative_number"], -123);
+    assert_eq!(parsed["snapshot"]["edge_cases"]["float_number"], 2.5);
 
     // Test deserialization
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
     if let EventData::StateSnapshot(data) = &deserialized.data {
-        let state_json = serde_json::to_value(&data.state).unwrap();
+        let state_json = serde_json::to_value(&data.snapshot).unwrap();
         assert_eq!(state_json["user_profile"]["preferences"]["theme"], "dark");
         assert_eq!(state_json["edge_cases"]["unicode_text"], "Special chars: 你好 こんにちは 안녕하세요 👋 🌍");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_event_comprehensive() {
     // Test all JSON Patch operations (matching TypeScript patterns)
     let delta = json!([
@@ -416,7 +382,6 @@ fn test_state_delta_event_comprehensive() {
     ]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -449,106 +414,50 @@ fn test_state_delta_event_comprehensive() {
 
 // ===== MESSAGES SNAPSHOT COMPREHENSIVE TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_messages_snapshot_event_comprehensive() {
     // Create messages with various complexities
     let messages = vec![
         // System message
-        Message {
+        Message::System(SystemMessage {
             id: "sys-001".to_string(),
-            role: Role::System,
             content: "You are a helpful assistant with access to various tools.".to_string(),
             name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: Some({
-                let mut map = HashMap::new();
-                map.insert("system_version".to_string(), json!("v2.1"));
-                map.insert("capabilities".to_string(), json!(["search", "calculate", "analyze"]));
-                map
-            }),
-            created_at: Some(Utc::now()),
-        },
+        }),
 
-        // User message with metadata
-        Message {
+        // User message with a name
+        Message::User(UserMessage {
             id: "user-001".to_string(),
-            role: Role::User,
             content: "Can you analyze the sales data and provide insights?".to_string(),
             name: Some("Alice Johnson".to_string()),
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: Some({
-                let mut map = HashMap::new();
-                map.insert("client_info".to_string(), json!({
-                    "browser": "Chrome",
-                    "version": "120.0",
-                    "platform": "Windows",
-                    "session_id": "sess_abc123"
-                }));
-                map.insert("request_context".to_string(), json!({
-                    "urgency": "high",
-                    "department": "sales"
-                }));
-                map
-            }),
-            created_at: Some(Utc::now()),
-        },
+        }),
 
         // Assistant message with complex tool calls
-        Message {
+        Message::Assistant(AssistantMessage {
             id: "asst-001".to_string(),
-            role: Role::Assistant,
-            content: "I'll analyze the sales data for you. Let me fetch and process the information.".to_string(),
+            content: Some("I'll analyze the sales data for you. Let me fetch and process the information.".to_string()),
             name: None,
-            tool_call_id: None,
             tool_calls: Some(vec![
-                ToolCall {
-                    id: "call-data-fetch".to_string(),
-                    name: "fetch_sales_data".to_string(),
-                    arguments: Some(json!({
-                        "time_period": "2023-Q4",
-                        "regions": ["North America", "Europe", "Asia"],
-                        "metrics": ["revenue", "units_sold", "conversion_rate"],
-                        "filters": {
-                            "product_categories": ["electronics", "software"],
-                            "min_transaction_value": 100
-                        }
-                    })),
-                },
-                ToolCall {
-                    id: "call-analysis".to_string(),
-                    name: "analyze_trends".to_string(),
-                    arguments: Some(json!({
-                        "analysis_type": "comprehensive",
-                        "include_forecasting": true,
-                        "comparison_periods": ["2023-Q3", "2022-Q4"]
-                    })),
-                }
+                ToolCall::new("call-data-fetch", "fetch_sales_data", json!({
+                    "time_period": "2023-Q4",
+                    "regions": ["North America", "Europe", "Asia"],
+                    "metrics": ["revenue", "units_sold", "conversion_rate"],
+                    "filters": {
+                        "product_categories": ["electronics", "software"],
+                        "min_transaction_value": 100
+                    }
+                }).to_string()),
+                ToolCall::new("call-analysis", "analyze_trends", json!({
+                    "analysis_type": "comprehensive",
+                    "include_forecasting": true,
+                    "comparison_periods": ["2023-Q3", "2022-Q4"]
+                }).to_string())
             ]),
-            function_call: None,
-            metadata: Some({
-                let mut map = HashMap::new();
-                map.insert("model_info".to_string(), json!({
-                    "model": "gpt-4-turbo",
-                    "temperature": 0.7,
-                    "max_tokens": 2000
-                }));
-                map.insert("processing_stats".to_string(), json!({
-                    "reasoning_time": 1.2,
-                    "token_usage": 450
-                }));
-                map
-            }),
-            created_at: Some(Utc::now()),
-        },
+        }),
 
         // Tool message with results
-        Message {
+        Message::Tool(ToolMessage {
             id: "tool-001".to_string(),
-            role: Role::Tool,
             content: json!({
                 "sales_data": {
                     "total_revenue": 2_450_000,
@@ -566,42 +475,19 @@ fn test_messages_snapshot_event_comprehensive() {
                     "forecast_next_quarter": 2_800_000
                 }
             }).to_string(),
-            name: None,
-            tool_call_id: Some("call-data-fetch".to_string()),
-            tool_calls: None,
-            function_call: None,
-            metadata: Some({
-                let mut map = HashMap::new();
-                map.insert("execution_time".to_string(), json!(2.5));
-                map.insert("data_sources".to_string(), json!(["CRM", "Analytics DB", "External API"]));
-                map.insert("cache_status".to_string(), json!("miss"));
-                map
-            }),
-            created_at: Some(Utc::now()),
-        },
+            tool_call_id: "call-data-fetch".to_string(),
+            error: None,
+        }),
 
         // Developer message
-        Message {
+        Message::Developer(DeveloperMessage {
             id: "dev-001".to_string(),
-            role: Role::Developer,
             content: "System note: Analysis completed successfully. All data sources were accessible and current.".to_string(),
             name: Some("System Monitor".to_string()),
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: Some({
-                let mut map = HashMap::new();
-                map.insert("log_level".to_string(), json!("INFO"));
-                map.insert("component".to_string(), json!("data_pipeline"));
-                map.insert("timestamp_utc".to_string(), json!(Utc::now().to_rfc3339()));
-                map
-            }),
-            created_at: Some(Utc::now()),
-        },
+        }),
     ];
 
     let event = BaseEvent {
-        event_type: EventType::MessagesSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::MessagesSnapshot(MessagesSnapshotEvent { messages }),
@@ -618,48 +504,47 @@ fn test_messages_snapshot_event_comprehensive() {
     // Verify system message
     assert_eq!(messages_array[0]["role"], "system");
     assert_eq!(messages_array[0]["id"], "sys-001");
-    assert_eq!(messages_array[0]["metadata"]["system_version"], "v2.1");
+    assert!(messages_array[0].get("toolCalls").is_none());
 
     // Verify user message
     assert_eq!(messages_array[1]["role"], "user");
     assert_eq!(messages_array[1]["name"], "Alice Johnson");
-    assert_eq!(messages_array[1]["metadata"]["client_info"]["browser"], "Chrome");
 
     // Verify assistant message with tool calls
     assert_eq!(messages_array[2]["role"], "assistant");
-    let tool_calls = messages_array[2]["tool_calls"].as_array().unwrap();
+    let tool_calls = messages_array[2]["toolCalls"].as_array().unwrap();
     assert_eq!(tool_calls.len(), 2);
-    assert_eq!(tool_calls[0]["name"], "fetch_sales_data");
-    assert_eq!(tool_calls[0]["arguments"]["metrics"][0], "revenue");
-    assert_eq!(tool_calls[1]["name"], "analyze_trends");
+    assert_eq!(tool_calls[0]["type"], "function");
+    assert_eq!(tool_calls[0]["function"]["name"], "fetch_sales_data");
+    let arguments: serde_json::Value = serde_json::from_str(tool_calls[0]["function"]["arguments"].as_str().unwrap()).unwrap();
+    assert_eq!(arguments["metrics"][0], "revenue");
+    assert_eq!(tool_calls[1]["function"]["name"], "analyze_trends");
 
     // Verify tool message
     assert_eq!(messages_array[3]["role"], "tool");
-    assert_eq!(messages_array[3]["tool_call_id"], "call-data-fetch");
+    assert_eq!(messages_array[3]["toolCallId"], "call-data-fetch");
     let tool_content: serde_json::Value = serde_json::from_str(messages_array[3]["content"].as_str().unwrap()).unwrap();
     assert_eq!(tool_content["sales_data"]["total_revenue"], 2_450_000);
 
     // Verify developer message
     assert_eq!(messages_array[4]["role"], "developer");
     assert_eq!(messages_array[4]["name"], "System Monitor");
-    assert_eq!(messages_array[4]["metadata"]["log_level"], "INFO");
 
     // Test deserialization
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
     if let EventData::MessagesSnapshot(data) = &deserialized.data {
         assert_eq!(data.messages.len(), 5);
-        assert_eq!(data.messages[2].tool_calls.as_ref().unwrap().len(), 2);
-        assert_eq!(data.messages[3].tool_call_id, Some("call-data-fetch".to_string()));
+        assert_eq!(data.messages[2].tool_calls().unwrap().len(), 2);
+        assert_eq!(data.messages[3].tool_call_id(), Some("call-data-fetch"));
     }
 }
 
 // ===== ERROR AND RUN LIFECYCLE EVENTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_error_event_comprehensive() {
     // Test with detailed error information
     let event = BaseEvent {
-        event_type: EventType::Error,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({
             "stack_trace": ["function1", "function2", "function3"],
@@ -692,10 +577,10 @@ fn test_error_event_comprehensive() {
     assert_eq!(parsed["details"]["timeout_duration"], 30000);
     assert_eq!(parsed["details"]["retry_count"], 3);
     assert_eq!(parsed["details"]["affected_operations"][0], "fetch_sales_data");
-    assert_eq!(parsed["raw_event"]["stack_trace"][1], "function2");
+    assert_eq!(parsed["rawEvent"]["stack_trace"][1], "function2");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_lifecycle_events_comprehensive() {
     let thread_id = "thread-comprehensive-test";
     let run_id = "run-comprehensive-test";
@@ -706,8 +591,8 @@ fn test_run_lifecycle_events_comprehensive() {
     let start_parsed: serde_json::Value = serde_json::from_str(&start_json).unwrap();
 
     assert_eq!(start_parsed["type"], "RUN_STARTED");
-    assert_eq!(start_parsed["thread_id"], thread_id);
-    assert_eq!(start_parsed["run_id"], run_id);
+    assert_eq!(start_parsed["threadId"], thread_id);
+    assert_eq!(start_parsed["runId"], run_id);
 
     // Test run finished
     let finish_event = BaseEvent::run_finished(thread_id.to_string(), run_id.to_string());
@@ -715,13 +600,13 @@ fn test_run_lifecycle_events_comprehensive() {
     let finish_parsed: serde_json::Value = serde_json::from_str(&finish_json).unwrap();
 
     assert_eq!(finish_parsed["type"], "RUN_FINISHED");
-    assert_eq!(finish_parsed["thread_id"], thread_id);
-    assert_eq!(finish_parsed["run_id"], run_id);
+    assert_eq!(finish_parsed["threadId"], thread_id);
+    assert_eq!(finish_parsed["runId"], run_id);
 
     // Test deserialization
     let start_deserialized: BaseEvent = serde_json::from_str(&start_json).unwrap();
     let finish_deserialized: BaseEvent = serde_json::from_str(&finish_json).unwrap();
 
-    assert_eq!(start_deserialized.event_type, EventType::RunStarted);
-    assert_eq!(finish_deserialized.event_type, EventType::RunFinished);
+    assert_eq!(start_deserialized.event_type(), EventType::RunStarted);
+    assert_eq!(finish_deserialized.event_type(), EventType::RunFinished);
 }
\ No newline at end of file
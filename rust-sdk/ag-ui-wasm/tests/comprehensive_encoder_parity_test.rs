@@ -2,7 +2,7 @@
 //! This test suite ensures full parity with the TypeScript EventEncoder and Python EventEncoder
 
 use ag_ui_wasm::{
-    BaseEvent, EventType, EventData, Role, RawEvent, ToolCall,
+    BaseEvent, EventType, EventData, Role, RawEvent,
     TextMessageStartEvent, ToolCallStartEvent, ToolCallChunkEvent, ToolCallEndEvent,
 };
 use wasm_bindgen_test::*;
@@ -13,26 +13,25 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== BASIC ENCODER TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_basic_event_serialization() {
     // Test basic event serialization (matching Python test_encode_method)
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: json!({"test": "data"}) }),
+        data: EventData::Raw(RawEvent { event: json!({"test": "data"}), source: None }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "RAW");
-    assert!(parsed["timestamp"].is_string());
+    assert!(parsed["timestamp"].is_number());
     // raw_event should be excluded when None (matching Python null exclusion test)
     assert!(!parsed.as_object().unwrap().contains_key("rawEvent"));
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_format_encoding() {
     // Test SSE format encoding (matching TypeScript/Python SSE format tests)
     let event = BaseEvent::text_message_content(
@@ -52,22 +51,21 @@ fn test_sse_format_encoding() {
     let parsed: serde_json::Value = serde_json::from_str(json_part).unwrap();
 
     assert_eq!(parsed["type"], "TEXT_MESSAGE_CONTENT");
-    assert_eq!(parsed["message_id"], "msg_123");
+    assert_eq!(parsed["messageId"], "msg_123");
     assert_eq!(parsed["delta"], "Hello, world!");
 }
 
 // ===== FIELD NAMING TESTS (camelCase vs snake_case) =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_field_name_consistency() {
     // Test that field names are consistent (matching Python camelCase tests)
     let event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: "call_123".to_string(),
-            tool_name: "search_tool".to_string(),
+            tool_call_name: "search_tool".to_string(),
             parent_message_id: Some("msg_parent".to_string()),
         }),
     };
@@ -75,30 +73,26 @@ fn test_field_name_consistency() {
     let json_str = serde_json::to_string(&event).unwrap();
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
-    // Check that field names follow consistent naming convention
-    if let Some(data) = parsed["data"].as_object() {
-        // Should have either camelCase or snake_case, but be consistent
-        let has_camel_case = data.contains_key("toolCallId") || data.contains_key("toolName") || data.contains_key("parentMessageId");
-        let has_snake_case = data.contains_key("tool_call_id") || data.contains_key("tool_name") || data.contains_key("parent_message_id");
+    // Event data is flattened and must use the camelCase wire names
+    let data = parsed.as_object().unwrap();
+    assert!(data.contains_key("toolCallId"));
+    assert!(data.contains_key("toolCallName"));
+    assert!(data.contains_key("parentMessageId"));
 
-        // Should have one consistent style, not a mix
-        assert!(has_camel_case || has_snake_case);
-        if has_camel_case {
-            assert!(!has_snake_case, "Should not mix camelCase and snake_case");
-        }
-    }
+    assert!(!data.contains_key("tool_call_id"));
+    assert!(!data.contains_key("tool_call_name"));
+    assert!(!data.contains_key("parent_message_id"));
 }
 
 // ===== NULL VALUE EXCLUSION TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_null_value_exclusion() {
     // Test that None/null values are excluded (matching Python test_null_value_exclusion)
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None, // Should be excluded
-        data: EventData::Raw(RawEvent { event: json!({"test": "value"}) }),
+        data: EventData::Raw(RawEvent { event: json!({"test": "value"}), source: None }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
@@ -113,16 +107,15 @@ fn test_null_value_exclusion() {
     assert!(!parsed.as_object().unwrap().contains_key("raw_event"));
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_optional_fields_exclusion() {
     // Test exclusion of optional fields when None (matching Python pattern)
     let event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: "call_123".to_string(),
-            tool_name: "test_tool".to_string(),
+            tool_call_name: "test_tool".to_string(),
             parent_message_id: None, // Optional field set to None
         }),
     };
@@ -130,29 +123,28 @@ fn test_optional_fields_exclusion() {
     let json_str = serde_json::to_string(&event).unwrap();
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
-    if let Some(data) = parsed["data"].as_object() {
-        // Required fields should be present
-        assert!(data.contains_key("tool_call_id") || data.contains_key("toolCallId"));
-        assert!(data.contains_key("tool_name") || data.contains_key("toolName"));
+    let data = parsed.as_object().unwrap();
 
-        // Optional field with None should be excluded
-        assert!(!data.contains_key("parent_message_id"));
-        assert!(!data.contains_key("parentMessageId"));
-    }
+    // Required fields should be present
+    assert!(data.contains_key("toolCallId"));
+    assert!(data.contains_key("toolCallName"));
+
+    // Optional field with None should be excluded
+    assert!(!data.contains_key("parentMessageId"));
+    assert!(!data.contains_key("parent_message_id"));
 }
 
 // ===== DIFFERENT EVENT TYPES TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_encoding_different_event_types() {
     // Test encoding various event types (matching Python test_encode_with_different_event_types)
 
     // Test base event
     let base_event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: json!({"test": "base"}) }),
+        data: EventData::Raw(RawEvent { event: json!({"test": "base"}), source: None }),
     };
 
     let base_json = serde_json::to_string(&base_event).unwrap();
@@ -171,12 +163,11 @@ fn test_encoding_different_event_types() {
 
     // Test tool call event
     let tool_event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: "call_789".to_string(),
-            tool_name: "search".to_string(),
+            tool_call_name: "search".to_string(),
             parent_message_id: None,
         }),
     };
@@ -189,16 +180,15 @@ fn test_encoding_different_event_types() {
 
 // ===== ROUND TRIP SERIALIZATION TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_round_trip_serialization() {
     // Test round-trip serialization (matching Python test_round_trip_serialization)
     let original_event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: "call_abc123".to_string(),
-            tool_name: "search_tool".to_string(),
+            tool_call_name: "search_tool".to_string(),
             parent_message_id: Some("msg_parent_456".to_string()),
         }),
     };
@@ -210,13 +200,13 @@ fn test_round_trip_serialization() {
     let deserialized_event: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
     // Verify event type matches
-    assert_eq!(deserialized_event.event_type, original_event.event_type);
+    assert_eq!(deserialized_event.event_type(), original_event.event_type());
 
     // Verify data matches
     if let (EventData::ToolCallStart(orig), EventData::ToolCallStart(deser)) =
         (&original_event.data, &deserialized_event.data) {
         assert_eq!(orig.tool_call_id, deser.tool_call_id);
-        assert_eq!(orig.tool_name, deser.tool_name);
+        assert_eq!(orig.tool_call_name, deser.tool_call_name);
         assert_eq!(orig.parent_message_id, deser.parent_message_id);
     } else {
         panic!("Event data types don't match after round-trip");
@@ -225,7 +215,7 @@ fn test_round_trip_serialization() {
 
 // ===== COMPLEX DATA STRUCTURES TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_complex_nested_data_encoding() {
     // Test encoding complex nested structures (matching TypeScript complex objects)
     let complex_data = json!({
@@ -273,10 +263,9 @@ fn test_complex_nested_data_encoding() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: complex_data.clone() }),
+        data: EventData::Raw(RawEvent { event: complex_data.clone(), source: None }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
@@ -291,7 +280,7 @@ fn test_complex_nested_data_encoding() {
 
 // ===== SPECIAL VALUES TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_special_values_handling() {
     // Test handling of special values (matching TypeScript special values test)
     let special_data = json!({
@@ -299,7 +288,7 @@ fn test_special_values_handling() {
         "emptyString": "",
         "zero": 0,
         "negativeNumber": -123,
-        "floatNumber": 3.14159,
+        "floatNumber": 2.5,
         "emptyArray": [],
         "emptyObject": {},
         "boolValues": {"true": true, "false": false}
@@ -307,10 +296,9 @@ fn test_special_values_handling() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: special_data.clone() }),
+        data: EventData::Raw(RawEvent { event: special_data.clone(), source: None }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
@@ -321,7 +309,7 @@ fn test_special_values_handling() {
         assert_eq!(data.event["emptyString"], "");
         assert_eq!(data.event["zero"], 0);
         assert_eq!(data.event["negativeNumber"], -123);
-        assert_eq!(data.event["floatNumber"], 3.14159);
+        assert_eq!(data.event["floatNumber"], 2.5);
         assert_eq!(data.event["emptyArray"], json!([]));
         assert_eq!(data.event["emptyObject"], json!({}));
         assert_eq!(data.event["boolValues"]["true"], true);
@@ -331,7 +319,7 @@ fn test_special_values_handling() {
 
 // ===== UNICODE AND SPECIAL CHARACTERS TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_unicode_and_special_characters() {
     // Test Unicode and special character handling (matching Python/TypeScript patterns)
     let unicode_content = "Hello 你好 こんにちは 안녕하세요 👋 🌍 \\n\\t\"'/<>";
@@ -351,7 +339,7 @@ fn test_unicode_and_special_characters() {
 
 // ===== LARGE CONTENT TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_large_content_handling() {
     // Test large content handling (matching Python 10K pattern)
     let large_content = "A".repeat(10000);
@@ -372,7 +360,7 @@ fn test_large_content_handling() {
 
 // ===== MESSAGE ROLE ENCODING TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_message_role_encoding() {
     // Test all message roles encode correctly (matching text roles patterns)
     let roles = vec![
@@ -381,7 +369,6 @@ fn test_message_role_encoding() {
 
     for role in roles {
         let event = BaseEvent {
-            event_type: EventType::TextMessageStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -410,7 +397,7 @@ fn test_message_role_encoding() {
 
 // ===== COMPREHENSIVE EVENT FLOW TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_comprehensive_event_flow_encoding() {
     // Test a complete event flow with multiple event types
     let events = vec![
@@ -418,35 +405,28 @@ fn test_comprehensive_event_flow_encoding() {
         BaseEvent::text_message_start("msg_001".to_string(), Some(Role::Assistant)),
         BaseEvent::text_message_content("msg_001".to_string(), "Processing your request...".to_string()),
         BaseEvent {
-            event_type: EventType::ToolCallStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallStart(ToolCallStartEvent {
                 tool_call_id: "call_001".to_string(),
-                tool_name: "search".to_string(),
+                tool_call_name: "search".to_string(),
                 parent_message_id: Some("msg_001".to_string()),
             }),
         },
         BaseEvent {
-            event_type: EventType::ToolCallChunk,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallChunk(ToolCallChunkEvent {
-                tool_call_id: "call_001".to_string(),
-                delta: "partial result".to_string(),
+                tool_call_id: Some("call_001".to_string()),
+                delta: Some("partial result".to_string()),
+                ..Default::default()
             }),
         },
         BaseEvent {
-            event_type: EventType::ToolCallEnd,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallEnd(ToolCallEndEvent {
                 tool_call_id: "call_001".to_string(),
-                tool_call: Some(ToolCall {
-                    id: "call_001".to_string(),
-                    name: "search".to_string(),
-                    arguments: Some(json!({"query": "test"})),
-                }),
             }),
         },
         BaseEvent::text_message_content("msg_001".to_string(), "Complete!".to_string()),
@@ -458,7 +438,7 @@ fn test_comprehensive_event_flow_encoding() {
     for event in &events {
         let json_str = serde_json::to_string(event).unwrap();
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
     }
 
     // Test batch serialization
@@ -467,26 +447,25 @@ fn test_comprehensive_event_flow_encoding() {
 
     assert_eq!(events.len(), deserialized_events.len());
     for (original, deserialized) in events.iter().zip(deserialized_events.iter()) {
-        assert_eq!(original.event_type, deserialized.event_type);
+        assert_eq!(original.event_type(), deserialized.event_type());
     }
 }
 
 // ===== EMPTY AND MINIMAL DATA TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_empty_and_minimal_data() {
     // Test empty data structures
     let empty_data_event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: None,
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: json!({}) }),
+        data: EventData::Raw(RawEvent { event: json!({}), source: None }),
     };
 
     let json_str = serde_json::to_string(&empty_data_event).unwrap();
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
-    assert_eq!(deserialized.event_type, EventType::Raw);
+    assert_eq!(deserialized.event_type(), EventType::Raw);
     if let EventData::Raw(data) = &deserialized.data {
         assert_eq!(data.event, json!({}));
     }
@@ -496,7 +475,7 @@ fn test_empty_and_minimal_data() {
     let minimal_json = serde_json::to_string(&minimal_event).unwrap();
     let minimal_deserialized: BaseEvent = serde_json::from_str(&minimal_json).unwrap();
 
-    assert_eq!(minimal_deserialized.event_type, EventType::TextMessageEnd);
+    assert_eq!(minimal_deserialized.event_type(), EventType::TextMessageEnd);
     if let EventData::TextMessageEnd(data) = &minimal_deserialized.data {
         assert_eq!(data.message_id, "msg_minimal");
     }
@@ -1,7 +1,7 @@
 //! Additional comprehensive encoder tests to complement existing encoder tests
 
 use ag_ui_wasm::{
-    BaseEvent, SSEEncoder, Role, EventType, EventData,
+    BaseEvent, SSEEncoder, Role, EventData,
     TextMessageStartEvent, StateSnapshotEvent,
 };
 use wasm_bindgen_test::*;
@@ -13,7 +13,7 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== ADDITIONAL ENCODER EDGE CASE TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_encoder_with_complex_state_snapshot() {
     // Test encoding complex state snapshot similar to TypeScript/Python patterns
     let mut state = HashMap::new();
@@ -41,10 +41,9 @@ fn test_encoder_with_complex_state_snapshot() {
     }));
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot: state }),
     };
 
     let result = SSEEncoder::encode_event_string(&event);
@@ -61,18 +60,17 @@ fn test_encoder_with_complex_state_snapshot() {
     let json_part = encoded_str.trim_start_matches("data: ").trim_end_matches("\n\n");
     let parsed: serde_json::Value = serde_json::from_str(json_part).unwrap();
 
-    assert_eq!(parsed["state"]["session"]["user"]["id"], "user_123");
-    assert_eq!(parsed["state"]["session"]["user"]["preferences"]["theme"], "dark");
-    assert_eq!(parsed["state"]["session"]["stats"]["interactions"]["searches"], 7);
-    assert_eq!(parsed["state"]["active_tools"][1], "calculator");
-    assert_eq!(parsed["state"]["settings"]["timezone"], "UTC-5");
+    assert_eq!(parsed["snapshot"]["session"]["user"]["id"], "user_123");
+    assert_eq!(parsed["snapshot"]["session"]["user"]["preferences"]["theme"], "dark");
+    assert_eq!(parsed["snapshot"]["session"]["stats"]["interactions"]["searches"], 7);
+    assert_eq!(parsed["snapshot"]["active_tools"][1], "calculator");
+    assert_eq!(parsed["snapshot"]["settings"]["timezone"], "UTC-5");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_encoder_with_raw_event_metadata() {
     // Test encoding events with complex raw_event metadata
     let event = BaseEvent {
-        event_type: EventType::TextMessageStart,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({
             "original": "event data",
@@ -103,15 +101,17 @@ fn test_encoder_with_raw_event_metadata() {
     let json_part = encoded_str.trim_start_matches("data: ").trim_end_matches("\n\n");
     let parsed: serde_json::Value = serde_json::from_str(json_part).unwrap();
 
-    assert!(parsed["raw_event"].is_object());
-    assert_eq!(parsed["raw_event"]["original"], "event data");
-    assert_eq!(parsed["raw_event"]["source"], "test system");
-    assert_eq!(parsed["raw_event"]["metadata"]["version"], "1.0");
-    assert_eq!(parsed["raw_event"]["metadata"]["priority"], "high");
-    assert_eq!(parsed["raw_event"]["metadata"]["nested_data"]["key1"], "value1");
-    assert_eq!(parsed["raw_event"]["metadata"]["nested_data"]["key2"][1], 2);
+    assert!(parsed["rawEvent"].is_object());
+    assert_eq!(parsed["rawEvent"]["original"], "event data");
+    assert_eq!(parsed["rawEvent"]["source"], "test system");
+    assert_eq!(parsed["rawEvent"]["metadata"]["version"], "1.0");
+    assert_eq!(parsed["rawEvent"]["metadata"]["priority"], "high");
+    assert_eq!(parsed["rawEvent"]["metadata"]["nested_data"]["key1"], "value1");
+    assert_eq!(parsed["rawEvent"]["metadata"]["nested_data"]["key2"][1], 2);
 }
 
+// Timed with the JS clock
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen_test]
 fn test_encoder_performance_with_many_events() {
     // Create a large number of events to test performance
@@ -139,7 +139,7 @@ fn test_encoder_performance_with_many_events() {
     assert!(duration < 1000.0, "Encoding took too long: {}ms", duration);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_encoder_with_special_json_characters() {
     // Test encoding events with complex JSON structures that might cause issues
     let complex_data = json!({
@@ -162,10 +162,9 @@ fn test_encoder_with_special_json_characters() {
     state.insert("complex_data".to_string(), complex_data);
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot: state }),
     };
 
     let result = SSEEncoder::encode_event_string(&event);
@@ -183,17 +182,16 @@ fn test_encoder_with_special_json_characters() {
     let json_part = encoded_str.trim_start_matches("data: ").trim_end_matches("\n\n");
     let parsed: serde_json::Value = serde_json::from_str(json_part).unwrap();
 
-    assert_eq!(parsed["state"]["complex_data"]["nested"]["array"][3]["inner"], "value");
-    assert_eq!(parsed["state"]["complex_data"]["emoji_array"][0], "😀");
+    assert_eq!(parsed["snapshot"]["complex_data"]["nested"]["array"][3]["inner"], "value");
+    assert_eq!(parsed["snapshot"]["complex_data"]["emoji_array"][0], "😀");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_encoder_timestamp_serialization() {
     // Test that timestamps are properly serialized across different events
     let timestamp = Utc::now();
 
     let event_with_timestamp = BaseEvent {
-        event_type: EventType::TextMessageStart,
         timestamp: Some(timestamp),
         raw_event: None,
         data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -203,7 +201,6 @@ fn test_encoder_timestamp_serialization() {
     };
 
     let event_without_timestamp = BaseEvent {
-        event_type: EventType::TextMessageStart,
         timestamp: None,
         raw_event: None,
         data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -236,7 +233,7 @@ fn test_encoder_timestamp_serialization() {
     assert!(json2["timestamp"].is_null() || !json2.as_object().unwrap().contains_key("timestamp"));
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_encoder_error_handling() {
     // Test that encoder handles various edge cases gracefully
     let empty_message_event = BaseEvent::text_message_content("".to_string(), "".to_string());
@@ -245,18 +242,17 @@ fn test_encoder_error_handling() {
 
     let encoded = result.unwrap();
     assert!(encoded.contains("TEXT_MESSAGE_CONTENT"));
-    assert!(encoded.contains(r#""message_id":"""#));
+    assert!(encoded.contains(r#""messageId":"""#));
     assert!(encoded.contains(r#""delta":"""#));
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_encoder_role_serialization() {
     // Test that all roles serialize correctly in events
     let roles = vec![Role::Developer, Role::System, Role::Assistant, Role::User, Role::Tool];
 
     for role in roles {
         let event = BaseEvent {
-            event_type: EventType::TextMessageStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -1,127 +1,92 @@
 //! Comprehensive types testing suite matching Python and TypeScript SDK patterns
 
 use ag_ui_wasm::{
-    Message, Role, RunAgentInput, Tool, Context, State, ToolCall, ToolResult,
+    Message, DeveloperMessage, SystemMessage, AssistantMessage, UserMessage, ToolMessage, Role, RunAgentInput, Tool, Context, State, ToolCall, ToolResult,
 };
 use wasm_bindgen_test::*;
 use serde_json::json;
-use chrono::Utc;
 use std::collections::HashMap;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
 // Test Message creation and serialization (matching Python test_types.py pattern)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_message_creation() {
-    let msg = Message::new(Role::User, "Hello, world!".to_string());
-
-    assert_eq!(msg.role, Role::User);
-    assert_eq!(msg.content, "Hello, world!");
-    assert!(!msg.id.is_empty());
-    assert!(msg.tool_call_id.is_none());
-    assert!(msg.metadata.is_none());
-    assert!(msg.created_at.is_some());
+    let msg = Message::user("Hello, world!");
+
+    assert_eq!(msg.role(), Role::User);
+    assert_eq!(msg.content(), Some("Hello, world!"));
+    assert!(!msg.id().is_empty());
+    assert!(msg.tool_call_id().is_none());
+    assert!(msg.tool_calls().is_none());
 }
 
 // Test Message serialization with camelCase conversion (matching Python pattern)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_message_serialization() {
-    let msg = Message {
+    let msg = Message::Tool(ToolMessage {
         id: "msg_123".to_string(),
-        role: Role::Assistant,
         content: "Hello there!".to_string(),
-        name: None,
-        tool_call_id: Some("call_456".to_string()),
-        tool_calls: None,
-        function_call: None,
-        metadata: Some({
-            let mut map = HashMap::new();
-            map.insert("key".to_string(), json!("value"));
-            map
-        }),
-        created_at: Some(Utc::now()),
-    };
+        tool_call_id: "call_456".to_string(),
+        error: None,
+    });
 
     let serialized = serde_json::to_value(&msg).unwrap();
     assert_eq!(serialized["id"], "msg_123");
-    assert_eq!(serialized["role"], "assistant");
+    assert_eq!(serialized["role"], "tool");
     assert_eq!(serialized["content"], "Hello there!");
     // Check for camelCase conversion
-    if serialized.get("toolCallId").is_some() {
-        assert_eq!(serialized["toolCallId"], "call_456");
-    } else {
-        assert_eq!(serialized["tool_call_id"], "call_456");
-    }
-    assert!(serialized["metadata"].is_object());
-    assert!(serialized["created_at"].is_string());
+    assert_eq!(serialized["toolCallId"], "call_456");
+    assert!(serialized.get("tool_call_id").is_none());
 }
 
-// Test Message deserialization with both camelCase and snake_case (matching Python pattern)
-#[wasm_bindgen_test]
+// Test Message deserialization from camelCase JSON (matching Python pattern)
+#[wasm_bindgen_test(unsupported = test)]
 fn test_message_deserialization() {
     let json_data = json!({
         "id": "msg_789",
         "role": "user",
-        "content": "How are you?",
-        "tool_call_id": null,
-        "metadata": null,
-        "created_at": "2023-10-01T12:00:00Z"
+        "content": "How are you?"
     });
 
     let msg: Message = serde_json::from_value(json_data).unwrap();
-    assert_eq!(msg.id, "msg_789");
-    assert_eq!(msg.role, Role::User);
-    assert_eq!(msg.content, "How are you?");
-    assert!(msg.tool_call_id.is_none());
-    assert!(msg.metadata.is_none());
+    assert_eq!(msg.id(), "msg_789");
+    assert_eq!(msg.role(), Role::User);
+    assert_eq!(msg.content(), Some("How are you?"));
+    assert!(msg.tool_call_id().is_none());
 
     // Test camelCase deserialization
     let camel_case_json = json!({
         "id": "msg_camel",
-        "role": "assistant",
+        "role": "tool",
         "content": "Response content",
         "toolCallId": "call_123"
     });
 
-    let camel_msg_result = serde_json::from_value::<Message>(camel_case_json);
-    // Should work with camelCase if supported, otherwise skip
-    if camel_msg_result.is_ok() {
-        let camel_msg = camel_msg_result.unwrap();
-        assert_eq!(camel_msg.tool_call_id, Some("call_123".to_string()));
-    }
+    let camel_msg = serde_json::from_value::<Message>(camel_case_json).unwrap();
+    assert_eq!(camel_msg.tool_call_id(), Some("call_123"));
 }
 
 // Test different Role variants
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_role_variants() {
-    let roles = vec![
-        (Role::User, "user"),
-        (Role::Assistant, "assistant"),
-        (Role::System, "system"),
-        (Role::Tool, "tool"),
-        (Role::Developer, "developer"),
+    let messages = vec![
+        (Message::user("test"), "user"),
+        (Message::assistant("test"), "assistant"),
+        (Message::system("test"), "system"),
+        (Message::tool("test", "call_1"), "tool"),
+        (Message::developer("test"), "developer"),
     ];
     
-    for (role, expected_str) in roles {
-        let msg = Message {
-            id: "test".to_string(),
-            role,
-            content: "test".to_string(),
-            tool_call_id: None,
-            metadata: None,
-            name: None,
-            tool_calls: None,
-            function_call: None,
-            created_at: None,
-        };
-        
+    for (msg, expected_str) in messages {
         let serialized = serde_json::to_value(&msg).unwrap();
         assert_eq!(serialized["role"], expected_str);
+        assert_eq!(serde_json::to_value(msg.role()).unwrap(), expected_str);
     }
 }
 
 // Test Tool structure
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_structure() {
     let tool = Tool {
         name: "search_database".to_string(),
@@ -150,27 +115,20 @@ fn test_tool_structure() {
 }
 
 // Test Context structure
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_context_structure() {
     let context = Context {
-        user_id: Some("user_123".to_string()),
-        session_id: Some("session_456".to_string()),
-        metadata: Some({
-            let mut map = HashMap::new();
-            map.insert("ip_address".to_string(), json!("192.168.1.1"));
-            map.insert("user_agent".to_string(), json!("Mozilla/5.0..."));
-            map
-        }),
+        description: "User preferences".to_string(),
+        value: r#"{"theme":"dark"}"#.to_string(),
     };
     
     let serialized = serde_json::to_value(&context).unwrap();
-    assert_eq!(serialized["user_id"], "user_123");
-    assert_eq!(serialized["session_id"], "session_456");
-    assert_eq!(serialized["metadata"]["ip_address"], "192.168.1.1");
+    assert_eq!(serialized, json!({"description": "User preferences", "value": "{\"theme\":\"dark\"}"}));
+    assert_eq!(serde_json::from_value::<Context>(serialized).unwrap(), context);
 }
 
 // Test State (HashMap) handling
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_handling() {
     let mut state: State = HashMap::new();
     state.insert("conversation_phase".to_string(), json!("greeting"));
@@ -197,44 +155,33 @@ fn test_state_handling() {
 }
 
 // Test RunAgentInput creation
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_agent_input_creation() {
     let input = RunAgentInput::new("thread_123".to_string(), "run_456".to_string());
     
     assert_eq!(input.thread_id, "thread_123");
     assert_eq!(input.run_id, "run_456");
-    assert!(input.messages.is_none());
-    assert!(input.tools.is_none());
-    assert!(input.context.is_none());
-    assert!(input.state.is_none());
+    assert!(input.messages.is_empty());
+    assert!(input.tools.is_empty());
+    assert!(input.context.is_empty());
+    assert!(input.state.is_empty());
+    assert!(input.forwarded_props.is_empty());
 }
 
 // Test comprehensive RunAgentInput
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_comprehensive_run_agent_input() {
     let messages = vec![
-        Message {
+        Message::System(SystemMessage {
             id: "msg_1".to_string(),
-            role: Role::System,
             content: "You are a helpful assistant.".to_string(),
-            tool_call_id: None,
-            metadata: None,
             name: None,
-            tool_calls: None,
-            function_call: None,
-            created_at: Some(Utc::now()),
-        },
-        Message {
+        }),
+        Message::User(UserMessage {
             id: "msg_2".to_string(),
-            role: Role::User,
             content: "Hello AI!".to_string(),
-            tool_call_id: None,
-            metadata: None,
             name: None,
-            tool_calls: None,
-            function_call: None,
-            created_at: Some(Utc::now()),
-        },
+        }),
     ];
     
     let tools = vec![
@@ -250,11 +197,7 @@ fn test_comprehensive_run_agent_input() {
         }
     ];
     
-    let context = Context {
-        user_id: Some("user_123".to_string()),
-        session_id: Some("session_456".to_string()),
-        metadata: None,
-    };
+    let context = Context::new("User ID", "user_123");
     
     let mut state: State = HashMap::new();
     state.insert("status".to_string(), json!("active"));
@@ -262,50 +205,49 @@ fn test_comprehensive_run_agent_input() {
     let input = RunAgentInput {
         thread_id: "thread_complex".to_string(),
         run_id: "run_complex".to_string(),
-        messages: Some(messages),
-        tools: Some(tools),
-        context: Some(vec![context]),
-        state: Some(state),
-        forwarded_props: None,
+        messages,
+        tools,
+        context: vec![context],
+        state,
+        forwarded_props: HashMap::new(),
     };
     
     // Test serialization
     let serialized = serde_json::to_value(&input).unwrap();
-    assert_eq!(serialized["thread_id"], "thread_complex");
-    assert_eq!(serialized["run_id"], "run_complex");
+    assert_eq!(serialized["threadId"], "thread_complex");
+    assert_eq!(serialized["runId"], "run_complex");
     assert_eq!(serialized["messages"].as_array().unwrap().len(), 2);
     assert_eq!(serialized["tools"].as_array().unwrap().len(), 1);
-    assert_eq!(serialized["context"]["user_id"], "user_123");
+    assert_eq!(serialized["context"][0]["value"], "user_123");
     assert_eq!(serialized["state"]["status"], "active");
+    assert_eq!(serialized["forwardedProps"], json!({}));
     
     // Test deserialization
     let deserialized: RunAgentInput = serde_json::from_value(serialized).unwrap();
     assert_eq!(deserialized.thread_id, "thread_complex");
-    assert_eq!(deserialized.messages.unwrap().len(), 2);
-    assert_eq!(deserialized.tools.unwrap()[0].name, "search");
+    assert_eq!(deserialized.messages.len(), 2);
+    assert_eq!(deserialized.tools[0].name, "search");
 }
 
 // Test ToolCall structure
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_structure() {
-    let tool_call = ToolCall {
-        id: "call_123".to_string(),
-        name: "calculate".to_string(),
-        arguments: Some(json!({
-            "operation": "add",
-            "operands": [1, 2, 3]
-        })),
-    };
+    let tool_call = ToolCall::new("call_123", "calculate", json!({
+        "operation": "add",
+        "operands": [1, 2, 3]
+    }).to_string());
     
     let serialized = serde_json::to_value(&tool_call).unwrap();
     assert_eq!(serialized["id"], "call_123");
-    assert_eq!(serialized["name"], "calculate");
-    assert_eq!(serialized["arguments"]["operation"], "add");
-    assert_eq!(serialized["arguments"]["operands"][1], 2);
+    assert_eq!(serialized["type"], "function");
+    assert_eq!(serialized["function"]["name"], "calculate");
+    let arguments: serde_json::Value = serde_json::from_str(serialized["function"]["arguments"].as_str().unwrap()).unwrap();
+    assert_eq!(arguments["operation"], "add");
+    assert_eq!(arguments["operands"][1], 2);
 }
 
 // Test ToolResult structure
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_result_structure() {
     let tool_result = ToolResult {
         tool_call_id: "call_123".to_string(),
@@ -314,7 +256,7 @@ fn test_tool_result_structure() {
     };
     
     let serialized = serde_json::to_value(&tool_result).unwrap();
-    assert_eq!(serialized["tool_call_id"], "call_123");
+    assert_eq!(serialized["toolCallId"], "call_123");
     assert_eq!(serialized["result"]["sum"], 6);
     assert!(serialized["error"].is_null());
     
@@ -329,70 +271,47 @@ fn test_tool_result_structure() {
     assert_eq!(error_serialized["error"], "Division by zero");
 }
 
-// Test Message with complex metadata
-#[wasm_bindgen_test]
-fn test_message_with_complex_metadata() {
-    let mut metadata = HashMap::new();
-    metadata.insert("tool_calls".to_string(), json!([
-        {
-            "id": "call_1",
-            "name": "search",
-            "arguments": {"query": "weather"}
-        },
-        {
-            "id": "call_2", 
-            "name": "calculate",
-            "arguments": {"expression": "2+2"}
-        }
-    ]));
-    metadata.insert("confidence".to_string(), json!(0.95));
-    metadata.insert("processing_time".to_string(), json!(1.5));
-    
-    let msg = Message {
-        id: "msg_complex".to_string(),
-        role: Role::Assistant,
-        content: "I'll help you with that calculation and weather check.".to_string(),
-        tool_call_id: None,
-        metadata: Some(metadata),
-            name: None,
-            tool_calls: None,
-            function_call: None,
-        created_at: Some(Utc::now()),
-    };
+// Test assistant message with multiple tool calls
+#[wasm_bindgen_test(unsupported = test)]
+fn test_message_with_multiple_tool_calls() {
+    let msg: Message = AssistantMessage::new()
+        .with_id("msg_complex")
+        .with_content("I'll help you with that calculation and weather check.")
+        .with_tool_calls(vec![
+            ToolCall::new("call_1", "search", json!({"query": "weather"}).to_string()),
+            ToolCall::new("call_2", "calculate", json!({"expression": "2+2"}).to_string()),
+        ])
+        .into();
     
     let serialized = serde_json::to_value(&msg).unwrap();
-    assert_eq!(serialized["metadata"]["tool_calls"].as_array().unwrap().len(), 2);
-    assert_eq!(serialized["metadata"]["confidence"], 0.95);
-    assert_eq!(serialized["metadata"]["processing_time"], 1.5);
+    assert_eq!(serialized["toolCalls"].as_array().unwrap().len(), 2);
+    assert_eq!(serialized["toolCalls"][1]["function"]["arguments"], r#"{"expression":"2+2"}"#);
+    assert_eq!(serde_json::from_value::<Message>(serialized).unwrap(), msg);
 }
 
 // Test round-trip serialization
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_round_trip_serialization() {
     let original = RunAgentInput {
         thread_id: "thread_round_trip".to_string(),
         run_id: "run_round_trip".to_string(),
-        messages: Some(vec![
-            Message::new(Role::User, "Test message".to_string())
-        ]),
-        tools: Some(vec![
+        messages: vec![
+            Message::user("Test message")
+        ],
+        tools: vec![
             Tool {
                 name: "test_tool".to_string(),
                 description: "A test tool".to_string(),
                 parameters: Some(json!({"type": "object"})),
             }
-        ]),
-        context: Some(vec![Context {
-            user_id: Some("user_test".to_string()),
-            session_id: None,
-            metadata: None,
-        }]),
-        state: Some({
+        ],
+        context: vec![Context::new("User ID", "user_test")],
+        state: {
             let mut map = HashMap::new();
             map.insert("test_key".to_string(), json!("test_value"));
             map
-        }),
-        forwarded_props: None,
+        },
+        forwarded_props: HashMap::new(),
     };
     
     // Serialize
@@ -404,15 +323,15 @@ fn test_round_trip_serialization() {
     // Verify
     assert_eq!(deserialized.thread_id, original.thread_id);
     assert_eq!(deserialized.run_id, original.run_id);
-    assert_eq!(deserialized.messages.as_ref().unwrap().len(), 1);
-    assert_eq!(deserialized.tools.as_ref().unwrap().len(), 1);
-    assert_eq!(deserialized.tools.as_ref().unwrap()[0].name, "test_tool");
-    assert_eq!(deserialized.context.as_ref().unwrap()[0].user_id, Some("user_test".to_string()));
-    assert_eq!(deserialized.state.as_ref().unwrap()["test_key"], json!("test_value"));
+    assert_eq!(deserialized.messages.len(), 1);
+    assert_eq!(deserialized.tools.len(), 1);
+    assert_eq!(deserialized.tools[0].name, "test_tool");
+    assert_eq!(deserialized.context, original.context);
+    assert_eq!(deserialized.state["test_key"], json!("test_value"));
 }
 
 // Test validation errors (matching Python pattern)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_validation_errors() {
     // Test invalid role deserialization
     let invalid_role_json = json!({
@@ -432,14 +351,23 @@ fn test_validation_errors() {
     });
 
     let missing_result = serde_json::from_value::<Message>(missing_content_json);
-    // Should fail if content is required, otherwise should have default
-    // This depends on the actual implementation
+    assert!(missing_result.is_err());
+
+    // Tool messages must reference the call they answer
+    let missing_tool_call_id_json = json!({
+        "id": "msg_789",
+        "role": "tool",
+        "content": "Result"
+    });
+
+    let missing_tool_call_id = serde_json::from_value::<Message>(missing_tool_call_id_json);
+    assert!(missing_tool_call_id.is_err());
 }
 
 // Test camelCase and snake_case field handling (Python/TypeScript compatibility)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_field_name_compatibility() {
-    // Test that we can deserialize from both camelCase and snake_case
+    // camelCase is the wire format; snake_case keys are not protocol fields
     let camel_case_json = json!({
         "id": "msg_camel",
         "role": "tool",
@@ -456,60 +384,40 @@ fn test_field_name_compatibility() {
         "created_at": "2023-10-01T12:00:00Z"
     });
 
-    // Both should deserialize successfully
-    let camel_result = serde_json::from_value::<Message>(camel_case_json);
-    let snake_result = serde_json::from_value::<Message>(snake_case_json);
-
-    // At least one format should work (depends on implementation)
-    if camel_result.is_ok() {
-        let msg = camel_result.unwrap();
-        assert_eq!(msg.tool_call_id, Some("call_123".to_string()));
-    }
+    let camel_msg = serde_json::from_value::<Message>(camel_case_json).unwrap();
+    assert_eq!(camel_msg.tool_call_id(), Some("call_123"));
 
-    if snake_result.is_ok() {
-        let msg = snake_result.unwrap();
-        assert_eq!(msg.tool_call_id, Some("call_456".to_string()));
-    }
+    // Without `toolCallId` a snake_case tool message is not a valid message
+    assert!(serde_json::from_value::<Message>(snake_case_json).is_err());
 }
 
 // Test message name field handling (matching Python/TypeScript patterns)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_message_name_field() {
     // Test messages with name field
-    let user_with_name = Message {
+    let user_with_name = Message::User(UserMessage {
         id: "user_named".to_string(),
-        role: Role::User,
         content: "Hello".to_string(),
         name: Some("John Doe".to_string()),
-        tool_call_id: None,
-        tool_calls: None,
-        function_call: None,
-        metadata: None,
-        created_at: Some(Utc::now()),
-    };
+    });
 
     let serialized = serde_json::to_value(&user_with_name).unwrap();
     assert_eq!(serialized["name"], "John Doe");
 
     // Test assistant with name
-    let assistant_with_name = Message {
+    let assistant_with_name = Message::Assistant(AssistantMessage {
         id: "asst_named".to_string(),
-        role: Role::Assistant,
-        content: "Hello!".to_string(),
+        content: Some("Hello!".to_string()),
         name: Some("AI Assistant".to_string()),
-        tool_call_id: None,
         tool_calls: None,
-        function_call: None,
-        metadata: None,
-        created_at: Some(Utc::now()),
-    };
+    });
 
     let asst_serialized = serde_json::to_value(&assistant_with_name).unwrap();
     assert_eq!(asst_serialized["name"], "AI Assistant");
 }
 
 // Test complex forwarded props (matching Python patterns)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_forwarded_props_variations() {
     let mut forwarded_props = HashMap::new();
     forwarded_props.insert("api_version".to_string(), json!("v2.0"));
@@ -527,35 +435,35 @@ fn test_forwarded_props_variations() {
     let input = RunAgentInput {
         thread_id: "thread_forwarded".to_string(),
         run_id: "run_forwarded".to_string(),
-        messages: None,
-        tools: None,
-        context: None,
-        state: None,
-        forwarded_props: Some(forwarded_props),
+        messages: Vec::new(),
+        tools: Vec::new(),
+        context: Vec::new(),
+        state: HashMap::new(),
+        forwarded_props,
     };
 
     let serialized = serde_json::to_value(&input).unwrap();
-    assert_eq!(serialized["forwarded_props"]["api_version"], "v2.0");
-    assert_eq!(serialized["forwarded_props"]["client_info"]["browser"], "Chrome");
-    assert_eq!(serialized["forwarded_props"]["session_config"]["timeout"], 30000);
+    assert_eq!(serialized["forwardedProps"]["api_version"], "v2.0");
+    assert_eq!(serialized["forwardedProps"]["client_info"]["browser"], "Chrome");
+    assert_eq!(serialized["forwardedProps"]["session_config"]["timeout"], 30000);
 }
 
 // Test content edge cases (matching Python pattern)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_content_edge_cases() {
     // Empty content message
-    let empty_msg = Message::new(Role::User, "".to_string());
-    assert_eq!(empty_msg.content, "");
+    let empty_msg = Message::user("");
+    assert_eq!(empty_msg.content(), Some(""));
 
     // Large content (matching Python 10K pattern)
     let large_content = "A".repeat(10000);
-    let large_msg = Message::new(Role::User, large_content.clone());
-    assert_eq!(large_msg.content.len(), 10000);
+    let large_msg = Message::user(large_content.clone());
+    assert_eq!(large_msg.content().unwrap().len(), 10000);
 
     // Unicode and special characters (matching Python pattern)
     let unicode_content = "Special chars: 你好 こんにちは 안녕하세요 👋 🌍 \n\t\"'\\/<>{}[]";
-    let unicode_msg = Message::new(Role::User, unicode_content.to_string());
-    assert_eq!(unicode_msg.content, unicode_content);
+    let unicode_msg = Message::user(unicode_content);
+    assert_eq!(unicode_msg.content(), Some(unicode_content));
 
     // Tool with no parameters
     let simple_tool = Tool {
@@ -568,120 +476,71 @@ fn test_content_edge_cases() {
 }
 
 // Test multiple tool calls (matching Python pattern)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_multiple_tool_calls() {
     let tool_calls = vec![
-        ToolCall {
-            id: "call_1".to_string(),
-            name: "get_weather".to_string(),
-            arguments: Some(json!({"location": "New York"})),
-        },
-        ToolCall {
-            id: "call_2".to_string(),
-            name: "search_database".to_string(),
-            arguments: Some(json!({"query": "recent sales"})),
-        },
-        ToolCall {
-            id: "call_3".to_string(),
-            name: "calculate".to_string(),
-            arguments: Some(json!({"operation": "sum", "values": [1, 2, 3, 4, 5]})),
-        },
+        ToolCall::new("call_1", "get_weather", json!({"location": "New York"}).to_string()),
+        ToolCall::new("call_2", "search_database", json!({"query": "recent sales"}).to_string()),
+        ToolCall::new("call_3", "calculate", json!({"operation": "sum", "values": [1, 2, 3, 4, 5]}).to_string()),
     ];
 
-    let msg = Message {
+    let msg = Message::Assistant(AssistantMessage {
         id: "msg_multi_tools".to_string(),
-        role: Role::Assistant,
-        content: "I'll perform multiple operations".to_string(),
-        tool_calls: Some(tool_calls),
+        content: Some("I'll perform multiple operations".to_string()),
         name: None,
-        tool_call_id: None,
-        function_call: None,
-        metadata: None,
-        created_at: Some(Utc::now()),
-    };
+        tool_calls: Some(tool_calls),
+    });
 
     let serialized = serde_json::to_value(&msg).unwrap();
-    if let Some(tool_calls_array) = serialized.get("tool_calls").or_else(|| serialized.get("toolCalls")) {
+    if let Some(tool_calls_array) = serialized.get("toolCalls") {
         assert_eq!(tool_calls_array.as_array().unwrap().len(), 3);
         assert_eq!(tool_calls_array[0]["id"], "call_1");
-        assert_eq!(tool_calls_array[1]["name"], "search_database");
-        assert_eq!(tool_calls_array[2]["arguments"]["operation"], "sum");
+        assert_eq!(tool_calls_array[1]["function"]["name"], "search_database");
+        assert_eq!(tool_calls_array[2]["function"]["arguments"], r#"{"operation":"sum","values":[1,2,3,4,5]}"#);
     }
 }
 
 // Test RunAgentInput with diverse message types (matching Python pattern)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_agent_input_diverse_messages() {
     let messages = vec![
-        Message {
+        Message::System(SystemMessage {
             id: "sys_001".to_string(),
-            role: Role::System,
             content: "You are a helpful assistant.".to_string(),
             name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
-        Message {
+        }),
+        Message::User(UserMessage {
             id: "user_001".to_string(),
-            role: Role::User,
             content: "Can you help me analyze this data?".to_string(),
             name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
-        Message {
+        }),
+        Message::Developer(DeveloperMessage {
             id: "dev_001".to_string(),
-            role: Role::Developer,
             content: "The assistant should provide a detailed analysis.".to_string(),
             name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
-        Message {
+        }),
+        Message::Assistant(AssistantMessage {
             id: "asst_001".to_string(),
-            role: Role::Assistant,
-            content: "I'll analyze the data for you.".to_string(),
-            tool_calls: Some(vec![ToolCall {
-                id: "call_001".to_string(),
-                name: "analyze_data".to_string(),
-                arguments: Some(json!({
-                    "dataset": "sales_2023",
-                    "metrics": ["mean", "median"]
-                })),
-            }]),
+            content: Some("I'll analyze the data for you.".to_string()),
             name: None,
-            tool_call_id: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
-        Message {
+            tool_calls: Some(vec![ToolCall::new("call_001", "analyze_data", json!({
+                "dataset": "sales_2023",
+                "metrics": ["mean", "median"]
+            }).to_string())]),
+        }),
+        Message::Tool(ToolMessage {
             id: "tool_001".to_string(),
-            role: Role::Tool,
             content: "{\"mean\": 42.5, \"median\": 38.0}".to_string(),
-            tool_call_id: Some("call_001".to_string()),
-            name: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
+            tool_call_id: "call_001".to_string(),
+            error: None,
+        }),
     ];
 
     let input = RunAgentInput {
         thread_id: "thread_12345".to_string(),
         run_id: "run_67890".to_string(),
-        messages: Some(messages),
-        tools: Some(vec![
+        messages,
+        tools: vec![
             Tool {
                 name: "analyze_data".to_string(),
                 description: "Analyze a dataset and return statistics".to_string(),
@@ -694,37 +553,29 @@ fn test_run_agent_input_diverse_messages() {
                     "required": ["dataset"]
                 })),
             }
-        ]),
-        context: Some(vec![
-            Context {
-                user_id: Some("user_123".to_string()),
-                session_id: Some("session_456".to_string()),
-                metadata: Some({
-                    let mut map = HashMap::new();
-                    map.insert("theme".to_string(), json!("dark"));
-                    map.insert("language".to_string(), json!("English"));
-                    map
-                }),
-            }
-        ]),
-        state: Some({
+        ],
+        context: vec![
+            Context::new("Theme", "dark"),
+            Context::new("Language", "English"),
+        ],
+        state: {
             let mut map = HashMap::new();
             map.insert("conversation_state".to_string(), json!("active"));
             map.insert("custom_data".to_string(), json!({"key": "value"}));
             map
-        }),
-        forwarded_props: Some({
+        },
+        forwarded_props: {
             let mut map = HashMap::new();
             map.insert("api_version".to_string(), json!("v1"));
             map.insert("custom_settings".to_string(), json!({"max_tokens": 500}));
             map
-        }),
+        },
     };
 
     // Test serialization
     let serialized = serde_json::to_value(&input).unwrap();
-    assert_eq!(serialized["thread_id"], "thread_12345");
-    assert_eq!(serialized["run_id"], "run_67890");
+    assert_eq!(serialized["threadId"], "thread_12345");
+    assert_eq!(serialized["runId"], "run_67890");
     assert_eq!(serialized["messages"].as_array().unwrap().len(), 5);
 
     // Verify message types and content
@@ -736,11 +587,11 @@ fn test_run_agent_input_diverse_messages() {
     assert_eq!(messages_array[4]["role"], "tool");
 
     // Verify tool call in assistant message
-    if let Some(tool_calls) = messages_array[3].get("tool_calls").or_else(|| messages_array[3].get("toolCalls")) {
+    if let Some(tool_calls) = messages_array[3].get("toolCalls") {
         assert_eq!(tool_calls.as_array().unwrap().len(), 1);
-        assert_eq!(tool_calls[0]["name"], "analyze_data");
+        assert_eq!(tool_calls[0]["function"]["name"], "analyze_data");
     }
 
     // Verify tool message has tool_call_id
-    assert!(messages_array[4].get("tool_call_id").is_some() || messages_array[4].get("toolCallId").is_some());
+    assert!(messages_array[4].get("toolCallId").is_some());
 }
\ No newline at end of file
@@ -0,0 +1,82 @@
+//! Tests for errors crossing into and out of JavaScript
+
+// These tests run under wasm-bindgen-test only
+#![cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+
+use ag_ui_wasm::{AgUiError, BaseEvent, EventVerifier};
+use js_sys::{Function, Reflect};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn js(source: &str) -> JsValue {
+    Function::new_no_args(&format!("return {}", source)).call0(&JsValue::NULL).unwrap()
+}
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn test_errors_become_js_errors_with_a_code() {
+    let error = JsValue::from(AgUiError::HttpError {
+        status: 503,
+        status_text: "Service Unavailable".to_string(),
+        body: "try later".to_string(),
+    });
+
+    assert!(error.is_instance_of::<js_sys::Error>());
+    assert_eq!(get(&error, "name"), "AgUiError");
+    assert_eq!(get(&error, "message"), "HTTP 503 Service Unavailable: try later");
+    assert_eq!(get(&error, "code"), "HTTP_ERROR");
+    assert_eq!(get(&error, "status"), 503);
+    assert_eq!(get(&error, "body"), "try later");
+    assert!(get(&error, "cause").is_undefined());
+
+    let error = JsValue::from(AgUiError::decoding("bad frame").at_offset(12));
+    assert_eq!(get(&error, "code"), "DECODING_ERROR");
+    assert_eq!(get(&error, "offset"), 12);
+}
+
+#[wasm_bindgen_test]
+fn test_protocol_violations_carry_the_event() {
+    let event = BaseEvent::text_message_content("msg-1".to_string(), "Hi".to_string());
+    let error = EventVerifier::new().verify(&event).unwrap_err();
+
+    let error = JsValue::from(error);
+    assert_eq!(get(&error, "code"), "PROTOCOL_VIOLATION");
+    assert_eq!(get(&get(&error, "event"), "type"), "TEXT_MESSAGE_CONTENT");
+    assert_eq!(get(&get(&error, "event"), "messageId"), "msg-1");
+}
+
+#[wasm_bindgen_test]
+fn test_js_errors_are_kept_as_the_cause() {
+    let error = AgUiError::from(js("new TypeError('Failed to fetch')"));
+    assert!(matches!(&error, AgUiError::JsError { name, message, .. } if name == "TypeError" && message == "Failed to fetch"));
+
+    let error = JsValue::from(error);
+    assert_eq!(get(&error, "code"), "JS_ERROR");
+    let cause = get(&error, "cause");
+    assert!(cause.is_instance_of::<js_sys::Error>());
+    assert_eq!(cause.unchecked_ref::<js_sys::Error>().name(), "TypeError");
+
+    // The thrown object itself, with its class and stack
+    let thrown = js("(() => { class QuotaError extends Error {}; const e = new QuotaError('over quota'); e.name = 'QuotaError'; return e; })()");
+    let cause = get(&JsValue::from(AgUiError::from(thrown.clone())), "cause");
+    assert!(js_sys::Object::is(&cause, &thrown));
+    assert_eq!(get(&cause, "stack"), get(&thrown, "stack"));
+
+    // An error that never was a JS value gets a new `Error` as its cause
+    let cause = get(&JsValue::from(AgUiError::js_error("TypeError", "fetch failed")), "cause");
+    assert_eq!(cause.unchecked_ref::<js_sys::Error>().message(), "fetch failed");
+}
+
+#[wasm_bindgen_test]
+fn test_dom_exceptions_map_onto_the_taxonomy() {
+    assert!(matches!(AgUiError::from(js("new DOMException('stopped', 'AbortError')")), AgUiError::Aborted));
+    let error = AgUiError::from(js("new DOMException('too slow', 'TimeoutError')"));
+    assert!(matches!(error, AgUiError::Timeout(message) if message == "too slow"));
+    assert!(matches!(AgUiError::from(js("'plain string'")), AgUiError::WasmBindgenError(message) if message == "plain string"));
+}
@@ -0,0 +1,145 @@
+//! Tests for the error taxonomy: stable codes, offsets and `RUN_ERROR` mapping
+
+use ag_ui_wasm::{AgUiError, BaseEvent, EventData, EventType, ProtocolViolation};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn run_error(event: &BaseEvent) -> (String, Option<String>) {
+    let EventData::RunError(data) = &event.data else { panic!("expected RUN_ERROR, got {:?}", event.event_type()) };
+    (data.message.clone(), data.code.clone())
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_every_kind_of_error_has_a_stable_code() {
+    let http = AgUiError::HttpError { status: 502, status_text: "Bad Gateway".to_string(), body: String::new() };
+    let violation = AgUiError::protocol(
+        ProtocolViolation::RunAlreadyStarted,
+        &BaseEvent::run_started("thread-1".to_string(), "run-2".to_string()),
+    );
+    let json = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+
+    assert_eq!(http.code(), "HTTP_ERROR");
+    assert_eq!(violation.code(), "PROTOCOL_VIOLATION");
+    assert_eq!(AgUiError::decoding("bad frame").code(), "DECODING_ERROR");
+    assert_eq!(AgUiError::Timeout("no response".to_string()).code(), "TIMEOUT");
+    assert_eq!(AgUiError::Aborted.code(), "ABORTED");
+    assert_eq!(AgUiError::ConnectionError("refused".to_string()).code(), "CONNECTION_ERROR");
+    assert_eq!(AgUiError::ResumeFailed("gone".to_string()).code(), "RESUME_FAILED");
+    assert_eq!(AgUiError::from(json).code(), "JSON_ERROR");
+    let missing = AgUiError::ChunkMissingField { event_type: EventType::TextMessageChunk, field: "messageId" };
+    assert_eq!(missing.code(), "PROTOCOL_VIOLATION");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_http_errors_show_the_response_body() {
+    let error = AgUiError::HttpError {
+        status: 429,
+        status_text: "Too Many Requests".to_string(),
+        body: "slow down".to_string(),
+    };
+    assert_eq!(error.to_string(), "HTTP 429 Too Many Requests: slow down");
+
+    let error = AgUiError::HttpError { status: 404, status_text: "Not Found".to_string(), body: String::new() };
+    assert_eq!(error.to_string(), "HTTP 404 Not Found");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_decoding_errors_take_an_offset() {
+    let error = AgUiError::decoding("bad frame").at_offset(42);
+    assert!(matches!(error, AgUiError::DecodingError { offset: Some(42), .. }));
+    assert_eq!(error.to_string(), "Decoding error at byte 42: bad frame");
+
+    // Malformed JSON read from a stream is a decoding error there
+    let json = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    assert!(matches!(AgUiError::from(json).at_offset(7), AgUiError::DecodingError { offset: Some(7), .. }));
+
+    assert!(matches!(AgUiError::Aborted.at_offset(7), AgUiError::Aborted));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_errors_map_onto_the_taxonomy() {
+    let event = BaseEvent::run_error("Run was aborted".to_string(), Some("ABORTED".to_string()));
+    assert!(matches!(AgUiError::from_event(&event), Some(AgUiError::Aborted)));
+
+    let event = BaseEvent::run_error("Model call timed out".to_string(), Some("TIMEOUT".to_string()));
+    assert!(matches!(AgUiError::from_event(&event), Some(AgUiError::Timeout(message)) if message == "Model call timed out"));
+
+    let event = BaseEvent::run_error("Rate limit exceeded".to_string(), Some("rate_limit".to_string()));
+    let error = AgUiError::from_event(&event).unwrap();
+    assert!(matches!(&error, AgUiError::RunError { code: Some(code), .. } if code == "rate_limit"));
+    assert_eq!(error.to_string(), "Run failed: Rate limit exceeded");
+
+    assert!(AgUiError::from_event(&BaseEvent::run_finished("thread-1".to_string(), "run-1".to_string())).is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_errors_become_run_errors_with_their_code() {
+    // Without the prefix its variant adds, which the client adds back
+    let (message, code) = run_error(&AgUiError::Timeout("no response".to_string()).to_run_error());
+    assert_eq!(message, "no response");
+    assert_eq!(code.as_deref(), Some("TIMEOUT"));
+
+    let (message, code) = run_error(&AgUiError::Aborted.to_run_error());
+    assert_eq!(message, "Run was aborted");
+    assert_eq!(code.as_deref(), Some("ABORTED"));
+
+    // A run error is passed on as it arrived
+    let error = AgUiError::from_run_error("Rate limit exceeded", Some("rate_limit"));
+    assert_eq!(run_error(&error.to_run_error()), ("Rate limit exceeded".to_string(), Some("rate_limit".to_string())));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_every_error_survives_a_run_error_round_trip() {
+    let json = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    let errors = [
+        AgUiError::ConnectionError("refused".to_string()),
+        AgUiError::HttpError { status: 503, status_text: "Service Unavailable".to_string(), body: "try later".to_string() },
+        AgUiError::HttpError { status: 404, status_text: "Not Found".to_string(), body: String::new() },
+        AgUiError::StreamError("reset".to_string()),
+        AgUiError::Timeout("no response".to_string()),
+        AgUiError::Aborted,
+        AgUiError::ResumeFailed("gone".to_string()),
+        AgUiError::RunError { message: "Rate limited".to_string(), code: Some("rate_limit".to_string()) },
+        AgUiError::RunError { message: "Failed".to_string(), code: None },
+        AgUiError::from(json),
+        AgUiError::AgentError("model unavailable".to_string()),
+        AgUiError::IoError("disk full".to_string()),
+        AgUiError::EncodingError("no proto message".to_string()),
+        AgUiError::decoding("bad frame"),
+        AgUiError::InvalidMessage("no role".to_string()),
+        AgUiError::PatchError("no such path".to_string()),
+        AgUiError::ChunkNotExpanded { event_type: EventType::TextMessageChunk },
+        AgUiError::ChunkMissingField { event_type: EventType::ToolCallChunk, field: "toolCallName" },
+        AgUiError::protocol(
+            ProtocolViolation::RunAlreadyStarted,
+            &BaseEvent::run_started("thread-1".to_string(), "run-2".to_string()),
+        ),
+        AgUiError::js_error("TypeError", "fetch failed"),
+        AgUiError::WasmBindgenError("not a function".to_string()),
+    ];
+
+    for error in errors {
+        // Equal text means the prefix was added back exactly once
+        let received = AgUiError::from_event(&error.to_run_error()).unwrap();
+        assert_eq!(received.code(), error.code(), "{}", error);
+        assert_eq!(received.to_string(), error.to_string());
+    }
+
+    // Structured details arrive intact where the message carries them
+    let http = AgUiError::from_run_error("HTTP 503 Service Unavailable: try later", Some("HTTP_ERROR"));
+    assert!(matches!(http, AgUiError::HttpError { status: 503, ref body, .. } if body == "try later"));
+    let chunk = AgUiError::from_run_error(
+        AgUiError::ChunkNotExpanded { event_type: EventType::ToolCallChunk }.to_string(),
+        Some("CHUNK_NOT_EXPANDED"),
+    );
+    assert!(matches!(chunk, AgUiError::ChunkNotExpanded { event_type: EventType::ToolCallChunk }));
+    let violation = AgUiError::from_run_error("First event must be 'RUN_STARTED'", Some("PROTOCOL_VIOLATION"));
+    assert!(matches!(violation.violation(), Some(ProtocolViolation::Reported { message }) if message.starts_with("First")));
+
+    // What a RUN_ERROR cannot carry is lost, and a message another SDK wrote is kept as it is
+    let decoding = AgUiError::from_event(&AgUiError::decoding("bad frame").at_offset(42).to_run_error()).unwrap();
+    assert!(matches!(decoding, AgUiError::DecodingError { offset: None, .. }));
+    let foreign = AgUiError::from_run_error("Upstream returned 503", Some("HTTP_ERROR"));
+    assert!(matches!(foreign, AgUiError::RunError { code: Some(code), .. } if code == "HTTP_ERROR"));
+}
@@ -0,0 +1,159 @@
+//! Tests for the `type`-discriminated event model
+//!
+//! Events are decoded by reading the `type` field and deserializing exactly
+//! one payload shape, so events with identical field sets stay distinct and
+//! malformed input fails with an error naming the offending field or type.
+
+use ag_ui_wasm::{
+    BaseEvent, EventData, EventType,
+    TextMessageChunkEvent, TextMessageContentEvent, ToolCallArgsEvent, ToolCallChunkEvent,
+};
+use serde_json::json;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn parse_error(value: serde_json::Value) -> String {
+    serde_json::from_value::<BaseEvent>(value)
+        .expect_err("event should be rejected")
+        .to_string()
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_same_shape_events_are_distinguished_by_type() {
+    let content: BaseEvent = serde_json::from_value(json!({
+        "type": "TEXT_MESSAGE_CONTENT",
+        "messageId": "msg-1",
+        "delta": "Hi"
+    })).unwrap();
+    let chunk: BaseEvent = serde_json::from_value(json!({
+        "type": "TEXT_MESSAGE_CHUNK",
+        "messageId": "msg-1",
+        "delta": "Hi"
+    })).unwrap();
+
+    assert!(matches!(content.data, EventData::TextMessageContent(_)));
+    assert!(matches!(chunk.data, EventData::TextMessageChunk(_)));
+    assert_eq!(content.event_type(), EventType::TextMessageContent);
+    assert_eq!(chunk.event_type(), EventType::TextMessageChunk);
+
+    let args: BaseEvent = serde_json::from_value(json!({
+        "type": "TOOL_CALL_ARGS",
+        "toolCallId": "call-1",
+        "delta": "{}"
+    })).unwrap();
+    let tool_chunk: BaseEvent = serde_json::from_value(json!({
+        "type": "TOOL_CALL_CHUNK",
+        "toolCallId": "call-1",
+        "delta": "{}"
+    })).unwrap();
+
+    assert!(matches!(args.data, EventData::ToolCallArgs(_)));
+    assert!(matches!(tool_chunk.data, EventData::ToolCallChunk(_)));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_finished_is_not_parsed_as_run_started() {
+    let event: BaseEvent = serde_json::from_value(json!({
+        "type": "RUN_FINISHED",
+        "threadId": "thread-1",
+        "runId": "run-1"
+    })).unwrap();
+
+    assert!(matches!(event.data, EventData::RunFinished(_)));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_type_is_derived_from_payload() {
+    let content = BaseEvent::new(TextMessageContentEvent {
+        message_id: "msg-1".to_string(),
+        delta: "Hello".to_string(),
+    });
+    assert_eq!(content.event_type(), EventType::TextMessageContent);
+    assert!(content.timestamp.is_some());
+
+    let chunk = BaseEvent::new(TextMessageChunkEvent {
+        message_id: Some("msg-1".to_string()),
+        delta: Some("Hello".to_string()),
+        ..Default::default()
+    });
+    assert_eq!(chunk.event_type(), EventType::TextMessageChunk);
+
+    let data: EventData = ToolCallChunkEvent {
+        tool_call_id: Some("call-1".to_string()),
+        delta: Some("{".to_string()),
+        ..Default::default()
+    }
+    .into();
+    assert_eq!(data.event_type(), EventType::ToolCallChunk);
+
+    let serialized = serde_json::to_value(&chunk).unwrap();
+    assert_eq!(serialized["type"], "TEXT_MESSAGE_CHUNK");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_type_field_is_emitted_first() {
+    let mut event = BaseEvent::new(ToolCallArgsEvent {
+        tool_call_id: "call-1".to_string(),
+        delta: "{}".to_string(),
+    });
+    event.timestamp = None;
+    event.raw_event = Some(json!({"source": "test"}));
+
+    assert_eq!(
+        serde_json::to_string(&event).unwrap(),
+        r#"{"type":"TOOL_CALL_ARGS","rawEvent":{"source":"test"},"toolCallId":"call-1","delta":"{}"}"#
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_data_serializes_with_type_tag() {
+    let data = EventData::TextMessageChunk(TextMessageChunkEvent {
+        message_id: Some("msg-1".to_string()),
+        delta: Some("Hi".to_string()),
+        ..Default::default()
+    });
+
+    let serialized = serde_json::to_value(&data).unwrap();
+    assert_eq!(serialized, json!({"type": "TEXT_MESSAGE_CHUNK", "messageId": "msg-1", "delta": "Hi"}));
+
+    let parsed: EventData = serde_json::from_value(serialized).unwrap();
+    assert!(matches!(parsed, EventData::TextMessageChunk(_)));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_unknown_event_type_is_rejected() {
+    let error = parse_error(json!({"type": "NOT_A_REAL_EVENT", "messageId": "msg-1"}));
+    assert!(error.contains("unknown variant `NOT_A_REAL_EVENT`"), "{}", error);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_missing_type_is_rejected() {
+    let error = parse_error(json!({"messageId": "msg-1", "delta": "Hi"}));
+    assert!(error.contains("missing field `type`"), "{}", error);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_missing_payload_field_is_rejected() {
+    let error = parse_error(json!({"type": "TEXT_MESSAGE_CONTENT", "messageId": "msg-1"}));
+    assert!(error.contains("missing field `delta`"), "{}", error);
+
+    let error = parse_error(json!({"type": "RUN_STARTED", "threadId": "thread-1"}));
+    assert!(error.contains("missing field `runId`"), "{}", error);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_mistyped_payload_field_is_rejected() {
+    let error = parse_error(json!({"type": "TEXT_MESSAGE_END", "messageId": 42}));
+    assert!(error.contains("invalid type"), "{}", error);
+
+    let error = parse_error(json!({"type": "TEXT_MESSAGE_END", "messageId": "msg-1", "timestamp": "now"}));
+    assert!(error.contains("invalid type"), "{}", error);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_payload_for_another_type_is_rejected() {
+    // A TEXT_MESSAGE_START payload labelled as a tool call must not be accepted
+    let error = parse_error(json!({"type": "TOOL_CALL_START", "messageId": "msg-1", "role": "assistant"}));
+    assert!(error.contains("missing field `toolCallId`"), "{}", error);
+}
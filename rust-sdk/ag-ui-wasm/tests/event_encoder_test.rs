@@ -0,0 +1,104 @@
+//! Tests for `EventEncoder` and `Accept` header negotiation
+//!
+//! Mirrors `encoder.test.ts` in `@ag-ui/encoder`, plus the negotiation rules
+//! its `media-type.ts` takes from `negotiator`.
+
+use ag_ui_wasm::encoder::media_type::preferred_media_types;
+use ag_ui_wasm::encoder::SSE_MEDIA_TYPE;
+use ag_ui_wasm::proto::{self, AGUI_MEDIA_TYPE};
+use ag_ui_wasm::{BaseEvent, EventEncoder, Role};
+use chrono::{TimeZone, Utc};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn test_event() -> BaseEvent {
+    let mut event = BaseEvent::text_message_start("msg123".to_string(), Some(Role::Assistant));
+    event.timestamp = Some(Utc.timestamp_millis_opt(123_456_789).unwrap());
+    event
+}
+
+fn accepts_protobuf(accept: &str) -> bool {
+    EventEncoder::from_accept(accept).accepts_protobuf()
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_protobuf_when_accept_includes_its_media_type() {
+    let encoder = EventEncoder::new(Some(format!("{}, text/event-stream", AGUI_MEDIA_TYPE)));
+    assert_eq!(encoder.content_type(), AGUI_MEDIA_TYPE);
+
+    let frame = encoder.encode_event(&test_event()).unwrap();
+    let length = u32::from_be_bytes(frame[..4].try_into().unwrap()) as usize;
+    assert_eq!(frame.len() - 4, length);
+    assert_eq!(proto::decode(&frame[4..]).unwrap().event_type(), test_event().event_type());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_sse_without_protobuf_in_accept() {
+    let expected = format!("data: {}\n\n", serde_json::to_string(&test_event()).unwrap());
+
+    for encoder in [EventEncoder::new(Some("text/event-stream".to_string())), EventEncoder::new(None)] {
+        assert_eq!(encoder.content_type(), SSE_MEDIA_TYPE);
+        assert_eq!(encoder.encode_event(&test_event()).unwrap(), expected.as_bytes());
+        assert_eq!(encoder.encode_sse(&test_event()).unwrap(), expected);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_protobuf_encoding_is_available_regardless_of_accept() {
+    let frame = EventEncoder::new(None).encode_protobuf(&test_event()).unwrap();
+    let length = u32::from_be_bytes(frame[..4].try_into().unwrap()) as usize;
+    assert!(length > 0);
+    assert_eq!(frame.len() - 4, length);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_quality_values_are_respected() {
+    assert!(accepts_protobuf("text/event-stream;q=0.5, application/vnd.ag-ui.event+proto"));
+    assert!(accepts_protobuf("application/*"));
+    assert!(accepts_protobuf("APPLICATION/VND.AG-UI.EVENT+PROTO"));
+    assert!(accepts_protobuf("text/event-stream;q=0.5, application/vnd.ag-ui.event+proto ; q=\"0.9\""));
+    assert!(accepts_protobuf("application/vnd.ag-ui.event+proto, */*"));
+
+    // SSE is preferred, or ties with protobuf
+    assert!(!accepts_protobuf("application/vnd.ag-ui.event+proto;q=0.5, text/event-stream"));
+    assert!(!accepts_protobuf("*/*"));
+    assert!(!accepts_protobuf("text/event-stream, application/vnd.ag-ui.event+proto"));
+    assert!(!accepts_protobuf("text/*, application/*"));
+    assert!(!accepts_protobuf("application/vnd.ag-ui.event+proto;q=0"));
+    assert!(!accepts_protobuf("application/vnd.ag-ui.event+proto;q=0.0, text/event-stream"));
+    assert!(!accepts_protobuf("application/*;q=0.5, application/vnd.ag-ui.event+proto;q=0"));
+    assert!(!accepts_protobuf("application/vnd.ag-ui.event+proto;q=bogus"));
+    assert!(!accepts_protobuf("application/json"));
+    assert!(!accepts_protobuf(""));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_preferred_media_types_order() {
+    let provided = ["text/event-stream", AGUI_MEDIA_TYPE, "application/json"];
+
+    assert_eq!(preferred_media_types(None, &provided), provided);
+    assert_eq!(
+        preferred_media_types(Some("text/event-stream;q=0.5, application/vnd.ag-ui.event+proto"), &provided),
+        [AGUI_MEDIA_TYPE, "text/event-stream"]
+    );
+    // The most specific range decides, even with a lower quality
+    assert_eq!(
+        preferred_media_types(Some("application/*, application/json;q=0.1"), &provided),
+        [AGUI_MEDIA_TYPE, "application/json"]
+    );
+    // Equal quality and specificity keep the header's order
+    assert_eq!(
+        preferred_media_types(Some("application/json, text/event-stream"), &provided),
+        ["application/json", "text/event-stream"]
+    );
+    // Parameters must match, and quoted commas do not split ranges
+    assert_eq!(
+        preferred_media_types(Some("text/event-stream;charset=\"utf-8,x\", text/plain"), &["text/event-stream;charset=utf-8"]),
+        Vec::<&str>::new()
+    );
+    assert_eq!(
+        preferred_media_types(Some("text/event-stream;charset=UTF-8"), &["text/event-stream;charset=utf-8"]),
+        ["text/event-stream;charset=utf-8"]
+    );
+}
@@ -0,0 +1,205 @@
+//! Tests for `EventStream` over JS `ReadableStream`s
+
+// These tests run under wasm-bindgen-test only
+#![cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+
+use ag_ui_wasm::{AgUiError, BaseEvent, EventStream, EventType, SSEEncoder, SseEncoderOptions};
+use js_sys::{Object, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+use web_sys::{ReadableStream, ReadableStreamDefaultController, ReadableStreamDefaultReader};
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// A response body that delivers `chunks` as separate network reads
+fn body(chunks: &[&str]) -> ReadableStream {
+    let chunks: Vec<Vec<u8>> = chunks.iter().map(|chunk| chunk.as_bytes().to_vec()).collect();
+    let start = Closure::once_into_js(move |controller: ReadableStreamDefaultController| {
+        for chunk in &chunks {
+            controller.enqueue_with_chunk(&Uint8Array::from(chunk.as_slice())).unwrap();
+        }
+        controller.close().unwrap();
+    });
+    let source = Object::new();
+    Reflect::set(&source, &JsValue::from_str("start"), &start).unwrap();
+    ReadableStream::new_with_underlying_source(&source).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_events_split_across_chunks() {
+    let mut stream = EventStream::from_readable_stream(body(&[
+        ": keep-alive\n\ndata: {\"type\":\"RUN_STA",
+        "RTED\",\"threadId\":\"t\",\"runId\":\"r\"}\r\n\r\ndata: {\"type\":\"RUN_FINISHED\",",
+        "\"threadId\":\"t\",\"runId\":\"r\"}\n\n",
+    ]))
+    .unwrap();
+
+    assert_eq!(stream.next_event().await.unwrap().unwrap().event_type(), EventType::RunStarted);
+    assert_eq!(stream.next_event().await.unwrap().unwrap().event_type(), EventType::RunFinished);
+    assert!(stream.next_event().await.unwrap().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn test_readable_stream_yields_event_objects() {
+    let events = vec![
+        Ok(BaseEvent::run_started("t".to_string(), "r".to_string())),
+        Ok(BaseEvent::run_finished("t".to_string(), "r".to_string())),
+    ];
+    let readable = EventStream::from_stream(futures::stream::iter(events)).into_readable_stream().unwrap();
+    assert!(Reflect::has(&readable, &js_sys::Symbol::async_iterator()).unwrap());
+
+    let reader = readable.get_reader().dyn_into::<ReadableStreamDefaultReader>().unwrap();
+    let first = JsFuture::from(reader.read()).await.unwrap();
+    let value = Reflect::get(&first, &JsValue::from_str("value")).unwrap();
+    assert_eq!(Reflect::get(&value, &JsValue::from_str("type")).unwrap(), "RUN_STARTED");
+    assert_eq!(Reflect::get(&value, &JsValue::from_str("threadId")).unwrap(), "t");
+
+    JsFuture::from(reader.read()).await.unwrap();
+    let done = JsFuture::from(reader.read()).await.unwrap();
+    assert_eq!(Reflect::get(&done, &JsValue::from_str("done")).unwrap(), JsValue::TRUE);
+}
+
+#[wasm_bindgen_test]
+async fn test_async_fn_stream_as_sse_body() {
+    let stream = EventStream::from_async_fn(|mut sink| async move {
+        sink.send(BaseEvent::run_started("t".to_string(), "r".to_string())).await?;
+        sink.send(BaseEvent::run_finished("t".to_string(), "r".to_string())).await
+    });
+
+    // Reading the encoded body back gives the events that were sent
+    let mut read_back = EventStream::from_readable_stream(stream.into_sse_stream().unwrap()).unwrap();
+    assert_eq!(read_back.next_event().await.unwrap().unwrap().event_type(), EventType::RunStarted);
+    assert_eq!(read_back.next_event().await.unwrap().unwrap().event_type(), EventType::RunFinished);
+    assert!(read_back.next_event().await.unwrap().is_none());
+}
+
+#[wasm_bindgen_test]
+async fn test_async_fn_error_errors_the_readable_stream() {
+    let stream = EventStream::from_async_fn(|mut sink| async move {
+        sink.send(BaseEvent::run_started("t".to_string(), "r".to_string())).await?;
+        Err(ag_ui_wasm::AgUiError::StreamError("model unavailable".to_string()))
+    });
+
+    let reader = stream.into_readable_stream().unwrap().get_reader().dyn_into::<ReadableStreamDefaultReader>().unwrap();
+    JsFuture::from(reader.read()).await.unwrap();
+    assert!(JsFuture::from(reader.read()).await.is_err());
+}
+
+#[wasm_bindgen_test]
+async fn test_async_fn_stream_as_proto_body() {
+    let stream = EventStream::from_async_fn(|mut sink| async move {
+        sink.send(BaseEvent::run_started("t".to_string(), "r".to_string())).await?;
+        sink.send(BaseEvent::run_finished("t".to_string(), "r".to_string())).await
+    });
+
+    let mut read_back = EventStream::from_proto_readable_stream(stream.into_proto_stream().unwrap()).unwrap();
+    assert_eq!(read_back.next_event().await.unwrap().unwrap().event_type(), EventType::RunStarted);
+    assert_eq!(read_back.next_event().await.unwrap().unwrap().event_type(), EventType::RunFinished);
+    assert!(read_back.next_event().await.unwrap().is_none());
+    assert_eq!(read_back.last_event_id(), None);
+}
+
+#[wasm_bindgen_test]
+async fn test_sse_stream_batches_ready_events() {
+    let events = vec![
+        Ok(BaseEvent::run_started("t".to_string(), "r".to_string())),
+        Ok(BaseEvent::run_finished("t".to_string(), "r".to_string())),
+    ];
+    let encoder = SSEEncoder::with_options(SseEncoderOptions { ids: true, ..Default::default() });
+    let body = EventStream::from_stream(futures::stream::iter(events)).into_sse_stream_with(encoder).unwrap();
+
+    // Both events are ready at the first pull, so they arrive in one chunk
+    let reader: ReadableStreamDefaultReader = body.get_reader().unchecked_into();
+    let result = JsFuture::from(reader.read()).await.unwrap();
+    let chunk: Uint8Array = Reflect::get(&result, &JsValue::from_str("value")).unwrap().unchecked_into();
+    let text = String::from_utf8(chunk.to_vec()).unwrap();
+    assert_eq!(text.matches("data: ").count(), 2);
+    assert!(text.contains("id: 1\n") && text.contains("id: 2\n"));
+
+    let result = JsFuture::from(reader.read()).await.unwrap();
+    assert!(Reflect::get(&result, &JsValue::from_str("done")).unwrap().is_truthy());
+}
+
+/// A response body that sends `chunk` and then fails, as an aborted fetch body does
+fn failing_body(chunk: &str) -> ReadableStream {
+    let chunk = chunk.as_bytes().to_vec();
+    let start = Closure::once_into_js(move |controller: ReadableStreamDefaultController| {
+        controller.enqueue_with_chunk(&Uint8Array::from(chunk.as_slice())).unwrap();
+        controller.error_with_e(&JsValue::from_str("AbortError"));
+    });
+    let source = Object::new();
+    Reflect::set(&source, &JsValue::from_str("start"), &start).unwrap();
+    ReadableStream::new_with_underlying_source(&source).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_read_error_after_abort_is_aborted() {
+    let run_started = "data: {\"type\":\"RUN_STARTED\",\"threadId\":\"t\",\"runId\":\"r\"}\n\n";
+
+    let controller = web_sys::AbortController::new().unwrap();
+    let mut stream = EventStream::from_readable_stream(failing_body(run_started)).unwrap()
+        .with_abort_signal(controller.signal());
+    controller.abort();
+    assert!(stream.next_event().await.unwrap().is_some());
+    assert!(matches!(stream.next_event().await, Err(AgUiError::Aborted)));
+
+    // Without an abort the read error is kept
+    let controller = web_sys::AbortController::new().unwrap();
+    let mut stream = EventStream::from_readable_stream(failing_body(run_started)).unwrap()
+        .with_abort_signal(controller.signal());
+    assert!(stream.next_event().await.unwrap().is_some());
+    assert!(matches!(stream.next_event().await, Err(AgUiError::WasmBindgenError(_))));
+}
+
+/// Write `chunks` through `EventStream::create_transform_stream` and read
+/// the event objects that come out, then the error that ended it, if any
+async fn transform(chunks: &[&str]) -> (Vec<JsValue>, Option<JsValue>) {
+    let transform = EventStream::create_transform_stream().unwrap();
+    let writer = transform.writable().get_writer().unwrap();
+    // Awaited only after reading, since each write waits for the reader
+    let mut writes: Vec<JsFuture> = chunks
+        .iter()
+        .map(|chunk| JsFuture::from(writer.write_with_chunk(&Uint8Array::from(chunk.as_bytes()))))
+        .collect();
+    writes.push(JsFuture::from(writer.close()));
+
+    let reader: ReadableStreamDefaultReader = transform.readable().get_reader().unchecked_into();
+    let mut events = Vec::new();
+    let error = loop {
+        match JsFuture::from(reader.read()).await {
+            Ok(result) if Reflect::get(&result, &JsValue::from_str("done")).unwrap().is_truthy() => break None,
+            Ok(result) => events.push(Reflect::get(&result, &JsValue::from_str("value")).unwrap()),
+            Err(error) => break Some(error),
+        }
+    };
+    for write in writes {
+        let _ = write.await;
+    }
+    (events, error)
+}
+
+#[wasm_bindgen_test]
+async fn test_transform_stream_parses_sse_bytes() {
+    let (events, error) = transform(&[
+        "data: {\"type\":\"RUN_STARTED\",\"threadId\":\"t\",",
+        "\"runId\":\"r\"}\n\ndata: {\"type\":\"RUN_FINISHED\",\"threadId\":\"t\",\"runId\":\"r\"}\n\n",
+    ])
+    .await;
+
+    assert!(error.is_none());
+    let types: Vec<JsValue> = events.iter().map(|event| Reflect::get(event, &JsValue::from_str("type")).unwrap()).collect();
+    assert_eq!(types, [JsValue::from_str("RUN_STARTED"), JsValue::from_str("RUN_FINISHED")]);
+}
+
+#[wasm_bindgen_test]
+async fn test_transform_stream_errors_on_a_truncated_body() {
+    let run_started = "data: {\"type\":\"RUN_STARTED\",\"threadId\":\"t\",\"runId\":\"r\"}\n\n";
+    let (events, error) = transform(&[run_started, "data: {\"type\":\"RUN_FIN"]).await;
+
+    assert_eq!(events.len(), 1);
+    let error = error.expect("the truncated event errors the stream");
+    assert_eq!(Reflect::get(&error, &JsValue::from_str("code")).unwrap(), "DECODING_ERROR");
+    assert_eq!(Reflect::get(&error, &JsValue::from_str("offset")).unwrap(), run_started.len() as f64);
+}
@@ -0,0 +1,161 @@
+//! Tests for consuming `EventStream` from Rust
+//!
+//! `EventStream` is a `futures::Stream` of events, so it works with the
+//! `futures` combinators and with `EventApplier::apply_stream`. Streams made
+//! with `from_async_fn` are driven the same way.
+
+use ag_ui_wasm::{AgUiError, BaseEvent, EventApplier, EventStream, EventType};
+use futures::executor::block_on;
+use std::cell::Cell;
+use std::rc::Rc;
+use futures::{StreamExt, TryStreamExt};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn message_events() -> Vec<BaseEvent> {
+    vec![
+        BaseEvent::run_started("thread-1".to_string(), "run-1".to_string()),
+        BaseEvent::text_message_start("msg-1".to_string(), None),
+        BaseEvent::text_message_content("msg-1".to_string(), "Hello".to_string()),
+        BaseEvent::text_message_end("msg-1".to_string()),
+        BaseEvent::run_finished("thread-1".to_string(), "run-1".to_string()),
+    ]
+}
+
+fn stream_of(events: Vec<BaseEvent>) -> EventStream {
+    EventStream::from_stream(futures::stream::iter(events.into_iter().map(Ok)))
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_next_event_reads_until_the_end() {
+    let mut stream = stream_of(message_events());
+
+    let mut types = Vec::new();
+    while let Some(event) = block_on(stream.next_event()).unwrap() {
+        types.push(event.event_type());
+    }
+
+    assert_eq!(types.len(), 5);
+    assert_eq!(types[0], EventType::RunStarted);
+    assert!(block_on(stream.next_event()).unwrap().is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_stream_is_a_futures_stream() {
+    let types: Vec<EventType> = block_on(
+        stream_of(message_events())
+            .map_ok(|event| event.event_type())
+            .try_filter(|event_type| futures::future::ready(*event_type != EventType::TextMessageContent))
+            .try_collect(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        types,
+        [EventType::RunStarted, EventType::TextMessageStart, EventType::TextMessageEnd, EventType::RunFinished]
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_errors_are_stream_items() {
+    let events = vec![
+        Ok(BaseEvent::run_started("thread-1".to_string(), "run-1".to_string())),
+        Err(AgUiError::StreamError("connection reset".to_string())),
+    ];
+    let mut stream = EventStream::from_stream(futures::stream::iter(events));
+
+    assert!(block_on(stream.next()).unwrap().is_ok());
+    assert!(matches!(block_on(stream.next_event()), Err(AgUiError::StreamError(_))));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_applier_folds_an_event_stream() {
+    let mut applier = EventApplier::default();
+    block_on(applier.apply_stream(stream_of(message_events()))).unwrap();
+
+    assert_eq!(applier.state().messages[0].content(), Some("Hello"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_cancel_ends_the_stream() {
+    let mut stream = stream_of(message_events());
+    block_on(stream.next_event()).unwrap();
+
+    stream.cancel();
+
+    assert!(block_on(stream.next_event()).unwrap().is_none());
+    assert_eq!(stream.last_event_id(), None);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_async_fn_events_arrive_in_order() {
+    let stream = EventStream::from_async_fn(|mut sink| async move {
+        for event in message_events() {
+            // Stands in for awaiting a model between events
+            futures::future::ready(()).await;
+            sink.send(event).await?;
+        }
+        Ok(())
+    });
+
+    let events: Vec<BaseEvent> = block_on(stream.try_collect()).unwrap();
+    let types: Vec<EventType> = events.iter().map(BaseEvent::event_type).collect();
+    let expected: Vec<EventType> = message_events().iter().map(BaseEvent::event_type).collect();
+    assert_eq!(types, expected);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_async_fn_error_follows_sent_events() {
+    let mut stream = EventStream::from_async_fn(|mut sink| async move {
+        sink.send(BaseEvent::run_started("thread-1".to_string(), "run-1".to_string())).await?;
+        sink.send(BaseEvent::text_message_start("msg-1".to_string(), None)).await?;
+        Err(AgUiError::StreamError("model unavailable".to_string()))
+    });
+
+    assert_eq!(block_on(stream.next_event()).unwrap().unwrap().event_type(), EventType::RunStarted);
+    assert_eq!(block_on(stream.next_event()).unwrap().unwrap().event_type(), EventType::TextMessageStart);
+    assert!(matches!(block_on(stream.next_event()), Err(AgUiError::StreamError(message)) if message == "model unavailable"));
+    assert!(block_on(stream.next_event()).unwrap().is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_async_fn_producer_waits_for_the_reader() {
+    let sent = Rc::new(Cell::new(0));
+    let counter = sent.clone();
+    let mut stream = EventStream::from_async_fn(move |mut sink| async move {
+        for index in 0..100 {
+            sink.send(BaseEvent::text_message_content("msg-1".to_string(), index.to_string())).await?;
+            counter.set(counter.get() + 1);
+        }
+        Ok(())
+    });
+
+    assert_eq!(sent.get(), 0);
+    for _ in 0..3 {
+        block_on(stream.next_event()).unwrap().unwrap();
+    }
+    assert!(sent.get() <= 4, "producer ran {} events ahead", sent.get());
+
+    stream.cancel();
+    assert!(block_on(stream.next_event()).unwrap().is_none());
+    assert!(sent.get() <= 4);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_sink_fails_once_the_stream_is_gone() {
+    let escaped = Rc::new(Cell::new(None));
+    let slot = escaped.clone();
+    let mut stream = EventStream::from_async_fn(move |sink| async move {
+        slot.set(Some(sink));
+        Ok(())
+    });
+
+    // The producer keeps no sink, so the stream ends only when it is dropped
+    assert!(futures::FutureExt::now_or_never(stream.next_event()).is_none());
+    drop(stream);
+
+    let mut sink = escaped.take().unwrap();
+    assert!(sink.is_closed());
+    assert!(block_on(sink.send(BaseEvent::run_started("thread-1".to_string(), "run-1".to_string()))).is_err());
+}
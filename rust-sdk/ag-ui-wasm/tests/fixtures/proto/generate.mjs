@@ -0,0 +1,24 @@
+// Regenerates the `hex` of every case in events.jsonl with `@ag-ui/proto`.
+//
+// Run from typescript-sdk/packages/proto after `pnpm install && pnpm generate && pnpm build`:
+//
+//   node ../../../rust-sdk/ag-ui-wasm/tests/fixtures/proto/generate.mjs
+//
+// Object keys in the events are sorted: ts-proto writes `Struct` fields in
+// insertion order and prost in key order, so only sorted keys give both
+// encoders the same bytes.
+
+import { readFileSync, writeFileSync } from "node:fs";
+import { fileURLToPath } from "node:url";
+import { encode } from "@ag-ui/proto";
+
+const fixture = fileURLToPath(new URL("./events.jsonl", import.meta.url));
+
+const cases = readFileSync(fixture, "utf8")
+  .split("\n")
+  .filter((line) => line.trim() !== "")
+  .map((line) => JSON.parse(line))
+  .map(({ name, event }) => ({ name, event, hex: Buffer.from(encode(event)).toString("hex") }));
+
+writeFileSync(fixture, cases.map((testCase) => JSON.stringify(testCase)).join("\n") + "\n");
+console.log(`Encoded ${cases.length} events`);
@@ -0,0 +1,136 @@
+// Regenerates events.jsonl, event_catalogue.jsonl and run_agent_input.json,
+// checking every document against the `@ag-ui/core` schemas.
+//
+// Run from typescript-sdk/packages/encoder after `pnpm install && pnpm build`:
+//
+//   node ../../../rust-sdk/ag-ui-wasm/tests/fixtures/wire/generate.mjs
+//
+// The documents are written as authored, in the key order of the schemas, so
+// that the Rust tests can compare them byte for byte. Keys inside free-form
+// values such as `state` or tool `parameters` are sorted, the order serde_json
+// writes them in. A document fails when
+// parsing it would drop a member, unless the member is listed in
+// NEWER_THAN_SCHEMA because the spec added it after this package.
+
+import { writeFileSync } from "node:fs";
+import { createRequire } from "node:module";
+import { join } from "node:path";
+import { fileURLToPath } from "node:url";
+
+const require = createRequire(join(process.cwd(), "package.json"));
+const { EventSchemas, RunAgentInputSchema } = require("@ag-ui/core");
+
+const NEWER_THAN_SCHEMA = {
+  RUN_STARTED: ["parentRunId", "input"],
+};
+
+const weatherCall = {
+  id: "call-1",
+  type: "function",
+  function: { name: "get_weather", arguments: '{"city":"Paris"}' },
+};
+
+const events = [
+  { type: "RUN_STARTED", timestamp: 1700000000000, threadId: "thread-1", runId: "run-1" },
+  { type: "TEXT_MESSAGE_START", timestamp: 1700000000001, messageId: "msg-1", role: "assistant" },
+  { type: "TEXT_MESSAGE_CONTENT", messageId: "msg-1", delta: "Hello" },
+  { type: "TEXT_MESSAGE_CONTENT", messageId: "msg-1", delta: ', "world" — \n👋' },
+  { type: "TEXT_MESSAGE_END", messageId: "msg-1" },
+  { type: "TOOL_CALL_ARGS", toolCallId: "call-1", delta: '{"city":"Paris"}' },
+  { type: "STATE_DELTA", delta: [{ op: "replace", path: "/count", value: 2 }] },
+  {
+    type: "MESSAGES_SNAPSHOT",
+    messages: [
+      { id: "u-1", role: "user", content: "Hi" },
+      { id: "a-1", role: "assistant", content: "Hello", toolCalls: [weatherCall] },
+      { id: "t-1", content: "18°C and sunny", role: "tool", toolCallId: "call-1" },
+    ],
+  },
+  {
+    type: "RAW",
+    rawEvent: { provider: "openai" },
+    event: { id: "chatcmpl-1", object: "chat.completion.chunk" },
+  },
+  { type: "RUN_FINISHED", timestamp: 1700000000002, threadId: "thread-1", runId: "run-1" },
+];
+
+// One event of every schema not covered by events.jsonl
+const catalogue = [
+  { type: "RUN_STARTED", timestamp: 1700000000000, threadId: "thread-1", runId: "run-2", parentRunId: "run-1" },
+  { type: "STEP_STARTED", stepName: "plan" },
+  { type: "THINKING_START", title: "Planning" },
+  { type: "THINKING_TEXT_MESSAGE_START" },
+  { type: "THINKING_TEXT_MESSAGE_CONTENT", delta: "Looking up the weather" },
+  { type: "THINKING_TEXT_MESSAGE_END" },
+  { type: "THINKING_END" },
+  { type: "STEP_FINISHED", stepName: "plan" },
+  { type: "TEXT_MESSAGE_CHUNK", messageId: "msg-2", role: "assistant", delta: "On it" },
+  { type: "TOOL_CALL_START", toolCallId: "call-2", toolCallName: "get_weather", parentMessageId: "msg-2" },
+  { type: "TOOL_CALL_ARGS", toolCallId: "call-2", delta: '{"city":"Paris"}' },
+  { type: "TOOL_CALL_END", toolCallId: "call-2" },
+  { type: "TOOL_CALL_CHUNK", toolCallId: "call-3", toolCallName: "get_time", parentMessageId: "msg-2", delta: "{}" },
+  { type: "TOOL_CALL_RESULT", messageId: "tool-1", toolCallId: "call-2", content: "18°C and sunny", role: "tool" },
+  {
+    type: "MESSAGES_SNAPSHOT",
+    messages: [
+      { id: "d-1", role: "developer", content: "Answer briefly", name: "ops" },
+      { id: "s-1", role: "system", content: "You are a weather bot" },
+      { id: "a-2", role: "assistant" },
+      { id: "tool-1", content: "18°C and sunny", role: "tool", toolCallId: "call-2" },
+      { id: "tool-2", content: "", role: "tool", toolCallId: "call-3", error: "Clock unavailable" },
+    ],
+  },
+  { type: "STATE_SNAPSHOT", snapshot: { count: 1 } },
+  { type: "RAW", event: { id: "evt-1" }, source: "openai" },
+  { type: "CUSTOM", name: "progress", value: { percent: 50 } },
+  { type: "RUN_FINISHED", timestamp: 1700000000001, threadId: "thread-1", runId: "run-2", result: { answer: 42 } },
+  { type: "RUN_ERROR", message: "Rate limited", code: "rate_limit" },
+];
+
+const runAgentInput = {
+  threadId: "thread-1",
+  runId: "run-1",
+  state: { count: 1 },
+  messages: [
+    { id: "u-1", role: "user", content: "What's the weather?" },
+    { id: "a-1", role: "assistant", toolCalls: [weatherCall] },
+    { id: "t-1", content: "18°C and sunny", role: "tool", toolCallId: "call-1" },
+  ],
+  tools: [
+    {
+      name: "get_weather",
+      description: "Look up the weather",
+      parameters: { properties: { city: { type: "string" } }, type: "object" },
+    },
+  ],
+  context: [{ description: "User location", value: "Amsterdam" }],
+  forwardedProps: { locale: "en-US" },
+};
+
+// Paths of the members of `authored` that `parsed` no longer has
+function droppedMembers(authored, parsed, path = "") {
+  if (typeof authored !== "object" || authored === null || typeof parsed !== "object" || parsed === null) {
+    return [];
+  }
+  return Object.keys(authored).flatMap((key) =>
+    key in parsed ? droppedMembers(authored[key], parsed[key], `${path}/${key}`) : [`${path}/${key}`],
+  );
+}
+
+function check(schema, document) {
+  const allowed = (NEWER_THAN_SCHEMA[document.type] ?? []).map((key) => `/${key}`);
+  const dropped = droppedMembers(document, schema.parse(document)).filter((path) => !allowed.includes(path));
+  if (dropped.length > 0) {
+    throw new Error(`${JSON.stringify(document)} has members the schema does not know: ${dropped.join(", ")}`);
+  }
+  return JSON.stringify(document);
+}
+
+function write(name, lines) {
+  writeFileSync(fileURLToPath(new URL(`./${name}`, import.meta.url)), lines.join("\n") + "\n");
+  console.log(`Wrote ${lines.length} document(s) to ${name}`);
+}
+
+write("events.jsonl", events.map((event) => check(EventSchemas, event)));
+write("event_catalogue.jsonl", catalogue.map((event) => check(EventSchemas, event)));
+write("run_agent_input.json", [check(RunAgentInputSchema, runAgentInput)]);
@@ -1,12 +1,14 @@
+// These tests run under wasm-bindgen-test only
+#![cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+
 use ag_ui_wasm::{
     BaseEvent,
-    Message, RunAgentInput, State, Role,
+    Message, SystemMessage, AssistantMessage, UserMessage, RunAgentInput, State, Role,
     SSEEncoder,
 };
 use wasm_bindgen_test::*;
 use std::collections::HashMap;
 use serde_json::json;
-use chrono::Utc;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
@@ -17,25 +19,24 @@ async fn test_complete_agent_conversation_flow() {
     let input = RunAgentInput {
         thread_id: "test-thread-123".to_string(),
         run_id: "test-run-123".to_string(),
-        messages: Some(vec![
-            Message {
+        messages: vec![
+            Message::User(UserMessage {
                 id: "msg-user-001".to_string(),
-                role: Role::User,
                 content: "What's the weather today?".to_string(),
                 name: None,
-                tool_call_id: None,
-                tool_calls: None,
-                function_call: None,
-                metadata: None,
-                created_at: Some(Utc::now()),
-            }
-        ]),
-        tools: None,
-        context: None,
-        state: Some(HashMap::new()),
-        forwarded_props: None,
+            })
+        ],
+        tools: Vec::new(),
+        context: Vec::new(),
+        state: HashMap::new(),
+        forwarded_props: HashMap::new(),
     };
 
+    // The request body must be valid camelCase JSON
+    let body = serde_json::to_value(&input).unwrap();
+    assert_eq!(body["threadId"], "test-thread-123");
+    assert_eq!(body["messages"][0]["role"], "user");
+
     // Test event sequence
     let events = vec![
         // 1. Run started
@@ -77,47 +78,30 @@ async fn test_complete_agent_conversation_flow() {
 #[wasm_bindgen_test]
 fn test_message_construction() {
     // Test user message
-    let user_msg = Message {
+    let user_msg = Message::User(UserMessage {
         id: "msg-1".to_string(),
-        role: Role::User,
         content: "Hello, AI!".to_string(),
         name: None,
-        tool_call_id: None,
-        tool_calls: None,
-        function_call: None,
-        metadata: None,
-        created_at: Some(Utc::now()),
-    };
-    assert_eq!(user_msg.role, Role::User);
-    assert_eq!(user_msg.content, "Hello, AI!");
+    });
+    assert_eq!(user_msg.role(), Role::User);
+    assert_eq!(user_msg.content(), Some("Hello, AI!"));
     
     // Test assistant message
-    let assistant_msg = Message {
+    let assistant_msg = Message::Assistant(AssistantMessage {
         id: "msg-2".to_string(),
-        role: Role::Assistant,
-        content: "Hello! How can I help you today?".to_string(),
+        content: Some("Hello! How can I help you today?".to_string()),
         name: None,
-        tool_call_id: None,
         tool_calls: None,
-        function_call: None,
-        metadata: None,
-        created_at: Some(Utc::now()),
-    };
-    assert_eq!(assistant_msg.role, Role::Assistant);
+    });
+    assert_eq!(assistant_msg.role(), Role::Assistant);
     
     // Test system message
-    let system_msg = Message {
+    let system_msg = Message::System(SystemMessage {
         id: "msg-3".to_string(),
-        role: Role::System,
         content: "You are a helpful assistant.".to_string(),
         name: None,
-        tool_call_id: None,
-        tool_calls: None,
-        function_call: None,
-        metadata: None,
-        created_at: Some(Utc::now()),
-    };
-    assert_eq!(system_msg.role, Role::System);
+    });
+    assert_eq!(system_msg.role(), Role::System);
 }
 
 // Test state management
@@ -197,7 +181,6 @@ fn test_streaming_text_chunks() {
     let message = "This is a longer message that would be streamed in chunks to provide a better user experience.";
     let chunks = message.split_whitespace().collect::<Vec<&str>>();
     
-    let encoder = SSEEncoder::new();
     let mut accumulated = String::new();
     
     for chunk in chunks.iter() {
@@ -0,0 +1,185 @@
+//! JSON Patch tests for `STATE_DELTA` events
+//!
+//! `tests/fixtures/json_patch/rfc6902.json` holds the examples from RFC 6902
+//! Appendix A (A.13 needs a JSON parser that rejects duplicate members and is
+//! left out) followed by edge cases in the same `doc`/`patch`/`expected` or
+//! `error` format used by the json-patch test suite.
+
+use ag_ui_wasm::core::patch::{apply_patch, apply_state_patch, diff, diff_states, parse_patch};
+use ag_ui_wasm::{AgUiError, PatchOperation, StateDeltaEvent};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const RFC6902_FIXTURE: &str = include_str!("fixtures/json_patch/rfc6902.json");
+
+fn patched(doc: &Value, patch: &Value) -> Result<Value, AgUiError> {
+    let mut doc = doc.clone();
+    apply_patch(&mut doc, &parse_patch(patch)?)?;
+    Ok(doc)
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_rfc6902_vectors() {
+    let cases: Vec<Value> = serde_json::from_str(RFC6902_FIXTURE).unwrap();
+    assert!(cases.len() > 40);
+
+    for case in &cases {
+        let comment = case["comment"].as_str().unwrap();
+        let result = patched(&case["doc"], &case["patch"]);
+        match case.get("expected") {
+            Some(expected) => assert_eq!(result.as_ref().ok(), Some(expected), "{}: {:?}", comment, result),
+            None => assert!(matches!(result, Err(AgUiError::PatchError(_))), "{}: {:?}", comment, result),
+        }
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_failed_patch_leaves_document_unchanged() {
+    let mut doc = json!({"foo": [1, 2], "bar": {"baz": true}});
+    let patch = parse_patch(&json!([
+        {"op": "remove", "path": "/foo/0"},
+        {"op": "add", "path": "/bar/qux", "value": 1},
+        {"op": "replace", "path": "/missing", "value": 1}
+    ]))
+    .unwrap();
+
+    let error = apply_patch(&mut doc, &patch).unwrap_err().to_string();
+
+    assert!(error.contains("operation 2 (replace)"), "{}", error);
+    assert_eq!(doc, json!({"foo": [1, 2], "bar": {"baz": true}}));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_operations_serialize_in_rfc_form() {
+    let patch = vec![
+        PatchOperation::Add { path: "/a".to_string(), value: json!(1) },
+        PatchOperation::Remove { path: "/b".to_string() },
+        PatchOperation::Move { from: "/c".to_string(), path: "/d".to_string() },
+    ];
+
+    let value = serde_json::to_value(&patch).unwrap();
+    assert_eq!(
+        value,
+        json!([
+            {"op": "add", "path": "/a", "value": 1},
+            {"op": "remove", "path": "/b"},
+            {"op": "move", "from": "/c", "path": "/d"}
+        ])
+    );
+    assert_eq!(parse_patch(&value).unwrap(), patch);
+    assert!(matches!(parse_patch(&json!({"op": "add"})), Err(AgUiError::PatchError(_))));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_state_must_remain_an_object() {
+    let mut state = HashMap::from([("count".to_string(), json!(1))]);
+    let patch = parse_patch(&json!([{"op": "replace", "path": "", "value": [1]}])).unwrap();
+
+    assert!(matches!(apply_state_patch(&mut state, &patch), Err(AgUiError::PatchError(_))));
+    assert_eq!(state, HashMap::from([("count".to_string(), json!(1))]));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_state_delta_event_applies_atomically() {
+    let mut state = HashMap::from([("count".to_string(), json!(1))]);
+
+    let delta = StateDeltaEvent { delta: json!([{"op": "replace", "path": "/count", "value": 2}]) };
+    delta.apply_to(&mut state).unwrap();
+    assert_eq!(state["count"], json!(2));
+
+    let delta = StateDeltaEvent {
+        delta: json!([
+            {"op": "replace", "path": "/count", "value": 3},
+            {"op": "test", "path": "/count", "value": 2}
+        ]),
+    };
+    assert!(delta.apply_to(&mut state).is_err());
+    assert_eq!(state, HashMap::from([("count".to_string(), json!(2))]));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_diff_only_touches_changed_members() {
+    let from = json!({"keep": 1, "change": "a", "drop": true, "nested": {"x": 1, "y": 2}});
+    let to = json!({"keep": 1, "change": "b", "nested": {"x": 1, "y": 3}, "new": [1]});
+
+    assert_eq!(
+        diff(&from, &to),
+        vec![
+            PatchOperation::Replace { path: "/change".to_string(), value: json!("b") },
+            PatchOperation::Remove { path: "/drop".to_string() },
+            PatchOperation::Replace { path: "/nested/y".to_string(), value: json!(3) },
+            PatchOperation::Add { path: "/new".to_string(), value: json!([1]) },
+        ]
+    );
+    assert!(diff(&from, &from).is_empty());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_diff_arrays_around_common_elements() {
+    let insert = diff(&json!(["a", "b", "c"]), &json!(["a", "x", "b", "c"]));
+    assert_eq!(insert, vec![PatchOperation::Add { path: "/1".to_string(), value: json!("x") }]);
+
+    let remove = diff(&json!(["a", "b", "c"]), &json!(["a", "c"]));
+    assert_eq!(remove, vec![PatchOperation::Remove { path: "/1".to_string() }]);
+
+    let edit = diff(&json!([{"id": 1, "done": false}, {"id": 2}]), &json!([{"id": 1, "done": true}, {"id": 2}]));
+    assert_eq!(edit, vec![PatchOperation::Replace { path: "/0/done".to_string(), value: json!(true) }]);
+
+    let truncate = diff(&json!([1, 2, 3, 4]), &json!([1, 2]));
+    assert_eq!(
+        truncate,
+        vec![PatchOperation::Remove { path: "/2".to_string() }, PatchOperation::Remove { path: "/2".to_string() }]
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_diff_escapes_member_names() {
+    let patch = diff(&json!({}), &json!({"a/b": 1, "c~d": 2}));
+    let paths: Vec<&str> = patch.iter().map(PatchOperation::path).collect();
+    assert_eq!(paths, ["/a~1b", "/c~0d"]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_diff_round_trips() {
+    let pairs = [
+        (json!({}), json!({"a": 1})),
+        (json!({"a": [1, 2, 3]}), json!({"a": [3, 2, 1]})),
+        (json!({"a": [1, 2, 3]}), json!({"a": []})),
+        (json!({"a": []}), json!({"a": [{"b": 1}, 2]})),
+        (json!({"a": [1, [2, 3], 4]}), json!({"a": [0, [2, 5, 3], 4, 6]})),
+        (json!({"a": {"b": {"c": [1]}}}), json!({"a": {"b": null}})),
+        (json!({"a": "text"}), json!({"a": {"now": "object"}})),
+        (json!([1, 2, 3, 4, 5]), json!([5, 4, 3, 2, 1])),
+        (json!(["x", "a", "b", "y", "c"]), json!(["a", "z", "b", "c", "w"])),
+        (json!(1), json!("one")),
+    ];
+
+    for (from, to) in &pairs {
+        let patch = diff(from, to);
+        let mut doc = from.clone();
+        apply_patch(&mut doc, &patch).unwrap();
+        assert_eq!(&doc, to, "{} -> {}: {:?}", from, to, patch);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_state_delta_between_states() {
+    let from = HashMap::from([("count".to_string(), json!(1)), ("items".to_string(), json!(["a"]))]);
+    let to = HashMap::from([("count".to_string(), json!(2)), ("items".to_string(), json!(["a", "b"]))]);
+
+    assert_eq!(
+        diff_states(&from, &to),
+        vec![
+            PatchOperation::Replace { path: "/count".to_string(), value: json!(2) },
+            PatchOperation::Add { path: "/items/1".to_string(), value: json!("b") },
+        ]
+    );
+
+    let event = StateDeltaEvent::between(&from, &to);
+    let mut state = from.clone();
+    event.apply_to(&mut state).unwrap();
+    assert_eq!(state, to);
+}
@@ -0,0 +1,276 @@
+//! Tests for reading events written by earlier releases of this crate
+//!
+//! Version 0 events used snake_case fields, RFC 3339 timestamps and several
+//! pre-spec payload shapes. `core::compat` upgrades them on input; the strict
+//! `Deserialize` implementation only accepts the spec layout.
+
+use ag_ui_wasm::core::compat::{parse_event, upgrade_event, SchemaVersion};
+use ag_ui_wasm::{BaseEvent, EventData, EventType, Role, ToolCall};
+use chrono::{TimeZone, Utc};
+use serde_json::{json, Value};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const CATALOGUE_FIXTURE: &str = include_str!("fixtures/wire/event_catalogue.jsonl");
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_detects_schema_version() {
+    let spec = json!({"type": "TOOL_CALL_START", "toolCallId": "call-1", "toolCallName": "search"});
+    assert_eq!(SchemaVersion::detect(&spec), SchemaVersion::V1);
+    assert_eq!(SchemaVersion::detect(&spec), SchemaVersion::CURRENT);
+
+    let snake_case = json!({"type": "RUN_STARTED", "thread_id": "t", "run_id": "r"});
+    assert_eq!(SchemaVersion::detect(&snake_case), SchemaVersion::V0);
+
+    let string_timestamp = json!({"type": "TEXT_MESSAGE_END", "timestamp": "2023-11-14T22:13:20Z", "messageId": "m"});
+    assert_eq!(SchemaVersion::detect(&string_timestamp), SchemaVersion::V0);
+
+    let old_payload = json!({"type": "TOOL_CALL_START", "toolCallId": "call-1", "toolName": "search"});
+    assert_eq!(SchemaVersion::detect(&old_payload), SchemaVersion::V0);
+
+    assert_eq!(SchemaVersion::detect(&json!("not an event")), SchemaVersion::V1);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_spec_events_are_left_unchanged() {
+    for line in CATALOGUE_FIXTURE.lines().filter(|line| !line.trim().is_empty()) {
+        let value: Value = serde_json::from_str(line).unwrap();
+        assert_eq!(SchemaVersion::detect(&value), SchemaVersion::CURRENT, "{}", line);
+        assert_eq!(upgrade_event(value.clone()), value);
+
+        let event = parse_event(line).unwrap();
+        assert_eq!(serde_json::to_string(&event).unwrap(), line);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_snake_case_event_with_rfc3339_timestamp() {
+    let event = parse_event(
+        r#"{"type":"TOOL_CALL_START","timestamp":"2023-11-14T22:13:20Z","raw_event":{"call_id":1},"tool_call_id":"call-1","tool_name":"search","parent_message_id":"msg-1"}"#,
+    )
+    .unwrap();
+
+    assert_eq!(event.timestamp, Some(Utc.timestamp_millis_opt(1_700_000_000_000).unwrap()));
+    // Opaque values keep their keys
+    assert_eq!(event.raw_event, Some(json!({"call_id": 1})));
+
+    match &event.data {
+        EventData::ToolCallStart(data) => {
+            assert_eq!(data.tool_call_id, "call-1");
+            assert_eq!(data.tool_call_name, "search");
+            assert_eq!(data.parent_message_id.as_deref(), Some("msg-1"));
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    // Events are always written in the current layout
+    assert_eq!(
+        serde_json::to_string(&event).unwrap(),
+        r#"{"type":"TOOL_CALL_START","timestamp":1700000000000,"rawEvent":{"call_id":1},"toolCallId":"call-1","toolCallName":"search","parentMessageId":"msg-1"}"#
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_camel_case_tool_name_is_upgraded() {
+    let event = parse_event(r#"{"type":"TOOL_CALL_START","toolCallId":"call-1","toolName":"search"}"#).unwrap();
+
+    match &event.data {
+        EventData::ToolCallStart(data) => assert_eq!(data.tool_call_name, "search"),
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_error_is_upgraded() {
+    let legacy = json!({"type": "RUN_ERROR", "thread_id": "t", "run_id": "r", "error": "boom", "code": "E1"});
+    assert_eq!(upgrade_event(legacy.clone()), json!({"type": "RUN_ERROR", "message": "boom", "code": "E1"}));
+
+    let event: BaseEvent = ag_ui_wasm::core::compat::parse_event_value(legacy).unwrap();
+    match &event.data {
+        EventData::RunError(data) => {
+            assert_eq!(data.message, "boom");
+            assert_eq!(data.code.as_deref(), Some("E1"));
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_step_events_are_upgraded() {
+    let started = parse_event(
+        r#"{"type":"STEP_STARTED","thread_id":"t","run_id":"r","step_id":"plan","step_type":"llm"}"#,
+    )
+    .unwrap();
+    let finished = parse_event(r#"{"type":"STEP_FINISHED","threadId":"t","runId":"r","stepId":"plan"}"#).unwrap();
+
+    match (&started.data, &finished.data) {
+        (EventData::StepStarted(started), EventData::StepFinished(finished)) => {
+            assert_eq!(started.step_name, "plan");
+            assert_eq!(finished.step_name, "plan");
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_custom_event_data_becomes_value() {
+    let legacy = json!({
+        "type": "CUSTOM",
+        "timestamp": "2023-11-14T22:13:20Z",
+        "event_type": "user_feedback",
+        "rating": 5,
+        "comment_text": "Great"
+    });
+
+    assert_eq!(
+        upgrade_event(legacy),
+        json!({
+            "type": "CUSTOM",
+            "timestamp": 1_700_000_000_000_i64,
+            "name": "user_feedback",
+            "value": {"rating": 5, "comment_text": "Great"}
+        })
+    );
+
+    let event = parse_event(r#"{"type":"CUSTOM","eventType":"heartbeat"}"#).unwrap();
+    match &event.data {
+        EventData::Custom(data) => {
+            assert_eq!(data.name, "heartbeat");
+            assert!(data.value.is_null());
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_tool_result_becomes_tool_message() {
+    let event = parse_event(
+        r#"{"type":"TOOL_CALL_RESULT","tool_result":{"tool_call_id":"call-1","result":{"temp":18}}}"#,
+    )
+    .unwrap();
+
+    match &event.data {
+        EventData::ToolCallResult(data) => {
+            assert_eq!(data.message_id, "call-1-result");
+            assert_eq!(data.tool_call_id, "call-1");
+            assert_eq!(data.content, r#"{"temp":18}"#);
+            assert_eq!(data.role, Some(Role::Tool));
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    let failed = parse_event(
+        r#"{"type":"TOOL_CALL_RESULT","toolResult":{"toolCallId":"call-2","result":null,"error":"timed out"}}"#,
+    )
+    .unwrap();
+    match &failed.data {
+        EventData::ToolCallResult(data) => assert_eq!(data.content, "timed out"),
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    let text = parse_event(r#"{"type":"TOOL_CALL_RESULT","toolResult":{"toolCallId":"call-3","result":"sunny"}}"#).unwrap();
+    match &text.data {
+        EventData::ToolCallResult(data) => assert_eq!(data.content, "sunny"),
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_state_snapshot_is_upgraded() {
+    let event = parse_event(r#"{"type":"STATE_SNAPSHOT","state":{"user_name":"Ada","count":2}}"#).unwrap();
+
+    match &event.data {
+        EventData::StateSnapshot(data) => {
+            // State keys belong to the application and are not renamed
+            assert_eq!(data.snapshot["user_name"], "Ada");
+            assert_eq!(data.snapshot["count"], 2);
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_snake_case_messages_are_upgraded() {
+    let event = parse_event(
+        r#"{"type":"MESSAGES_SNAPSHOT","messages":[
+            {"id":"a-1","role":"assistant","content":"","tool_calls":[{"id":"call-1","name":"search"}]},
+            {"id":"t-1","role":"tool","content":"done","tool_call_id":"call-1"}
+        ]}"#,
+    )
+    .unwrap();
+
+    match &event.data {
+        EventData::MessagesSnapshot(data) => {
+            assert_eq!(data.messages[0].tool_calls().unwrap()[0], ToolCall::new("call-1", "search", ""));
+            assert_eq!(data.messages[1].tool_call_id(), Some("call-1"));
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_flat_messages_are_converted_to_the_message_union() {
+    let legacy = json!({
+        "type": "MESSAGES_SNAPSHOT",
+        "messages": [
+            {"id": "u-1", "role": "user", "content": "Hi", "metadata": {"client": "web"}, "created_at": "2023-11-14T22:13:20Z"},
+            {"id": "a-1", "role": "assistant", "content": "", "function_call": {"name": "search", "arguments": "{\"q\":\"rust\"}"}}
+        ]
+    });
+
+    assert_eq!(
+        upgrade_event(legacy)["messages"],
+        json!([
+            {"id": "u-1", "role": "user", "content": "Hi"},
+            {"id": "a-1", "role": "assistant", "content": "", "toolCalls": [{"id": "a-1", "type": "function", "function": {"name": "search", "arguments": "{\"q\":\"rust\"}"}}]}
+        ])
+    );
+
+    // A tool message without a tool call id has no spec equivalent
+    let error = parse_event(r#"{"type":"MESSAGES_SNAPSHOT","messages":[{"id":"t-1","role":"tool","content":"done","created_at":null}]}"#)
+        .unwrap_err();
+    assert!(error.to_string().contains("missing field `toolCallId`"), "{}", error);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_obsolete_thinking_fields_are_dropped() {
+    let events = [
+        r#"{"type":"THINKING_START","thread_id":"t","run_id":"r"}"#,
+        r#"{"type":"THINKING_TEXT_MESSAGE_START","message_id":"m","role":"assistant"}"#,
+        r#"{"type":"THINKING_TEXT_MESSAGE_CONTENT","message_id":"m","delta":"Hmm"}"#,
+        r#"{"type":"THINKING_TEXT_MESSAGE_END","message_id":"m"}"#,
+        r#"{"type":"THINKING_END","thread_id":"t","run_id":"r"}"#,
+    ];
+    let expected = [
+        r#"{"type":"THINKING_START"}"#,
+        r#"{"type":"THINKING_TEXT_MESSAGE_START"}"#,
+        r#"{"type":"THINKING_TEXT_MESSAGE_CONTENT","delta":"Hmm"}"#,
+        r#"{"type":"THINKING_TEXT_MESSAGE_END"}"#,
+        r#"{"type":"THINKING_END"}"#,
+    ];
+
+    for (legacy, expected) in events.iter().zip(expected) {
+        let upgraded = upgrade_event(serde_json::from_str(legacy).unwrap());
+        assert_eq!(upgraded, serde_json::from_str::<Value>(expected).unwrap());
+        assert!(parse_event(legacy).is_ok());
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_strict_deserialization_rejects_legacy_shapes() {
+    let legacy = r#"{"type":"TOOL_CALL_START","toolCallId":"call-1","toolName":"search"}"#;
+
+    let error = serde_json::from_str::<BaseEvent>(legacy).unwrap_err().to_string();
+    assert!(error.contains("missing field `toolCallName`"), "{}", error);
+
+    assert_eq!(parse_event(legacy).unwrap().event_type(), EventType::ToolCallStart);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_malformed_legacy_event_is_still_rejected() {
+    let error = parse_event(r#"{"type":"STEP_STARTED","thread_id":"t"}"#).unwrap_err();
+    assert!(error.to_string().contains("missing field `stepName`"), "{}", error);
+
+    assert!(parse_event("not json").is_err());
+}
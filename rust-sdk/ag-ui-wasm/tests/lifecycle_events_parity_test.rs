@@ -3,7 +3,7 @@
 
 use ag_ui_wasm::{
     BaseEvent, EventType, EventData,
-    RunStartedEvent, ErrorEvent,
+    RunStartedEvent, RunFinishedEvent, ErrorEvent,
     StepStartedEvent, StepFinishedEvent,
 };
 use wasm_bindgen_test::*;
@@ -14,12 +14,12 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== RUN LIFECYCLE EVENTS TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_started_event_basic() {
     // Test basic RunStarted event (matching TypeScript round-trip test)
     let event = BaseEvent::run_started("thread-1234".to_string(), "run-5678".to_string());
 
-    assert_eq!(event.event_type, EventType::RunStarted);
+    assert_eq!(event.event_type(), EventType::RunStarted);
 
     if let EventData::RunStarted(data) = &event.data {
         assert_eq!(data.thread_id, "thread-1234");
@@ -34,43 +34,46 @@ fn test_run_started_event_basic() {
 
     assert_eq!(parsed["type"], "RUN_STARTED");
     if let Some(data) = parsed["data"].as_object() {
-        assert_eq!(data["thread_id"], "thread-1234");
-        assert_eq!(data["run_id"], "run-5678");
+        assert_eq!(data["threadId"], "thread-1234");
+        assert_eq!(data["runId"], "run-5678");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_started_event_with_timestamp() {
     // Test RunStarted event with explicit timestamp
     let timestamp = Utc::now();
     let event = BaseEvent {
-        event_type: EventType::RunStarted,
         timestamp: Some(timestamp),
         raw_event: None,
         data: EventData::RunStarted(RunStartedEvent {
             thread_id: "thread-abc".to_string(),
             run_id: "run-def".to_string(),
+            parent_run_id: Some("run-parent".to_string()),
+            input: None,
         }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
-    assert_eq!(deserialized.event_type, EventType::RunStarted);
+    assert_eq!(deserialized.event_type(), EventType::RunStarted);
     assert!(deserialized.timestamp.is_some());
 
     if let EventData::RunStarted(data) = &deserialized.data {
         assert_eq!(data.thread_id, "thread-abc");
         assert_eq!(data.run_id, "run-def");
+        assert_eq!(data.parent_run_id.as_deref(), Some("run-parent"));
+        assert!(data.input.is_none());
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_finished_event_basic() {
     // Test basic RunFinished event (matching TypeScript round-trip test)
     let event = BaseEvent::run_finished("thread-1234".to_string(), "run-5678".to_string());
 
-    assert_eq!(event.event_type, EventType::RunFinished);
+    assert_eq!(event.event_type(), EventType::RunFinished);
 
     if let EventData::RunFinished(data) = &event.data {
         assert_eq!(data.thread_id, "thread-1234");
@@ -85,12 +88,12 @@ fn test_run_finished_event_basic() {
 
     assert_eq!(parsed["type"], "RUN_FINISHED");
     if let Some(data) = parsed["data"].as_object() {
-        assert_eq!(data["thread_id"], "thread-1234");
-        assert_eq!(data["run_id"], "run-5678");
+        assert_eq!(data["threadId"], "thread-1234");
+        assert_eq!(data["runId"], "run-5678");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_error_event_basic() {
     // Test basic RunError event (matching TypeScript round-trip test)
     let event = BaseEvent::error(
@@ -98,7 +101,7 @@ fn test_run_error_event_basic() {
         None,
     );
 
-    assert_eq!(event.event_type, EventType::Error);
+    assert_eq!(event.event_type(), EventType::Error);
 
     if let EventData::Error(data) = &event.data {
         assert_eq!(data.error, "Failed to execute tool call");
@@ -118,7 +121,7 @@ fn test_run_error_event_basic() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_error_event_with_code() {
     // Test RunError event with error code (matching TypeScript detailed error test)
     let event = BaseEvent::error(
@@ -143,30 +146,77 @@ fn test_run_error_event_with_code() {
     }
 }
 
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_finished_event_with_result() {
+    // Test RunFinished carrying a result (matching TypeScript RunFinishedEventSchema)
+    let event = BaseEvent {
+        timestamp: None,
+        raw_event: None,
+        data: EventData::RunFinished(RunFinishedEvent {
+            thread_id: "thread-1234".to_string(),
+            run_id: "run-5678".to_string(),
+            result: Some(json!({"answer": 42, "sources": ["a", "b"]})),
+        }),
+    };
+
+    let parsed = serde_json::to_value(&event).unwrap();
+    assert_eq!(parsed["result"]["answer"], 42);
+
+    let deserialized: BaseEvent = serde_json::from_value(parsed).unwrap();
+    if let EventData::RunFinished(data) = &deserialized.data {
+        assert_eq!(data.result, Some(json!({"answer": 42, "sources": ["a", "b"]})));
+    } else {
+        panic!("Expected RunFinished event data");
+    }
+
+    // Result is omitted when absent
+    let plain = serde_json::to_value(BaseEvent::run_finished("t".to_string(), "r".to_string())).unwrap();
+    assert!(plain.get("result").is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_error_spec_event() {
+    // Test RUN_ERROR with the spec fields (matching TypeScript RunErrorEventSchema)
+    let event = BaseEvent::run_error("Rate limit exceeded".to_string(), Some("rate_limit".to_string()));
+
+    assert_eq!(event.event_type(), EventType::RunError);
+
+    let parsed = serde_json::to_value(&event).unwrap();
+    assert_eq!(parsed["type"], "RUN_ERROR");
+    assert_eq!(parsed["message"], "Rate limit exceeded");
+    assert_eq!(parsed["code"], "rate_limit");
+    assert!(parsed.get("threadId").is_none());
+    assert!(parsed.get("error").is_none());
+
+    let deserialized: BaseEvent = serde_json::from_value(parsed).unwrap();
+    if let EventData::RunError(data) = &deserialized.data {
+        assert_eq!(data.message, "Rate limit exceeded");
+        assert_eq!(data.code, Some("rate_limit".to_string()));
+    } else {
+        panic!("Expected RunError event data");
+    }
+
+    let minimal = serde_json::to_value(BaseEvent::run_error("Failed".to_string(), None)).unwrap();
+    assert!(minimal.get("code").is_none());
+}
+
 // ===== STEP LIFECYCLE EVENTS TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_step_started_event_basic() {
     // Test basic StepStarted event (matching TypeScript round-trip test)
     let event = BaseEvent {
-        event_type: EventType::StepStarted,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StepStarted(StepStartedEvent {
-            thread_id: "thread_123".to_string(),
-            run_id: "run_456".to_string(),
-            step_id: "step_data_analysis".to_string(),
-            step_type: Some("data_analysis".to_string()),
+            step_name: "data_analysis".to_string(),
         }),
     };
 
-    assert_eq!(event.event_type, EventType::StepStarted);
+    assert_eq!(event.event_type(), EventType::StepStarted);
 
     if let EventData::StepStarted(data) = &event.data {
-        assert_eq!(data.thread_id, "thread_123");
-        assert_eq!(data.run_id, "run_456");
-        assert_eq!(data.step_id, "step_data_analysis");
-        assert_eq!(data.step_type, Some("data_analysis".to_string()));
+        assert_eq!(data.step_name, "data_analysis");
     } else {
         panic!("Expected StepStarted event data");
     }
@@ -176,32 +226,26 @@ fn test_step_started_event_basic() {
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "STEP_STARTED");
-    assert_eq!(parsed["thread_id"], "thread_123");
-    assert_eq!(parsed["run_id"], "run_456");
-    assert_eq!(parsed["step_id"], "step_data_analysis");
-    assert_eq!(parsed["step_type"], "data_analysis");
+    assert_eq!(parsed["stepName"], "data_analysis");
+    assert!(parsed.get("threadId").is_none());
+    assert!(parsed.get("stepType").is_none());
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_step_finished_event_basic() {
     // Test basic StepFinished event (matching TypeScript round-trip test)
     let event = BaseEvent {
-        event_type: EventType::StepFinished,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StepFinished(StepFinishedEvent {
-            thread_id: "thread_123".to_string(),
-            run_id: "run_456".to_string(),
-            step_id: "step_data_analysis".to_string(),
+            step_name: "data_analysis".to_string(),
         }),
     };
 
-    assert_eq!(event.event_type, EventType::StepFinished);
+    assert_eq!(event.event_type(), EventType::StepFinished);
 
     if let EventData::StepFinished(data) = &event.data {
-        assert_eq!(data.thread_id, "thread_123");
-        assert_eq!(data.run_id, "run_456");
-        assert_eq!(data.step_id, "step_data_analysis");
+        assert_eq!(data.step_name, "data_analysis");
     } else {
         panic!("Expected StepFinished event data");
     }
@@ -211,61 +255,51 @@ fn test_step_finished_event_basic() {
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "STEP_FINISHED");
-    assert_eq!(parsed["thread_id"], "thread_123");
-    assert_eq!(parsed["run_id"], "run_456");
-    assert_eq!(parsed["step_id"], "step_data_analysis");
+    assert_eq!(parsed["stepName"], "data_analysis");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_step_events_minimal_fields() {
     // Test step events with minimal fields (matching TypeScript minimal fields tests)
 
     // StepStarted with minimal fields
     let step_started = BaseEvent {
-        event_type: EventType::StepStarted,
         timestamp: None, // No timestamp
         raw_event: None,
         data: EventData::StepStarted(StepStartedEvent {
-            thread_id: "thread_minimal".to_string(),
-            run_id: "run_minimal".to_string(),
-            step_id: "step_process_payment".to_string(),
-            step_type: Some("process_payment".to_string()),
+            step_name: "process_payment".to_string(),
         }),
     };
 
     let started_json = serde_json::to_string(&step_started).unwrap();
     let started_deser: BaseEvent = serde_json::from_str(&started_json).unwrap();
 
-    assert_eq!(started_deser.event_type, EventType::StepStarted);
+    assert_eq!(started_deser.event_type(), EventType::StepStarted);
     if let EventData::StepStarted(data) = &started_deser.data {
-        assert_eq!(data.step_id, "step_process_payment");
-        assert_eq!(data.step_type, Some("process_payment".to_string()));
+        assert_eq!(data.step_name, "process_payment");
     }
 
     // StepFinished with minimal fields
     let step_finished = BaseEvent {
-        event_type: EventType::StepFinished,
         timestamp: None, // No timestamp
         raw_event: None,
         data: EventData::StepFinished(StepFinishedEvent {
-            thread_id: "thread_minimal".to_string(),
-            run_id: "run_minimal".to_string(),
-            step_id: "step_process_payment".to_string(),
+            step_name: "process_payment".to_string(),
         }),
     };
 
     let finished_json = serde_json::to_string(&step_finished).unwrap();
     let finished_deser: BaseEvent = serde_json::from_str(&finished_json).unwrap();
 
-    assert_eq!(finished_deser.event_type, EventType::StepFinished);
+    assert_eq!(finished_deser.event_type(), EventType::StepFinished);
     if let EventData::StepFinished(data) = &finished_deser.data {
-        assert_eq!(data.step_id, "step_process_payment");
+        assert_eq!(data.step_name, "process_payment");
     }
 }
 
 // ===== COMPREHENSIVE LIFECYCLE FLOW TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_complete_run_lifecycle() {
     // Test a complete run lifecycle with multiple steps
     let events = vec![
@@ -274,51 +308,37 @@ fn test_complete_run_lifecycle() {
 
         // Start first step
         BaseEvent {
-            event_type: EventType::StepStarted,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::StepStarted(StepStartedEvent {
-                thread_id: "thread_lifecycle".to_string(),
-                run_id: "run_lifecycle".to_string(),
-                step_id: "step_initialization".to_string(),
-                step_type: Some("initialization".to_string()),
+                step_name: "initialization".to_string(),
             }),
         },
 
         // Finish first step
         BaseEvent {
-            event_type: EventType::StepFinished,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::StepFinished(StepFinishedEvent {
-                thread_id: "thread_lifecycle".to_string(),
-                run_id: "run_lifecycle".to_string(),
-                step_id: "step_initialization".to_string(),
+                step_name: "initialization".to_string(),
             }),
         },
 
         // Start second step
         BaseEvent {
-            event_type: EventType::StepStarted,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::StepStarted(StepStartedEvent {
-                thread_id: "thread_lifecycle".to_string(),
-                run_id: "run_lifecycle".to_string(),
-                step_id: "step_data_processing".to_string(),
-                step_type: Some("data_processing".to_string()),
+                step_name: "data_processing".to_string(),
             }),
         },
 
         // Finish second step
         BaseEvent {
-            event_type: EventType::StepFinished,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::StepFinished(StepFinishedEvent {
-                thread_id: "thread_lifecycle".to_string(),
-                run_id: "run_lifecycle".to_string(),
-                step_id: "step_data_processing".to_string(),
+                step_name: "data_processing".to_string(),
             }),
         },
 
@@ -330,7 +350,7 @@ fn test_complete_run_lifecycle() {
     for event in &events {
         let json_str = serde_json::to_string(event).unwrap();
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
     }
 
     // Test batch serialization
@@ -340,12 +360,12 @@ fn test_complete_run_lifecycle() {
     assert_eq!(events.len(), deserialized_events.len());
 
     // Verify the lifecycle flow
-    assert_eq!(deserialized_events[0].event_type, EventType::RunStarted);
-    assert_eq!(deserialized_events[1].event_type, EventType::StepStarted);
-    assert_eq!(deserialized_events[2].event_type, EventType::StepFinished);
-    assert_eq!(deserialized_events[3].event_type, EventType::StepStarted);
-    assert_eq!(deserialized_events[4].event_type, EventType::StepFinished);
-    assert_eq!(deserialized_events[5].event_type, EventType::RunFinished);
+    assert_eq!(deserialized_events[0].event_type(), EventType::RunStarted);
+    assert_eq!(deserialized_events[1].event_type(), EventType::StepStarted);
+    assert_eq!(deserialized_events[2].event_type(), EventType::StepFinished);
+    assert_eq!(deserialized_events[3].event_type(), EventType::StepStarted);
+    assert_eq!(deserialized_events[4].event_type(), EventType::StepFinished);
+    assert_eq!(deserialized_events[5].event_type(), EventType::RunFinished);
 
     // Verify specific data
     if let EventData::RunStarted(data) = &deserialized_events[0].data {
@@ -354,30 +374,24 @@ fn test_complete_run_lifecycle() {
     }
 
     if let EventData::StepStarted(data) = &deserialized_events[1].data {
-        assert_eq!(data.step_id, "step_initialization");
-        assert_eq!(data.step_type, Some("initialization".to_string()));
+        assert_eq!(data.step_name, "initialization");
     }
 
     if let EventData::StepStarted(data) = &deserialized_events[3].data {
-        assert_eq!(data.step_id, "step_data_processing");
-        assert_eq!(data.step_type, Some("data_processing".to_string()));
+        assert_eq!(data.step_name, "data_processing");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_error_during_lifecycle() {
     // Test error event during a run lifecycle
     let events = vec![
         BaseEvent::run_started("thread_error".to_string(), "run_error".to_string()),
         BaseEvent {
-            event_type: EventType::StepStarted,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::StepStarted(StepStartedEvent {
-                thread_id: "thread_error".to_string(),
-                run_id: "run_error".to_string(),
-                step_id: "step_failing".to_string(),
-                step_type: Some("failing_step".to_string()),
+                step_name: "failing_step".to_string(),
             }),
         },
         BaseEvent::error(
@@ -392,7 +406,7 @@ fn test_error_during_lifecycle() {
     for event in &events {
         let json_str = serde_json::to_string(event).unwrap();
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
     }
 
     // Verify error event details
@@ -404,7 +418,7 @@ fn test_error_during_lifecycle() {
 
 // ===== EDGE CASES AND SPECIAL VALUES TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_lifecycle_events_with_special_characters() {
     // Test events with special characters in IDs and names
     let special_chars_events = vec![
@@ -413,14 +427,10 @@ fn test_lifecycle_events_with_special_characters() {
             "run-with-unicode-你好".to_string(),
         ),
         BaseEvent {
-            event_type: EventType::StepStarted,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::StepStarted(StepStartedEvent {
-                thread_id: "thread_special".to_string(),
-                run_id: "run_special".to_string(),
-                step_id: "step_special".to_string(),
-                step_type: Some("step_with_special\"quotes'and\\backslashes".to_string()),
+                step_name: "step_with_special\"quotes'and\\backslashes".to_string(),
             }),
         },
         BaseEvent::error(
@@ -432,7 +442,7 @@ fn test_lifecycle_events_with_special_characters() {
     for event in &special_chars_events {
         let json_str = serde_json::to_string(event).unwrap();
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
     }
 
     // Verify special characters are preserved
@@ -442,8 +452,8 @@ fn test_lifecycle_events_with_special_characters() {
     }
 
     if let EventData::StepStarted(data) = &special_chars_events[1].data {
-        assert!(data.step_type.as_ref().unwrap().contains("\"quotes'"));
-        assert!(data.step_type.as_ref().unwrap().contains("\\backslashes"));
+        assert!(data.step_name.contains("\"quotes'"));
+        assert!(data.step_name.contains("\\backslashes"));
     }
 
     if let EventData::Error(data) = &special_chars_events[2].data {
@@ -453,7 +463,7 @@ fn test_lifecycle_events_with_special_characters() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_lifecycle_events_edge_cases() {
     // Test edge cases for lifecycle events
 
@@ -461,14 +471,10 @@ fn test_lifecycle_events_edge_cases() {
     let empty_events = vec![
         BaseEvent::run_started("".to_string(), "".to_string()),
         BaseEvent {
-            event_type: EventType::StepStarted,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::StepStarted(StepStartedEvent {
-                thread_id: "thread_empty".to_string(),
-                run_id: "run_empty".to_string(),
-                step_id: "step_empty".to_string(),
-                step_type: Some("".to_string()),
+                step_name: "".to_string(),
             }),
         },
         BaseEvent::error("".to_string(), Some("".to_string())),
@@ -477,7 +483,7 @@ fn test_lifecycle_events_edge_cases() {
     for event in &empty_events {
         let json_str = serde_json::to_string(event).unwrap();
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
     }
 
     // Very long strings
@@ -485,14 +491,10 @@ fn test_lifecycle_events_edge_cases() {
     let long_events = vec![
         BaseEvent::run_started(long_string.clone(), long_string.clone()),
         BaseEvent {
-            event_type: EventType::StepStarted,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::StepStarted(StepStartedEvent {
-                thread_id: "thread_long".to_string(),
-                run_id: "run_long".to_string(),
-                step_id: "step_long".to_string(),
-                step_type: Some(long_string.clone()),
+                step_name: long_string.clone(),
             }),
         },
         BaseEvent::error(long_string.clone(), Some(long_string.clone())),
@@ -501,13 +503,13 @@ fn test_lifecycle_events_edge_cases() {
     for event in &long_events {
         let json_str = serde_json::to_string(event).unwrap();
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
     }
 }
 
 // ===== EVENTS WITH RAW EVENT DATA =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_lifecycle_events_with_raw_event() {
     // Test lifecycle events with rawEvent data (matching TypeScript all base fields test)
     let raw_data = json!({
@@ -521,16 +523,16 @@ fn test_lifecycle_events_with_raw_event() {
 
     let events = vec![
         BaseEvent {
-            event_type: EventType::RunStarted,
             timestamp: Some(Utc::now()),
             raw_event: Some(raw_data.clone()),
             data: EventData::RunStarted(RunStartedEvent {
                 thread_id: "thread_with_raw".to_string(),
                 run_id: "run_with_raw".to_string(),
+                parent_run_id: None,
+                input: None,
             }),
         },
         BaseEvent {
-            event_type: EventType::Error,
             timestamp: Some(Utc::now()),
             raw_event: Some(raw_data.clone()),
             data: EventData::Error(ErrorEvent {
@@ -546,14 +548,14 @@ fn test_lifecycle_events_with_raw_event() {
         let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
         // Verify raw_event is included
-        assert!(parsed["raw_event"].is_object());
-        assert_eq!(parsed["raw_event"]["original"], "external_event_data");
-        assert_eq!(parsed["raw_event"]["source"], "external_system");
-        assert_eq!(parsed["raw_event"]["metadata"]["version"], "1.0.0");
+        assert!(parsed["rawEvent"].is_object());
+        assert_eq!(parsed["rawEvent"]["original"], "external_event_data");
+        assert_eq!(parsed["rawEvent"]["source"], "external_system");
+        assert_eq!(parsed["rawEvent"]["metadata"]["version"], "1.0.0");
 
         // Round-trip test
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
         assert!(deserialized.raw_event.is_some());
     }
 }
\ No newline at end of file
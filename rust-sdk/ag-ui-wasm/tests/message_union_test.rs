@@ -0,0 +1,158 @@
+//! Tests for the role-discriminated message union
+//!
+//! Messages follow the TypeScript `MessageSchema`: the `role` field selects
+//! the variant and each variant only accepts the fields valid for its role.
+
+use ag_ui_wasm::{
+    AgUiError, AssistantMessage, DeveloperMessage, FunctionCall, LegacyMessage, Message, Role,
+    SystemMessage, ToolCall, ToolMessage, UserMessage,
+};
+use chrono::Utc;
+use serde_json::json;
+use std::collections::HashMap;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn legacy(id: &str, role: Role, content: &str) -> LegacyMessage {
+    LegacyMessage {
+        id: id.to_string(),
+        role,
+        content: content.to_string(),
+        name: None,
+        tool_call_id: None,
+        tool_calls: None,
+        function_call: None,
+        metadata: None,
+        created_at: None,
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_messages_serialize_in_schema_order() {
+    let messages: Vec<Message> = vec![
+        DeveloperMessage::new("Be brief").with_id("d-1").with_name("ops").into(),
+        SystemMessage::new("You are helpful").with_id("s-1").into(),
+        UserMessage::new("Hi").with_id("u-1").into(),
+        AssistantMessage::new().with_id("a-1").with_content("Hello").into(),
+        ToolMessage::new("18°C", "call-1").with_id("t-1").into(),
+        ToolMessage::new("", "call-2").with_id("t-2").with_error("City not found").into(),
+    ];
+
+    let lines: Vec<String> = messages.iter().map(|message| serde_json::to_string(message).unwrap()).collect();
+    assert_eq!(
+        lines,
+        [
+            r#"{"id":"d-1","role":"developer","content":"Be brief","name":"ops"}"#,
+            r#"{"id":"s-1","role":"system","content":"You are helpful"}"#,
+            r#"{"id":"u-1","role":"user","content":"Hi"}"#,
+            r#"{"id":"a-1","role":"assistant","content":"Hello"}"#,
+            r#"{"id":"t-1","content":"18°C","role":"tool","toolCallId":"call-1"}"#,
+            r#"{"id":"t-2","content":"","role":"tool","toolCallId":"call-2","error":"City not found"}"#,
+        ]
+    );
+
+    for (line, message) in lines.iter().zip(&messages) {
+        assert_eq!(&serde_json::from_str::<Message>(line).unwrap(), message);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_assistant_message_may_omit_content() {
+    let message: Message = serde_json::from_value(json!({
+        "id": "a-1",
+        "role": "assistant",
+        "toolCalls": [{"id": "call-1", "type": "function", "function": {"name": "search", "arguments": "{\"q\":\"rust\"}"}}]
+    }))
+    .unwrap();
+
+    assert_eq!(message.content(), None);
+    assert_eq!(message.tool_calls().unwrap()[0], ToolCall::new("call-1", "search", r#"{"q":"rust"}"#));
+    assert_eq!(
+        serde_json::to_string(&message).unwrap(),
+        r#"{"id":"a-1","role":"assistant","toolCalls":[{"id":"call-1","type":"function","function":{"name":"search","arguments":"{\"q\":\"rust\"}"}}]}"#
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_role_selects_the_variant() {
+    let message: Message = serde_json::from_value(json!({"id": "s-1", "role": "system", "content": "Rules"})).unwrap();
+    assert!(matches!(message, Message::System(_)));
+    assert_eq!(message.role(), Role::System);
+
+    let error = serde_json::from_value::<Message>(json!({"id": "x", "role": "robot", "content": "?"}))
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("unknown variant `robot`"), "{}", error);
+
+    let error = serde_json::from_value::<Message>(json!({"id": "t-1", "role": "tool", "content": "done"}))
+        .unwrap_err()
+        .to_string();
+    assert!(error.contains("missing field `toolCallId`"), "{}", error);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_builders_generate_ids() {
+    let first = Message::user("Hi");
+    let second = Message::user("Hi");
+
+    assert!(!first.id().is_empty());
+    assert_ne!(first.id(), second.id());
+    assert_eq!(Message::tool("done", "call-1").tool_call_id(), Some("call-1"));
+    assert_eq!(Message::assistant("Hello").content(), Some("Hello"));
+    assert_eq!(Message::developer("Note").role(), Role::Developer);
+    assert_eq!(Message::system("Rules").name(), None);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_legacy_messages_convert_by_role() {
+    let mut user = legacy("u-1", Role::User, "Hi");
+    user.name = Some("Ada".to_string());
+    user.metadata = Some(HashMap::from([("client".to_string(), json!("web"))]));
+    user.created_at = Some(Utc::now());
+    assert_eq!(
+        Message::try_from(user).unwrap(),
+        Message::User(UserMessage { id: "u-1".to_string(), content: "Hi".to_string(), name: Some("Ada".to_string()) })
+    );
+
+    let mut tool = legacy("t-1", Role::Tool, "18°C");
+    tool.tool_call_id = Some("call-1".to_string());
+    assert_eq!(Message::try_from(tool).unwrap(), Message::from(ToolMessage::new("18°C", "call-1").with_id("t-1")));
+
+    let mut assistant = legacy("a-1", Role::Assistant, "Let me check");
+    assistant.tool_calls = Some(vec![ToolCall::new("call-1", "search", "")]);
+    assistant.function_call = Some(FunctionCall { name: "lookup".to_string(), arguments: "not json".to_string() });
+    let converted = Message::try_from(assistant).unwrap();
+    let tool_calls = converted.tool_calls().unwrap();
+    assert_eq!(tool_calls.len(), 2);
+    assert_eq!(tool_calls[1].id, "a-1");
+    assert_eq!(tool_calls[1], ToolCall::new("a-1", "lookup", "not json"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_invalid_legacy_messages_are_rejected() {
+    let tool = legacy("t-1", Role::Tool, "done");
+    assert!(matches!(Message::try_from(tool), Err(AgUiError::InvalidMessage(_))));
+
+    let mut user = legacy("u-1", Role::User, "Hi");
+    user.tool_calls = Some(Vec::new());
+    assert!(matches!(Message::try_from(user), Err(AgUiError::InvalidMessage(_))));
+
+    let mut assistant = legacy("a-1", Role::Assistant, "Hi");
+    assistant.tool_call_id = Some("call-1".to_string());
+    assert!(matches!(Message::try_from(assistant), Err(AgUiError::InvalidMessage(_))));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_message_converts_back_to_legacy() {
+    let legacy_message = LegacyMessage::from(Message::tool("done", "call-1"));
+    assert_eq!(legacy_message.role, Role::Tool);
+    assert_eq!(legacy_message.tool_call_id.as_deref(), Some("call-1"));
+
+    let round_trip = Message::try_from(legacy_message.clone()).unwrap();
+    assert_eq!(round_trip.id(), legacy_message.id);
+
+    // The flat struct has nowhere to keep a tool error
+    let failed = LegacyMessage::from(Message::from(ToolMessage::new("", "call-1").with_error("Timed out")));
+    assert!(Message::try_from(failed).unwrap().error().is_none());
+}
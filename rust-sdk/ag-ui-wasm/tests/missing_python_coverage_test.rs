@@ -1,24 +1,22 @@
 //! Tests covering functionality missing from Python SDK comparison
 
 use ag_ui_wasm::{
-    BaseEvent, EventType, EventData, ErrorEvent,
-    SSEEncoder, Message, Role, ToolCall, ToolResult, Context,
+    BaseEvent, EventData, ErrorEvent,
+    SSEEncoder, Message, AssistantMessage, DeveloperMessage, UserMessage, ToolMessage, Role, ToolCall, ToolCallType, ToolResult, Context,
 };
 use wasm_bindgen_test::*;
 use serde_json::json;
 use chrono::Utc;
-use std::collections::HashMap;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== ENCODER TESTS MISSING FROM PYTHON =====
 
 // Python test_encoder.py::test_null_value_exclusion equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_null_value_exclusion_in_encoding() {
     // Test event with None/null fields
     let event = BaseEvent {
-        event_type: EventType::Error,
         timestamp: Some(Utc::now()),
         raw_event: None, // This should be excluded from JSON
         data: EventData::Error(ErrorEvent {
@@ -41,21 +39,20 @@ fn test_null_value_exclusion_in_encoding() {
     assert!(parsed.get("code").is_some());
     
     // None/null fields should be excluded or null
-    assert!(parsed.get("raw_event").is_none() || parsed["raw_event"].is_null());
+    assert!(parsed.get("rawEvent").is_none() || parsed["rawEvent"].is_null());
     assert!(parsed.get("details").is_none() || parsed["details"].is_null());
 }
 
 // Python test_encoder.py::test_round_trip_serialization equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_encoder_round_trip_serialization() {
     // Create complex event
     let original_event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallStart(ag_ui_wasm::ToolCallStartEvent {
             tool_call_id: "call_abc123".to_string(),
-            tool_name: "search_tool".to_string(),
+            tool_call_name: "search_tool".to_string(),
             parent_message_id: Some("msg_parent_456".to_string()),
         }),
     };
@@ -67,19 +64,19 @@ fn test_encoder_round_trip_serialization() {
     
     // Verify field names and values
     assert_eq!(json_data["type"], "TOOL_CALL_START");
-    assert_eq!(json_data["tool_call_id"], "call_abc123");
-    assert_eq!(json_data["tool_name"], "search_tool");
-    assert_eq!(json_data["parent_message_id"], "msg_parent_456");
+    assert_eq!(json_data["toolCallId"], "call_abc123");
+    assert_eq!(json_data["toolCallName"], "search_tool");
+    assert_eq!(json_data["parentMessageId"], "msg_parent_456");
     
     // Deserialize back to event
     let deserialized: BaseEvent = serde_json::from_str(json_part).unwrap();
     
     // Verify round-trip preserved data
-    assert_eq!(deserialized.event_type, original_event.event_type);
+    assert_eq!(deserialized.event_type(), original_event.event_type());
     match (&original_event.data, &deserialized.data) {
         (EventData::ToolCallStart(orig), EventData::ToolCallStart(deser)) => {
             assert_eq!(deser.tool_call_id, orig.tool_call_id);
-            assert_eq!(deser.tool_name, orig.tool_name);
+            assert_eq!(deser.tool_call_name, orig.tool_call_name);
             assert_eq!(deser.parent_message_id, orig.parent_message_id);
         },
         _ => panic!("Event data type mismatch"),
@@ -89,205 +86,155 @@ fn test_encoder_round_trip_serialization() {
 // ===== TYPES TESTS MISSING FROM PYTHON =====
 
 // Python test_types.py::test_function_call_creation equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_function_call_equivalent() {
-    // In Rust SDK, we have ToolCall which is similar to FunctionCall
-    let tool_call = ToolCall {
-        id: "call_123".to_string(),
-        name: "test_function".to_string(),
-        arguments: Some(json!({"param": "value"})),
-    };
+    // A ToolCall wraps the FunctionCall it invokes
+    let tool_call = ToolCall::new("call_123", "test_function", json!({"param": "value"}).to_string());
     
     assert_eq!(tool_call.id, "call_123");
-    assert_eq!(tool_call.name, "test_function");
-    assert!(tool_call.arguments.is_some());
+    assert_eq!(tool_call.call_type, ToolCallType::Function);
+    assert_eq!(tool_call.function.name, "test_function");
+    assert_eq!(tool_call.function.arguments, r#"{"param":"value"}"#);
 }
 
 // Python test_types.py::test_tool_call_serialization equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_serialization() {
-    let tool_call = ToolCall {
-        id: "call_123".to_string(),
-        name: "test_function".to_string(),
-        arguments: Some(json!({"key": "value"})),
-    };
+    let tool_call = ToolCall::new("call_123", "test_function", json!({"key": "value"}).to_string());
     
     let serialized = serde_json::to_value(&tool_call).unwrap();
     assert_eq!(serialized["id"], "call_123");
-    assert_eq!(serialized["name"], "test_function");
-    assert!(serialized["arguments"].is_object());
+    assert_eq!(serialized["type"], "function");
+    assert_eq!(serialized["function"], json!({"name": "test_function", "arguments": "{\"key\":\"value\"}"}));
 }
 
 // Python test_types.py::test_tool_message_camel_case equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_message_serialization() {
-    let tool_msg = Message {
+    let tool_msg = Message::Tool(ToolMessage {
         id: "tool_123".to_string(),
-        role: Role::Tool,
         content: "Tool result".to_string(),
-        tool_call_id: Some("call_456".to_string()),
-        metadata: None,
-            name: None,
-            tool_calls: None,
-            function_call: None,
-        created_at: Some(Utc::now()),
-    };
+        tool_call_id: "call_456".to_string(),
+        error: None,
+    });
     
     let serialized = serde_json::to_value(&tool_msg).unwrap();
     assert_eq!(serialized["role"], "tool");
-    assert_eq!(serialized["tool_call_id"], "call_456");
+    assert_eq!(serialized["toolCallId"], "call_456");
 }
 
 // Python test_types.py::test_parse_camel_case_json_tool_message equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_parse_tool_message_json() {
     let json_data = json!({
         "id": "tool_789",
         "role": "tool",
         "content": "Result from tool",
-        "tool_call_id": "call_123"
+        "toolCallId": "call_123"
     });
     
     let tool_msg: Message = serde_json::from_value(json_data).unwrap();
-    assert_eq!(tool_msg.id, "tool_789");
-    assert_eq!(tool_msg.role, Role::Tool);
-    assert_eq!(tool_msg.content, "Result from tool");
-    assert_eq!(tool_msg.tool_call_id, Some("call_123".to_string()));
+    assert_eq!(tool_msg.id(), "tool_789");
+    assert_eq!(tool_msg.role(), Role::Tool);
+    assert_eq!(tool_msg.content(), Some("Result from tool"));
+    assert_eq!(tool_msg.tool_call_id(), Some("call_123"));
 }
 
 // Python test_types.py::test_developer_message equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_developer_message() {
-    let msg = Message {
+    let msg = Message::Developer(DeveloperMessage {
         id: "dev_123".to_string(),
-        role: Role::Developer, // Now using Developer role
         content: "Developer note".to_string(),
         name: None,
-        tool_call_id: None,
-        tool_calls: None,
-        function_call: None,
-        metadata: Some({
-            let mut map = HashMap::new();
-            map.insert("role_type".to_string(), json!("developer"));
-            map
-        }),
-        created_at: Some(Utc::now()),
-    };
+    });
     
     let serialized = serde_json::to_value(&msg).unwrap();
-    assert_eq!(serialized["role"], "developer");
-    assert_eq!(serialized["content"], "Developer note");
-    assert_eq!(serialized["metadata"]["role_type"], "developer");
+    assert_eq!(serialized, json!({"id": "dev_123", "role": "developer", "content": "Developer note"}));
 }
 
 // Python test_types.py::test_multiple_tool_calls equivalent
-#[wasm_bindgen_test]
-fn test_multiple_tool_calls_in_metadata() {
-    let metadata = json!({
+#[wasm_bindgen_test(unsupported = test)]
+fn test_multiple_tool_calls() {
+    let calls = json!({
         "tool_calls": [
             {
                 "id": "call_1",
-                "name": "get_weather",
-                "arguments": {"location": "New York"}
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": "{\"location\": \"New York\"}"}
             },
             {
                 "id": "call_2",
-                "name": "search_database",
-                "arguments": {"query": "recent sales"}
+                "type": "function",
+                "function": {"name": "search_database", "arguments": "{\"query\": \"recent sales\"}"}
             },
             {
                 "id": "call_3",
-                "name": "calculate",
-                "arguments": {"operation": "sum", "values": [1, 2, 3, 4, 5]}
+                "type": "function",
+                "function": {"name": "calculate", "arguments": "{\"operation\": \"sum\", \"values\": [1, 2, 3, 4, 5]}"}
             }
         ]
     });
     
-    let msg = Message {
+    let msg = Message::Assistant(AssistantMessage {
         id: "asst_multi".to_string(),
-        role: Role::Assistant,
-        content: "I'll perform multiple operations".to_string(),
+        content: Some("I'll perform multiple operations".to_string()),
         name: None,
-        tool_call_id: None,
-        tool_calls: None,
-        function_call: None,
-        metadata: Some({
-            let mut map = HashMap::new();
-            map.insert("tool_calls".to_string(), metadata["tool_calls"].clone());
-            map
-        }),
-        created_at: Some(Utc::now()),
-    };
+        tool_calls: Some(serde_json::from_value(calls["tool_calls"].clone()).unwrap()),
+    });
     
     let serialized = serde_json::to_value(&msg).unwrap();
-    let tool_calls = &serialized["metadata"]["tool_calls"];
+    let tool_calls = &serialized["toolCalls"];
     assert_eq!(tool_calls.as_array().unwrap().len(), 3);
     assert_eq!(tool_calls[0]["id"], "call_1");
-    assert_eq!(tool_calls[1]["name"], "search_database");
-    assert_eq!(tool_calls[2]["arguments"]["operation"], "sum");
+    assert_eq!(tool_calls[1]["function"]["name"], "search_database");
+    assert_eq!(tool_calls[2]["function"]["arguments"], "{\"operation\": \"sum\", \"values\": [1, 2, 3, 4, 5]}");
 }
 
 // Python test_types.py::test_validation_errors equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_validation_behaviors() {
     // Test message with empty content (should be allowed)
-    let empty_msg = Message {
+    let empty_msg = Message::User(UserMessage {
         id: "empty_123".to_string(),
-        role: Role::User,
         content: "".to_string(),
-        tool_call_id: None,
-        metadata: None,
-            name: None,
-            tool_calls: None,
-            function_call: None,
-        created_at: Some(Utc::now()),
-    };
+        name: None,
+    });
     
     let json_result = serde_json::to_string(&empty_msg);
     assert!(json_result.is_ok());
     
     // Test tool message with tool_call_id
-    let tool_msg = Message {
+    let tool_msg = Message::Tool(ToolMessage {
         id: "tool_456".to_string(),
-        role: Role::Tool,
         content: "Tool result".to_string(),
-        tool_call_id: Some("call_789".to_string()),
-        metadata: None,
-            name: None,
-            tool_calls: None,
-            function_call: None,
-        created_at: Some(Utc::now()),
-    };
+        tool_call_id: "call_789".to_string(),
+        error: None,
+    });
     
     let json_result = serde_json::to_string(&tool_msg);
     assert!(json_result.is_ok());
 }
 
 // Python test_types.py::test_name_field_handling equivalent
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_message_name_handling() {
-    // Test message with name in metadata
-    let metadata = json!({"name": "AI Assistant"});
-    
-    let msg = Message {
+    // Test message with a name
+    let msg = Message::Assistant(AssistantMessage {
         id: "asst_named".to_string(),
-        role: Role::Assistant,
-        content: "Hello".to_string(),
+        content: Some("Hello".to_string()),
         name: Some("AI Assistant".to_string()),
-        tool_call_id: None,
         tool_calls: None,
-        function_call: None,
-        metadata: None,
-        created_at: Some(Utc::now()),
-    };
+    });
     
     let serialized = serde_json::to_value(&msg).unwrap();
-    assert_eq!(serialized["metadata"]["name"], "AI Assistant");
+    assert_eq!(serialized["name"], "AI Assistant");
+    assert!(serialized.get("metadata").is_none());
 }
 
 // ===== TOOL RESULT TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_result_structure() {
     // Test successful tool result
     let success_result = ToolResult {
@@ -297,7 +244,7 @@ fn test_tool_result_structure() {
     };
     
     let serialized = serde_json::to_value(&success_result).unwrap();
-    assert_eq!(serialized["tool_call_id"], "call_123");
+    assert_eq!(serialized["toolCallId"], "call_123");
     assert_eq!(serialized["result"]["sum"], 6);
     assert!(serialized["error"].is_null());
     
@@ -315,23 +262,16 @@ fn test_tool_result_structure() {
 
 // ===== CONTEXT TESTS =====
 
-#[wasm_bindgen_test]
-fn test_context_with_metadata() {
+#[wasm_bindgen_test(unsupported = test)]
+fn test_context_description_and_value() {
     let context = Context {
-        user_id: Some("user_123".to_string()),
-        session_id: Some("session_456".to_string()),
-        metadata: Some({
-            let mut map = HashMap::new();
-            map.insert("ip_address".to_string(), json!("192.168.1.1"));
-            map.insert("user_agent".to_string(), json!("Mozilla/5.0..."));
-            map.insert("custom_data".to_string(), json!({"key": "value"}));
-            map
-        }),
+        description: "Client info".to_string(),
+        value: json!({"ip_address": "192.168.1.1", "user_agent": "Mozilla/5.0..."}).to_string(),
     };
     
     let serialized = serde_json::to_value(&context).unwrap();
-    assert_eq!(serialized["user_id"], "user_123");
-    assert_eq!(serialized["session_id"], "session_456");
-    assert_eq!(serialized["metadata"]["ip_address"], "192.168.1.1");
-    assert_eq!(serialized["metadata"]["custom_data"]["key"], "value");
+    assert_eq!(serialized["description"], "Client info");
+    assert_eq!(serialized.as_object().unwrap().len(), 2);
+    let value: serde_json::Value = serde_json::from_str(serialized["value"].as_str().unwrap()).unwrap();
+    assert_eq!(value["ip_address"], "192.168.1.1");
 }
\ No newline at end of file
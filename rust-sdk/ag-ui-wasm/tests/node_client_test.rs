@@ -0,0 +1,335 @@
+//! Tests for `WebAgent` against a local mock server
+//!
+//! Node has no `window`, so these tests also check that `WebAgent` finds
+//! `fetch` on `globalThis`. The server is `tests/support/mock_server.js`.
+
+// These tests run under wasm-bindgen-test in Node only:
+// `wasm-pack test --node -- --test node_client_test`
+#![cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+
+use ag_ui_wasm::{
+    AgUiError, Agent, BaseEvent, EventType, Message, ProtoEncoder, RetryPolicy, RunAgentInput, SSEEncoder,
+    SseEncoderOptions, WebAgent, WebAgentConfig, AGUI_MEDIA_TYPE,
+};
+use js_sys::{Array, Function, Object, Promise, Reflect, Uint8Array};
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+#[wasm_bindgen(module = "/tests/support/mock_server.js")]
+extern "C" {
+    type MockServer;
+
+    #[wasm_bindgen(js_name = "startMockServer")]
+    fn start_mock_server(routes: &Object) -> Promise;
+
+    #[wasm_bindgen(method, getter)]
+    fn url(this: &MockServer) -> String;
+
+    #[wasm_bindgen(method, getter)]
+    fn requests(this: &MockServer) -> Array;
+
+    #[wasm_bindgen(method)]
+    fn close(this: &MockServer);
+}
+
+/// A route answering with `body` as `content_type`
+fn route(content_type: &str, body: &JsValue) -> Object {
+    let route = Object::new();
+    Reflect::set(&route, &"contentType".into(), &content_type.into()).unwrap();
+    Reflect::set(&route, &"body".into(), body).unwrap();
+    route
+}
+
+async fn serve(path: &str, route: impl Into<JsValue>) -> MockServer {
+    let routes = Object::new();
+    Reflect::set(&routes, &path.into(), &route.into()).unwrap();
+    JsFuture::from(start_mock_server(&routes)).await.unwrap().unchecked_into()
+}
+
+fn events() -> Vec<BaseEvent> {
+    vec![
+        BaseEvent::run_started("thread-1".to_string(), "run-1".to_string()),
+        BaseEvent::text_message_start("msg-2".to_string(), None),
+        BaseEvent::text_message_content("msg-2".to_string(), "Hello".to_string()),
+        BaseEvent::text_message_end("msg-2".to_string()),
+        BaseEvent::run_finished("thread-1".to_string(), "run-1".to_string()),
+    ]
+}
+
+fn sse_body(events: &[BaseEvent]) -> JsValue {
+    SSEEncoder::encode_events_string(events).unwrap().into()
+}
+
+/// `events` as SSE with the IDs `last_event_id + 1`, `last_event_id + 2`, …
+fn sse_body_with_ids(last_event_id: u64, events: &[BaseEvent]) -> JsValue {
+    let encoder = SSEEncoder::with_options(SseEncoderOptions {
+        ids: true,
+        last_event_id: Some(last_event_id),
+        ..SseEncoderOptions::default()
+    });
+    Uint8Array::from(encoder.encode_events(events).unwrap().as_slice()).into()
+}
+
+/// A route that sends `body` and then drops the connection
+fn dropped(body: &JsValue) -> Object {
+    let dropped = route("text/event-stream", body);
+    Reflect::set(&dropped, &"drop".into(), &true.into()).unwrap();
+    dropped
+}
+
+fn retrying_agent(server: &MockServer) -> WebAgent {
+    let policy = RetryPolicy { initial_delay_ms: 1, ..RetryPolicy::default() };
+    WebAgent::with_config(WebAgentConfig::new(format!("{}/agent", server.url())).with_retry(policy))
+}
+
+fn input() -> RunAgentInput {
+    RunAgentInput::new("thread-1".to_string(), "run-1".to_string())
+}
+
+/// The request the server received at `index`
+fn request(server: &MockServer, index: u32) -> JsValue {
+    server.requests().get(index)
+}
+
+fn request_header(server: &MockServer, index: u32, name: &str) -> Option<String> {
+    let headers = Reflect::get(&request(server, index), &"headers".into()).unwrap();
+    Reflect::get(&headers, &name.into()).unwrap().as_string()
+}
+
+fn request_body(server: &MockServer, index: u32) -> Value {
+    let body = Reflect::get(&request(server, index), &"body".into()).unwrap().as_string().unwrap();
+    serde_json::from_str(&body).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_run_agent_against_a_server() {
+    let server = serve("/agent", route("text/event-stream", &sse_body(&events()))).await;
+    let mut agent = WebAgent::new(format!("{}/agent", server.url()));
+    agent.set_messages(vec![Message::user("Hi")]);
+
+    let result = agent.run_agent(input()).await.unwrap();
+
+    assert_eq!(result.new_messages.len(), 1);
+    assert_eq!(result.new_messages[0].content(), Some("Hello"));
+    assert_eq!(agent.messages().len(), 2);
+
+    let sent = request_body(&server, 0);
+    assert_eq!(sent["threadId"], "thread-1");
+    assert_eq!(sent["messages"][0]["content"], "Hi");
+    assert_eq!(request_header(&server, 0, "content-type").as_deref(), Some("application/json"));
+    assert_eq!(request_header(&server, 0, "accept").as_deref(), Some("text/event-stream"));
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_protobuf_response() {
+    let encoder = ProtoEncoder::new();
+    let frames: Vec<u8> = events().iter().flat_map(|event| encoder.encode_event(event).unwrap()).collect();
+    let server = serve("/agent", route(AGUI_MEDIA_TYPE, &Uint8Array::from(frames.as_slice()).into())).await;
+    let agent = WebAgent::new(format!("{}/agent", server.url()));
+
+    let mut seen = Vec::new();
+    agent.run_agent_with(input(), |event| seen.push(event.event_type())).await.unwrap();
+
+    assert_eq!(seen.len(), 5);
+    assert_eq!(seen[4], EventType::RunFinished);
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_configured_headers_are_sent() {
+    let server = serve("/agent", route("text/event-stream", &sse_body(&events()))).await;
+    let config = WebAgentConfig::new(format!("{}/agent", server.url()))
+        .with_header("X-Team", "search")
+        .with_header_provider(|| async { Ok(vec![("Authorization".to_string(), "Bearer fresh".to_string())]) });
+    let agent = WebAgent::with_config(config);
+
+    agent.run_agent(input()).await.unwrap();
+
+    assert_eq!(request_header(&server, 0, "x-team").as_deref(), Some("search"));
+    assert_eq!(request_header(&server, 0, "authorization").as_deref(), Some("Bearer fresh"));
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_error_status_fails_the_run() {
+    let server = serve("/agent", route("text/event-stream", &sse_body(&events()))).await;
+    let agent = WebAgent::new(format!("{}/missing", server.url()));
+
+    let error = agent.run_agent(input()).await.unwrap_err();
+
+    assert!(matches!(error, AgUiError::HttpError { status: 404, .. }));
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_error_status_keeps_the_response_body() {
+    let unavailable = route("text/plain", &"model unavailable".into());
+    Reflect::set(&unavailable, &"status".into(), &503.into()).unwrap();
+    let server = serve("/agent", unavailable).await;
+    let agent = WebAgent::new(format!("{}/agent", server.url()));
+
+    let error = agent.run_agent(input()).await.unwrap_err();
+
+    assert!(matches!(&error, AgUiError::HttpError { status: 503, body, .. } if body == "model unavailable"));
+    let js_error = JsValue::from(error);
+    assert_eq!(Reflect::get(&js_error, &"code".into()).unwrap(), "HTTP_ERROR");
+    assert_eq!(Reflect::get(&js_error, &"status".into()).unwrap(), 503);
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_abort_run_stops_an_open_response() {
+    let open = route("text/event-stream", &sse_body(&events()[..3]));
+    Reflect::set(&open, &"hang".into(), &true.into()).unwrap();
+    let server = serve("/agent", open).await;
+    let agent = WebAgent::new(format!("{}/agent", server.url()));
+
+    let mut seen = Vec::new();
+    let outcome = agent
+        .run_agent_with(input(), |event| {
+            seen.push(event.event_type());
+            // Stop once the message has started streaming
+            if event.event_type() == EventType::TextMessageContent {
+                agent.abort_run();
+            }
+        })
+        .await;
+
+    assert!(matches!(outcome, Err(AgUiError::Aborted)));
+    assert_eq!(seen.last(), Some(&EventType::RunError));
+    assert_eq!(agent.messages()[0].content(), Some("Hello"));
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_injected_fetch_is_used() {
+    let server = serve("/agent", route("text/event-stream", &sse_body(&events()))).await;
+    // Records each request's URL, then passes it to the real fetch
+    let fetch: Function = Function::new_no_args(
+        "const calls = []; \
+         const fetch = (request) => { calls.push(request.url); return globalThis.fetch(request); }; \
+         fetch.calls = calls; \
+         return fetch;",
+    )
+    .call0(&JsValue::NULL)
+    .unwrap()
+    .unchecked_into();
+    let url = format!("{}/agent", server.url());
+    let agent = WebAgent::with_config(WebAgentConfig::new(url.clone()).with_fetch(fetch.clone()));
+
+    agent.run_agent(input()).await.unwrap();
+
+    let calls: Array = Reflect::get(&fetch, &"calls".into()).unwrap().unchecked_into();
+    assert_eq!(calls.length(), 1);
+    assert_eq!(calls.get(0).as_string(), Some(url));
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_retry_resumes_a_dropped_stream() {
+    let events = events();
+    // The resumed stream starts with the last event received, which is dropped
+    let responses = Array::of2(
+        &dropped(&sse_body_with_ids(0, &events[..3])),
+        &route("text/event-stream", &sse_body_with_ids(2, &events[2..])),
+    );
+    let server = serve("/agent", responses).await;
+    let agent = retrying_agent(&server);
+
+    let mut seen = Vec::new();
+    let result = agent.run_agent_with(input(), |event| seen.push(event.event_type())).await.unwrap();
+
+    assert_eq!(seen, events.iter().map(BaseEvent::event_type).collect::<Vec<_>>());
+    assert_eq!(result.new_messages[0].content(), Some("Hello"));
+    assert_eq!(request_header(&server, 0, "last-event-id"), None);
+    assert_eq!(request_header(&server, 1, "last-event-id").as_deref(), Some("3"));
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_events_without_their_own_id_are_not_replays() {
+    let events = events();
+    // Only some events carry an `id:`; the others inherit the last one sent
+    let body: String = events
+        .iter()
+        .enumerate()
+        .map(|(index, event)| {
+            let id = if index % 2 == 0 { format!("id: {}\n", index + 1) } else { String::new() };
+            format!("{}data: {}\n\n", id, serde_json::to_string(event).unwrap())
+        })
+        .collect();
+    let server = serve("/agent", route("text/event-stream", &body.into())).await;
+
+    let mut seen = Vec::new();
+    retrying_agent(&server).run_agent_with(input(), |event| seen.push(event.event_type())).await.unwrap();
+
+    assert_eq!(seen, events.iter().map(BaseEvent::event_type).collect::<Vec<_>>());
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_retry_reconnects_after_fetch_rejects() {
+    let server = serve("/agent", route("text/event-stream", &sse_body(&events()))).await;
+    // Fails the first request as a network error would, then passes requests to the real fetch
+    let fetch: Function = Function::new_no_args(
+        "let calls = 0; \
+         const fetch = (request) => { \
+           calls += 1; fetch.calls = calls; \
+           return calls === 1 ? Promise.reject(new TypeError('fetch failed')) : globalThis.fetch(request); \
+         }; \
+         return fetch;",
+    )
+    .call0(&JsValue::NULL)
+    .unwrap()
+    .unchecked_into();
+    let policy = RetryPolicy { initial_delay_ms: 1, ..RetryPolicy::default() };
+    let config = WebAgentConfig::new(format!("{}/agent", server.url())).with_fetch(fetch.clone()).with_retry(policy);
+    let agent = WebAgent::with_config(config);
+
+    let result = agent.run_agent(input()).await.unwrap();
+
+    assert_eq!(result.new_messages[0].content(), Some("Hello"));
+    assert_eq!(Reflect::get(&fetch, &"calls".into()).unwrap(), 2);
+    assert_eq!(server.requests().length(), 1);
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_abort_run_ends_the_wait_between_retries() {
+    let server = serve("/agent", dropped(&sse_body_with_ids(0, &events()[..3]))).await;
+    // Long enough that only the abort can end the wait
+    let policy = RetryPolicy { initial_delay_ms: 60_000, ..RetryPolicy::default() };
+    let agent = WebAgent::with_config(WebAgentConfig::new(format!("{}/agent", server.url())).with_retry(policy));
+
+    let mut stream = agent.run(input()).await.unwrap();
+    for _ in 0..3 {
+        stream.next_event().await.unwrap().unwrap();
+    }
+    let aborter = agent.clone();
+    let abort = Closure::once_into_js(move || aborter.abort_run());
+    let set_timeout: Function = Reflect::get(&js_sys::global(), &"setTimeout".into()).unwrap().unchecked_into();
+    set_timeout.call2(&JsValue::NULL, &abort, &10.into()).unwrap();
+
+    assert!(matches!(stream.next_event().await, Err(AgUiError::Aborted)));
+    assert_eq!(server.requests().length(), 1);
+    server.close();
+}
+
+#[wasm_bindgen_test]
+async fn test_resume_fails_when_the_server_refuses() {
+    let gone = Object::new();
+    Reflect::set(&gone, &"status".into(), &410.into()).unwrap();
+    let server = serve("/agent", Array::of2(&dropped(&sse_body_with_ids(0, &events()[..3])), &gone)).await;
+    let agent = retrying_agent(&server);
+
+    let error = agent.run_agent(input()).await.unwrap_err();
+
+    assert!(matches!(error, AgUiError::ResumeFailed(message) if message.contains("HTTP 410")));
+    // The events received before the connection dropped are kept
+    assert_eq!(agent.messages()[0].content(), Some("Hello"));
+    server.close();
+}
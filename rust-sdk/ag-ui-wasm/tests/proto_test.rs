@@ -0,0 +1,253 @@
+//! Tests for the protobuf encoding of events
+//!
+//! `tests/fixtures/proto/events.jsonl` pairs events in the `@ag-ui/core`
+//! JSON shape with their `ag_ui.Event` encoding as `@ag-ui/proto` writes it.
+//! The bytes were first worked out from the encoders ts-proto generates for
+//! the package, without running it; `generate.mjs` next to the fixture
+//! rewrites them with the package itself and should leave them unchanged.
+
+use ag_ui_wasm::proto::{self, AGUI_MEDIA_TYPE};
+use ag_ui_wasm::{
+    AgUiError, AssistantMessage, BaseEvent, EventType, MessagesSnapshotEvent, PatchOperation, ProtoEncoder,
+    ProtoParser, Role, StateDeltaEvent, TextMessageChunkEvent, ToolCall, ToolCallChunkEvent, ToolMessage, UserMessage,
+};
+use serde_json::{json, Value};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const EVENTS_FIXTURE: &str = include_str!("fixtures/proto/events.jsonl");
+
+struct GoldenCase {
+    name: String,
+    event: Value,
+    bytes: Vec<u8>,
+}
+
+fn golden_cases() -> Vec<GoldenCase> {
+    EVENTS_FIXTURE
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let case: Value = serde_json::from_str(line).unwrap();
+            GoldenCase {
+                name: case["name"].as_str().unwrap().to_string(),
+                event: case["event"].clone(),
+                bytes: from_hex(case["hex"].as_str().unwrap()),
+            }
+        })
+        .collect()
+}
+
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+fn round_trip(event: &BaseEvent) -> BaseEvent {
+    proto::decode(&proto::encode(event).unwrap()).unwrap()
+}
+
+fn to_json(event: &BaseEvent) -> Value {
+    serde_json::to_value(event).unwrap()
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_events_encode_to_the_proto_package_bytes() {
+    for case in golden_cases() {
+        let event: BaseEvent = serde_json::from_value(case.event).unwrap();
+        assert_eq!(proto::encode(&event).unwrap(), case.bytes, "{}", case.name);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_proto_package_bytes_decode_to_the_events() {
+    for case in golden_cases() {
+        let event = proto::decode(&case.bytes).unwrap_or_else(|e| panic!("{}: {}", case.name, e));
+        assert_eq!(to_json(&event), case.event, "{}", case.name);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_frames_are_length_prefixed() {
+    let event = BaseEvent::text_message_end("msg-1".to_string());
+    let message = proto::encode(&event).unwrap();
+    let frame = ProtoEncoder::new().encode_event(&event).unwrap();
+
+    assert_eq!(frame[..4], (message.len() as u32).to_be_bytes());
+    assert_eq!(frame[4..], message);
+    assert_eq!(ProtoEncoder::new().content_type(), AGUI_MEDIA_TYPE);
+    assert_eq!(AGUI_MEDIA_TYPE, "application/vnd.ag-ui.event+proto");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_parser_reassembles_frames_split_anywhere() {
+    let events: Vec<BaseEvent> = golden_cases()
+        .into_iter()
+        .map(|case| serde_json::from_value(case.event).unwrap())
+        .collect();
+    let body = ProtoEncoder::new().encode_events(&events).unwrap();
+
+    for chunk_size in [1, 3, 4, 7, body.len()] {
+        let mut parser = ProtoParser::new();
+        let mut decoded = Vec::new();
+        for chunk in body.chunks(chunk_size) {
+            decoded.extend(parser.feed_events(chunk).unwrap());
+        }
+        parser.finish().unwrap();
+
+        let decoded: Vec<Value> = decoded.iter().map(to_json).collect();
+        let expected: Vec<Value> = events.iter().map(to_json).collect();
+        assert_eq!(decoded, expected, "chunks of {} bytes", chunk_size);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_truncated_stream_is_an_error() {
+    let frame = ProtoEncoder::new().encode_event(&BaseEvent::text_message_end("msg-1".to_string())).unwrap();
+
+    let mut parser = ProtoParser::new();
+    assert_eq!(parser.feed(&frame).unwrap().len(), 1);
+    assert!(parser.feed(&frame[..frame.len() - 1]).unwrap().is_empty());
+    let error = parser.finish().unwrap_err();
+    let offset = frame.len() as u64;
+    assert!(matches!(error, AgUiError::DecodingError { offset: Some(at), .. } if at == offset));
+    assert_eq!(error.code(), "DECODING_ERROR");
+
+    // A partial length prefix is truncated too
+    let mut parser = ProtoParser::new();
+    assert!(parser.feed(&frame[..2]).unwrap().is_empty());
+    assert!(parser.finish().is_err());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_frames_over_the_limit_are_rejected_from_their_prefix() {
+    assert_eq!(ProtoParser::new().max_frame_len(), 16 * 1024 * 1024);
+
+    let frame = ProtoEncoder::new().encode_event(&BaseEvent::text_message_end("msg-1".to_string())).unwrap();
+    let message_len = frame.len() - 4;
+    let mut parser = ProtoParser::new().with_max_frame_len(message_len);
+    assert_eq!(parser.feed(&frame).unwrap().len(), 1);
+
+    let mut parser = ProtoParser::new().with_max_frame_len(message_len - 1);
+    let error = parser.feed(&frame).unwrap_err();
+    assert!(matches!(error, AgUiError::DecodingError { offset: Some(0), .. }));
+
+    // Only the prefix is needed, not the gigabytes it announces
+    let error = ProtoParser::new().feed_events(&[0xff, 0xff, 0xff, 0xff]).unwrap_err();
+    assert_eq!(error.code(), "DECODING_ERROR");
+    assert!(error.to_string().contains("exceeds the limit of 16777216 bytes"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_messages_before_an_oversized_frame_are_kept() {
+    let frame = ProtoEncoder::new().encode_event(&BaseEvent::text_message_end("msg-1".to_string())).unwrap();
+    let mut chunk = frame.clone();
+    chunk.extend_from_slice(&[0xff, 0xff, 0xff, 0xff, 0x0a]);
+
+    let mut parser = ProtoParser::new();
+    let events = parser.feed_events(&chunk).unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].event_type(), EventType::TextMessageEnd);
+
+    // The error comes with the next call, and every one after it
+    let offset = frame.len() as u64;
+    for error in [parser.feed(&frame).unwrap_err(), parser.feed(&[]).unwrap_err(), parser.finish().unwrap_err()] {
+        assert!(matches!(error, AgUiError::DecodingError { offset: Some(at), .. } if at == offset), "{}", error);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_decoding_errors_carry_the_frame_offset() {
+    let frame = ProtoEncoder::new().encode_event(&BaseEvent::text_message_end("msg-1".to_string())).unwrap();
+    let mut body = frame.clone();
+    body.extend_from_slice(&[0, 0, 0, 3, 0x0a, 0x05, 0x0a]);
+
+    let error = ProtoParser::new().feed_events(&body).unwrap_err();
+    let offset = frame.len() as u64;
+    assert!(matches!(error, AgUiError::DecodingError { offset: Some(at), .. } if at == offset));
+    assert!(error.to_string().starts_with(&format!("Decoding error at byte {}: ", offset)));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_invalid_messages_fail_to_decode() {
+    // An `Event` with no event set
+    assert!(matches!(proto::decode(&[]), Err(AgUiError::DecodingError { .. })));
+    // A length-delimited field running past the end of the message
+    assert!(matches!(proto::decode(&[0x0a, 0x05, 0x0a]), Err(AgUiError::DecodingError { .. })));
+    // A state snapshot that is not an object
+    assert!(proto::decode(&from_hex("3a070a0012031a0178")).is_err());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_events_without_a_proto_message_are_rejected() {
+    let unsupported = [
+        json!({"type": "RUN_ABORTED", "threadId": "thread-1", "runId": "run-1"}),
+        json!({"type": "ERROR", "error": "boom"}),
+        json!({"type": "TOOL_CALL_RESULT", "messageId": "msg-2", "toolCallId": "call-1", "content": "ok"}),
+        json!({"type": "THINKING_START"}),
+        json!({"type": "THINKING_TEXT_MESSAGE_CONTENT", "delta": "hmm"}),
+    ];
+    for event in unsupported {
+        let event: BaseEvent = serde_json::from_value(event).unwrap();
+        assert!(matches!(proto::encode(&event), Err(AgUiError::EncodingError(_))), "{}", event.event_type());
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_chunk_events_round_trip() {
+    // The proto `EventType` enum has no chunk types; the `oneof` still tells them apart
+    let text_chunk = BaseEvent::new(TextMessageChunkEvent {
+        message_id: Some("msg-1".to_string()),
+        role: Some(Role::Assistant),
+        delta: Some("Hi".to_string()),
+    });
+    let tool_chunk = BaseEvent::new(ToolCallChunkEvent {
+        tool_call_id: Some("call-1".to_string()),
+        tool_call_name: Some("search".to_string()),
+        parent_message_id: None,
+        delta: Some("{}".to_string()),
+    });
+
+    for event in [text_chunk, tool_chunk] {
+        assert_eq!(to_json(&round_trip(&event)), to_json(&event));
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_messages_and_patches_round_trip() {
+    let messages = BaseEvent::new(MessagesSnapshotEvent {
+        messages: vec![
+            UserMessage::new("Search for rust").with_id("msg-0").into(),
+            AssistantMessage::new()
+                .with_id("msg-1")
+                .with_tool_calls(vec![
+                    ToolCall::new("call-1", "search", json!({"q": "rust"}).to_string()),
+                    ToolCall::new("call-2", "echo", "not json"),
+                    ToolCall::new("call-3", "now", ""),
+                ])
+                .into(),
+            ToolMessage::new("", "call-3").with_id("msg-2").with_error("Clock unavailable").into(),
+        ],
+    });
+    assert_eq!(to_json(&round_trip(&messages)), to_json(&messages));
+
+    let delta = BaseEvent::new(StateDeltaEvent::new(&[
+        PatchOperation::Add { path: "/big".to_string(), value: json!(9_007_199_254_740_991i64) },
+        PatchOperation::Replace { path: "/ratio".to_string(), value: json!(0.1) },
+        PatchOperation::Test { path: "/nothing".to_string(), value: Value::Null },
+    ]));
+    assert_eq!(to_json(&round_trip(&delta)), to_json(&delta));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_started_loses_fields_the_schema_lacks() {
+    let event: BaseEvent = serde_json::from_value(json!({
+        "type": "RUN_STARTED",
+        "threadId": "thread-1",
+        "runId": "run-2",
+        "parentRunId": "run-1",
+    }))
+    .unwrap();
+
+    assert_eq!(to_json(&round_trip(&event)), json!({"type": "RUN_STARTED", "threadId": "thread-1", "runId": "run-2"}));
+}
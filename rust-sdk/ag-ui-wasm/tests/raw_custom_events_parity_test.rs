@@ -12,7 +12,7 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== RAW EVENT TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_raw_event_basic() {
     // Test basic RawEvent (matching TypeScript round-trip test)
     let raw_data = json!({
@@ -23,13 +23,12 @@ fn test_raw_event_basic() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: raw_data.clone() }),
+        data: EventData::Raw(RawEvent { event: raw_data.clone(), source: None }),
     };
 
-    assert_eq!(event.event_type, EventType::Raw);
+    assert_eq!(event.event_type(), EventType::Raw);
 
     if let EventData::Raw(data) = &event.data {
         assert_eq!(data.event["type"], "user_action");
@@ -49,7 +48,7 @@ fn test_raw_event_basic() {
     assert_eq!(parsed["event"]["action"], "button_click");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_raw_event_with_source() {
     // Test RawEvent with source field
     let raw_data = json!({
@@ -58,24 +57,23 @@ fn test_raw_event_with_source() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: raw_data.clone() }),
+        data: EventData::Raw(RawEvent { event: raw_data.clone(), source: None }),
     };
 
     // Test round-trip serialization
     let json_str = serde_json::to_string(&event).unwrap();
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
-    assert_eq!(deserialized.event_type, EventType::Raw);
+    assert_eq!(deserialized.event_type(), EventType::Raw);
     if let EventData::Raw(data) = &deserialized.data {
         assert_eq!(data.event["eventId"], "evt_123");
         assert_eq!(data.event["payload"], "test_data");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_raw_event_complex_nested_data() {
     // Test RawEvent with complex nested data (matching TypeScript complex nested test)
     let complex_data = json!({
@@ -119,10 +117,9 @@ fn test_raw_event_complex_nested_data() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: complex_data.clone() }),
+        data: EventData::Raw(RawEvent { event: complex_data.clone(), source: None }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
@@ -145,16 +142,15 @@ fn test_raw_event_complex_nested_data() {
 
 // ===== CUSTOM EVENT TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_custom_event_basic() {
     // Test basic CustomEvent (matching TypeScript round-trip test)
     let event = BaseEvent {
-        event_type: EventType::Custom,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::Custom(CustomEvent {
-            event_type: "user_preference_updated".to_string(),
-            data: json!({
+            name: "user_preference_updated".to_string(),
+            value: json!({
                 "theme": "dark",
                 "fontSize": "medium",
                 "notifications": true
@@ -162,13 +158,13 @@ fn test_custom_event_basic() {
         }),
     };
 
-    assert_eq!(event.event_type, EventType::Custom);
+    assert_eq!(event.event_type(), EventType::Custom);
 
     if let EventData::Custom(data) = &event.data {
-        assert_eq!(data.event_type, "user_preference_updated");
-        assert_eq!(data.data["theme"], "dark");
-        assert_eq!(data.data["fontSize"], "medium");
-        assert_eq!(data.data["notifications"], true);
+        assert_eq!(data.name, "user_preference_updated");
+        assert_eq!(data.value["theme"], "dark");
+        assert_eq!(data.value["fontSize"], "medium");
+        assert_eq!(data.value["notifications"], true);
     } else {
         panic!("Expected Custom event data");
     }
@@ -178,26 +174,25 @@ fn test_custom_event_basic() {
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "CUSTOM");
-    assert_eq!(parsed["event_type"], "user_preference_updated");
-    assert_eq!(parsed["theme"], "dark");
-    assert_eq!(parsed["fontSize"], "medium");
+    assert_eq!(parsed["name"], "user_preference_updated");
+    assert_eq!(parsed["value"]["theme"], "dark");
+    assert_eq!(parsed["value"]["fontSize"], "medium");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_custom_event_without_value() {
     // Test CustomEvent without value (matching TypeScript no value test)
     let event = BaseEvent {
-        event_type: EventType::Custom,
         timestamp: None,
         raw_event: None,
         data: EventData::Custom(CustomEvent {
-            event_type: "heartbeat".to_string(),
-            data: json!({}),
+            name: "heartbeat".to_string(),
+            value: serde_json::Value::Null,
         }),
     };
 
     if let EventData::Custom(data) = &event.data {
-        assert_eq!(data.event_type, "heartbeat");
+        assert_eq!(data.name, "heartbeat");
     } else {
         panic!("Expected Custom event data");
     }
@@ -207,12 +202,20 @@ fn test_custom_event_without_value() {
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
     if let EventData::Custom(data) = &deserialized.data {
-        assert_eq!(data.event_type, "heartbeat");
-        // Value should be None or excluded from JSON
+        assert_eq!(data.name, "heartbeat");
+        assert!(data.value.is_null());
+    }
+
+    // A missing value is accepted, as in the TypeScript schema
+    let minimal: BaseEvent = serde_json::from_str(r#"{"type":"CUSTOM","name":"heartbeat"}"#).unwrap();
+    if let EventData::Custom(data) = &minimal.data {
+        assert!(data.value.is_null());
+    } else {
+        panic!("Expected Custom event data");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_custom_event_complex_values() {
     // Test CustomEvent with complex values (matching TypeScript complex values test)
     let complex_value = json!({
@@ -251,12 +254,11 @@ fn test_custom_event_complex_values() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Custom,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::Custom(CustomEvent {
-            event_type: "analytics_update".to_string(),
-            data: complex_value.clone(),
+            name: "analytics_update".to_string(),
+            value: complex_value.clone(),
         }),
     };
 
@@ -264,8 +266,8 @@ fn test_custom_event_complex_values() {
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
     if let EventData::Custom(data) = &deserialized.data {
-        assert_eq!(data.event_type, "analytics_update");
-        let value = &data.data;
+        assert_eq!(data.name, "analytics_update");
+        let value = &data.value;
             // Verify complex nested structure
             assert_eq!(value["metrics"]["active_users"], 12345);
             assert_eq!(value["metrics"]["conversion_rate"], 0.0354);
@@ -282,7 +284,7 @@ fn test_custom_event_complex_values() {
 
 // ===== EDGE CASES AND SPECIAL VALUES =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_raw_event_special_values() {
     // Test RawEvent with special values (matching encoder special values patterns)
     let special_data = json!({
@@ -290,7 +292,7 @@ fn test_raw_event_special_values() {
         "emptyString": "",
         "zero": 0,
         "negativeNumber": -123,
-        "floatNumber": 3.14159,
+        "floatNumber": 2.5,
         "emptyArray": [],
         "emptyObject": {},
         "boolValues": {
@@ -302,10 +304,9 @@ fn test_raw_event_special_values() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: special_data.clone() }),
+        data: EventData::Raw(RawEvent { event: special_data.clone(), source: None }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
@@ -316,7 +317,7 @@ fn test_raw_event_special_values() {
         assert_eq!(data.event["emptyString"], "");
         assert_eq!(data.event["zero"], 0);
         assert_eq!(data.event["negativeNumber"], -123);
-        assert_eq!(data.event["floatNumber"], 3.14159);
+        assert_eq!(data.event["floatNumber"], 2.5);
         assert_eq!(data.event["emptyArray"], json!([]));
         assert_eq!(data.event["emptyObject"], json!({}));
         assert_eq!(data.event["boolValues"]["true"], true);
@@ -327,7 +328,7 @@ fn test_raw_event_special_values() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_custom_event_unicode_and_special_chars() {
     // Test CustomEvent with Unicode and special characters
     let unicode_name = "event_with_unicode_你好_🚀";
@@ -339,12 +340,11 @@ fn test_custom_event_unicode_and_special_chars() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Custom,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::Custom(CustomEvent {
-            event_type: unicode_name.to_string(),
-            data: unicode_value.clone(),
+            name: unicode_name.to_string(),
+            value: unicode_value.clone(),
         }),
     };
 
@@ -352,8 +352,8 @@ fn test_custom_event_unicode_and_special_chars() {
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
     if let EventData::Custom(data) = &deserialized.data {
-        assert_eq!(data.event_type, unicode_name);
-        let value = &data.data;
+        assert_eq!(data.name, unicode_name);
+        let value = &data.value;
             assert!(value["message"].as_str().unwrap().contains("你好"));
             assert!(value["message"].as_str().unwrap().contains("🌍"));
             assert!(value["specialChars"].as_str().unwrap().contains("\\n\\t"));
@@ -362,7 +362,7 @@ fn test_custom_event_unicode_and_special_chars() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_raw_event_large_data() {
     // Test RawEvent with large data structures
     let large_array: Vec<serde_json::Value> = (0..1000)
@@ -390,10 +390,9 @@ fn test_raw_event_large_data() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: large_data.clone() }),
+        data: EventData::Raw(RawEvent { event: large_data.clone(), source: None }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
@@ -410,13 +409,12 @@ fn test_raw_event_large_data() {
 
 // ===== COMPREHENSIVE MIXED EVENT TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_mixed_raw_and_custom_events() {
     // Test a mixture of raw and custom events
     let events = vec![
         // Raw event
         BaseEvent {
-            event_type: EventType::Raw,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::Raw(RawEvent { event: json!({
@@ -424,17 +422,16 @@ fn test_mixed_raw_and_custom_events() {
                 "event": "user_login",
                 "userId": "user_123",
                 "timestamp": 1676480210000i64
-            }) }),
+            }), source: Some("api".to_string()) }),
         },
 
         // Custom event
         BaseEvent {
-            event_type: EventType::Custom,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::Custom(CustomEvent {
-                event_type: "feature_flag_toggled".to_string(),
-                data: json!({
+                name: "feature_flag_toggled".to_string(),
+                value: json!({
                     "flag": "new_ui",
                     "enabled": true,
                     "userId": "user_123"
@@ -444,7 +441,6 @@ fn test_mixed_raw_and_custom_events() {
 
         // Raw event with complex structure
         BaseEvent {
-            event_type: EventType::Raw,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::Raw(RawEvent { event: json!({
@@ -455,17 +451,16 @@ fn test_mixed_raw_and_custom_events() {
                     "jsExecutionTime": 0.3
                 },
                 "userAgent": "Mozilla/5.0..."
-            }) }),
+            }), source: None }),
         },
 
         // Custom event without value
         BaseEvent {
-            event_type: EventType::Custom,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::Custom(CustomEvent {
-                event_type: "session_ping".to_string(),
-                data: json!({}),
+                name: "session_ping".to_string(),
+                value: json!({}),
             }),
         },
     ];
@@ -474,7 +469,7 @@ fn test_mixed_raw_and_custom_events() {
     for event in &events {
         let json_str = serde_json::to_string(event).unwrap();
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
     }
 
     // Test batch serialization
@@ -487,12 +482,13 @@ fn test_mixed_raw_and_custom_events() {
     if let EventData::Raw(data) = &deserialized_events[0].data {
         assert_eq!(data.event["source"], "api");
         assert_eq!(data.event["event"], "user_login");
+        assert_eq!(data.source.as_deref(), Some("api"));
     }
 
     if let EventData::Custom(data) = &deserialized_events[1].data {
-        assert_eq!(data.event_type, "feature_flag_toggled");
-        assert_eq!(data.data["flag"], "new_ui");
-        assert_eq!(data.data["enabled"], true);
+        assert_eq!(data.name, "feature_flag_toggled");
+        assert_eq!(data.value["flag"], "new_ui");
+        assert_eq!(data.value["enabled"], true);
     }
 
     if let EventData::Raw(data) = &deserialized_events[2].data {
@@ -501,29 +497,27 @@ fn test_mixed_raw_and_custom_events() {
     }
 
     if let EventData::Custom(data) = &deserialized_events[3].data {
-        assert_eq!(data.event_type, "session_ping");
+        assert_eq!(data.name, "session_ping");
     }
 }
 
 // ===== BASIC FIELD VALIDATION TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_basic_fields_validation() {
     // Test basic fields for each event type (matching TypeScript basic fields test)
     let raw_event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: None,
         raw_event: None,
-        data: EventData::Raw(RawEvent { event: json!({"test": "value"}) }),
+        data: EventData::Raw(RawEvent { event: json!({"test": "value"}), source: None }),
     };
 
     let custom_event = BaseEvent {
-        event_type: EventType::Custom,
         timestamp: None,
         raw_event: None,
         data: EventData::Custom(CustomEvent {
-            event_type: "empty".to_string(),
-            data: json!({}),
+            name: "empty".to_string(),
+            value: json!({}),
         }),
     };
 
@@ -542,36 +536,34 @@ fn test_basic_fields_validation() {
                 assert!(parsed["event"].is_object()); // RawEvent uses "event" field
             },
             "CUSTOM" => {
-                assert!(parsed["event_type"].is_string()); // CustomEvent has "event_type" field directly flattened
+                assert!(parsed["name"].is_string()); // CustomEvent carries "name" and "value"
             },
             _ => panic!("Unexpected event type"),
         }
 
         // Verify deserialization
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_events_with_all_base_fields() {
     // Test events with all base fields (matching TypeScript all base fields test)
     let raw_event_data = json!({"original": "data", "from": "external_system"});
 
     let raw_event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({"original": "raw_data", "from": "external_system"})),
-        data: EventData::Raw(RawEvent { event: raw_event_data.clone() }),
+        data: EventData::Raw(RawEvent { event: raw_event_data.clone(), source: None }),
     };
 
     let custom_event = BaseEvent {
-        event_type: EventType::Custom,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({"original": "raw_data", "from": "external_system"})),
         data: EventData::Custom(CustomEvent {
-            event_type: "full_event".to_string(),
-            data: json!({"custom": "data"}),
+            name: "full_event".to_string(),
+            value: json!({"custom": "data"}),
         }),
     };
 
@@ -583,8 +575,8 @@ fn test_events_with_all_base_fields() {
 
         // Verify all fields are present
         assert!(parsed["type"].is_string());
-        assert!(parsed["timestamp"].is_string());
-        assert!(parsed["raw_event"].is_object());
+        assert!(parsed["timestamp"].is_number());
+        assert!(parsed["rawEvent"].is_object());
 
         // Verify event-specific data field
         match parsed["type"].as_str().unwrap() {
@@ -592,19 +584,19 @@ fn test_events_with_all_base_fields() {
                 assert!(parsed["event"].is_object()); // RawEvent uses "event" field
             },
             "CUSTOM" => {
-                assert!(parsed["event_type"].is_string()); // CustomEvent has "event_type" field
-                // CustomEvent data is flattened, so additional properties are at the top level
+                assert!(parsed["name"].is_string()); // CustomEvent carries "name" and "value"
+                assert_eq!(parsed["value"]["custom"], "data");
             },
             _ => panic!("Unexpected event type"),
         }
 
         // Verify raw_event content
-        assert_eq!(parsed["raw_event"]["original"], "raw_data");
-        assert_eq!(parsed["raw_event"]["from"], "external_system");
+        assert_eq!(parsed["rawEvent"]["original"], "raw_data");
+        assert_eq!(parsed["rawEvent"]["from"], "external_system");
 
         // Test round-trip
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(event.event_type, deserialized.event_type);
+        assert_eq!(event.event_type(), deserialized.event_type());
         assert!(deserialized.timestamp.is_some());
         assert!(deserialized.raw_event.is_some());
     }
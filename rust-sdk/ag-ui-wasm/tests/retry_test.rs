@@ -0,0 +1,85 @@
+//! Tests for following a run across reconnections with `Resumption`
+//!
+//! `Resumption` sends nothing and sleeps nowhere, so these tests also run
+//! natively.
+
+use ag_ui_wasm::core::events::RunAbortedEvent;
+use ag_ui_wasm::core::retry::Resumption;
+use ag_ui_wasm::{AgUiError, BaseEvent, RetryPolicy};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn content(delta: &str) -> BaseEvent {
+    BaseEvent::text_message_content("msg-1".to_string(), delta.to_string())
+}
+
+fn dropped_connection() -> AgUiError {
+    AgUiError::StreamError("connection reset".to_string())
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_numeric_ids_up_to_the_last_accepted_are_replays() {
+    let mut resumption = Resumption::new(RetryPolicy::default());
+    for id in ["1", "2", "3"] {
+        assert!(resumption.accept(&content(id), Some(id)).unwrap());
+    }
+    resumption.retry_after(dropped_connection()).unwrap();
+
+    // The resumed stream repeats from an earlier event
+    assert!(!resumption.accept(&content("2"), Some("2")).unwrap());
+    assert!(!resumption.accept(&content("3"), Some("3")).unwrap());
+    assert!(resumption.accept(&content("4"), Some("4")).unwrap());
+    assert_eq!(resumption.last_event_id(), Some("4"));
+
+    // Replays are recognized however long the run has been going
+    for id in 5..10_000u64 {
+        resumption.accept(&content("x"), Some(&id.to_string())).unwrap();
+    }
+    assert!(!resumption.accept(&content("1"), Some("1")).unwrap());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_other_ids_are_remembered_in_a_bounded_window() {
+    let mut resumption = Resumption::new(RetryPolicy::default());
+    assert!(resumption.accept(&content("a"), Some("evt-a")).unwrap());
+    assert!(!resumption.accept(&content("a"), Some("evt-a")).unwrap());
+
+    for index in 0..Resumption::RECENT_ID_WINDOW {
+        resumption.accept(&content("x"), Some(&format!("evt-{}-x", index))).unwrap();
+    }
+    // The newest IDs are still known; the oldest was pushed out by them
+    assert!(!resumption.accept(&content("x"), Some("evt-0-x")).unwrap());
+    assert!(resumption.accept(&content("a"), Some("evt-a")).unwrap());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_lost_events_fail_the_resumed_run() {
+    let mut resumption = Resumption::new(RetryPolicy::default());
+    resumption.accept(&content("1"), Some("1")).unwrap();
+    resumption.retry_after(dropped_connection()).unwrap();
+
+    let error = resumption.accept(&content("5"), Some("5")).unwrap_err();
+    assert!(matches!(error, AgUiError::ResumeFailed(message) if message.contains("after 1")));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_every_terminal_event_finishes_the_run() {
+    let aborted = BaseEvent::new(RunAbortedEvent {
+        thread_id: "thread-1".to_string(),
+        run_id: "run-1".to_string(),
+        reason: Some("user cancelled".to_string()),
+    });
+    for terminal in [
+        BaseEvent::run_finished("thread-1".to_string(), "run-1".to_string()),
+        BaseEvent::run_error("Rate limited".to_string(), None),
+        aborted,
+    ] {
+        let mut resumption = Resumption::new(RetryPolicy::default());
+        resumption.accept(&terminal, Some("1")).unwrap();
+
+        assert!(resumption.is_finished(), "{}", terminal.event_type());
+        assert!(resumption.end().unwrap().is_none());
+        assert!(matches!(resumption.retry_after(dropped_connection()), Err(AgUiError::StreamError(_))));
+    }
+}
@@ -1,11 +1,11 @@
 //! Round-trip serialization tests matching TypeScript and Python patterns
 
 use ag_ui_wasm::{
-    BaseEvent, EventType, EventData, Role,
+    BaseEvent, EventData, Role,
     ToolCallStartEvent, ToolCallChunkEvent, ToolCallEndEvent,
     StateSnapshotEvent, StateDeltaEvent, MessagesSnapshotEvent,
-    TextMessageStartEvent, TextMessageContentEvent, TextMessageEndEvent,
-    ErrorEvent, Message, ToolCall,
+    TextMessageStartEvent,
+    ErrorEvent, Message, AssistantMessage, UserMessage, ToolCall,
 };
 use wasm_bindgen_test::*;
 use serde_json::json;
@@ -23,7 +23,7 @@ fn expect_round_trip_equality(event: &BaseEvent) {
     let deserialized: BaseEvent = serde_json::from_str(&json_str).expect("Failed to deserialize event");
 
     // Verify type matches
-    assert_eq!(event.event_type, deserialized.event_type);
+    assert_eq!(event.event_type(), deserialized.event_type());
 
     // Verify timestamps are preserved (allowing for None timestamps)
     match (&event.timestamp, &deserialized.timestamp) {
@@ -51,30 +51,22 @@ fn expect_round_trip_equality(event: &BaseEvent) {
         }
         (EventData::ToolCallStart(orig), EventData::ToolCallStart(deser)) => {
             assert_eq!(orig.tool_call_id, deser.tool_call_id);
-            assert_eq!(orig.tool_name, deser.tool_name);
+            assert_eq!(orig.tool_call_name, deser.tool_call_name);
             assert_eq!(orig.parent_message_id, deser.parent_message_id);
         }
         (EventData::ToolCallChunk(orig), EventData::ToolCallChunk(deser)) => {
             assert_eq!(orig.tool_call_id, deser.tool_call_id);
+            assert_eq!(orig.tool_call_name, deser.tool_call_name);
+            assert_eq!(orig.parent_message_id, deser.parent_message_id);
             assert_eq!(orig.delta, deser.delta);
         }
         (EventData::ToolCallEnd(orig), EventData::ToolCallEnd(deser)) => {
             assert_eq!(orig.tool_call_id, deser.tool_call_id);
-            // Compare tool_call if present
-            match (&orig.tool_call, &deser.tool_call) {
-                (Some(orig_call), Some(deser_call)) => {
-                    assert_eq!(orig_call.id, deser_call.id);
-                    assert_eq!(orig_call.name, deser_call.name);
-                    assert_eq!(orig_call.arguments, deser_call.arguments);
-                }
-                (None, None) => {}
-                _ => panic!("ToolCall preservation failed"),
-            }
         }
         (EventData::StateSnapshot(orig), EventData::StateSnapshot(deser)) => {
             // Compare state objects as JSON values for deep equality
-            let orig_json = serde_json::to_value(&orig.state).unwrap();
-            let deser_json = serde_json::to_value(&deser.state).unwrap();
+            let orig_json = serde_json::to_value(&orig.snapshot).unwrap();
+            let deser_json = serde_json::to_value(&deser.snapshot).unwrap();
             assert_eq!(orig_json, deser_json);
         }
         (EventData::StateDelta(orig), EventData::StateDelta(deser)) => {
@@ -83,23 +75,7 @@ fn expect_round_trip_equality(event: &BaseEvent) {
         (EventData::MessagesSnapshot(orig), EventData::MessagesSnapshot(deser)) => {
             assert_eq!(orig.messages.len(), deser.messages.len());
             for (orig_msg, deser_msg) in orig.messages.iter().zip(deser.messages.iter()) {
-                assert_eq!(orig_msg.id, deser_msg.id);
-                assert_eq!(orig_msg.role, deser_msg.role);
-                assert_eq!(orig_msg.content, deser_msg.content);
-                assert_eq!(orig_msg.tool_call_id, deser_msg.tool_call_id);
-                // Compare tool_calls if present
-                match (&orig_msg.tool_calls, &deser_msg.tool_calls) {
-                    (Some(orig_calls), Some(deser_calls)) => {
-                        assert_eq!(orig_calls.len(), deser_calls.len());
-                        for (orig_call, deser_call) in orig_calls.iter().zip(deser_calls.iter()) {
-                            assert_eq!(orig_call.id, deser_call.id);
-                            assert_eq!(orig_call.name, deser_call.name);
-                            assert_eq!(orig_call.arguments, deser_call.arguments);
-                        }
-                    }
-                    (None, None) => {}
-                    _ => panic!("Tool calls preservation failed"),
-                }
+                assert_eq!(orig_msg, deser_msg);
             }
         }
         (EventData::Error(orig), EventData::Error(deser)) => {
@@ -115,22 +91,21 @@ fn expect_round_trip_equality(event: &BaseEvent) {
             assert_eq!(orig.thread_id, deser.thread_id);
             assert_eq!(orig.run_id, deser.run_id);
         }
-        _ => panic!("Event data types don't match: {:?} vs {:?}", event.event_type, deserialized.event_type),
+        _ => panic!("Event data types don't match: {:?} vs {:?}", event.event_type(), deserialized.event_type()),
     }
 }
 
 // ===== TEXT MESSAGE EVENTS ROUND-TRIP TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_start_round_trip() {
     let event = BaseEvent::text_message_start("msg-123".to_string(), Some(Role::Assistant));
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_start_missing_optional_fields() {
     let event = BaseEvent {
-        event_type: EventType::TextMessageStart,
         timestamp: None, // Missing timestamp
         raw_event: None,
         data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -141,20 +116,20 @@ fn test_text_message_start_missing_optional_fields() {
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_content_round_trip() {
     let event = BaseEvent::text_message_content("msg-789".to_string(), "Hello, how can I help you today?".to_string());
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_content_special_characters() {
     let special_text = "Special chars: 🚀 ñ € 😊 \n\t\"'\\`";
     let event = BaseEvent::text_message_content("msg-special".to_string(), special_text.to_string());
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_end_round_trip() {
     let event = BaseEvent::text_message_end("msg-end".to_string());
     expect_round_trip_equality(&event);
@@ -162,51 +137,49 @@ fn test_text_message_end_round_trip() {
 
 // ===== TOOL CALL EVENTS ROUND-TRIP TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_start_round_trip() {
     let event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: "tool-123".to_string(),
-            tool_name: "get_weather".to_string(),
+            tool_call_name: "get_weather".to_string(),
             parent_message_id: Some("msg-parent".to_string()),
         }),
     };
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_start_with_all_optional_fields() {
     let event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({"original": "event data", "from": "source system"})),
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: "tool-call-id-123".to_string(),
-            tool_name: "very_long_tool_name_with_underscores".to_string(),
+            tool_call_name: "very_long_tool_name_with_underscores".to_string(),
             parent_message_id: Some("parent-message-id-456".to_string()),
         }),
     };
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_chunk_round_trip() {
     let event = BaseEvent {
-        event_type: EventType::ToolCallChunk,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallChunk(ToolCallChunkEvent {
-            tool_call_id: "tool-456".to_string(),
-            delta: "{\"location\":\"San Francisco\"}".to_string(),
+            tool_call_id: Some("tool-456".to_string()),
+            delta: Some("{\"location\":\"San Francisco\"}".to_string()),
+            ..Default::default()
         }),
     };
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_chunk_complex_json() {
     let complex_json = json!({
         "query": "SELECT * FROM users",
@@ -226,54 +199,52 @@ fn test_tool_call_chunk_complex_json() {
     });
 
     let event = BaseEvent {
-        event_type: EventType::ToolCallChunk,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallChunk(ToolCallChunkEvent {
-            tool_call_id: "db-query-tool-123".to_string(),
-            delta: complex_json.to_string(),
+            tool_call_id: Some("db-query-tool-123".to_string()),
+            delta: Some(complex_json.to_string()),
+            ..Default::default()
         }),
     };
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_end_round_trip() {
     let event = BaseEvent {
-        event_type: EventType::ToolCallEnd,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallEnd(ToolCallEndEvent {
             tool_call_id: "tool-end-123".to_string(),
-            tool_call: None,
         }),
     };
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
-fn test_tool_call_end_with_tool_call() {
-    let tool_call = ToolCall {
-        id: "call-with-result".to_string(),
-        name: "calculate".to_string(),
-        arguments: Some(json!({"operation": "sum", "values": [1, 2, 3, 4, 5]})),
-    };
+#[wasm_bindgen_test(unsupported = test)]
+fn test_tool_call_end_drops_legacy_tool_call() {
+    // Older Rust servers attached the finished call; the spec only carries the id
+    let legacy = json!({
+        "type": "TOOL_CALL_END",
+        "toolCallId": "call-with-result",
+        "toolCall": {
+            "id": "call-with-result",
+            "name": "calculate",
+            "arguments": {"operation": "sum", "values": [1, 2, 3, 4, 5]}
+        }
+    });
 
-    let event = BaseEvent {
-        event_type: EventType::ToolCallEnd,
-        timestamp: Some(Utc::now()),
-        raw_event: None,
-        data: EventData::ToolCallEnd(ToolCallEndEvent {
-            tool_call_id: "call-with-result".to_string(),
-            tool_call: Some(tool_call),
-        }),
-    };
+    let event: BaseEvent = serde_json::from_value(legacy).unwrap();
     expect_round_trip_equality(&event);
+
+    let serialized = serde_json::to_value(&event).unwrap();
+    assert_eq!(serialized, json!({"type": "TOOL_CALL_END", "toolCallId": "call-with-result"}));
 }
 
 // ===== STATE EVENTS ROUND-TRIP TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_snapshot_round_trip() {
     let mut state = HashMap::new();
     state.insert("counter".to_string(), json!(42));
@@ -284,28 +255,26 @@ fn test_state_snapshot_round_trip() {
     }));
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot: state }),
     };
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_snapshot_empty() {
     let state = HashMap::new();
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: None,
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot: state }),
     };
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_snapshot_complex_nested() {
     let mut state = HashMap::new();
     state.insert("userProfile".to_string(), json!({
@@ -352,15 +321,14 @@ fn test_state_snapshot_complex_nested() {
     }));
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot: state }),
     };
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_round_trip() {
     let delta = json!([
         {"op": "add", "path": "/counter", "value": 42},
@@ -368,7 +336,6 @@ fn test_state_delta_round_trip() {
     ]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -376,7 +343,7 @@ fn test_state_delta_round_trip() {
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_all_operations() {
     let delta = json!([
         {"op": "add", "path": "/users/123", "value": {"name": "John", "age": 30}},
@@ -388,7 +355,6 @@ fn test_state_delta_all_operations() {
     ]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -398,35 +364,23 @@ fn test_state_delta_all_operations() {
 
 // ===== MESSAGES SNAPSHOT ROUND-TRIP TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_messages_snapshot_multiple_messages() {
     let messages = vec![
-        Message {
+        Message::User(UserMessage {
             id: "msg-1".to_string(),
-            role: Role::User,
             content: "Can you help me with my task?".to_string(),
             name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
-        Message {
+        }),
+        Message::Assistant(AssistantMessage {
             id: "msg-2".to_string(),
-            role: Role::Assistant,
-            content: "I'd be happy to help! What task do you need assistance with?".to_string(),
+            content: Some("I'd be happy to help! What task do you need assistance with?".to_string()),
             name: None,
-            tool_call_id: None,
             tool_calls: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
+        }),
     ];
 
     let event = BaseEvent {
-        event_type: EventType::MessagesSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::MessagesSnapshot(MessagesSnapshotEvent { messages }),
@@ -434,43 +388,27 @@ fn test_messages_snapshot_multiple_messages() {
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_messages_snapshot_with_tool_calls() {
     let tool_calls = vec![
-        ToolCall {
-            id: "tool-1".to_string(),
-            name: "get_weather".to_string(),
-            arguments: Some(json!({"location": "San Francisco"})),
-        }
+        ToolCall::new("tool-1", "get_weather", json!({"location": "San Francisco"}).to_string())
     ];
 
     let messages = vec![
-        Message {
+        Message::User(UserMessage {
             id: "msg-1".to_string(),
-            role: Role::User,
             content: "What's the weather in San Francisco?".to_string(),
             name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
-        Message {
+        }),
+        Message::Assistant(AssistantMessage {
             id: "msg-2".to_string(),
-            role: Role::Assistant,
-            content: "Let me check the weather for you.".to_string(),
+            content: Some("Let me check the weather for you.".to_string()),
             name: None,
-            tool_call_id: None,
             tool_calls: Some(tool_calls),
-            function_call: None,
-            metadata: None,
-            created_at: Some(Utc::now()),
-        },
+        }),
     ];
 
     let event = BaseEvent {
-        event_type: EventType::MessagesSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::MessagesSnapshot(MessagesSnapshotEvent { messages }),
@@ -480,16 +418,15 @@ fn test_messages_snapshot_with_tool_calls() {
 
 // ===== ERROR EVENTS ROUND-TRIP TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_error_event_round_trip() {
     let event = BaseEvent::error("Something went wrong".to_string(), Some("ERR_001".to_string()));
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_error_event_with_details() {
     let event = BaseEvent {
-        event_type: EventType::Error,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::Error(ErrorEvent {
@@ -508,13 +445,13 @@ fn test_error_event_with_details() {
 
 // ===== RUN LIFECYCLE EVENTS ROUND-TRIP TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_started_round_trip() {
     let event = BaseEvent::run_started("thread-abc".to_string(), "run-def".to_string());
     expect_round_trip_equality(&event);
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_run_finished_round_trip() {
     let event = BaseEvent::run_finished("thread-ghi".to_string(), "run-jkl".to_string());
     expect_round_trip_equality(&event);
@@ -522,38 +459,35 @@ fn test_run_finished_round_trip() {
 
 // ===== COMPLEX EVENT SEQUENCE ROUND-TRIP TEST =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_complex_event_sequence_round_trip() {
     let events = vec![
         BaseEvent::run_started("thread_complex".to_string(), "run_complex".to_string()),
         BaseEvent::text_message_start("msg-1".to_string(), Some(Role::Assistant)),
         BaseEvent::text_message_content("msg-1".to_string(), "Processing your request...".to_string()),
         BaseEvent {
-            event_type: EventType::ToolCallStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallStart(ToolCallStartEvent {
                 tool_call_id: "tool-1".to_string(),
-                tool_name: "search_database".to_string(),
+                tool_call_name: "search_database".to_string(),
                 parent_message_id: Some("msg-1".to_string()),
             }),
         },
         BaseEvent {
-            event_type: EventType::ToolCallChunk,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallChunk(ToolCallChunkEvent {
-                tool_call_id: "tool-1".to_string(),
-                delta: r#"{"query":"SELECT *"#.to_string(),
+                tool_call_id: Some("tool-1".to_string()),
+                delta: Some(r#"{"query":"SELECT *"#.to_string()),
+                ..Default::default()
             }),
         },
         BaseEvent {
-            event_type: EventType::ToolCallEnd,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallEnd(ToolCallEndEvent {
                 tool_call_id: "tool-1".to_string(),
-                tool_call: None,
             }),
         },
         BaseEvent::text_message_content("msg-1".to_string(), r#" FROM users"}"#.to_string()),
@@ -562,7 +496,7 @@ fn test_complex_event_sequence_round_trip() {
     ];
 
     // Test each event in the sequence
-    for (i, event) in events.iter().enumerate() {
+    for event in &events {
         expect_round_trip_equality(event);
     }
 }
\ No newline at end of file
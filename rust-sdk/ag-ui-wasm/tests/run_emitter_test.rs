@@ -0,0 +1,198 @@
+//! Tests for producing a run's events with `RunEmitter`
+//!
+//! Every run must start with `RUN_STARTED` and end with exactly one
+//! `RUN_FINISHED` or `RUN_ERROR`, whatever the producer does.
+
+use ag_ui_wasm::{AgUiError, BaseEvent, EventData, EventStream, EventType, EventVerifier, ProtocolViolation, RunEmitter};
+use futures::executor::block_on;
+use futures::TryStreamExt;
+use serde_json::json;
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn collect(stream: EventStream) -> Vec<BaseEvent> {
+    block_on(stream.try_collect()).unwrap()
+}
+
+fn types(events: &[BaseEvent]) -> Vec<EventType> {
+    events.iter().map(BaseEvent::event_type).collect()
+}
+
+fn terminal_count(events: &[BaseEvent]) -> usize {
+    events.iter().filter(|event| matches!(event.event_type(), EventType::RunFinished | EventType::RunError)).count()
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_is_started_and_finished_around_the_producer() {
+    let events = collect(RunEmitter::stream("thread-1", "run-1", |mut run| async move {
+        run.start_step("answer").await?;
+        let message_id = run.start_message().await?;
+        run.push_text(&message_id, "Hello").await?;
+        run.push_text(&message_id, "").await?;
+        run.push_text(&message_id, " world").await?;
+        run.end_message(&message_id).await?;
+        run.tool_call("search", &json!({"q": "rust"}), Some(&message_id)).await?;
+        run.finish_step("answer").await
+    }));
+
+    assert_eq!(
+        types(&events),
+        [
+            EventType::RunStarted,
+            EventType::StepStarted,
+            EventType::TextMessageStart,
+            EventType::TextMessageContent,
+            EventType::TextMessageContent,
+            EventType::TextMessageEnd,
+            EventType::ToolCallStart,
+            EventType::ToolCallArgs,
+            EventType::ToolCallEnd,
+            EventType::StepFinished,
+            EventType::RunFinished,
+        ]
+    );
+    EventVerifier::new().verify_all(&events).unwrap();
+
+    let EventData::RunStarted(started) = &events[0].data else { panic!("expected RUN_STARTED") };
+    assert_eq!((started.thread_id.as_str(), started.run_id.as_str()), ("thread-1", "run-1"));
+    let EventData::TextMessageStart(start) = &events[2].data else { panic!("expected TEXT_MESSAGE_START") };
+    assert!(uuid::Uuid::parse_str(&start.message_id).is_ok());
+    let EventData::ToolCallArgs(args) = &events[7].data else { panic!("expected TOOL_CALL_ARGS") };
+    assert_eq!(args.delta, r#"{"q":"rust"}"#);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_open_messages_and_steps_are_ended_on_return() {
+    let events = collect(RunEmitter::stream("thread-1", "run-1", |mut run| async move {
+        run.start_step("answer").await?;
+        let message_id = run.start_message().await?;
+        run.push_text(&message_id, "Cut short").await?;
+        run.start_tool_call("search", Some(&message_id)).await?;
+        Ok(())
+    }));
+
+    assert_eq!(
+        types(&events)[5..],
+        [EventType::ToolCallEnd, EventType::TextMessageEnd, EventType::StepFinished, EventType::RunFinished]
+    );
+    EventVerifier::new().verify_all(&events).unwrap();
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_producer_error_becomes_run_error() {
+    let events = collect(RunEmitter::stream("thread-1", "run-1", |mut run| async move {
+        let message_id = run.start_message().await?;
+        run.push_text(&message_id, "Thinking").await?;
+        Err(AgUiError::AgentError("model unavailable".to_string()))
+    }));
+
+    assert_eq!(
+        types(&events),
+        [EventType::RunStarted, EventType::TextMessageStart, EventType::TextMessageContent, EventType::RunError]
+    );
+    let EventData::RunError(error) = &events[3].data else { panic!("expected RUN_ERROR") };
+    assert_eq!(error.message, "model unavailable");
+    assert_eq!(error.code.as_deref(), Some("AGENT_ERROR"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_invalid_events_are_rejected_at_emit_time() {
+    let rejected = Rc::new(Cell::new(false));
+    let seen = rejected.clone();
+    let events = collect(RunEmitter::stream("thread-1", "run-1", move |mut run| async move {
+        let error = run.push_text("no-such-message", "Hi").await.unwrap_err();
+        seen.set(matches!(error.violation(), Some(ProtocolViolation::TextMessageNotActive { .. })));
+        assert!(run.emit(BaseEvent::run_started("thread-1".to_string(), "run-2".to_string())).await.is_err());
+        run.text_message("Recovered").await?;
+        // Propagating a rejection ends the run with it
+        run.end_message("no-such-message").await
+    }));
+
+    assert!(rejected.get());
+    assert_eq!(
+        types(&events),
+        [
+            EventType::RunStarted,
+            EventType::TextMessageStart,
+            EventType::TextMessageContent,
+            EventType::TextMessageEnd,
+            EventType::RunError,
+        ]
+    );
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_ends_exactly_once() {
+    let returned = Rc::new(Cell::new(false));
+    let flag = returned.clone();
+    let events = collect(RunEmitter::stream("thread-1", "run-1", move |run| async move {
+        run.finish(Some(json!({"answer": 42}))).await?;
+        flag.set(true);
+        Err(AgUiError::AgentError("too late".to_string()))
+    }));
+
+    assert!(returned.get());
+    assert_eq!(types(&events), [EventType::RunStarted, EventType::RunFinished]);
+    let EventData::RunFinished(finished) = &events[1].data else { panic!("expected RUN_FINISHED") };
+    assert_eq!(finished.result, Some(json!({"answer": 42})));
+
+    let events = collect(RunEmitter::stream("thread-1", "run-1", |run| async move {
+        run.fail("gave up", Some("GAVE_UP".to_string())).await
+    }));
+    assert_eq!(types(&events), [EventType::RunStarted, EventType::RunError]);
+    assert_eq!(terminal_count(&events), 1);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_aborting_drops_the_producer_and_errors_the_run() {
+    let (mut stream, abort) = RunEmitter::abortable_stream("thread-1", "run-1", |mut run| async move {
+        let message_id = run.start_message().await?;
+        run.push_text(&message_id, "Waiting for the model").await?;
+        futures::future::pending::<()>().await;
+        Ok(())
+    });
+
+    let mut events = Vec::new();
+    for _ in 0..3 {
+        events.push(block_on(stream.next_event()).unwrap().unwrap());
+    }
+    abort.abort();
+    while let Some(event) = block_on(stream.next_event()).unwrap() {
+        events.push(event);
+    }
+
+    assert_eq!(types(&events)[3..], [EventType::RunError]);
+    assert_eq!(terminal_count(&events), 1);
+    let EventData::RunError(error) = &events[3].data else { panic!("expected RUN_ERROR") };
+    assert_eq!(error.code.as_deref(), Some("ABORTED"));
+}
+
+// Panics abort on wasm32, so only a native run can catch one
+#[cfg(not(target_arch = "wasm32"))]
+#[test]
+fn test_panicking_producer_errors_the_run() {
+    let events = collect(RunEmitter::stream("thread-1", "run-1", |mut run| async move {
+        run.start_message().await?;
+        panic!("model crashed");
+    }));
+
+    assert_eq!(types(&events), [EventType::RunStarted, EventType::TextMessageStart, EventType::RunError]);
+    let EventData::RunError(error) = &events[2].data else { panic!("expected RUN_ERROR") };
+    assert_eq!(error.message, "Run panicked: model crashed");
+    assert_eq!(error.code.as_deref(), Some("AGENT_ERROR"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_is_finished_after_the_producer_drops_its_emitter() {
+    let events = collect(RunEmitter::stream("thread-1", "run-1", |mut run| async move {
+        run.text_message("Done").await?;
+        drop(run);
+        Ok(())
+    }));
+
+    assert_eq!(types(&events)[4..], [EventType::RunFinished]);
+    assert_eq!(terminal_count(&events), 1);
+}
@@ -6,7 +6,7 @@ use wasm_bindgen_test::*;
 wasm_bindgen_test_configure!(run_in_browser);
 
 // Test SSE encoder creation
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encoder_creation() {
     let encoder = SSEEncoder::new();
     
@@ -15,14 +15,14 @@ fn test_sse_encoder_creation() {
     assert!(result.is_ok());
     
     let comment_result = encoder.encode_comment("test comment");
-    assert!(result.is_ok());
+    assert!(comment_result.is_ok());
     
     let ping_result = encoder.encode_ping();
     assert!(ping_result.is_ok());
 }
 
 // Test event string encoding (static method)
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_event_string_encoding() {
     let event = BaseEvent::text_message_content("msg_123".to_string(), "Hello, world!".to_string());
     
@@ -38,7 +38,7 @@ fn test_event_string_encoding() {
 }
 
 // Test multiple events encoding
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_multiple_events_encoding() {
     let events = vec![
         BaseEvent::run_started("thread_123".to_string(), "run_456".to_string()),
@@ -64,7 +64,7 @@ fn test_multiple_events_encoding() {
 }
 
 // Test Unicode encoding
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_unicode_event_encoding() {
     let unicode_text = "Hello 你好 こんにちは 안녕하세요 👋 🌍";
     let event = BaseEvent::text_message_content("msg_unicode".to_string(), unicode_text.to_string());
@@ -77,7 +77,7 @@ fn test_unicode_event_encoding() {
 }
 
 // Test empty content encoding
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_empty_content_encoding() {
     let event = BaseEvent::text_message_content("msg_empty".to_string(), "".to_string());
     
@@ -89,7 +89,7 @@ fn test_empty_content_encoding() {
 }
 
 // Test large content encoding
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_large_content_encoding() {
     let large_content = "A".repeat(1000);
     let event = BaseEvent::text_message_content("msg_large".to_string(), large_content.clone());
@@ -102,7 +102,7 @@ fn test_large_content_encoding() {
 }
 
 // Test error event encoding
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_error_event_encoding() {
     let event = BaseEvent::error("Something went wrong".to_string(), Some("ERR_001".to_string()));
     
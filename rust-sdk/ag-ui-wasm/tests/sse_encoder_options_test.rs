@@ -0,0 +1,141 @@
+//! Tests for the optional SSE fields `SseEncoderOptions` turns on
+//!
+//! Output is read back with `SseParser`, the same parser `HttpAgent` and
+//! `WebAgent` use, so every field is checked the way a client sees it.
+
+use ag_ui_wasm::{BaseEvent, SSEEncoder, SseEncoderOptions, SseParser, Role};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn test_events() -> Vec<BaseEvent> {
+    vec![
+        BaseEvent::run_started("thread1".to_string(), "run1".to_string()),
+        BaseEvent::text_message_start("msg1".to_string(), Some(Role::Assistant)),
+        BaseEvent::text_message_content("msg1".to_string(), "Hello".to_string()),
+    ]
+}
+
+fn encoder(options: SseEncoderOptions) -> SSEEncoder {
+    SSEEncoder::with_options(options)
+}
+
+fn to_string(bytes: Vec<u8>) -> String {
+    String::from_utf8(bytes).unwrap()
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_default_output_is_data_only() {
+    let encoded = to_string(SSEEncoder::new().encode_events(&test_events()).unwrap());
+
+    assert!(!encoded.contains("event: "));
+    assert!(!encoded.contains("id: "));
+    assert!(!encoded.contains("retry: "));
+    assert_eq!(encoded.matches("data: ").count(), 3);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_names() {
+    let encoder = encoder(SseEncoderOptions { event_names: true, ..Default::default() });
+    let encoded = to_string(encoder.encode_event(&test_events()[0]).unwrap());
+
+    assert!(encoded.starts_with("event: RUN_STARTED\ndata: {"));
+
+    let mut parser = SseParser::new();
+    let parsed = parser.feed(&to_string(encoder.encode_events(&test_events()).unwrap()));
+    let names: Vec<_> = parsed.iter().map(|event| event.event.as_str()).collect();
+    assert_eq!(names, ["RUN_STARTED", "TEXT_MESSAGE_START", "TEXT_MESSAGE_CONTENT"]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_ids_increase_from_one() {
+    let encoder = encoder(SseEncoderOptions { ids: true, ..Default::default() });
+    let mut parser = SseParser::new();
+
+    let mut ids = Vec::new();
+    for event in test_events() {
+        for parsed in parser.feed(&to_string(encoder.encode_event(&event).unwrap())) {
+            ids.push(parsed.id.unwrap());
+        }
+    }
+    assert_eq!(ids, ["1", "2", "3"]);
+    assert_eq!(parser.last_event_id(), "3");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_ids_continue_after_last_event_id() {
+    let encoder = encoder(SseEncoderOptions { ids: true, last_event_id: Some(41), ..Default::default() });
+    let mut parser = SseParser::new();
+
+    let parsed = parser.feed(&to_string(encoder.encode_events(&test_events()).unwrap()));
+    let ids: Vec<_> = parsed.into_iter().map(|event| event.id.unwrap()).collect();
+    assert_eq!(ids, ["42", "43", "44"]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_retry_is_sent_once() {
+    let encoder = encoder(SseEncoderOptions { retry: Some(3000), ..Default::default() });
+    let first = to_string(encoder.encode_event(&test_events()[0]).unwrap());
+    let rest = to_string(encoder.encode_events(&test_events()[1..]).unwrap());
+
+    assert!(first.contains("retry: 3000\n"));
+    assert!(!rest.contains("retry: "));
+
+    let mut parser = SseParser::new();
+    parser.feed(&first);
+    assert_eq!(parser.retry(), Some(3000));
+
+    assert_eq!(to_string(encoder.encode_retry(5000).unwrap()), "retry: 5000\n\n");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_options_deserialize_from_camel_case() {
+    let options: SseEncoderOptions = serde_json::from_value(serde_json::json!({
+        "eventNames": true,
+        "lastEventId": 7,
+    }))
+    .unwrap();
+
+    assert_eq!(options, SseEncoderOptions { event_names: true, last_event_id: Some(7), ..Default::default() });
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_multi_line_text_is_split_into_fields() {
+    let encoder = SSEEncoder::new();
+
+    let message = to_string(encoder.encode_message("one\ntwo\r\nthree\rfour").unwrap());
+    assert_eq!(message, "data: one\ndata: two\ndata: three\ndata: four\n\n");
+
+    let mut parser = SseParser::new();
+    let parsed = parser.feed(&message);
+    assert_eq!(parsed.len(), 1);
+    assert_eq!(parsed[0].data, "one\ntwo\nthree\nfour");
+
+    // A blank line inside a comment must not end an event early
+    let comment = to_string(encoder.encode_comment("first\n\nsecond").unwrap());
+    assert_eq!(comment, ": first\n: \n: second\n\n");
+    assert!(parser.feed(&comment).is_empty());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_batched_events_round_trip() {
+    let encoder = encoder(SseEncoderOptions {
+        event_names: true,
+        ids: true,
+        last_event_id: None,
+        retry: Some(1000),
+    });
+    let events = test_events();
+    let batch = to_string(encoder.encode_events(&events).unwrap());
+
+    let mut parser = SseParser::new();
+    let parsed = parser.feed(&batch);
+    assert_eq!(parsed.len(), events.len());
+    for (parsed, event) in parsed.iter().zip(&events) {
+        let decoded: BaseEvent = serde_json::from_str(&parsed.data).unwrap();
+        assert_eq!(decoded.event_type(), event.event_type());
+        assert_eq!(parsed.event, event.event_type().to_string());
+    }
+    assert_eq!(parser.last_event_id(), "3");
+    assert_eq!(parser.retry(), Some(1000));
+}
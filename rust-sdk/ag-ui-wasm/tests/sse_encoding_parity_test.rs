@@ -2,7 +2,7 @@
 //! Tests SSE format encoding, binary conversion, and JavaScript interop
 
 use ag_ui_wasm::{
-    BaseEvent, EventType, EventData, SSEEncoder, RawEvent,
+    BaseEvent, EventData, SSEEncoder, RawEvent,
     TextMessageStartEvent, TextMessageContentEvent, Role,
 };
 use wasm_bindgen_test::*;
@@ -13,20 +13,19 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== SSE ENCODER BASIC TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encoder_creation() {
     // Test SSE encoder initialization (matching Python encoder initialization test)
-    let encoder = SSEEncoder::new();
+    let _encoder = SSEEncoder::new();
 
     // Should create successfully
     // No specific assertions needed, just verifies constructor works
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_event_string() {
     // Test encoding event to SSE string format (matching Python encode_sse_method test)
     let event = BaseEvent {
-        event_type: EventType::TextMessageContent,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::TextMessageContent(TextMessageContentEvent {
@@ -46,11 +45,11 @@ fn test_sse_encode_event_string() {
     let parsed: serde_json::Value = serde_json::from_str(json_part).unwrap();
 
     assert_eq!(parsed["type"], "TEXT_MESSAGE_CONTENT");
-    assert_eq!(parsed["message_id"], "msg_123");
+    assert_eq!(parsed["messageId"], "msg_123");
     assert_eq!(parsed["delta"], "Hello, world!");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_multiple_events() {
     // Test encoding multiple events (batch encoding)
     let events = vec![
@@ -78,7 +77,7 @@ fn test_sse_encode_multiple_events() {
     assert!(sse_string.ends_with("\n\n"));
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_message() {
     // Test encoding plain message (matching Python/TypeScript message encoding)
     let encoder = SSEEncoder::new();
@@ -93,7 +92,7 @@ fn test_sse_encode_message() {
     assert_eq!(sse_string, "data: Plain text message\n\n");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_comment() {
     // Test encoding SSE comment
     let encoder = SSEEncoder::new();
@@ -105,10 +104,10 @@ fn test_sse_encode_comment() {
     let bytes: Vec<u8> = result.to_vec();
     let sse_string = String::from_utf8(bytes).unwrap();
 
-    assert_eq!(sse_string, ": This is a comment\n");
+    assert_eq!(sse_string, ": This is a comment\n\n");
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_ping() {
     // Test encoding SSE ping (keep-alive)
     let encoder = SSEEncoder::new();
@@ -124,11 +123,10 @@ fn test_sse_encode_ping() {
 
 // ===== BINARY ENCODING TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_binary_encoding() {
     // Test binary encoding functionality (similar to TypeScript encodeBinary tests)
     let event = BaseEvent {
-        event_type: EventType::TextMessageStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -141,7 +139,7 @@ fn test_sse_binary_encoding() {
     let result = encoder.encode_event(&event).unwrap();
 
     // Verify it's a Uint8Array
-    assert!(result.length() > 0);
+    assert!(!result.is_empty());
 
     // Convert back to string and verify SSE format
     let bytes: Vec<u8> = result.to_vec();
@@ -153,6 +151,8 @@ fn test_sse_binary_encoding() {
     assert!(sse_string.contains("msg123"));
 }
 
+// Uint8Array needs a JS host
+#[cfg(target_arch = "wasm32")]
 #[wasm_bindgen_test]
 fn test_to_uint8_array_conversion() {
     // Test converting SSE string to Uint8Array
@@ -172,11 +172,10 @@ fn test_to_uint8_array_conversion() {
 
 // ===== COMPLEX DATA ENCODING TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_complex_event() {
     // Test encoding complex event with nested data
     let complex_event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({
             "source": "external_system",
@@ -199,7 +198,7 @@ fn test_sse_encode_complex_event() {
                     {"type": "scroll", "position": 100}
                 ]
             }
-        }) }),
+        }), source: None }),
     };
 
     let sse_string = SSEEncoder::encode_event_string(&complex_event).unwrap();
@@ -215,12 +214,12 @@ fn test_sse_encode_complex_event() {
     assert_eq!(parsed["type"], "RAW");
     assert_eq!(parsed["event"]["userProfile"]["name"], "John Doe");
     assert_eq!(parsed["event"]["analytics"]["events"][0]["type"], "click");
-    assert_eq!(parsed["raw_event"]["source"], "external_system");
+    assert_eq!(parsed["rawEvent"]["source"], "external_system");
 }
 
 // ===== SPECIAL CHARACTER AND UNICODE TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_unicode_content() {
     // Test encoding Unicode and special characters (matching encoder Unicode tests)
     let unicode_text = "Hello 你好 こんにちは 안녕하세요 👋 🌍 \\n\\t\"'/<>";
@@ -248,7 +247,7 @@ fn test_sse_encode_unicode_content() {
     assert!(decoded_string.contains("👋"));
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_special_characters_in_message() {
     // Test special characters in plain message encoding
     let encoder = SSEEncoder::new();
@@ -267,7 +266,7 @@ fn test_sse_encode_special_characters_in_message() {
 
 // ===== LARGE DATA TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_large_event() {
     // Test encoding large event data (matching large content tests)
     let large_content = "A".repeat(10000);
@@ -290,12 +289,12 @@ fn test_sse_encode_large_event() {
     // Test binary encoding of large data
     let encoder = SSEEncoder::new();
     let binary_result = encoder.encode_event(&event).unwrap();
-    assert!(binary_result.length() > 10000);
+    assert!(binary_result.len() > 10000);
 }
 
 // ===== ERROR HANDLING TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_empty_content() {
     // Test encoding events with empty content
     let empty_event = BaseEvent::text_message_content(
@@ -318,14 +317,13 @@ fn test_sse_encode_empty_content() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_null_fields() {
     // Test encoding events with null/None fields (matching null exclusion tests)
     let event = BaseEvent {
-        event_type: EventType::Raw,
         timestamp: None, // None field
         raw_event: None, // None field
-        data: EventData::Raw(RawEvent { event: json!({"test": "value"}) }),
+        data: EventData::Raw(RawEvent { event: json!({"test": "value"}), source: None }),
     };
 
     let sse_string = SSEEncoder::encode_event_string(&event).unwrap();
@@ -342,16 +340,15 @@ fn test_sse_encode_null_fields() {
 
     // None fields should be excluded when skip_serializing_if is used
     assert!(!parsed.as_object().unwrap().contains_key("timestamp"));
-    assert!(!parsed.as_object().unwrap().contains_key("raw_event"));
+    assert!(!parsed.as_object().unwrap().contains_key("rawEvent"));
 }
 
 // ===== ROUND TRIP TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_round_trip_encoding() {
     // Test round-trip encoding/decoding (matching round-trip tests)
     let original_event = BaseEvent {
-        event_type: EventType::TextMessageStart,
         timestamp: Some(Utc::now()),
         raw_event: Some(json!({"source": "test"})),
         data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -370,7 +367,7 @@ fn test_sse_round_trip_encoding() {
     let deserialized_event: BaseEvent = serde_json::from_str(json_part).unwrap();
 
     // Verify round-trip preservation
-    assert_eq!(original_event.event_type, deserialized_event.event_type);
+    assert_eq!(original_event.event_type(), deserialized_event.event_type());
 
     if let (EventData::TextMessageStart(orig), EventData::TextMessageStart(deser)) =
         (&original_event.data, &deserialized_event.data) {
@@ -381,27 +378,24 @@ fn test_sse_round_trip_encoding() {
 
 // ===== BATCH PROCESSING TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_batch_encoding() {
     // Test batch encoding of multiple events
     let events = vec![
         BaseEvent {
-            event_type: EventType::Raw,
             timestamp: Some(Utc::now()),
             raw_event: None,
-            data: EventData::Raw(RawEvent { event: json!({"event": 1}) }),
+            data: EventData::Raw(RawEvent { event: json!({"event": 1}), source: None }),
         },
         BaseEvent {
-            event_type: EventType::Raw,
             timestamp: Some(Utc::now()),
             raw_event: None,
-            data: EventData::Raw(RawEvent { event: json!({"event": 2}) }),
+            data: EventData::Raw(RawEvent { event: json!({"event": 2}), source: None }),
         },
         BaseEvent {
-            event_type: EventType::Raw,
             timestamp: Some(Utc::now()),
             raw_event: None,
-            data: EventData::Raw(RawEvent { event: json!({"event": 3}) }),
+            data: EventData::Raw(RawEvent { event: json!({"event": 3}), source: None }),
         },
     ];
 
@@ -422,15 +416,14 @@ fn test_sse_batch_encoding() {
 
 // ===== PERFORMANCE AND EDGE CASE TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_many_events() {
     // Test encoding many events (performance test)
     let events: Vec<BaseEvent> = (0..100)
         .map(|i| BaseEvent {
-            event_type: EventType::Raw,
             timestamp: Some(Utc::now()),
             raw_event: None,
-            data: EventData::Raw(RawEvent { event: json!({"index": i, "data": format!("event_{}", i)}) }),
+            data: EventData::Raw(RawEvent { event: json!({"index": i, "data": format!("event_{}", i)}), source: None }),
         })
         .collect();
 
@@ -447,7 +440,7 @@ fn test_sse_encode_many_events() {
     assert!(batch_sse.contains("event_99"));
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_sse_encode_empty_event_list() {
     // Test encoding empty event list
     let events: Vec<BaseEvent> = vec![];
@@ -0,0 +1,230 @@
+//! Tests for the incremental SSE parser behind `EventStream`
+//!
+//! Cases follow the event stream interpretation rules of the WHATWG HTML
+//! specification. The parser is pure Rust, so these tests also run natively.
+
+use ag_ui_wasm::core::compat;
+use ag_ui_wasm::{EventType, SseEvent, SseParser};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn message(data: &str) -> SseEvent {
+    SseEvent { event: "message".to_string(), data: data.to_string(), id: None, has_own_id: false, offset: 0 }
+}
+
+/// The events of `input`, with their offsets cleared to compare them with `message`
+fn parse(input: &str) -> Vec<SseEvent> {
+    without_offsets(SseParser::new().feed(input))
+}
+
+fn parse_split(input: &[u8], cuts: &[usize]) -> Vec<SseEvent> {
+    without_offsets(feed_split(input, cuts))
+}
+
+fn without_offsets(events: Vec<SseEvent>) -> Vec<SseEvent> {
+    events.into_iter().map(|event| SseEvent { offset: 0, ..event }).collect()
+}
+
+/// Feed `input` in pieces cut at `cuts` byte offsets
+fn feed_split(input: &[u8], cuts: &[usize]) -> Vec<SseEvent> {
+    let mut parser = SseParser::new();
+    let mut events = Vec::new();
+    let mut start = 0;
+    for &cut in cuts.iter().chain(std::iter::once(&input.len())) {
+        events.extend(parser.feed_bytes(&input[start..cut]));
+        start = cut;
+    }
+    events
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_events_end_at_blank_lines() {
+    assert_eq!(parse("data: one\n\ndata: two\n\n"), [message("one"), message("two")]);
+    assert_eq!(parse("data: one\n"), []);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_line_endings() {
+    let expected = [message("a\nb"), message("c")];
+    assert_eq!(parse("data: a\ndata: b\n\ndata: c\n\n"), expected);
+    assert_eq!(parse("data: a\r\ndata: b\r\n\r\ndata: c\r\n\r\n"), expected);
+    assert_eq!(parse("data: a\rdata: b\r\rdata: c\r\r"), expected);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_field_parsing() {
+    // Only one leading space is stripped from the value
+    assert_eq!(parse("data:  padded\n\n"), [message(" padded")]);
+    assert_eq!(parse("data:tight\n\n"), [message("tight")]);
+    // A line without a colon is a field with an empty value
+    assert_eq!(parse("data\n\n"), [message("")]);
+    assert_eq!(parse("data\ndata\n\n"), [message("\n")]);
+    // Values may contain colons
+    assert_eq!(parse("data: a: b\n\n"), [message("a: b")]);
+    // Unknown fields and comments are ignored
+    assert_eq!(parse(": keep-alive\nfoo: bar\ndata: x\n\n"), [message("x")]);
+    // Field names are case sensitive
+    assert_eq!(parse("Data: x\n\n"), []);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_comments_and_empty_events_dispatch_nothing() {
+    assert_eq!(parse(": ping\n\n: ping\n\n"), []);
+    assert_eq!(parse("event: update\n\ndata: x\n\n"), [message("x")]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_and_id_fields() {
+    let mut parser = SseParser::new();
+    let events = parser.feed("event: update\nid: 1\ndata: a\n\ndata: b\n\nid\ndata: c\n\n");
+
+    assert_eq!(
+        events,
+        [
+            SseEvent {
+                event: "update".to_string(),
+                data: "a".to_string(),
+                id: Some("1".to_string()),
+                has_own_id: true,
+                offset: 0,
+            },
+            SseEvent {
+                event: "message".to_string(),
+                data: "b".to_string(),
+                id: Some("1".to_string()),
+                has_own_id: false,
+                offset: 29,
+            },
+            SseEvent { has_own_id: true, offset: 38, ..message("c") },
+        ]
+    );
+    // Only the event that set the ID has it as its own
+    assert_eq!(events[0].own_id(), Some("1"));
+    assert_eq!(events[1].own_id(), None);
+    assert_eq!(events[2].own_id(), None);
+
+    // IDs containing NULL are ignored
+    parser.feed("id: 7\ndata: d\n\nid: 8\0\ndata: e\n\n");
+    assert_eq!(parser.last_event_id(), "7");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_retry_field() {
+    let mut parser = SseParser::new();
+    parser.feed("retry: 2500\n\n");
+    assert_eq!(parser.retry(), Some(2500));
+
+    parser.feed("retry: soon\nretry: 10ms\nretry:\n\n");
+    assert_eq!(parser.retry(), Some(2500));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_leading_byte_order_mark_is_skipped() {
+    assert_eq!(parse("\u{feff}data: x\n\n"), [message("x")]);
+    assert_eq!(parse_split("\u{feff}data: x\n\n".as_bytes(), &[1, 2]), [message("x")]);
+    // Only at the very start of the stream
+    assert_eq!(parse("data: x\n\n\u{feff}data: y\n\n"), [message("x")]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_unterminated_event_is_discarded_at_end() {
+    let mut parser = SseParser::new();
+    assert_eq!(parser.feed("id: 3\ndata: x\n\ndata: partial"), [SseEvent {
+        event: "message".to_string(),
+        data: "x".to_string(),
+        id: Some("3".to_string()),
+        has_own_id: true,
+        offset: 0,
+    }]);
+
+    parser.finish();
+    assert_eq!(parser.feed("\n\n"), []);
+    assert_eq!(parser.last_event_id(), "3");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_unterminated_offset_points_at_what_finish_discards() {
+    let mut parser = SseParser::new();
+    parser.feed("data: x\n\n: ping\n\n");
+    assert_eq!(parser.unterminated_offset(), None);
+
+    parser.feed("id: 4\ndata: par");
+    assert_eq!(parser.unterminated_offset(), Some(17));
+    parser.finish();
+    assert_eq!(parser.unterminated_offset(), None);
+
+    // A line not yet ended, or a UTF-8 sequence not yet complete, is unterminated too
+    parser.feed("da");
+    assert_eq!(parser.unterminated_offset(), Some(0));
+    let mut parser = SseParser::new();
+    parser.feed_bytes(&[0xc3]);
+    assert_eq!(parser.unterminated_offset(), Some(0));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_events_carry_the_offset_of_their_first_field() {
+    let offsets = |input: &str| SseParser::new().feed(input).iter().map(|event| event.offset).collect::<Vec<_>>();
+
+    assert_eq!(offsets("data: a\n\n: ping\n\nid: 2\ndata: b\n\n"), [0, 17]);
+    // Comments before the first field are not part of the event
+    assert_eq!(offsets("data: a\n\n: ping\ndata: b\n\n"), [0, 16]);
+    assert_eq!(offsets("data: a\r\n\r\ndata: é\r\n\r\ndata: c\n\n"), [0, 11, 23]);
+    assert_eq!(offsets("\u{feff}data: x\n\n"), [3]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_invalid_utf8_is_replaced() {
+    assert_eq!(parse_split(b"data: a\xffb\n\n", &[]), [message("a\u{fffd}b")]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_every_split_point_gives_the_same_events() {
+    let input = "\u{feff}: hello\r\nevent: custom\r\nid: 42\r\ndata: {\"emoji\": \"🦀\"}\r\ndata: second\r\r\
+                 retry: 100\ndata: café\n\ndata: x\r\n\r\n";
+    let bytes = input.as_bytes();
+    let expected = SseParser::new().feed(input);
+    assert_eq!(expected.len(), 3);
+
+    for first in 0..=bytes.len() {
+        assert_eq!(feed_split(bytes, &[first]), expected, "split at {}", first);
+        for second in first..=bytes.len() {
+            assert_eq!(feed_split(bytes, &[first, second]), expected, "split at {} and {}", first, second);
+        }
+    }
+
+    let single_bytes: Vec<usize> = (1..bytes.len()).collect();
+    assert_eq!(feed_split(bytes, &single_bytes), expected);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_arbitrary_input_does_not_panic() {
+    // A small linear congruential generator keeps the test deterministic
+    let mut seed: u64 = 0x5eed;
+    let mut next = move || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (seed >> 33) as usize
+    };
+    let alphabet: &[u8] = b"data:event:id:retry: \r\n\n:0123\xf0\x9f\xa6\x80\xff\x00";
+
+    for _ in 0..500 {
+        let length = next() % 200;
+        let input: Vec<u8> = (0..length).map(|_| alphabet[next() % alphabet.len()]).collect();
+        let mut cuts: Vec<usize> = (0..next() % 8).map(|_| next() % (length + 1)).collect();
+        cuts.sort_unstable();
+
+        let whole = feed_split(&input, &[]);
+        assert_eq!(feed_split(&input, &cuts), whole, "input {:?} cut at {:?}", input, cuts);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_parsed_data_is_an_ag_ui_event() {
+    let events = parse_split(
+        b"data: {\"type\":\"TEXT_MESSAGE_CONTENT\",\"messageId\":\"m1\",\ndata: \"delta\":\"Hi\"}\n\n",
+        &[9, 30],
+    );
+
+    let event = compat::parse_event(&events[0].data).unwrap();
+    assert_eq!(event.event_type(), EventType::TextMessageContent);
+}
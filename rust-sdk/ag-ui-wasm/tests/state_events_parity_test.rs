@@ -13,7 +13,7 @@ wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== STATE SNAPSHOT TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_snapshot_basic() {
     // Test basic StateSnapshot event (matching TypeScript round-trip test)
     let mut snapshot: State = HashMap::new();
@@ -25,50 +25,48 @@ fn test_state_snapshot_basic() {
     }));
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state: snapshot }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
-    assert_eq!(deserialized.event_type, EventType::StateSnapshot);
+    assert_eq!(deserialized.event_type(), EventType::StateSnapshot);
 
     if let EventData::StateSnapshot(state_data) = &deserialized.data {
-        assert_eq!(state_data.state["counter"], 42);
-        assert_eq!(state_data.state["items"], json!(["apple", "banana", "cherry"]));
-        assert_eq!(state_data.state["config"]["enabled"], true);
-        assert_eq!(state_data.state["config"]["maxRetries"], 3);
+        assert_eq!(state_data.snapshot["counter"], 42);
+        assert_eq!(state_data.snapshot["items"], json!(["apple", "banana", "cherry"]));
+        assert_eq!(state_data.snapshot["config"]["enabled"], true);
+        assert_eq!(state_data.snapshot["config"]["maxRetries"], 3);
     } else {
         panic!("Expected StateSnapshot event data");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_snapshot_empty_object() {
     // Test empty snapshot object (matching TypeScript empty snapshot test)
     let snapshot: State = HashMap::new();
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: None,
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state: snapshot }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
     if let EventData::StateSnapshot(state_data) = &deserialized.data {
-        assert!(state_data.state.is_empty());
+        assert!(state_data.snapshot.is_empty());
     } else {
         panic!("Expected StateSnapshot event data");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_snapshot_complex_nested_objects() {
     // Test complex nested objects (matching TypeScript complex nested test)
     let mut snapshot: State = HashMap::new();
@@ -128,10 +126,9 @@ fn test_state_snapshot_complex_nested_objects() {
     }));
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state: snapshot }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
@@ -139,19 +136,19 @@ fn test_state_snapshot_complex_nested_objects() {
 
     if let EventData::StateSnapshot(state_data) = &deserialized.data {
         // Verify deeply nested structure is preserved
-        assert_eq!(state_data.state["userProfile"]["name"], "John Doe");
-        assert_eq!(state_data.state["userProfile"]["contact"]["address"]["city"], "Anytown");
-        assert_eq!(state_data.state["userProfile"]["contact"]["address"]["coordinates"]["lat"], 37.7749);
-        assert_eq!(state_data.state["serviceConfig"]["endpoints"][0]["name"], "api1");
-        assert_eq!(state_data.state["serviceConfig"]["retryPolicy"]["maxRetries"], 3);
-        assert_eq!(state_data.state["stats"]["visits"], 1042);
-        assert_eq!(state_data.state["stats"]["dataPoints"][1]["date"], "2023-01-02");
+        assert_eq!(state_data.snapshot["userProfile"]["name"], "John Doe");
+        assert_eq!(state_data.snapshot["userProfile"]["contact"]["address"]["city"], "Anytown");
+        assert_eq!(state_data.snapshot["userProfile"]["contact"]["address"]["coordinates"]["lat"], 37.7749);
+        assert_eq!(state_data.snapshot["serviceConfig"]["endpoints"][0]["name"], "api1");
+        assert_eq!(state_data.snapshot["serviceConfig"]["retryPolicy"]["maxRetries"], 3);
+        assert_eq!(state_data.snapshot["stats"]["visits"], 1042);
+        assert_eq!(state_data.snapshot["stats"]["dataPoints"][1]["date"], "2023-01-02");
     } else {
         panic!("Expected StateSnapshot event data");
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_snapshot_special_values() {
     // Test special values in snapshot (matching TypeScript special values test)
     let mut snapshot: State = HashMap::new();
@@ -160,32 +157,31 @@ fn test_state_snapshot_special_values() {
     snapshot.insert("emptyString".to_string(), json!(""));
     snapshot.insert("zero".to_string(), json!(0));
     snapshot.insert("negativeNumber".to_string(), json!(-123));
-    snapshot.insert("floatNumber".to_string(), json!(3.14159));
+    snapshot.insert("floatNumber".to_string(), json!(2.5));
     snapshot.insert("emptyArray".to_string(), json!([]));
     snapshot.insert("emptyObject".to_string(), json!({}));
     snapshot.insert("boolValues".to_string(), json!({"true": true, "false": false}));
     // Note: Skipping Infinity/NaN as they don't serialize well in JSON
 
     let event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state: snapshot }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot }),
     };
 
     let json_str = serde_json::to_string(&event).unwrap();
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
     if let EventData::StateSnapshot(state_data) = &deserialized.data {
-        assert_eq!(state_data.state["nullValue"], json!(null));
-        assert_eq!(state_data.state["emptyString"], "");
-        assert_eq!(state_data.state["zero"], 0);
-        assert_eq!(state_data.state["negativeNumber"], -123);
-        assert_eq!(state_data.state["floatNumber"], 3.14159);
-        assert_eq!(state_data.state["emptyArray"], json!([]));
-        assert_eq!(state_data.state["emptyObject"], json!({}));
-        assert_eq!(state_data.state["boolValues"]["true"], true);
-        assert_eq!(state_data.state["boolValues"]["false"], false);
+        assert_eq!(state_data.snapshot["nullValue"], json!(null));
+        assert_eq!(state_data.snapshot["emptyString"], "");
+        assert_eq!(state_data.snapshot["zero"], 0);
+        assert_eq!(state_data.snapshot["negativeNumber"], -123);
+        assert_eq!(state_data.snapshot["floatNumber"], 2.5);
+        assert_eq!(state_data.snapshot["emptyArray"], json!([]));
+        assert_eq!(state_data.snapshot["emptyObject"], json!({}));
+        assert_eq!(state_data.snapshot["boolValues"]["true"], true);
+        assert_eq!(state_data.snapshot["boolValues"]["false"], false);
     } else {
         panic!("Expected StateSnapshot event data");
     }
@@ -193,7 +189,7 @@ fn test_state_snapshot_special_values() {
 
 // ===== STATE DELTA TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_basic() {
     // Test basic StateDelta event (matching TypeScript round-trip test)
     let delta = json!([
@@ -202,7 +198,6 @@ fn test_state_delta_basic() {
     ]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -211,7 +206,7 @@ fn test_state_delta_basic() {
     let json_str = serde_json::to_string(&event).unwrap();
     let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
 
-    assert_eq!(deserialized.event_type, EventType::StateDelta);
+    assert_eq!(deserialized.event_type(), EventType::StateDelta);
 
     if let EventData::StateDelta(delta_data) = &deserialized.data {
         let patches = delta_data.delta.as_array().unwrap();
@@ -227,7 +222,7 @@ fn test_state_delta_basic() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_all_json_patch_operations() {
     // Test all JSON Patch operation types (matching TypeScript comprehensive test)
     let delta = json!([
@@ -240,7 +235,6 @@ fn test_state_delta_all_json_patch_operations() {
     ]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -274,7 +268,7 @@ fn test_state_delta_all_json_patch_operations() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_complex_values() {
     // Test complex values in add operations (matching TypeScript complex values test)
     let delta = json!([
@@ -293,7 +287,6 @@ fn test_state_delta_complex_values() {
     ]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -315,7 +308,7 @@ fn test_state_delta_complex_values() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_array_operations() {
     // Test array operations (matching TypeScript array operations test)
     let delta = json!([
@@ -327,7 +320,6 @@ fn test_state_delta_array_operations() {
     ]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -357,7 +349,7 @@ fn test_state_delta_array_operations() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_special_characters_in_paths() {
     // Test special characters in paths (matching TypeScript special chars test)
     let delta = json!([
@@ -369,7 +361,6 @@ fn test_state_delta_special_characters_in_paths() {
     ]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -398,13 +389,12 @@ fn test_state_delta_special_characters_in_paths() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_empty_array() {
     // Test empty delta array (matching TypeScript empty delta test)
     let delta = json!([]);
 
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta }),
@@ -423,7 +413,7 @@ fn test_state_delta_empty_array() {
 
 // ===== COMPREHENSIVE STATE FLOW TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_comprehensive_state_flow() {
     // Test a comprehensive state management flow
 
@@ -436,10 +426,9 @@ fn test_comprehensive_state_flow() {
     }));
 
     let snapshot_event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state: initial_state }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot: initial_state }),
     };
 
     // 2. State delta updates
@@ -450,7 +439,6 @@ fn test_comprehensive_state_flow() {
     ]);
 
     let delta_event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta: delta_update }),
@@ -466,9 +454,9 @@ fn test_comprehensive_state_flow() {
 
     // Verify snapshot
     if let EventData::StateSnapshot(state_data) = &deser_snapshot.data {
-        assert_eq!(state_data.state["session"]["id"], "sess_123");
-        assert_eq!(state_data.state["session"]["user"]["name"], "Alice");
-        assert_eq!(state_data.state["session"]["status"], "active");
+        assert_eq!(state_data.snapshot["session"]["id"], "sess_123");
+        assert_eq!(state_data.snapshot["session"]["user"]["name"], "Alice");
+        assert_eq!(state_data.snapshot["session"]["status"], "active");
     } else {
         panic!("Expected StateSnapshot event data");
     }
@@ -488,7 +476,7 @@ fn test_comprehensive_state_flow() {
 
 // ===== EDGE CASES AND ERROR HANDLING =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_edge_cases() {
     // Test various edge cases for state management
 
@@ -505,10 +493,9 @@ fn test_state_edge_cases() {
     }
 
     let large_event = BaseEvent {
-        event_type: EventType::StateSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
-        data: EventData::StateSnapshot(StateSnapshotEvent { state: large_state }),
+        data: EventData::StateSnapshot(StateSnapshotEvent { snapshot: large_state }),
     };
 
     // Should handle large state without issues
@@ -516,8 +503,8 @@ fn test_state_edge_cases() {
     let large_deser: BaseEvent = serde_json::from_str(&large_json).unwrap();
 
     if let EventData::StateSnapshot(state_data) = &large_deser.data {
-        assert_eq!(state_data.state.len(), 1000);
-        assert_eq!(state_data.state["item_500"]["id"], 500);
+        assert_eq!(state_data.snapshot.len(), 1000);
+        assert_eq!(state_data.snapshot["item_500"]["id"], 500);
     }
 
     // Complex delta with many operations
@@ -526,7 +513,6 @@ fn test_state_edge_cases() {
     }).collect::<Vec<_>>());
 
     let complex_delta_event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent { delta: complex_delta }),
@@ -0,0 +1,51 @@
+// A local AG-UI endpoint for the Node tests in `node_client_test.rs`
+const http = require('node:http');
+
+// Serve `routes` on a free local port. Each route maps a path to
+// `{ status, contentType, body, hang, drop }`: `body` is a string or a
+// Uint8Array, `hang` keeps the response open after the body is sent and
+// `drop` cuts the connection after it. A route may also be an array of these,
+// answering each request with the next one and repeating the last. Resolves
+// to a server whose `requests` records every request's method, URL, headers
+// and body.
+exports.startMockServer = function (routes) {
+  return new Promise((resolve) => {
+    const requests = [];
+    const server = http.createServer((req, res) => {
+      let body = '';
+      req.on('data', (chunk) => { body += chunk; });
+      req.on('end', () => {
+        requests.push({ method: req.method, url: req.url, headers: req.headers, body });
+        const path = req.url.split('?')[0];
+        let route = routes[path];
+        if (Array.isArray(route)) {
+          const served = requests.filter((request) => request.url.split('?')[0] === path).length;
+          route = route[Math.min(served, route.length) - 1];
+        }
+        if (!route) {
+          res.writeHead(404);
+          res.end();
+          return;
+        }
+        res.writeHead(route.status || 200, { 'Content-Type': route.contentType || 'text/event-stream' });
+        if (route.hang) {
+          res.write(route.body || '');
+        } else if (route.drop) {
+          res.write(route.body || '', () => res.destroy());
+        } else {
+          res.end(route.body || '');
+        }
+      });
+    });
+    server.listen(0, '127.0.0.1', () => {
+      resolve({
+        url: `http://127.0.0.1:${server.address().port}`,
+        requests,
+        close() {
+          server.closeAllConnections();
+          server.close();
+        },
+      });
+    });
+  });
+};
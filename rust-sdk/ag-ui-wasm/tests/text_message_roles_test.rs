@@ -12,11 +12,10 @@ const TEXT_MESSAGE_ROLES: &[Role] = &[Role::Developer, Role::System, Role::Assis
 
 // ===== TEXT MESSAGE START WITH ALL ROLES =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_start_with_all_roles() {
     for role in TEXT_MESSAGE_ROLES {
         let event = BaseEvent {
-            event_type: EventType::TextMessageStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -25,7 +24,7 @@ fn test_text_message_start_with_all_roles() {
             }),
         };
 
-        assert_eq!(event.event_type, EventType::TextMessageStart);
+        assert_eq!(event.event_type(), EventType::TextMessageStart);
 
         if let EventData::TextMessageStart(data) = &event.data {
             assert_eq!(data.message_id, "test-msg");
@@ -39,7 +38,7 @@ fn test_text_message_start_with_all_roles() {
         let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
         assert_eq!(parsed["type"], "TEXT_MESSAGE_START");
-        assert_eq!(parsed["message_id"], "test-msg");
+        assert_eq!(parsed["messageId"], "test-msg");
 
         // Verify role serialization
         let role_str = match role {
@@ -55,7 +54,7 @@ fn test_text_message_start_with_all_roles() {
 
 // ===== TEXT MESSAGE CONTENT WITH ALL ROLES =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_content_with_all_roles() {
     for role in TEXT_MESSAGE_ROLES {
         let content = format!("Hello from {}", match role {
@@ -67,7 +66,6 @@ fn test_text_message_content_with_all_roles() {
         });
 
         let event = BaseEvent {
-            event_type: EventType::TextMessageContent,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageContent(TextMessageContentEvent {
@@ -76,7 +74,7 @@ fn test_text_message_content_with_all_roles() {
             }),
         };
 
-        assert_eq!(event.event_type, EventType::TextMessageContent);
+        assert_eq!(event.event_type(), EventType::TextMessageContent);
 
         if let EventData::TextMessageContent(data) = &event.data {
             assert_eq!(data.message_id, "test-msg");
@@ -90,17 +88,16 @@ fn test_text_message_content_with_all_roles() {
         let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
         assert_eq!(parsed["type"], "TEXT_MESSAGE_CONTENT");
-        assert_eq!(parsed["message_id"], "test-msg");
+        assert_eq!(parsed["messageId"], "test-msg");
         assert_eq!(parsed["delta"], content);
     }
 }
 
 // ===== TEXT MESSAGE CONTENT WITHOUT ROLE (OPTIONAL) =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_content_without_role() {
     let event = BaseEvent {
-        event_type: EventType::TextMessageContent,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::TextMessageContent(TextMessageContentEvent {
@@ -109,7 +106,7 @@ fn test_text_message_content_without_role() {
         }),
     };
 
-    assert_eq!(event.event_type, EventType::TextMessageContent);
+    assert_eq!(event.event_type(), EventType::TextMessageContent);
 
     if let EventData::TextMessageContent(data) = &event.data {
         assert_eq!(data.message_id, "test-msg");
@@ -123,7 +120,7 @@ fn test_text_message_content_without_role() {
     let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
     assert_eq!(parsed["type"], "TEXT_MESSAGE_CONTENT");
-    assert_eq!(parsed["message_id"], "test-msg");
+    assert_eq!(parsed["messageId"], "test-msg");
     assert_eq!(parsed["delta"], "Hello without role");
     // Role should be null or not present
     assert!(parsed["role"].is_null() || !parsed.as_object().unwrap().contains_key("role"));
@@ -131,7 +128,7 @@ fn test_text_message_content_without_role() {
 
 // ===== MULTIPLE MESSAGES WITH DIFFERENT ROLES =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_multiple_messages_different_roles() {
     let mut events = Vec::new();
 
@@ -145,7 +142,6 @@ fn test_multiple_messages_different_roles() {
         };
 
         let start_event = BaseEvent {
-            event_type: EventType::TextMessageStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -170,7 +166,7 @@ fn test_multiple_messages_different_roles() {
     // Verify each start event has the correct role
     for (i, role) in TEXT_MESSAGE_ROLES.iter().enumerate() {
         let start_event = &events[i * 3];
-        assert_eq!(start_event.event_type, EventType::TextMessageStart);
+        assert_eq!(start_event.event_type(), EventType::TextMessageStart);
 
         if let EventData::TextMessageStart(data) = &start_event.data {
             assert_eq!(data.role, Some(*role));
@@ -188,11 +184,10 @@ fn test_multiple_messages_different_roles() {
 
 // ===== TEXT MESSAGE SERIALIZATION WITH ROLES =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_serialization() {
     for role in TEXT_MESSAGE_ROLES {
         let event = BaseEvent {
-            event_type: EventType::TextMessageStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -215,11 +210,11 @@ fn test_text_message_serialization() {
 
         assert_eq!(parsed["role"], role_str);
         assert_eq!(parsed["type"], "TEXT_MESSAGE_START");
-        assert_eq!(parsed["message_id"], "test-msg");
+        assert_eq!(parsed["messageId"], "test-msg");
 
         // Test round-trip deserialization
         let deserialized: BaseEvent = serde_json::from_str(&json_str).unwrap();
-        assert_eq!(deserialized.event_type, EventType::TextMessageStart);
+        assert_eq!(deserialized.event_type(), EventType::TextMessageStart);
 
         if let EventData::TextMessageStart(data) = &deserialized.data {
             assert_eq!(data.role, Some(*role));
@@ -230,12 +225,12 @@ fn test_text_message_serialization() {
 
 // ===== DEFAULT ROLE BEHAVIOR =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_text_message_start_default_role() {
     // Test that we can create a TextMessageStart without specifying role
     let event = BaseEvent::text_message_start("test-msg".to_string(), None);
 
-    assert_eq!(event.event_type, EventType::TextMessageStart);
+    assert_eq!(event.event_type(), EventType::TextMessageStart);
 
     if let EventData::TextMessageStart(data) = &event.data {
         assert_eq!(data.message_id, "test-msg");
@@ -246,7 +241,7 @@ fn test_text_message_start_default_role() {
 
 // ===== ROLE VALIDATION TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_role_enum_serialization() {
     // Test that all roles serialize correctly
     let roles_and_strings = vec![
@@ -258,7 +253,7 @@ fn test_role_enum_serialization() {
     ];
 
     for (role, expected_str) in roles_and_strings {
-        let serialized = serde_json::to_value(&role).unwrap();
+        let serialized = serde_json::to_value(role).unwrap();
         assert_eq!(serialized, expected_str);
 
         // Test deserialization back to role
@@ -269,7 +264,7 @@ fn test_role_enum_serialization() {
 
 // ===== COMPREHENSIVE TEXT MESSAGE FLOW =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_comprehensive_text_message_flow_all_roles() {
     // Test a complete text message flow for each role
     for role in TEXT_MESSAGE_ROLES {
@@ -285,7 +280,6 @@ fn test_comprehensive_text_message_flow_all_roles() {
 
         // Create start event
         let start_event = BaseEvent {
-            event_type: EventType::TextMessageStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageStart(TextMessageStartEvent {
@@ -296,7 +290,6 @@ fn test_comprehensive_text_message_flow_all_roles() {
 
         // Create content events with role-specific content
         let content1 = BaseEvent {
-            event_type: EventType::TextMessageContent,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageContent(TextMessageContentEvent {
@@ -306,7 +299,6 @@ fn test_comprehensive_text_message_flow_all_roles() {
         };
 
         let content2 = BaseEvent {
-            event_type: EventType::TextMessageContent,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageContent(TextMessageContentEvent {
@@ -317,7 +309,6 @@ fn test_comprehensive_text_message_flow_all_roles() {
 
         // Create end event
         let end_event = BaseEvent {
-            event_type: EventType::TextMessageEnd,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::TextMessageEnd(TextMessageEndEvent {
@@ -332,15 +323,15 @@ fn test_comprehensive_text_message_flow_all_roles() {
             let json_str = serde_json::to_string(event).unwrap();
             let parsed: serde_json::Value = serde_json::from_str(&json_str).unwrap();
 
-            assert_eq!(parsed["message_id"], message_id);
+            assert_eq!(parsed["messageId"], message_id);
 
-            // Check role is present for events that should have it
-            match event.event_type {
-                EventType::TextMessageStart | EventType::TextMessageContent => {
+            // Only the start event carries the role, as in the TypeScript schemas
+            match event.event_type() {
+                EventType::TextMessageStart => {
                     assert_eq!(parsed["role"], role_name);
                 }
-                EventType::TextMessageEnd => {
-                    // End events might not have role field
+                EventType::TextMessageContent | EventType::TextMessageEnd => {
+                    assert!(parsed.get("role").is_none());
                 }
                 _ => panic!("Unexpected event type in text message flow"),
             }
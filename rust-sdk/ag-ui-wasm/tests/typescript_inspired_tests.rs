@@ -1,20 +1,19 @@
 //! Tests inspired by TypeScript SDK functionality
 
 use ag_ui_wasm::{
-    BaseEvent, EventType, EventData,
+    BaseEvent, EventData,
     ToolCallStartEvent, ToolCallChunkEvent, ToolCallEndEvent, StateDeltaEvent, MessagesSnapshotEvent, ErrorEvent,
-    SSEEncoder, Message, Role,
+    SSEEncoder, Message, Role, AssistantMessage, ToolCall, ToolMessage, UserMessage,
 };
 use wasm_bindgen_test::*;
 use serde_json::json;
 use chrono::Utc;
-use std::collections::HashMap;
 
 wasm_bindgen_test_configure!(run_in_browser);
 
 // ===== PROTOBUF-INSPIRED TESTS (TypeScript has protobuf encoding) =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_event_encoding_different_formats() {
     // TypeScript SDK supports both SSE and protobuf encoding
     // We test SSE encoding with binary output (similar concept)
@@ -34,12 +33,12 @@ fn test_event_encoding_different_formats() {
     let json_part = sse_string.trim_start_matches("data: ").trim_end();
     let parsed: serde_json::Value = serde_json::from_str(json_part).unwrap();
     assert_eq!(parsed["type"], "TEXT_MESSAGE_START");
-    assert_eq!(parsed["message_id"], "msg123");
+    assert_eq!(parsed["messageId"], "msg123");
 }
 
 // ===== EVENT VALIDATION TESTS (TypeScript has comprehensive validation) =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_event_id_consistency_validation() {
     // Simulate validation that message IDs are consistent across related events
     let message_id = "msg_123".to_string();
@@ -62,39 +61,36 @@ fn test_event_id_consistency_validation() {
     }
 }
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_tool_call_id_consistency_validation() {
     // Simulate validation that tool call IDs are consistent
     let tool_call_id = "call_123".to_string();
     
     let start_event = BaseEvent {
-        event_type: EventType::ToolCallStart,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallStart(ToolCallStartEvent {
             tool_call_id: tool_call_id.clone(),
-            tool_name: "search_tool".to_string(),
+            tool_call_name: "search_tool".to_string(),
             parent_message_id: None,
         }),
     };
     
     let chunk_event = BaseEvent {
-        event_type: EventType::ToolCallChunk,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallChunk(ToolCallChunkEvent {
-            tool_call_id: tool_call_id.clone(),
-            delta: "partial args".to_string(),
+            tool_call_id: Some(tool_call_id.clone()),
+            delta: Some("partial args".to_string()),
+            ..Default::default()
         }),
     };
     
     let end_event = BaseEvent {
-        event_type: EventType::ToolCallEnd,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::ToolCallEnd(ToolCallEndEvent {
             tool_call_id: tool_call_id.clone(),
-            tool_call: None,
         }),
     };
     
@@ -104,7 +100,7 @@ fn test_tool_call_id_consistency_validation() {
     }
     
     if let EventData::ToolCallChunk(data) = &chunk_event.data {
-        assert_eq!(data.tool_call_id, tool_call_id);
+        assert_eq!(data.tool_call_id.as_ref(), Some(&tool_call_id));
     }
     
     if let EventData::ToolCallEnd(data) = &end_event.data {
@@ -114,7 +110,7 @@ fn test_tool_call_id_consistency_validation() {
 
 // ===== COMPLEX EVENT SEQUENCES (TypeScript tests complex workflows) =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_complex_conversation_flow() {
     // Test a complex conversation flow similar to TypeScript tests
     let events = vec![
@@ -125,31 +121,28 @@ fn test_complex_conversation_flow() {
         
         // Tool call sequence
         BaseEvent {
-            event_type: EventType::ToolCallStart,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallStart(ToolCallStartEvent {
                 tool_call_id: "t1".to_string(),
-                tool_name: "search_tool".to_string(),
+                tool_call_name: "search_tool".to_string(),
                 parent_message_id: Some("msg1".to_string()),
             }),
         },
         BaseEvent {
-            event_type: EventType::ToolCallChunk,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallChunk(ToolCallChunkEvent {
-                tool_call_id: "t1".to_string(),
-                delta: "search args".to_string(),
+                tool_call_id: Some("t1".to_string()),
+                delta: Some("search args".to_string()),
+                ..Default::default()
             }),
         },
         BaseEvent {
-            event_type: EventType::ToolCallEnd,
             timestamp: Some(Utc::now()),
             raw_event: None,
             data: EventData::ToolCallEnd(ToolCallEndEvent {
                 tool_call_id: "t1".to_string(),
-                tool_call: None,
             }),
         },
         
@@ -184,7 +177,7 @@ fn test_complex_conversation_flow() {
 
 // ===== STATE MANAGEMENT TESTS (TypeScript has sophisticated state handling) =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_state_delta_operations() {
     // Test JSON Patch-style operations like TypeScript
     let delta_operations = vec![
@@ -197,7 +190,6 @@ fn test_state_delta_operations() {
     ];
     
     let event = BaseEvent {
-        event_type: EventType::StateDelta,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::StateDelta(StateDeltaEvent {
@@ -221,78 +213,30 @@ fn test_state_delta_operations() {
     }
 }
 
-#[wasm_bindgen_test]
-fn test_messages_snapshot_with_complex_metadata() {
-    // Test messages with complex metadata like TypeScript
+#[wasm_bindgen_test(unsupported = test)]
+fn test_messages_snapshot_with_tool_call_conversation() {
+    // Test a conversation with every message field the TypeScript schemas allow
     let messages = vec![
-        Message {
+        Message::User(UserMessage {
             id: "user_1".to_string(),
-            role: Role::User,
-            content: "Hello AI".to_string(),
-            name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: Some({
-                let mut map = HashMap::new();
-                map.insert("client_info".to_string(), json!({
-                    "browser": "Chrome",
-                    "version": "91.0",
-                    "platform": "Windows"
-                }));
-                map
-            }),
-            created_at: Some(Utc::now()),
-        },
-        Message {
+            content: "What's the weather in New York?".to_string(),
+            name: Some("Alice".to_string()),
+        }),
+        Message::Assistant(AssistantMessage {
             id: "asst_1".to_string(),
-            role: Role::Assistant,
-            content: "Hello! How can I help?".to_string(),
+            content: None,
             name: None,
-            tool_call_id: None,
-            tool_calls: None,
-            function_call: None,
-            metadata: Some({
-                let mut map = HashMap::new();
-                map.insert("model_info".to_string(), json!({
-                    "model": "gpt-4",
-                    "temperature": 0.7,
-                    "max_tokens": 1000
-                }));
-                map.insert("tool_calls".to_string(), json!([
-                    {
-                        "id": "call_1",
-                        "type": "function",
-                        "function": {
-                            "name": "get_weather",
-                            "arguments": "{\"location\": \"New York\"}"
-                        }
-                    }
-                ]));
-                map
-            }),
-            created_at: Some(Utc::now()),
-        },
-        Message {
+            tool_calls: Some(vec![ToolCall::new("call_1", "get_weather", json!({"location": "New York"}).to_string())]),
+        }),
+        Message::Tool(ToolMessage {
             id: "tool_1".to_string(),
-            role: Role::Tool,
             content: "{\"temperature\": 72, \"condition\": \"sunny\"}".to_string(),
-            name: None,
-            tool_call_id: Some("call_1".to_string()),
-            tool_calls: None,
-            function_call: None,
-            metadata: Some({
-                let mut map = HashMap::new();
-                map.insert("execution_time".to_string(), json!(0.5));
-                map.insert("api_version".to_string(), json!("v1.2"));
-                map
-            }),
-            created_at: Some(Utc::now()),
-        },
+            tool_call_id: "call_1".to_string(),
+            error: None,
+        }),
     ];
     
     let event = BaseEvent {
-        event_type: EventType::MessagesSnapshot,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::MessagesSnapshot(MessagesSnapshotEvent { messages }),
@@ -305,25 +249,26 @@ fn test_messages_snapshot_with_complex_metadata() {
     if let EventData::MessagesSnapshot(data) = &deserialized.data {
         assert_eq!(data.messages.len(), 3);
         
-        // Verify user message metadata
-        assert_eq!(data.messages[0].metadata.as_ref().unwrap()["client_info"]["browser"], "Chrome");
+        // Verify user message name
+        assert_eq!(data.messages[0].name(), Some("Alice"));
         
-        // Verify assistant message metadata
-        assert_eq!(data.messages[1].metadata.as_ref().unwrap()["model_info"]["model"], "gpt-4");
-        assert_eq!(data.messages[1].metadata.as_ref().unwrap()["tool_calls"][0]["function"]["name"], "get_weather");
+        // Verify assistant tool calls without content
+        assert_eq!(data.messages[1].content(), None);
+        assert_eq!(data.messages[1].tool_calls().unwrap()[0].function.name, "get_weather");
         
-        // Verify tool message metadata
-        assert_eq!(data.messages[2].tool_call_id, Some("call_1".to_string()));
-        assert_eq!(data.messages[2].metadata.as_ref().unwrap()["execution_time"], 0.5);
+        // Verify tool message links back to the call
+        assert_eq!(data.messages[2].role(), Role::Tool);
+        assert_eq!(data.messages[2].tool_call_id(), Some("call_1"));
+    } else {
+        panic!("Expected MessagesSnapshot event data");
     }
 }
 
 // ===== ERROR HANDLING TESTS (TypeScript has comprehensive error handling) =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_error_event_with_details() {
     let event = BaseEvent {
-        event_type: EventType::Error,
         timestamp: Some(Utc::now()),
         raw_event: None,
         data: EventData::Error(ErrorEvent {
@@ -350,7 +295,7 @@ fn test_error_event_with_details() {
 
 // ===== TIMESTAMP CONSISTENCY TESTS =====
 
-#[wasm_bindgen_test]
+#[wasm_bindgen_test(unsupported = test)]
 fn test_timestamp_consistency() {
     let before_time = Utc::now();
     
@@ -0,0 +1,297 @@
+//! Protocol verification tests matching the TypeScript client's verify tests
+//!
+//! `EventVerifier` is fed events one at a time and reports the first event
+//! that breaks the AG-UI sequencing rules as a typed `ProtocolViolation`.
+
+use ag_ui_wasm::{
+    AgUiError, BaseEvent, EventType, EventVerifier, ProtocolViolation, Role, RunAbortedEvent, StepFinishedEvent,
+    StepStartedEvent, TextMessageChunkEvent, ThinkingEndEvent, ThinkingStartEvent,
+    ThinkingTextMessageContentEvent, ThinkingTextMessageEndEvent, ThinkingTextMessageStartEvent,
+    ToolCallArgsEvent, ToolCallEndEvent, ToolCallStartEvent,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn run_started(run_id: &str) -> BaseEvent {
+    BaseEvent::run_started("thread-1".to_string(), run_id.to_string())
+}
+
+fn run_finished(run_id: &str) -> BaseEvent {
+    BaseEvent::run_finished("thread-1".to_string(), run_id.to_string())
+}
+
+fn message_start(message_id: &str) -> BaseEvent {
+    BaseEvent::text_message_start(message_id.to_string(), Some(Role::Assistant))
+}
+
+fn message_content(message_id: &str) -> BaseEvent {
+    BaseEvent::text_message_content(message_id.to_string(), "Hi".to_string())
+}
+
+fn message_end(message_id: &str) -> BaseEvent {
+    BaseEvent::text_message_end(message_id.to_string())
+}
+
+fn tool_call_start(tool_call_id: &str) -> BaseEvent {
+    BaseEvent::new(ToolCallStartEvent {
+        tool_call_id: tool_call_id.to_string(),
+        tool_call_name: "search".to_string(),
+        parent_message_id: None,
+    })
+}
+
+fn tool_call_args(tool_call_id: &str) -> BaseEvent {
+    BaseEvent::new(ToolCallArgsEvent { tool_call_id: tool_call_id.to_string(), delta: "{}".to_string() })
+}
+
+fn tool_call_end(tool_call_id: &str) -> BaseEvent {
+    BaseEvent::new(ToolCallEndEvent { tool_call_id: tool_call_id.to_string() })
+}
+
+fn step_started(step_name: &str) -> BaseEvent {
+    BaseEvent::new(StepStartedEvent { step_name: step_name.to_string() })
+}
+
+fn step_finished(step_name: &str) -> BaseEvent {
+    BaseEvent::new(StepFinishedEvent { step_name: step_name.to_string() })
+}
+
+/// Feed `events` to a fresh verifier and return the first violation
+fn first_violation(events: &[BaseEvent]) -> Option<ProtocolViolation> {
+    match EventVerifier::new().verify_all(events) {
+        Ok(()) => None,
+        Err(AgUiError::ProtocolViolation { violation, .. }) => Some(violation),
+        Err(other) => panic!("Unexpected error: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_valid_run_is_accepted() {
+    let events = vec![
+        run_started("run-1"),
+        step_started("plan"),
+        message_start("msg-1"),
+        message_content("msg-1"),
+        message_end("msg-1"),
+        tool_call_start("call-1"),
+        tool_call_args("call-1"),
+        tool_call_end("call-1"),
+        step_finished("plan"),
+        run_finished("run-1"),
+    ];
+
+    assert!(first_violation(&events).is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_first_event_must_be_run_started() {
+    let error = first_violation(&[message_start("msg-1")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::RunNotStarted { event_type: EventType::TextMessageStart }));
+    assert_eq!(error.to_string(), "First event must be 'RUN_STARTED', got 'TEXT_MESSAGE_START'");
+
+    // A run may fail before it starts
+    assert!(first_violation(&[BaseEvent::run_error("boom".to_string(), None)]).is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_violations_carry_the_offending_event() {
+    let error = EventVerifier::new().verify_all(&[run_started("run-1"), message_content("msg-1")]).unwrap_err();
+
+    assert_eq!(error.code(), "PROTOCOL_VIOLATION");
+    let AgUiError::ProtocolViolation { event, .. } = &error else { panic!("Unexpected error: {:?}", error) };
+    assert_eq!(event.event_type(), EventType::TextMessageContent);
+    assert_eq!(error.to_string(), error.violation().unwrap().to_string());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_content_before_start_is_rejected() {
+    let error = first_violation(&[run_started("run-1"), message_content("msg-1")]).unwrap();
+    match error {
+        ProtocolViolation::TextMessageNotActive { event_type, message_id } => {
+            assert_eq!(event_type, EventType::TextMessageContent);
+            assert_eq!(message_id, "msg-1");
+        }
+        other => panic!("Unexpected error: {:?}", other),
+    }
+
+    let error = first_violation(&[run_started("run-1"), message_end("msg-1")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::TextMessageNotActive { event_type: EventType::TextMessageEnd, .. }));
+
+    let error = first_violation(&[run_started("run-1"), message_start("msg-1"), message_start("msg-1")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::TextMessageAlreadyActive { message_id } if message_id == "msg-1"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_events_after_run_finished_are_rejected() {
+    let error = first_violation(&[run_started("run-1"), run_finished("run-1"), message_start("msg-1")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::RunAlreadyFinished { event_type: EventType::TextMessageStart }));
+
+    // RUN_ERROR may still report a failure after the run finished
+    let events = [run_started("run-1"), run_finished("run-1"), BaseEvent::run_error("late".to_string(), None)];
+    assert!(first_violation(&events).is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_events_after_run_error_are_rejected() {
+    let events = [
+        run_started("run-1"),
+        BaseEvent::run_error("boom".to_string(), Some("E1".to_string())),
+        run_started("run-2"),
+    ];
+    let error = first_violation(&events).unwrap();
+    assert!(matches!(error, ProtocolViolation::RunAlreadyErrored { event_type: EventType::RunStarted }));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_started_twice_is_rejected() {
+    let error = first_violation(&[run_started("run-1"), run_started("run-2")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::RunAlreadyStarted));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_sequential_runs_are_accepted() {
+    let events = [
+        run_started("run-1"),
+        message_start("msg-1"),
+        message_end("msg-1"),
+        run_finished("run-1"),
+        run_started("run-2"),
+        // The same IDs may be reused by a later run
+        message_start("msg-1"),
+        message_end("msg-1"),
+        run_finished("run-2"),
+    ];
+    assert!(first_violation(&events).is_none());
+
+    let aborted = [
+        run_started("run-1"),
+        message_start("msg-1"),
+        BaseEvent::new(RunAbortedEvent { thread_id: "thread-1".to_string(), run_id: "run-1".to_string(), reason: None }),
+        run_started("run-2"),
+        run_finished("run-2"),
+    ];
+    assert!(first_violation(&aborted).is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_mismatched_step_names_are_rejected() {
+    let error = first_violation(&[run_started("run-1"), step_started("plan"), step_finished("act")]).unwrap();
+    assert!(matches!(&error, ProtocolViolation::StepNotActive { step_name } if step_name == "act"));
+    assert_eq!(error.to_string(), "Cannot send 'STEP_FINISHED' for step \"act\" that was not started");
+
+    let error = first_violation(&[run_started("run-1"), step_started("plan"), step_started("plan")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::StepAlreadyActive { step_name } if step_name == "plan"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_unbalanced_tool_calls_are_rejected() {
+    let error = first_violation(&[run_started("run-1"), tool_call_args("call-1")]).unwrap();
+    assert!(matches!(
+        error,
+        ProtocolViolation::ToolCallNotActive { event_type: EventType::ToolCallArgs, tool_call_id } if tool_call_id == "call-1"
+    ));
+
+    let events = [run_started("run-1"), tool_call_start("call-1"), tool_call_end("call-1"), tool_call_end("call-1")];
+    let error = first_violation(&events).unwrap();
+    assert!(matches!(error, ProtocolViolation::ToolCallNotActive { event_type: EventType::ToolCallEnd, .. }));
+
+    let error = first_violation(&[run_started("run-1"), tool_call_start("call-1"), tool_call_start("call-1")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::ToolCallAlreadyActive { tool_call_id } if tool_call_id == "call-1"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_finished_with_open_work_is_rejected() {
+    let error = first_violation(&[run_started("run-1"), step_started("plan"), run_finished("run-1")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::UnfinishedSteps { step_names } if step_names == ["plan"]));
+
+    let events = [run_started("run-1"), message_start("msg-1"), message_start("msg-2"), run_finished("run-1")];
+    let error = first_violation(&events).unwrap();
+    assert_eq!(
+        error.to_string(),
+        "Cannot send 'RUN_FINISHED' while text messages are still active: msg-1, msg-2"
+    );
+
+    let error = first_violation(&[run_started("run-1"), tool_call_start("call-1"), run_finished("run-1")]).unwrap();
+    assert!(matches!(error, ProtocolViolation::UnfinishedToolCalls { tool_call_ids } if tool_call_ids == ["call-1"]));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_concurrent_messages_and_tool_calls_are_accepted() {
+    let events = [
+        run_started("run-1"),
+        message_start("msg-1"),
+        tool_call_start("call-1"),
+        message_start("msg-2"),
+        tool_call_start("call-2"),
+        message_content("msg-1"),
+        tool_call_args("call-2"),
+        message_content("msg-2"),
+        tool_call_args("call-1"),
+        message_end("msg-2"),
+        tool_call_end("call-1"),
+        message_end("msg-1"),
+        tool_call_end("call-2"),
+        run_finished("run-1"),
+    ];
+    assert!(first_violation(&events).is_none());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_thinking_sequence_is_verified() {
+    let events = [
+        run_started("run-1"),
+        BaseEvent::new(ThinkingStartEvent { title: None }),
+        BaseEvent::new(ThinkingTextMessageStartEvent {}),
+        BaseEvent::new(ThinkingTextMessageContentEvent { delta: "Hmm".to_string() }),
+        BaseEvent::new(ThinkingTextMessageEndEvent {}),
+        BaseEvent::new(ThinkingEndEvent {}),
+        run_finished("run-1"),
+    ];
+    assert!(first_violation(&events).is_none());
+
+    let error = first_violation(&[run_started("run-1"), BaseEvent::new(ThinkingTextMessageStartEvent {})]).unwrap();
+    assert!(matches!(error, ProtocolViolation::ThinkingNotActive { event_type: EventType::ThinkingTextMessageStart }));
+
+    let events = [
+        run_started("run-1"),
+        BaseEvent::new(ThinkingStartEvent { title: None }),
+        BaseEvent::new(ThinkingTextMessageContentEvent { delta: "Hmm".to_string() }),
+    ];
+    let error = first_violation(&events).unwrap();
+    assert!(matches!(error, ProtocolViolation::ThinkingMessageNotActive { event_type: EventType::ThinkingTextMessageContent }));
+
+    let events = [
+        run_started("run-1"),
+        BaseEvent::new(ThinkingStartEvent { title: None }),
+        BaseEvent::new(ThinkingStartEvent { title: None }),
+    ];
+    assert!(matches!(first_violation(&events).unwrap(), ProtocolViolation::ThinkingAlreadyActive));
+
+    let error = first_violation(&[run_started("run-1"), BaseEvent::new(ThinkingEndEvent {})]).unwrap();
+    assert!(matches!(error, ProtocolViolation::ThinkingNotActive { event_type: EventType::ThinkingEnd }));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_rejected_event_leaves_state_unchanged() {
+    let mut verifier = EventVerifier::new();
+    verifier.verify(&run_started("run-1")).unwrap();
+    verifier.verify(&message_start("msg-1")).unwrap();
+
+    assert!(verifier.verify(&run_finished("run-1")).is_err());
+    assert!(verifier.verify(&message_start("msg-1")).is_err());
+
+    verifier.verify(&message_content("msg-1")).unwrap();
+    verifier.verify(&message_end("msg-1")).unwrap();
+    verifier.verify(&run_finished("run-1")).unwrap();
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_chunk_events_are_not_checked() {
+    let chunk = BaseEvent::new(TextMessageChunkEvent {
+        message_id: Some("msg-1".to_string()),
+        delta: Some("Hi".to_string()),
+        ..Default::default()
+    });
+    assert!(first_violation(&[run_started("run-1"), chunk, run_finished("run-1")]).is_none());
+}
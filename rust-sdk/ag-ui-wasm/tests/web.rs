@@ -16,10 +16,10 @@ fn test_version() {
 
 #[wasm_bindgen_test]
 fn test_message_creation() {
-    let message = Message::new(Role::User, "Hello, world!".to_string());
-    assert_eq!(message.role, Role::User);
-    assert_eq!(message.content, "Hello, world!");
-    assert!(!message.id.is_empty());
+    let message = Message::user("Hello, world!");
+    assert_eq!(message.role(), Role::User);
+    assert_eq!(message.content(), Some("Hello, world!"));
+    assert!(!message.id().is_empty());
 }
 
 #[wasm_bindgen_test]
@@ -27,13 +27,13 @@ fn test_run_agent_input() {
     let input = RunAgentInput::new("thread-123".to_string(), "run-456".to_string());
     assert_eq!(input.thread_id, "thread-123");
     assert_eq!(input.run_id, "run-456");
-    assert!(input.messages.is_none());
+    assert!(input.messages.is_empty());
 }
 
 #[wasm_bindgen_test]
 fn test_base_event_creation() {
     let event = BaseEvent::run_started("thread-1".to_string(), "run-1".to_string());
-    assert_eq!(event.event_type, EventType::RunStarted);
+    assert_eq!(event.event_type(), EventType::RunStarted);
 }
 
 #[wasm_bindgen_test]
@@ -0,0 +1,83 @@
+//! Tests for building `WebAgent` requests from a `WebAgentConfig`
+
+use ag_ui_wasm::{AgUiError, WebAgent, WebAgentConfig};
+use futures::executor::block_on;
+use std::cell::Cell;
+use std::rc::Rc;
+use wasm_bindgen_test::*;
+use web_sys::RequestCredentials;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Vec<&'a str> {
+    headers.iter().filter(|(header, _)| header == name).map(|(_, value)| value.as_str()).collect()
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_default_headers() {
+    let config = WebAgentConfig::new("https://agent.example.com/run");
+
+    let headers = block_on(config.request_headers()).unwrap();
+
+    assert_eq!(
+        headers,
+        [
+            ("Content-Type".to_string(), "application/json".to_string()),
+            ("Accept".to_string(), "text/event-stream".to_string()),
+        ]
+    );
+    assert_eq!(config.credentials(), None);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_provided_headers_follow_static_headers() {
+    let config = WebAgentConfig::new("https://agent.example.com/run")
+        .with_header("X-Team", "search")
+        .with_header("Authorization", "Bearer stale")
+        .with_header_provider(|| async { Ok(vec![("Authorization".to_string(), "Bearer fresh".to_string())]) });
+
+    let headers = block_on(config.request_headers()).unwrap();
+
+    // `Headers::set` keeps the last value, so the provider's token wins
+    assert_eq!(header(&headers, "Authorization"), ["Bearer stale", "Bearer fresh"]);
+    assert_eq!(header(&headers, "X-Team"), ["search"]);
+    assert_eq!(headers[0].0, "Content-Type");
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_header_provider_is_asked_for_every_request() {
+    let calls = Rc::new(Cell::new(0));
+    let counter = calls.clone();
+    let config = WebAgentConfig::new("https://agent.example.com/run").with_header_provider(move || {
+        counter.set(counter.get() + 1);
+        let token = format!("Bearer {}", counter.get());
+        async move { Ok(vec![("Authorization".to_string(), token)]) }
+    });
+
+    block_on(config.request_headers()).unwrap();
+    let headers = block_on(config.request_headers()).unwrap();
+
+    assert_eq!(calls.get(), 2);
+    assert_eq!(header(&headers, "Authorization"), ["Bearer 2"]);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_header_provider_error_fails_the_request() {
+    let config = WebAgentConfig::new("https://agent.example.com/run")
+        .with_header_provider(|| async { Err(AgUiError::AgentError("token expired".to_string())) });
+
+    let error = block_on(config.request_headers()).unwrap_err();
+
+    assert!(matches!(error, AgUiError::AgentError(message) if message == "token expired"));
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_web_agent_keeps_its_config() {
+    let config = WebAgentConfig::new("https://agent.example.com/run").with_credentials(RequestCredentials::Include);
+
+    let agent = WebAgent::with_config(config);
+
+    assert_eq!(agent.config().url(), "https://agent.example.com/run");
+    assert_eq!(agent.config().credentials(), Some(RequestCredentials::Include));
+    assert_eq!(WebAgent::new("https://other.example.com".to_string()).config().credentials(), None);
+}
@@ -0,0 +1,70 @@
+//! Tests for `WebAgent` runs that need `fetch`
+
+// These tests run under wasm-bindgen-test only
+#![cfg_attr(not(target_arch = "wasm32"), allow(dead_code))]
+
+use ag_ui_wasm::{AgUiError, Agent, EventType, Message, RetryPolicy, RunAgentInput, WebAgent, WebAgentConfig};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+use web_sys::RequestCredentials;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+async fn test_abort_run_cancels_the_fetch() {
+    let mut agent = WebAgent::new("http://127.0.0.1:9/agent".to_string());
+    agent.set_messages(vec![Message::user("Hi")]);
+
+    let mut seen = Vec::new();
+    let run = agent.run_agent_with(RunAgentInput::new("thread-1".to_string(), String::new()), |event| {
+        seen.push(event.event_type())
+    });
+    // The run starts its fetch when first polled, and the abort follows it
+    let (outcome, ()) = futures::join!(run, async { agent.abort_run() });
+
+    assert!(matches!(outcome, Err(AgUiError::Aborted)));
+    assert_eq!(seen, [EventType::RunError]);
+    assert_eq!(agent.messages().len(), 1);
+    assert_eq!(agent.thread_id().as_deref(), Some("thread-1"));
+}
+
+fn js_config(source: &str) -> JsValue {
+    js_sys::Function::new_no_args(&format!("return {}", source)).call0(&JsValue::NULL).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn test_config_from_js() {
+    let config = WebAgentConfig::from_js(&js_config(
+        "{ url: 'https://agent.example.com/run', headers: { 'X-Team': 'search' }, credentials: 'include', \
+         headerProvider: async () => ({ Authorization: 'Bearer fresh' }), retry: { maxRetries: 5 } }",
+    ))
+    .unwrap();
+
+    assert_eq!(config.url(), "https://agent.example.com/run");
+    assert_eq!(config.retry(), Some(&RetryPolicy { max_retries: 5, ..RetryPolicy::default() }));
+    assert_eq!(config.credentials(), Some(RequestCredentials::Include));
+    let headers = config.request_headers().await.unwrap();
+    assert!(headers.contains(&("X-Team".to_string(), "search".to_string())));
+    assert_eq!(headers.last().unwrap(), &("Authorization".to_string(), "Bearer fresh".to_string()));
+}
+
+#[wasm_bindgen_test]
+async fn test_request_mutator_from_js() {
+    let config = WebAgentConfig::from_js(&js_config(
+        "{ url: 'https://agent.example.com/run', \
+         requestMutator: (request) => new Request(request.url + '?team=search', request) }",
+    ))
+    .unwrap();
+
+    let request = web_sys::Request::new_with_str(config.url()).unwrap();
+    let request = config.mutate_request(request).await.unwrap();
+
+    assert_eq!(request.url(), "https://agent.example.com/run?team=search");
+}
+
+#[wasm_bindgen_test]
+fn test_invalid_config_from_js() {
+    assert!(WebAgentConfig::from_js(&js_config("{}")).is_err());
+    assert!(WebAgentConfig::from_js(&js_config("{ url: 'https://a.example', credentials: 'always' }")).is_err());
+    assert!(WebAgentConfig::from_js(&js_config("{ url: 'https://a.example', headerProvider: 'token' }")).is_err());
+}
@@ -0,0 +1,376 @@
+//! Cross-SDK wire format tests
+//!
+//! The fixtures in `tests/fixtures/wire` are JSON documents in the shape
+//! produced by `@ag-ui/core` (camelCase fields, millisecond timestamps),
+//! generated and checked against its schemas by `generate.mjs`.
+//! Events built in Rust must serialize to exactly the fixture bytes, and
+//! every fixture must parse and serialize back to the same bytes.
+//! `event_catalogue.jsonl` holds one event of every remaining spec schema.
+
+use ag_ui_wasm::{
+    BaseEvent, Context, EventData, EventType, RawEvent, RunAgentInput, Role,
+    CustomEvent, MessagesSnapshotEvent, RunErrorEvent, RunFinishedEvent, RunStartedEvent,
+    StateDeltaEvent, StateSnapshotEvent, StepFinishedEvent, StepStartedEvent,
+    TextMessageChunkEvent, ThinkingEndEvent, ThinkingStartEvent,
+    ThinkingTextMessageContentEvent, ThinkingTextMessageEndEvent, ThinkingTextMessageStartEvent,
+    ToolCallArgsEvent, ToolCallChunkEvent, ToolCallEndEvent, ToolCallResultEvent, ToolCallStartEvent,
+    ToolCall, AssistantMessage, DeveloperMessage, SystemMessage, ToolMessage, UserMessage,
+};
+use chrono::{DateTime, TimeZone, Utc};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const EVENTS_FIXTURE: &str = include_str!("fixtures/wire/events.jsonl");
+const CATALOGUE_FIXTURE: &str = include_str!("fixtures/wire/event_catalogue.jsonl");
+const RUN_AGENT_INPUT_FIXTURE: &str = include_str!("fixtures/wire/run_agent_input.json");
+
+fn fixture_lines(fixture: &str) -> impl Iterator<Item = &str> {
+    fixture.lines().map(str::trim).filter(|line| !line.is_empty())
+}
+
+fn at(millis: i64) -> Option<DateTime<Utc>> {
+    Some(Utc.timestamp_millis_opt(millis).unwrap())
+}
+
+fn untimed(data: impl Into<EventData>) -> BaseEvent {
+    BaseEvent { timestamp: None, raw_event: None, data: data.into() }
+}
+
+/// The Rust-side construction of every line in `events.jsonl`
+fn expected_events() -> Vec<BaseEvent> {
+    let mut run_started = BaseEvent::run_started("thread-1".to_string(), "run-1".to_string());
+    run_started.timestamp = at(1_700_000_000_000);
+
+    let mut message_start = BaseEvent::text_message_start("msg-1".to_string(), Some(Role::Assistant));
+    message_start.timestamp = at(1_700_000_000_001);
+
+    let mut hello = BaseEvent::text_message_content("msg-1".to_string(), "Hello".to_string());
+    hello.timestamp = None;
+
+    let mut escaped = BaseEvent::text_message_content("msg-1".to_string(), ", \"world\" — \n👋".to_string());
+    escaped.timestamp = None;
+
+    let mut message_end = BaseEvent::text_message_end("msg-1".to_string());
+    message_end.timestamp = None;
+
+    let mut raw = untimed(RawEvent {
+        event: json!({"id": "chatcmpl-1", "object": "chat.completion.chunk"}),
+        source: None,
+    });
+    raw.raw_event = Some(json!({"provider": "openai"}));
+
+    let mut run_finished = BaseEvent::run_finished("thread-1".to_string(), "run-1".to_string());
+    run_finished.timestamp = at(1_700_000_000_002);
+
+    vec![
+        run_started,
+        message_start,
+        hello,
+        escaped,
+        message_end,
+        untimed(ToolCallArgsEvent {
+            tool_call_id: "call-1".to_string(),
+            delta: r#"{"city":"Paris"}"#.to_string(),
+        }),
+        untimed(StateDeltaEvent {
+            delta: json!([{"op": "replace", "path": "/count", "value": 2}]),
+        }),
+        untimed(MessagesSnapshotEvent {
+            messages: vec![
+                UserMessage::new("Hi").with_id("u-1").into(),
+                AssistantMessage::new()
+                    .with_id("a-1")
+                    .with_content("Hello")
+                    .with_tool_calls(vec![ToolCall::new("call-1", "get_weather", r#"{"city":"Paris"}"#)])
+                    .into(),
+                ToolMessage::new("18°C and sunny", "call-1").with_id("t-1").into(),
+            ],
+        }),
+        raw,
+        run_finished,
+    ]
+}
+
+/// The Rust-side construction of every line in `event_catalogue.jsonl`
+fn expected_catalogue() -> Vec<BaseEvent> {
+    let mut run_started = untimed(RunStartedEvent {
+        thread_id: "thread-1".to_string(),
+        run_id: "run-2".to_string(),
+        parent_run_id: Some("run-1".to_string()),
+        input: None,
+    });
+    run_started.timestamp = at(1_700_000_000_000);
+
+    let mut run_finished = untimed(RunFinishedEvent {
+        thread_id: "thread-1".to_string(),
+        run_id: "run-2".to_string(),
+        result: Some(json!({"answer": 42})),
+    });
+    run_finished.timestamp = at(1_700_000_000_001);
+
+    vec![
+        run_started,
+        untimed(StepStartedEvent { step_name: "plan".to_string() }),
+        untimed(ThinkingStartEvent { title: Some("Planning".to_string()) }),
+        untimed(ThinkingTextMessageStartEvent {}),
+        untimed(ThinkingTextMessageContentEvent { delta: "Looking up the weather".to_string() }),
+        untimed(ThinkingTextMessageEndEvent {}),
+        untimed(ThinkingEndEvent {}),
+        untimed(StepFinishedEvent { step_name: "plan".to_string() }),
+        untimed(TextMessageChunkEvent {
+            message_id: Some("msg-2".to_string()),
+            role: Some(Role::Assistant),
+            delta: Some("On it".to_string()),
+        }),
+        untimed(ToolCallStartEvent {
+            tool_call_id: "call-2".to_string(),
+            tool_call_name: "get_weather".to_string(),
+            parent_message_id: Some("msg-2".to_string()),
+        }),
+        untimed(ToolCallArgsEvent {
+            tool_call_id: "call-2".to_string(),
+            delta: r#"{"city":"Paris"}"#.to_string(),
+        }),
+        untimed(ToolCallEndEvent { tool_call_id: "call-2".to_string() }),
+        untimed(ToolCallChunkEvent {
+            tool_call_id: Some("call-3".to_string()),
+            tool_call_name: Some("get_time".to_string()),
+            parent_message_id: Some("msg-2".to_string()),
+            delta: Some("{}".to_string()),
+        }),
+        untimed(ToolCallResultEvent {
+            message_id: "tool-1".to_string(),
+            tool_call_id: "call-2".to_string(),
+            content: "18°C and sunny".to_string(),
+            role: Some(Role::Tool),
+        }),
+        untimed(MessagesSnapshotEvent {
+            messages: vec![
+                DeveloperMessage::new("Answer briefly").with_id("d-1").with_name("ops").into(),
+                SystemMessage::new("You are a weather bot").with_id("s-1").into(),
+                AssistantMessage::new().with_id("a-2").into(),
+                ToolMessage::new("18°C and sunny", "call-2").with_id("tool-1").into(),
+                ToolMessage::new("", "call-3").with_id("tool-2").with_error("Clock unavailable").into(),
+            ],
+        }),
+        untimed(StateSnapshotEvent {
+            snapshot: HashMap::from([("count".to_string(), json!(1))]),
+        }),
+        untimed(RawEvent {
+            event: json!({"id": "evt-1"}),
+            source: Some("openai".to_string()),
+        }),
+        untimed(CustomEvent {
+            name: "progress".to_string(),
+            value: json!({"percent": 50}),
+        }),
+        run_finished,
+        untimed(RunErrorEvent {
+            message: "Rate limited".to_string(),
+            code: Some("rate_limit".to_string()),
+        }),
+    ]
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_emitted_events_match_fixtures_byte_for_byte() {
+    let lines: Vec<&str> = fixture_lines(EVENTS_FIXTURE).collect();
+    let events = expected_events();
+    assert_eq!(lines.len(), events.len());
+
+    for (event, line) in events.iter().zip(lines) {
+        assert_eq!(serde_json::to_string(event).unwrap(), line);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_fixtures_round_trip_byte_for_byte() {
+    for line in fixture_lines(EVENTS_FIXTURE) {
+        let event: BaseEvent = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("Failed to parse fixture {}: {}", line, e));
+        assert_eq!(serde_json::to_string(&event).unwrap(), line);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_emitted_catalogue_matches_fixture_byte_for_byte() {
+    let lines: Vec<&str> = fixture_lines(CATALOGUE_FIXTURE).collect();
+    let events = expected_catalogue();
+    assert_eq!(lines.len(), events.len());
+
+    for (event, line) in events.iter().zip(lines) {
+        assert_eq!(serde_json::to_string(event).unwrap(), line);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_catalogue_fixture_round_trips_byte_for_byte() {
+    for line in fixture_lines(CATALOGUE_FIXTURE) {
+        let event: BaseEvent = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("Failed to parse fixture {}: {}", line, e));
+        assert_eq!(serde_json::to_string(&event).unwrap(), line);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_fixtures_cover_every_spec_event_type() {
+    let covered: HashSet<String> = fixture_lines(EVENTS_FIXTURE)
+        .chain(fixture_lines(CATALOGUE_FIXTURE))
+        .map(|line| serde_json::from_str::<Value>(line).unwrap()["type"].as_str().unwrap().to_string())
+        .collect();
+
+    let spec_types = [
+        EventType::TextMessageStart, EventType::TextMessageContent, EventType::TextMessageEnd,
+        EventType::TextMessageChunk, EventType::ThinkingTextMessageStart,
+        EventType::ThinkingTextMessageContent, EventType::ThinkingTextMessageEnd,
+        EventType::ToolCallStart, EventType::ToolCallArgs, EventType::ToolCallEnd,
+        EventType::ToolCallChunk, EventType::ToolCallResult,
+        EventType::ThinkingStart, EventType::ThinkingEnd,
+        EventType::StateSnapshot, EventType::StateDelta, EventType::MessagesSnapshot,
+        EventType::Raw, EventType::Custom,
+        EventType::RunStarted, EventType::RunFinished, EventType::RunError,
+        EventType::StepStarted, EventType::StepFinished,
+    ];
+    for event_type in spec_types {
+        let name = serde_json::to_value(event_type).unwrap();
+        assert!(covered.contains(name.as_str().unwrap()), "no fixture for {}", name);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_fixtures_are_accepted() {
+    for (line, expected) in fixture_lines(EVENTS_FIXTURE).zip(expected_events()) {
+        let event: BaseEvent = serde_json::from_str(line)
+            .unwrap_or_else(|e| panic!("Failed to parse fixture {}: {}", line, e));
+        assert_eq!(event.event_type(), expected.event_type());
+        assert_eq!(event.timestamp, expected.timestamp);
+        assert_eq!(event.raw_event, expected.raw_event);
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_fixtures_contain_no_snake_case_keys() {
+    fn assert_camel_case(value: &Value, path: &str) {
+        if let Value::Object(map) = value {
+            for (key, child) in map {
+                assert!(!key.contains('_'), "snake_case key {}.{}", path, key);
+                // Payload values (state, raw events, tool parameters) are opaque
+                if !matches!(key.as_str(), "delta" | "event" | "rawEvent" | "snapshot" | "state" | "parameters" | "value" | "result") {
+                    assert_camel_case(child, &format!("{}.{}", path, key));
+                }
+            }
+        } else if let Value::Array(items) = value {
+            for (i, item) in items.iter().enumerate() {
+                assert_camel_case(item, &format!("{}[{}]", path, i));
+            }
+        }
+    }
+
+    for line in fixture_lines(EVENTS_FIXTURE).chain(fixture_lines(CATALOGUE_FIXTURE)) {
+        let event: BaseEvent = serde_json::from_str(line).unwrap();
+        assert_camel_case(&serde_json::to_value(&event).unwrap(), "$");
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_event_fixture_field_values() {
+    let events: Vec<BaseEvent> = fixture_lines(EVENTS_FIXTURE)
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    match &events[0].data {
+        EventData::RunStarted(data) => {
+            assert_eq!(data.thread_id, "thread-1");
+            assert_eq!(data.run_id, "run-1");
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    match &events[1].data {
+        EventData::TextMessageStart(data) => {
+            assert_eq!(data.message_id, "msg-1");
+            assert_eq!(data.role, Some(Role::Assistant));
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    match &events[3].data {
+        EventData::TextMessageContent(data) => {
+            assert_eq!(data.message_id, "msg-1");
+            assert_eq!(data.delta, ", \"world\" — \n👋");
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    match &events[4].data {
+        EventData::TextMessageEnd(data) => assert_eq!(data.message_id, "msg-1"),
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    match &events[5].data {
+        EventData::ToolCallArgs(data) => assert_eq!(data.tool_call_id, "call-1"),
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    match &events[7].data {
+        EventData::MessagesSnapshot(data) => {
+            assert_eq!(data.messages.len(), 3);
+            assert_eq!(data.messages[0].role(), Role::User);
+            assert_eq!(data.messages[1].tool_calls().unwrap()[0].function.name, "get_weather");
+            assert_eq!(data.messages[2].tool_call_id(), Some("call-1"));
+        }
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+
+    match &events[9].data {
+        EventData::RunFinished(data) => assert_eq!(data.run_id, "run-1"),
+        other => panic!("Unexpected event data: {:?}", other),
+    }
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_agent_input_fixture_round_trip() {
+    let fixture = RUN_AGENT_INPUT_FIXTURE.trim();
+    let input: RunAgentInput = serde_json::from_str(fixture).unwrap();
+
+    assert_eq!(input.thread_id, "thread-1");
+    assert_eq!(input.run_id, "run-1");
+    assert_eq!(input.messages[0].content(), Some("What's the weather?"));
+    assert_eq!(input.messages[1].tool_calls(), Some(&[ToolCall::new("call-1", "get_weather", r#"{"city":"Paris"}"#)][..]));
+    assert_eq!(input.messages[2].tool_call_id(), Some("call-1"));
+    assert_eq!(input.tools[0].name, "get_weather");
+    assert_eq!(input.context, vec![Context::new("User location", "Amsterdam")]);
+    assert_eq!(input.forwarded_props["locale"], "en-US");
+
+    assert_eq!(serde_json::to_string(&input).unwrap(), fixture);
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_run_agent_input_always_serializes_every_member() {
+    let input = RunAgentInput::new("thread-1".to_string(), "run-1".to_string());
+    assert_eq!(
+        serde_json::to_string(&input).unwrap(),
+        r#"{"threadId":"thread-1","runId":"run-1","state":{},"messages":[],"tools":[],"context":[],"forwardedProps":{}}"#
+    );
+
+    let input: RunAgentInput =
+        serde_json::from_str(r#"{"threadId":"t","runId":"r","state":null,"forwardedProps":null}"#).unwrap();
+    assert!(input.messages.is_empty() && input.context.is_empty());
+    assert!(input.state.is_empty() && input.forwarded_props.is_empty());
+}
+
+#[wasm_bindgen_test(unsupported = test)]
+fn test_snake_case_input_is_not_the_wire_format() {
+    let result = serde_json::from_str::<RunAgentInput>(r#"{"thread_id":"t","run_id":"r"}"#);
+    assert!(result.is_err());
+
+    let event = BaseEvent::run_started("t".to_string(), "r".to_string());
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["threadId"], "t");
+    assert_eq!(json["runId"], "r");
+    assert!(json["timestamp"].is_i64());
+    assert!(json.get("thread_id").is_none());
+}